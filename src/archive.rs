@@ -0,0 +1,160 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::model::DB;
+use crate::reports::archive_report;
+use crate::snapshots::{create_snapshot, list_snapshots, read_snapshot, Snapshot};
+
+/// Where the navigator keeps the snapshots of the board and the reports of
+/// closed sprints.
+pub trait Archive {
+    /// Saves `board` as the snapshot `name`, never over an existing one.
+    fn create_snapshot(&self, name: &str, board: &DB) -> Result<()>;
+    fn read_snapshot(&self, name: &str) -> Result<DB>;
+    /// The snapshots, oldest first.
+    fn list_snapshots(&self) -> Result<Vec<Snapshot>>;
+    /// Keeps `report` as `name`, returning where it went for the notice.
+    fn archive_report(&self, name: &str, report: &str) -> Result<String>;
+}
+
+/// Keeps snapshots and reports as files, in the directories the config names.
+pub struct DirectoryArchive {
+    snapshots_dir: PathBuf,
+    reports_dir: PathBuf,
+}
+
+impl DirectoryArchive {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            snapshots_dir: config.snapshots_dir(),
+            reports_dir: config.reports_dir(),
+        }
+    }
+}
+
+impl Archive for DirectoryArchive {
+    fn create_snapshot(&self, name: &str, board: &DB) -> Result<()> {
+        create_snapshot(&self.snapshots_dir, name, board)?;
+        Ok(())
+    }
+
+    fn read_snapshot(&self, name: &str) -> Result<DB> {
+        read_snapshot(&self.snapshots_dir, name)
+    }
+
+    fn list_snapshots(&self) -> Result<Vec<Snapshot>> {
+        list_snapshots(&self.snapshots_dir)
+    }
+
+    fn archive_report(&self, name: &str, report: &str) -> Result<String> {
+        let path = archive_report(&self.reports_dir, name, report)?;
+
+        Ok(path.display().to_string())
+    }
+}
+
+/// Keeps snapshots and reports in memory only, for frontends without a file
+/// system, like a browser playground. The navigator starts with one.
+#[derive(Default)]
+pub struct MemoryArchive {
+    snapshots: RefCell<Vec<(Snapshot, DB)>>,
+    reports: RefCell<Vec<(String, String)>>,
+}
+
+impl MemoryArchive {
+    /// The report archived as `name`, for the frontend to show.
+    pub fn report(&self, name: &str) -> Option<String> {
+        self.reports
+            .borrow()
+            .iter()
+            .find(|(report, _)| report == name)
+            .map(|(_, report)| report.clone())
+    }
+}
+
+impl Archive for MemoryArchive {
+    fn create_snapshot(&self, name: &str, board: &DB) -> Result<()> {
+        let mut snapshots = self.snapshots.borrow_mut();
+
+        if name.trim().is_empty() {
+            return Err(anyhow!("\"{name}\" can't be used as a snapshot name"));
+        }
+
+        if snapshots.iter().any(|(snapshot, _)| snapshot.name == name) {
+            return Err(anyhow!("There already is a snapshot called \"{name}\""));
+        }
+
+        snapshots.push((
+            Snapshot {
+                name: name.to_owned(),
+                created_at: Utc::now(),
+            },
+            board.clone(),
+        ));
+        Ok(())
+    }
+
+    fn read_snapshot(&self, name: &str) -> Result<DB> {
+        self.snapshots
+            .borrow()
+            .iter()
+            .find(|(snapshot, _)| snapshot.name == name)
+            .map(|(_, board)| board.clone())
+            .ok_or_else(|| anyhow!("There is no snapshot called \"{name}\""))
+    }
+
+    fn list_snapshots(&self) -> Result<Vec<Snapshot>> {
+        Ok(self
+            .snapshots
+            .borrow()
+            .iter()
+            .map(|(snapshot, _)| snapshot.clone())
+            .collect())
+    }
+
+    fn archive_report(&self, name: &str, report: &str) -> Result<String> {
+        let mut reports = self.reports.borrow_mut();
+
+        reports.retain(|(archived, _)| archived != name);
+        reports.push((name.to_owned(), report.to_owned()));
+        Ok(format!("the archive as {name}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+
+    #[test]
+    fn memory_archive_should_keep_snapshots_and_reports() {
+        let archive = MemoryArchive::default();
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        db.create_story("Refunds".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let board = db.read_db().unwrap();
+
+        archive.create_snapshot("before-reorg", &board).unwrap();
+
+        assert!(archive.create_snapshot("before-reorg", &board).is_err());
+        assert!(archive.create_snapshot(" ", &board).is_err());
+        assert_eq!(
+            archive
+                .list_snapshots()
+                .unwrap()
+                .into_iter()
+                .map(|snapshot| snapshot.name)
+                .collect::<Vec<_>>(),
+            vec!["before-reorg".to_owned()]
+        );
+        assert_eq!(archive.read_snapshot("before-reorg").unwrap(), board);
+        assert!(archive.read_snapshot("missing").is_err());
+
+        archive.archive_report("SPRINT-0", "# SPRINT-0").unwrap();
+        assert_eq!(archive.report("SPRINT-0"), Some("# SPRINT-0".to_owned()));
+        assert_eq!(archive.report("SPRINT-1"), None);
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use std::cell::RefCell;
+
+use crate::db::DataBase;
+use crate::model::DB;
+
+/// Keeps every write in memory until `persist` hands the board to the wrapped
+/// database, so a session touches the file once per save instead of once per
+/// change.
+pub struct BufferedDatabase {
+    inner: Box<dyn DataBase>,
+    pending: RefCell<Option<DB>>,
+}
+
+impl BufferedDatabase {
+    pub fn new(inner: Box<dyn DataBase>) -> Self {
+        Self {
+            inner,
+            pending: RefCell::new(None),
+        }
+    }
+}
+
+impl DataBase for BufferedDatabase {
+    fn read_db(&self) -> Result<DB> {
+        match self.pending.borrow().as_ref() {
+            Some(db) => Ok(db.clone()),
+            None => self.inner.read_db(),
+        }
+    }
+
+    fn write_db(&self, database: &DB) -> Result<()> {
+        *self.pending.borrow_mut() = Some(database.clone());
+        Ok(())
+    }
+
+    fn revision(&self) -> Result<u64> {
+        match self.pending.borrow().as_ref() {
+            Some(db) => Ok(db.revision),
+            None => self.inner.revision(),
+        }
+    }
+
+    fn persist(&self) -> Result<()> {
+        let Some(pending) = self.pending.borrow().clone() else {
+            return Ok(());
+        };
+
+        self.inner.write_db(&pending)?;
+        *self.pending.borrow_mut() = None;
+        Ok(())
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        self.pending.borrow().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{JSONFileDatabase, JiraDataBase};
+    use std::io::Write;
+
+    #[test]
+    fn should_write_through_only_on_persist() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{ "last_item": {{ "type": "None" }}, "epics": {{}}, "stories": {{}} }}"#
+        )
+        .unwrap();
+        let on_disk = JSONFileDatabase::new(file.path().to_owned());
+        let db = JiraDataBase::from_database(Box::new(BufferedDatabase::new(Box::new(
+            JSONFileDatabase::new(file.path().to_owned()),
+        ))));
+
+        assert!(!db.has_unsaved_changes());
+
+        db.create_epic("Checkout".to_owned(), "".to_owned())
+            .unwrap();
+
+        assert!(db.has_unsaved_changes());
+        assert_eq!(db.read_db().unwrap().epics.len(), 1);
+        assert!(on_disk.read_db().unwrap().epics.is_empty());
+
+        db.persist().unwrap();
+
+        assert!(!db.has_unsaved_changes());
+        assert_eq!(on_disk.read_db().unwrap().epics.len(), 1);
+    }
+}
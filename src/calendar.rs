@@ -0,0 +1,252 @@
+use anyhow::{anyhow, Error};
+use chrono::{
+    DateTime, Datelike, Days, FixedOffset, Local, MappedLocalTime, NaiveDate, NaiveDateTime,
+    Offset, TimeZone, Utc, Weekday,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// How dates and times are shown: in which zone, in which order and from which
+/// day weeks count. Pages, prompts and reports all format through this so they
+/// agree with each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Calendar {
+    pub zone: Zone,
+    pub format: DateFormat,
+    pub week_start: Weekday,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self {
+            zone: Zone::Local,
+            format: DateFormat::Iso,
+            week_start: Weekday::Mon,
+        }
+    }
+}
+
+impl Calendar {
+    pub fn now(&self) -> DateTime<Zone> {
+        self.zone.now()
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+
+    /// `day` in the configured format, e.g. `2024-05-02` or `05/02/2024`.
+    pub fn date(&self, day: NaiveDate) -> String {
+        day.format(self.format.date_pattern()).to_string()
+    }
+
+    /// `at` in the configured zone and format, e.g. `2024-05-02 09:30`.
+    pub fn date_time(&self, at: DateTime<Utc>) -> String {
+        at.with_timezone(&self.zone)
+            .format(self.format.date_time_pattern())
+            .to_string()
+    }
+
+    /// The first day of the week `day` falls in.
+    pub fn start_of_week(&self, day: NaiveDate) -> NaiveDate {
+        let days_in =
+            (7 + day.weekday().num_days_from_monday() - self.week_start.num_days_from_monday()) % 7;
+
+        day - Days::new(days_in.into())
+    }
+}
+
+/// The order dates are written in. `iso` is also what date inputs always
+/// accept, next to the configured one.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateFormat {
+    /// `2024-05-02 09:30`
+    #[default]
+    Iso,
+    /// `05/02/2024 9:30 AM`
+    Us,
+    /// `02.05.2024 09:30`
+    Eu,
+}
+
+impl DateFormat {
+    pub fn date_pattern(&self) -> &'static str {
+        match self {
+            DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::Us => "%m/%d/%Y",
+            DateFormat::Eu => "%d.%m.%Y",
+        }
+    }
+
+    fn date_time_pattern(&self) -> &'static str {
+        match self {
+            DateFormat::Iso => "%Y-%m-%d %H:%M",
+            DateFormat::Us => "%m/%d/%Y %-I:%M %p",
+            DateFormat::Eu => "%d.%m.%Y %H:%M",
+        }
+    }
+
+    /// How a date is typed, for prompts, e.g. `YYYY-MM-DD`.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            DateFormat::Iso => "YYYY-MM-DD",
+            DateFormat::Us => "MM/DD/YYYY",
+            DateFormat::Eu => "DD.MM.YYYY",
+        }
+    }
+
+    /// Reads a date written in this format or as `YYYY-MM-DD`.
+    pub fn parse(&self, input: &str) -> Option<NaiveDate> {
+        [self.date_pattern(), DateFormat::Iso.date_pattern()]
+            .into_iter()
+            .find_map(|pattern| NaiveDate::parse_from_str(input, pattern).ok())
+    }
+}
+
+/// The time zone timestamps are shown in. They are always stored as UTC, this
+/// only changes how they read: the machine's own zone, or a fixed offset for
+/// teams that agree on one, e.g. `"+05:30"` or `"UTC"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Zone {
+    #[default]
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    pub fn is_local(&self) -> bool {
+        *self == Zone::Local
+    }
+
+    pub fn now(&self) -> DateTime<Zone> {
+        Utc::now().with_timezone(self)
+    }
+}
+
+impl TimeZone for Zone {
+    type Offset = FixedOffset;
+
+    fn from_offset(offset: &FixedOffset) -> Self {
+        Zone::Fixed(*offset)
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+        match self {
+            Zone::Local => Local
+                .offset_from_local_date(local)
+                .map(|offset| offset.fix()),
+            Zone::Fixed(offset) => MappedLocalTime::Single(*offset),
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<FixedOffset> {
+        match self {
+            Zone::Local => Local
+                .offset_from_local_datetime(local)
+                .map(|offset| offset.fix()),
+            Zone::Fixed(offset) => MappedLocalTime::Single(*offset),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        match self {
+            Zone::Local => Local.offset_from_utc_date(utc).fix(),
+            Zone::Fixed(offset) => *offset,
+        }
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        match self {
+            Zone::Local => Local.offset_from_utc_datetime(utc).fix(),
+            Zone::Fixed(offset) => *offset,
+        }
+    }
+}
+
+impl FromStr for Zone {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "local" => Ok(Zone::Local),
+            "UTC" | "utc" | "Z" => Ok(Zone::Fixed(Utc.fix())),
+            offset => offset.parse().map(Zone::Fixed).map_err(|_| {
+                anyhow!("Unknown time zone '{offset}', use \"local\", \"UTC\" or an offset like \"+05:30\"")
+            }),
+        }
+    }
+}
+
+impl TryFrom<String> for Zone {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Zone::Local => write!(f, "local"),
+            Zone::Fixed(offset) if offset.local_minus_utc() == 0 => write!(f, "UTC"),
+            Zone::Fixed(offset) => write!(f, "{offset}"),
+        }
+    }
+}
+
+impl From<Zone> for String {
+    fn from(zone: Zone) -> Self {
+        zone.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zone_should_parse_and_shift_utc_times() {
+        let zone = "+05:30".parse::<Zone>().unwrap();
+        let at = Utc.with_ymd_and_hms(2024, 5, 1, 20, 0, 0).unwrap();
+
+        assert_eq!(
+            at.with_timezone(&zone).format("%Y-%m-%d %H:%M").to_string(),
+            "2024-05-02 01:30"
+        );
+        assert_eq!(zone.to_string(), "+05:30");
+        assert_eq!("UTC".parse::<Zone>().unwrap().to_string(), "UTC");
+        assert_eq!("local".parse::<Zone>().unwrap(), Zone::Local);
+        assert!("Mars/Olympus".parse::<Zone>().is_err());
+    }
+
+    #[test]
+    fn calendar_should_format_in_the_configured_style() {
+        let calendar = Calendar {
+            zone: "UTC".parse().unwrap(),
+            format: DateFormat::Us,
+            week_start: Weekday::Sun,
+        };
+        let at = Utc.with_ymd_and_hms(2024, 5, 2, 14, 5, 0).unwrap();
+        // A Thursday.
+        let day = at.date_naive();
+
+        assert_eq!(calendar.date(day), "05/02/2024");
+        assert_eq!(calendar.date_time(at), "05/02/2024 2:05 PM");
+        assert_eq!(
+            calendar.start_of_week(day),
+            NaiveDate::from_ymd_opt(2024, 4, 28).unwrap()
+        );
+        assert_eq!(
+            Calendar::default().start_of_week(day),
+            NaiveDate::from_ymd_opt(2024, 4, 29).unwrap()
+        );
+
+        assert_eq!(DateFormat::Eu.parse("02.05.2024"), Some(day));
+        assert_eq!(DateFormat::Eu.parse("2024-05-02"), Some(day));
+        assert_eq!(DateFormat::Us.parse("02.05.2024"), None);
+    }
+}
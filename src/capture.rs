@@ -0,0 +1,336 @@
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+use crate::calendar::DateFormat;
+use crate::keys::parse_id;
+use crate::model::{FieldEdit, ItemId, Priority};
+
+/// A story described in a single line, e.g.
+/// `Fix login redirect !high @alice #auth ^epic:3 due:fri`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Capture {
+    pub name: String,
+    pub priority: Option<Priority>,
+    pub assignee: Option<String>,
+    pub labels: Vec<String>,
+    pub epic_id: Option<u32>,
+    pub due: Option<NaiveDate>,
+}
+
+/// Splits the input into tokens: `!priority`, `@assignee`, `#label`, `^epic:<id>`
+/// and `due:<date>` fill the matching fields and every other word becomes part of
+/// the name. Relative due dates are resolved against `today` and others can be
+/// written in `format`.
+pub fn parse_capture(input: &str, today: NaiveDate, format: DateFormat) -> Result<Capture> {
+    let mut capture = Capture::default();
+    let mut name = Vec::new();
+
+    for token in input.split_whitespace() {
+        if let Some(priority) = token.strip_prefix('!').filter(|p| !p.is_empty()) {
+            capture.priority = Some(parse_priority(priority)?);
+        } else if let Some(assignee) = token.strip_prefix('@').filter(|a| !a.is_empty()) {
+            capture.assignee = Some(assignee.to_owned());
+        } else if let Some(label) = token.strip_prefix('#').filter(|l| !l.is_empty()) {
+            if !capture.labels.iter().any(|existing| existing == label) {
+                capture.labels.push(label.to_owned());
+            }
+        } else if let Some(epic_id) = token.strip_prefix("^epic:") {
+            capture.epic_id =
+                Some(parse_id(epic_id).ok_or_else(|| anyhow!("{epic_id} is not a valid epic ID"))?);
+        } else if let Some(due) = token.strip_prefix("due:") {
+            capture.due = Some(parse_due_date(due, today, format)?);
+        } else {
+            name.push(token);
+        }
+    }
+
+    if name.is_empty() {
+        return Err(anyhow!("A story needs a name"));
+    }
+
+    capture.name = name.join(" ");
+    Ok(capture)
+}
+
+impl Capture {
+    /// Swaps the labels and assignee for the existing ones they stand for, see
+    /// [`complete`]. Returns what was swapped, e.g. `#back to #backend`.
+    pub fn complete(&mut self, labels: &[String], assignees: &[String]) -> Vec<String> {
+        let mut completed = Vec::new();
+        let mut note = |sigil: char, typed: &str, known: &str| {
+            if typed != known {
+                completed.push(format!("{sigil}{typed} to {sigil}{known}"));
+            }
+        };
+
+        if let Some(assignee) = &mut self.assignee {
+            let known = complete(assignee, assignees);
+            note('@', assignee, known);
+            *assignee = known.to_owned();
+        }
+
+        let mut seen = Vec::new();
+        for label in &self.labels {
+            let known = complete(label, labels);
+            note('#', label, known);
+            if !seen.iter().any(|existing| existing == known) {
+                seen.push(known.to_owned());
+            }
+        }
+        self.labels = seen;
+
+        completed
+    }
+}
+
+/// The value in `known` that `typed` stands for: the one it spells ignoring case,
+/// `-` and `_`, or else the only one starting with it. Anything else is kept as
+/// a new value.
+pub fn complete<'a>(typed: &'a str, known: &'a [String]) -> &'a str {
+    let normalize = |value: &str| {
+        value
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let typed_key = normalize(typed);
+
+    if known.iter().any(|value| value == typed) {
+        return typed;
+    }
+    if let Some(value) = known.iter().find(|value| normalize(value) == typed_key) {
+        return value;
+    }
+
+    match known
+        .iter()
+        .filter(|value| normalize(value).starts_with(&typed_key))
+        .collect::<Vec<_>>()[..]
+    {
+        [value] => value,
+        _ => typed,
+    }
+}
+
+/// Reads the changes of a bulk edit, e.g. `#billing -#legacy !high @alice`:
+/// `#label` adds a label and `-#label` removes it, `!priority` and `@assignee`
+/// set those fields, `!-` and `@-` clear them, and `sprint:<id>` moves the
+/// stories into a sprint.
+pub fn parse_field_edits(input: &str) -> Result<Vec<FieldEdit>> {
+    let edits = input
+        .split_whitespace()
+        .map(|token| {
+            if let Some(label) = token.strip_prefix("-#").filter(|l| !l.is_empty()) {
+                Ok(FieldEdit::RemoveLabel(label.to_owned()))
+            } else if let Some(label) = token.strip_prefix('#').filter(|l| !l.is_empty()) {
+                Ok(FieldEdit::AddLabel(label.to_owned()))
+            } else if let Some(priority) = token.strip_prefix('!').filter(|p| !p.is_empty()) {
+                match priority {
+                    "-" => Ok(FieldEdit::SetPriority(None)),
+                    _ => Ok(FieldEdit::SetPriority(Some(parse_priority(priority)?))),
+                }
+            } else if let Some(assignee) = token.strip_prefix('@').filter(|a| !a.is_empty()) {
+                Ok(FieldEdit::SetAssignee(
+                    (assignee != "-").then(|| assignee.to_owned()),
+                ))
+            } else if let Some(sprint_id) = token.strip_prefix("sprint:") {
+                parse_id(sprint_id)
+                    .map(|id| FieldEdit::MoveToSprint(ItemId(id)))
+                    .ok_or_else(|| anyhow!("{sprint_id} is not a valid sprint ID"))
+            } else {
+                Err(anyhow!(
+                    "{token} is not a change, use #label, -#label, !priority, @assignee or sprint:<id>"
+                ))
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if edits.is_empty() {
+        return Err(anyhow!("Say what to change, e.g. #label or @assignee"));
+    }
+
+    Ok(edits)
+}
+
+pub fn parse_priority(input: &str) -> Result<Priority> {
+    match input.to_lowercase().as_str() {
+        "critical" | "crit" => Ok(Priority::Critical),
+        "high" => Ok(Priority::High),
+        "medium" | "med" => Ok(Priority::Medium),
+        "low" => Ok(Priority::Low),
+        _ => Err(anyhow!("{input} is not a valid priority")),
+    }
+}
+
+/// Accepts `today`, `tomorrow`, a weekday (the next one, today included) or a
+/// date written in `format` or as `YYYY-MM-DD`.
+pub fn parse_due_date(input: &str, today: NaiveDate, format: DateFormat) -> Result<NaiveDate> {
+    let weekday = match input.to_lowercase().as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + Days::new(1)),
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => {
+            return format
+                .parse(input)
+                .ok_or_else(|| anyhow!("{input} is not a valid due date"))
+        }
+    };
+    let days_ahead =
+        (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+
+    Ok(today + Days::new(days_ahead.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wednesday() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()
+    }
+
+    #[test]
+    fn parse_capture_should_fill_every_field() {
+        let capture = parse_capture(
+            "Fix login redirect !high @alice #auth #web ^epic:3 due:fri",
+            wednesday(),
+            DateFormat::Iso,
+        )
+        .unwrap();
+
+        assert_eq!(
+            capture,
+            Capture {
+                name: "Fix login redirect".to_owned(),
+                priority: Some(Priority::High),
+                assignee: Some("alice".to_owned()),
+                labels: vec!["auth".to_owned(), "web".to_owned()],
+                epic_id: Some(3),
+                due: NaiveDate::from_ymd_opt(2024, 5, 3),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_capture_should_keep_tokens_in_any_order() {
+        let capture =
+            parse_capture("#ops Rotate !low keys #ops", wednesday(), DateFormat::Iso).unwrap();
+
+        assert_eq!(capture.name, "Rotate keys");
+        assert_eq!(capture.priority, Some(Priority::Low));
+        assert_eq!(capture.labels, vec!["ops".to_owned()]);
+        assert_eq!(capture.epic_id, None);
+    }
+
+    #[test]
+    fn parse_capture_should_treat_bare_symbols_as_words() {
+        let capture =
+            parse_capture("Support C# and ! in names", wednesday(), DateFormat::Iso).unwrap();
+
+        assert_eq!(capture.name, "Support C# and ! in names");
+    }
+
+    #[test]
+    fn complete_should_pick_the_existing_value_typed() {
+        let known = ["backend".to_owned(), "billing".to_owned(), "bug".to_owned()];
+
+        assert_eq!(complete("backend", &known), "backend");
+        assert_eq!(complete("Back-End", &known), "backend");
+        assert_eq!(complete("bi", &known), "billing");
+        assert_eq!(complete("b", &known), "b");
+        assert_eq!(complete("frontend", &known), "frontend");
+    }
+
+    #[test]
+    fn capture_complete_should_swap_in_existing_labels_and_assignees() {
+        let mut capture = parse_capture(
+            "Fix it @al #back #Backend #new",
+            wednesday(),
+            DateFormat::Iso,
+        )
+        .unwrap();
+
+        let completed = capture.complete(&["backend".to_owned()], &["alice".to_owned()]);
+
+        assert_eq!(capture.assignee, Some("alice".to_owned()));
+        assert_eq!(capture.labels, vec!["backend".to_owned(), "new".to_owned()]);
+        assert_eq!(
+            completed,
+            vec!["@al to @alice", "#back to #backend", "#Backend to #backend"]
+        );
+    }
+
+    #[test]
+    fn parse_capture_should_fail_on_invalid_tokens() {
+        assert!(parse_capture("Fix !urgent", wednesday(), DateFormat::Iso).is_err());
+        assert!(parse_capture("Fix ^epic:three", wednesday(), DateFormat::Iso).is_err());
+        assert!(parse_capture("Fix due:someday", wednesday(), DateFormat::Iso).is_err());
+        assert!(parse_capture("!high @alice", wednesday(), DateFormat::Iso).is_err());
+    }
+
+    #[test]
+    fn parse_field_edits_should_read_every_change() {
+        assert_eq!(
+            parse_field_edits("#billing -#legacy !high @- sprint:2").unwrap(),
+            vec![
+                FieldEdit::AddLabel("billing".to_owned()),
+                FieldEdit::RemoveLabel("legacy".to_owned()),
+                FieldEdit::SetPriority(Some(Priority::High)),
+                FieldEdit::SetAssignee(None),
+                FieldEdit::MoveToSprint(ItemId(2)),
+            ]
+        );
+        assert_eq!(
+            parse_field_edits("!- @alice").unwrap(),
+            vec![
+                FieldEdit::SetPriority(None),
+                FieldEdit::SetAssignee(Some("alice".to_owned())),
+            ]
+        );
+        assert_eq!(
+            parse_field_edits("sprint:SPRINT-4").unwrap(),
+            vec![FieldEdit::MoveToSprint(ItemId(4))]
+        );
+        assert!(parse_field_edits("").is_err());
+        assert!(parse_field_edits("billing").is_err());
+        assert!(parse_field_edits("sprint:next").is_err());
+    }
+
+    #[test]
+    fn parse_due_date_should_resolve_relative_dates() {
+        let today = wednesday();
+
+        assert_eq!(
+            parse_due_date("today", today, DateFormat::Iso).unwrap(),
+            today
+        );
+        assert_eq!(
+            parse_due_date("tomorrow", today, DateFormat::Iso).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 5, 2).unwrap()
+        );
+        assert_eq!(
+            parse_due_date("wed", today, DateFormat::Iso).unwrap(),
+            today
+        );
+        assert_eq!(
+            parse_due_date("Monday", today, DateFormat::Iso).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 5, 6).unwrap()
+        );
+        assert_eq!(
+            parse_due_date("2024-12-24", today, DateFormat::Iso).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 24).unwrap()
+        );
+        assert_eq!(
+            parse_due_date("12/24/2024", today, DateFormat::Us).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 24).unwrap()
+        );
+        assert!(parse_due_date("12/24/2024", today, DateFormat::Iso).is_err());
+    }
+}
@@ -0,0 +1,1371 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use std::{io::Read, path::PathBuf};
+
+use crate::config::{hash_passphrase, Config};
+use crate::db::{DataBase, JiraDataBase, SortBy};
+use crate::directory_db::DirectoryDatabase;
+use crate::export::{is_stdio, open_input, read_epic_bundle, write_epic_bundle, write_output};
+use crate::graph::{dependency_graph, GraphFormat, GraphScope};
+use crate::interchange::{add_board, export_board};
+use crate::io_utils::{confirm, get_hidden_input};
+use crate::jira_import::{add_issues, parse_backup};
+use crate::keys::{self, epic_key, story_key};
+use crate::model::{Filter, ItemDetail, ItemId, ItemStatus};
+use crate::obsidian::write_vault;
+use crate::org::board_org;
+use crate::plugins::Plugins;
+use crate::reports::{
+    archive_report, cumulative_flow, cumulative_flow_csv, gantt, sprint_report, standup,
+};
+use crate::search::{parse_filter, parse_status};
+use crate::snapshots::{create_snapshot, list_snapshots, read_snapshot};
+use crate::taskwarrior::Taskwarrior;
+use crate::todoist::{bundles, Todoist};
+use crate::ui::{delete_epic_question, delete_story_question, restore_snapshot_question};
+
+pub const USAGE: &str = "Usage:
+    jira-clone                              start the interactive board
+    jira-clone last [n]                     open the board on the most recently changed item, or the nth
+    jira-clone export-epic <id> <file>      write an epic and its stories to a JSON bundle
+    jira-clone import-epic <file>           add the epic bundle in <file> to this board
+    jira-clone export json [file]           write the board in the versioned interchange format
+    jira-clone import json <file>           add the epics and stories of an interchange file
+    jira-clone standup [user]               print yesterday's, today's and blocked stories
+    jira-clone cfd <file>                   write daily story counts per status as CSV
+    jira-clone gantt [file]                 write sprints and epics as a Mermaid gantt chart
+    jira-clone org [file]                   write the board as an Org file
+    jira-clone obsidian <dir>               write a note per epic and story into an Obsidian vault
+    jira-clone graph <dot|mermaid> [--epic id] [--unresolved]
+                                            print which stories block or were split from others
+    jira-clone delete-epic <id>             delete an epic and its stories
+    jira-clone delete-story <id>            delete a story
+    jira-clone close-sprint <id>            close a sprint and write its report to reports/<key>.md
+    jira-clone snapshot create <name>       save a copy of the board under <name> in snapshots/
+    jira-clone snapshot restore <name>      put the board back the way snapshot <name> has it
+    jira-clone snapshot list                print the saved snapshots
+    jira-clone convert-to-dir <dir>         copy the board to one file per item in <dir>
+    jira-clone run <command> [args]         run a command added by a plugin
+    jira-clone list <epics|stories> [--sort id|name|status|priority] [--offset n] [--limit n]
+                                            print a page of epics or stories
+    jira-clone bulk-status <status> <filter>
+                                            move every item matching the filter to <status>
+    jira-clone replace <text> <with>        replace text in item names and descriptions, asking per item
+    jira-clone import-jira <file>           add the issues of a Jira Server XML or CSV export
+    jira-clone import-todoist               add Todoist projects as epics, using $TODOIST_API_TOKEN
+    jira-clone taskwarrior [project]        sync stories with Taskwarrior tasks, in <project> if given
+    jira-clone serve <address>              share this board with --remote clients over HTTP, on host:port or unix:<path>,
+                                            to other machines only with LGR_SERVER_TOKEN set on both ends
+    jira-clone hash-passphrase              hash a passphrase for \"passphrases\" in the config
+    jira-clone doctor                       check the config and the board and say how to fix problems
+
+A <file> of - reads from stdin or writes to stdout.
+
+Options:
+    --yes, -y                               don't ask before deleting or bulk updates
+    --dry-run                               print what would change without writing the board
+    --verbose, -v                           also print the log on stderr, for headless commands
+    --view <name>                           start the board on a saved filter, on the items assigned to
+                                            you with mine, or on the dashboard, week, stale, board,
+                                            tree, notes, sprints, archive, standup, workload, labels,
+                                            snapshots or notifications page
+    --remote <address>                      use the board a `jira-clone serve` shares instead of a local one";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Interactive,
+    Last {
+        nth: usize,
+    },
+    ExportEpic {
+        epic_id: u32,
+        path: PathBuf,
+    },
+    ImportEpic {
+        path: PathBuf,
+    },
+    ExportJson {
+        path: PathBuf,
+    },
+    ImportJson {
+        path: PathBuf,
+    },
+    Standup {
+        user: Option<String>,
+    },
+    CumulativeFlow {
+        path: PathBuf,
+    },
+    Gantt {
+        path: PathBuf,
+    },
+    Org {
+        path: PathBuf,
+    },
+    Obsidian {
+        path: PathBuf,
+    },
+    Graph {
+        format: GraphFormat,
+        scope: GraphScope,
+    },
+    DeleteEpic {
+        epic_id: u32,
+    },
+    DeleteStory {
+        story_id: u32,
+    },
+    CloseSprint {
+        sprint_id: u32,
+    },
+    CreateSnapshot {
+        name: String,
+    },
+    RestoreSnapshot {
+        name: String,
+    },
+    ListSnapshots,
+    Replace {
+        from: String,
+        to: String,
+    },
+    BulkStatus {
+        status: ItemStatus,
+        filter: Filter,
+    },
+    ConvertToDirectory {
+        path: PathBuf,
+    },
+    Plugin {
+        name: String,
+        args: Vec<String>,
+    },
+    List(ListOptions),
+    HashPassphrase,
+    Doctor,
+    Serve {
+        address: String,
+    },
+    ImportTodoist,
+    ImportJira {
+        path: PathBuf,
+    },
+    Taskwarrior {
+        project: Option<String>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListOptions {
+    pub stories: bool,
+    pub sort: SortBy,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// How many items `list` prints without `--limit`.
+const DEFAULT_LIST_LIMIT: usize = 50;
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+
+        match args.as_slice() {
+            [] => Ok(Command::Interactive),
+            ["last"] => Ok(Command::Last { nth: 1 }),
+            ["last", nth] => match nth.parse() {
+                Ok(nth) if nth > 0 => Ok(Command::Last { nth }),
+                _ => Err(anyhow!(
+                    "{nth} is not a position, use 1 for the most recent item"
+                )),
+            },
+            ["export-epic", epic_id, path] => Ok(Command::ExportEpic {
+                epic_id: parse_id(epic_id)?,
+                path: PathBuf::from(path),
+            }),
+            ["import-epic", path] => Ok(Command::ImportEpic {
+                path: PathBuf::from(path),
+            }),
+            ["export", "json"] => Ok(Command::ExportJson {
+                path: PathBuf::from("-"),
+            }),
+            ["export", "json", path] => Ok(Command::ExportJson {
+                path: PathBuf::from(path),
+            }),
+            ["import", "json", path] => Ok(Command::ImportJson {
+                path: PathBuf::from(path),
+            }),
+            ["standup"] => Ok(Command::Standup { user: None }),
+            ["standup", user] => Ok(Command::Standup {
+                user: Some(user.to_string()),
+            }),
+            ["cfd", path] => Ok(Command::CumulativeFlow {
+                path: PathBuf::from(path),
+            }),
+            ["gantt"] => Ok(Command::Gantt {
+                path: PathBuf::from("-"),
+            }),
+            ["gantt", path] => Ok(Command::Gantt {
+                path: PathBuf::from(path),
+            }),
+            ["org"] => Ok(Command::Org {
+                path: PathBuf::from("-"),
+            }),
+            ["org", path] => Ok(Command::Org {
+                path: PathBuf::from(path),
+            }),
+            ["obsidian", path] => Ok(Command::Obsidian {
+                path: PathBuf::from(path),
+            }),
+            ["graph", format, options @ ..] => Ok(Command::Graph {
+                format: format.parse()?,
+                scope: parse_graph_scope(options)?,
+            }),
+            ["delete-epic", epic_id] => Ok(Command::DeleteEpic {
+                epic_id: parse_id(epic_id)?,
+            }),
+            ["delete-story", story_id] => Ok(Command::DeleteStory {
+                story_id: parse_id(story_id)?,
+            }),
+            ["close-sprint", sprint_id] => Ok(Command::CloseSprint {
+                sprint_id: parse_id(sprint_id)?,
+            }),
+            ["snapshot", "create", name] => Ok(Command::CreateSnapshot {
+                name: name.to_string(),
+            }),
+            ["snapshot", "restore", name] => Ok(Command::RestoreSnapshot {
+                name: name.to_string(),
+            }),
+            ["snapshot", "list"] => Ok(Command::ListSnapshots),
+            ["convert-to-dir", path] => Ok(Command::ConvertToDirectory {
+                path: PathBuf::from(path),
+            }),
+            ["run", name, args @ ..] => Ok(Command::Plugin {
+                name: name.to_string(),
+                args: args.iter().map(|arg| arg.to_string()).collect(),
+            }),
+            ["list", kind, options @ ..] => Ok(Command::List(parse_list_options(kind, options)?)),
+            ["bulk-status", status, filter @ ..] if !filter.is_empty() => Ok(Command::BulkStatus {
+                status: parse_status(status)?,
+                filter: parse_filter(&filter.join(" "))?,
+            }),
+            ["replace", from, to] if !from.is_empty() => Ok(Command::Replace {
+                from: from.to_string(),
+                to: to.to_string(),
+            }),
+            ["hash-passphrase"] => Ok(Command::HashPassphrase),
+            ["doctor"] => Ok(Command::Doctor),
+            ["serve", address] => Ok(Command::Serve {
+                address: address.to_string(),
+            }),
+            ["import-todoist"] => Ok(Command::ImportTodoist),
+            ["import-jira", path] => Ok(Command::ImportJira {
+                path: PathBuf::from(path),
+            }),
+            ["taskwarrior"] => Ok(Command::Taskwarrior { project: None }),
+            ["taskwarrior", project] => Ok(Command::Taskwarrior {
+                project: Some(project.to_string()),
+            }),
+            _ => Err(anyhow!("Invalid arguments: {}", args.join(" "))),
+        }
+    }
+}
+
+fn parse_list_options(kind: &str, options: &[&str]) -> Result<ListOptions> {
+    let mut list = ListOptions {
+        stories: match kind {
+            "epics" => false,
+            "stories" => true,
+            _ => return Err(anyhow!("Can only list epics or stories, not {kind}")),
+        },
+        sort: SortBy::Id,
+        offset: 0,
+        limit: DEFAULT_LIST_LIMIT,
+    };
+    let count = |value: &str| {
+        value
+            .parse::<usize>()
+            .map_err(|_| anyhow!("{value} is not a valid number"))
+    };
+
+    for option in options.chunks(2) {
+        match option {
+            ["--sort", sort] => list.sort = sort.parse()?,
+            ["--offset", offset] => list.offset = count(offset)?,
+            ["--limit", limit] => list.limit = count(limit)?,
+            _ => return Err(anyhow!("Invalid list option: {}", option.join(" "))),
+        }
+    }
+
+    Ok(list)
+}
+
+fn parse_graph_scope(options: &[&str]) -> Result<GraphScope> {
+    let mut scope = GraphScope::default();
+    let mut options = options.iter();
+
+    while let Some(option) = options.next() {
+        match *option {
+            "--unresolved" => scope.unresolved = true,
+            "--epic" => {
+                let epic_id = options
+                    .next()
+                    .ok_or_else(|| anyhow!("--epic needs an epic ID"))?;
+
+                scope.epic = Some(ItemId(parse_id(epic_id)?));
+            }
+            _ => return Err(anyhow!("Invalid graph option: {option}")),
+        }
+    }
+
+    Ok(scope)
+}
+
+fn parse_id(input: &str) -> Result<u32> {
+    keys::parse_id(input).ok_or_else(|| anyhow!("{input} is not a valid ID"))
+}
+
+/// Removes a flag spelled any of `names` from `args`, returning whether it was
+/// there.
+pub fn take_flag(args: &mut Vec<String>, names: &[&str]) -> bool {
+    let count = args.len();
+
+    args.retain(|arg| !names.contains(&arg.as_str()));
+    args.len() != count
+}
+
+/// Removes an option spelled `name` and the value after it from `args`,
+/// returning the value if it was there.
+pub fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>> {
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+
+    if index + 1 == args.len() {
+        return Err(anyhow!("{name} needs a value"));
+    }
+
+    args.remove(index);
+    Ok(Some(args.remove(index)))
+}
+
+/// Asks `question` on stdin, for commands run without `--yes`.
+pub fn ask_confirmation(question: &str) -> bool {
+    confirm(question, false)
+}
+
+/// Runs a command that doesn't need the interactive UI. Destructive commands
+/// check `confirm` first when the config asks for a confirmation.
+pub fn run(
+    command: Command,
+    database: &JiraDataBase,
+    config: &Config,
+    plugins: &Plugins,
+    confirm: &dyn Fn(&str) -> bool,
+) -> Result<()> {
+    match command {
+        Command::Interactive | Command::Last { .. } => {
+            Err(anyhow!("The interactive board can't run headless"))
+        }
+        Command::Serve { .. } => Err(anyhow!("The board server can't run as a command")),
+        Command::Doctor => Err(anyhow!("The doctor runs before the board is opened")),
+        Command::ExportEpic { epic_id, path } => {
+            let bundle = database.export_epic(ItemId(epic_id))?;
+
+            write_epic_bundle(&path, &bundle)?;
+            if !is_stdio(&path) {
+                println!(
+                    "Exported {} with {} stories to {}",
+                    bundle.epic.key(),
+                    bundle.stories.len(),
+                    path.display()
+                );
+            }
+
+            Ok(())
+        }
+        Command::ImportEpic { path } => {
+            let bundle = read_epic_bundle(&path)?;
+            let story_count = bundle.stories.len();
+            let epic_id = database.import_epic(bundle)?;
+
+            println!(
+                "Imported {} with {story_count} stories",
+                epic_key(epic_id.0)
+            );
+
+            Ok(())
+        }
+        Command::ExportJson { path } => {
+            let board = export_board(&database.read_db()?);
+
+            write_output(
+                &path,
+                &format!("{}\n", serde_json::to_string_pretty(&board)?),
+            )?;
+            if !is_stdio(&path) {
+                println!(
+                    "Exported {} epics and {} stories to {}",
+                    board.epics.len(),
+                    board.stories.len(),
+                    path.display()
+                );
+            }
+
+            Ok(())
+        }
+        Command::ImportJson { path } => {
+            let input =
+                open_input(&path).with_context(|| anyhow!("Failed to read {}", path.display()))?;
+            let board = serde_json::from_reader(input)
+                .with_context(|| anyhow!("{} is not an interchange file", path.display()))?;
+            let mut db = database.read_db()?;
+            let summary = add_board(&mut db, board, Utc::now())?;
+
+            database.replace_db(db)?;
+            println!(
+                "Imported {} epics and {} stories from {}",
+                summary.epics,
+                summary.stories,
+                path.display()
+            );
+
+            Ok(())
+        }
+        Command::Standup { user } => {
+            let user = user.unwrap_or_else(|| database.user().to_owned());
+
+            println!(
+                "{}",
+                standup(&database.read_db()?, &user, &config.calendar(), Utc::now())
+            );
+
+            Ok(())
+        }
+        Command::CumulativeFlow { path } => {
+            let flow = cumulative_flow(&database.read_db()?, config.calendar().today());
+
+            write_output(&path, &cumulative_flow_csv(&flow))?;
+            if !is_stdio(&path) {
+                println!(
+                    "Wrote {} days of flow data to {}",
+                    flow.len(),
+                    path.display()
+                );
+            }
+
+            Ok(())
+        }
+        Command::Gantt { path } => {
+            write_output(&path, &gantt(&database.read_db()?))?;
+            if !is_stdio(&path) {
+                println!("Wrote the gantt chart to {}", path.display());
+            }
+
+            Ok(())
+        }
+        Command::Org { path } => {
+            write_output(&path, &board_org(&database.read_db()?))?;
+            if !is_stdio(&path) {
+                println!("Wrote the board to {}", path.display());
+            }
+
+            Ok(())
+        }
+        Command::Obsidian { path } => {
+            let count = write_vault(&path, &database.read_db()?)?;
+
+            println!("Wrote {count} notes to {}", path.display());
+
+            Ok(())
+        }
+        Command::Graph { format, scope } => {
+            if let Some(epic_id) = scope.epic {
+                database.get_epic(epic_id)?;
+            }
+
+            print!("{}", dependency_graph(&database.read_db()?, format, &scope));
+
+            Ok(())
+        }
+        Command::DeleteEpic { epic_id } => {
+            let epic = database.get_epic(ItemId(epic_id))?;
+            if config.confirm.delete_epic && !confirm(&delete_epic_question(&epic)) {
+                println!("Nothing was deleted");
+                return Ok(());
+            }
+
+            database.delete_epic(ItemId(epic_id))?;
+            println!("Deleted {}", epic_key(epic_id));
+
+            Ok(())
+        }
+        Command::DeleteStory { story_id } => {
+            let db = database.read_db()?;
+            let Some(story) = db.stories.get(&story_id) else {
+                return Err(anyhow!("Story ID: {:?} was not found.", ItemId(story_id)));
+            };
+
+            if config.confirm.delete_story && !confirm(&delete_story_question(story)) {
+                println!("Nothing was deleted");
+                return Ok(());
+            }
+
+            let epic_id = db
+                .epics
+                .values()
+                .find(|epic| epic.stories.contains(&ItemId(story_id)))
+                .map(|epic| epic.detail.id);
+
+            database.delete_story(ItemId(story_id), epic_id)?;
+            println!("Deleted {}", story_key(story_id));
+
+            Ok(())
+        }
+        Command::CloseSprint { sprint_id } => {
+            let calendar = config.calendar();
+            let sprint = database.close_sprint(ItemId(sprint_id), calendar.today())?;
+            let report = sprint_report(&database.read_db()?, &sprint, &calendar);
+            let path = archive_report(&config.reports_dir(), &sprint.key(), &report)?;
+
+            println!(
+                "Closed {}, the report is in {}",
+                sprint.key(),
+                path.display()
+            );
+
+            Ok(())
+        }
+        Command::CreateSnapshot { name } => {
+            let path = create_snapshot(&config.snapshots_dir(), &name, &database.read_db()?)?;
+
+            println!("Saved the board as \"{name}\" in {}", path.display());
+
+            Ok(())
+        }
+        Command::RestoreSnapshot { name } => {
+            let snapshot = read_snapshot(&config.snapshots_dir(), &name)?;
+            if config.confirm.restore_snapshot && !confirm(&restore_snapshot_question(&name)) {
+                println!("Nothing was restored");
+                return Ok(());
+            }
+
+            database.restore(snapshot)?;
+            println!("Restored the board to \"{name}\"");
+
+            Ok(())
+        }
+        Command::ListSnapshots => {
+            let calendar = config.calendar();
+
+            for snapshot in list_snapshots(&config.snapshots_dir())? {
+                println!(
+                    "{}  {}",
+                    calendar.date_time(snapshot.created_at),
+                    snapshot.name
+                );
+            }
+
+            Ok(())
+        }
+        Command::Replace { from, to } => {
+            let mentions = database.items_mentioning(&from)?;
+
+            if mentions.is_empty() {
+                println!("Nothing mentions \"{from}\"");
+                return Ok(());
+            }
+
+            println!("\"{from}\" appears in:");
+            for (item, detail) in &mentions {
+                match detail.name.replace(&from, &to) {
+                    name if name != detail.name => {
+                        println!("- {} '{}' -> '{name}'", item.key(), detail.name)
+                    }
+                    _ => println!("- {} '{}' (description)", item.key(), detail.name),
+                }
+            }
+
+            let items = mentions
+                .into_iter()
+                .filter(|(item, detail)| {
+                    confirm(&format!("Replace it in {} '{}'?", item.key(), detail.name))
+                })
+                .map(|(item, _)| item)
+                .collect::<Vec<_>>();
+
+            database.replace_text(&from, &to, &items)?;
+            println!("Replaced \"{from}\" in {} items", items.len());
+
+            Ok(())
+        }
+        Command::BulkStatus { status, filter } => {
+            let items = database
+                .search(&filter)?
+                .into_iter()
+                .map(|hit| hit.item)
+                .collect::<Vec<_>>();
+            let question = format!("Move {} items to {status}?", items.len());
+
+            if config.confirm.bulk_update && !confirm(&question) {
+                println!("Nothing was updated");
+                return Ok(());
+            }
+
+            database.update_statuses(&items, status.clone())?;
+            println!("Updated {} items to {status}", items.len());
+
+            Ok(())
+        }
+        Command::Plugin { name, args } => plugins.run_command(&name, &args, database),
+        Command::List(list) => {
+            let row = |key: String, detail: &ItemDetail| {
+                format!("{key}\t{}\t{}", detail.status, detail.name)
+            };
+            let (rows, total) = if list.stories {
+                let page = database.list_stories(list.offset, list.limit, list.sort)?;
+                let rows = page
+                    .items
+                    .iter()
+                    .map(|story| row(story.key(), &story.detail))
+                    .collect::<Vec<_>>();
+
+                (rows, page.total)
+            } else {
+                let page = database.list_epics(list.offset, list.limit, list.sort)?;
+                let rows = page
+                    .items
+                    .iter()
+                    .map(|epic| row(epic.key(), &epic.detail))
+                    .collect::<Vec<_>>();
+
+                (rows, page.total)
+            };
+
+            rows.iter().for_each(|row| println!("{row}"));
+
+            if rows.is_empty() {
+                println!("Nothing to list ({total} in total)");
+            } else {
+                println!(
+                    "{}-{} of {total}",
+                    list.offset + 1,
+                    list.offset + rows.len()
+                );
+            }
+
+            Ok(())
+        }
+        Command::Taskwarrior { project } => {
+            let taskwarrior = Taskwarrior::new(project);
+            let mut db = database.read_db()?;
+            let summary = taskwarrior.sync(&mut db, Utc::now())?;
+
+            database.replace_db(db)?;
+            taskwarrior.import(&summary.tasks)?;
+            println!(
+                "Synced with Taskwarrior: {} stories created, {} updated, {} tasks sent",
+                summary.stories_created,
+                summary.stories_updated,
+                summary.tasks.len()
+            );
+
+            Ok(())
+        }
+        Command::ImportJira { path } => {
+            let mut content = String::new();
+
+            open_input(&path)
+                .and_then(|mut input| input.read_to_string(&mut content))
+                .with_context(|| anyhow!("Failed to read {}", path.display()))?;
+
+            let issues = parse_backup(&content)
+                .with_context(|| anyhow!("{} is not a Jira backup", path.display()))?;
+            let mut db = database.read_db()?;
+            let summary = add_issues(&mut db, issues, Utc::now());
+
+            database.replace_db(db)?;
+            println!(
+                "Imported {} epics and {} stories from {}",
+                summary.epics,
+                summary.stories,
+                path.display()
+            );
+
+            Ok(())
+        }
+        Command::ImportTodoist => {
+            let token = std::env::var("TODOIST_API_TOKEN")
+                .map_err(|_| anyhow!("Set TODOIST_API_TOKEN to your Todoist API token"))?;
+            let todoist = Todoist::new(token);
+            let bundles = bundles(todoist.projects()?, todoist.tasks()?, Utc::now());
+            let project_count = bundles.len();
+            let story_count = bundles
+                .iter()
+                .map(|bundle| bundle.stories.len())
+                .sum::<usize>();
+
+            for bundle in bundles {
+                database.import_epic(bundle)?;
+            }
+
+            println!("Imported {project_count} projects with {story_count} tasks from Todoist");
+
+            Ok(())
+        }
+        Command::HashPassphrase => {
+            let mut salt = [0; 16];
+
+            getrandom::getrandom(&mut salt)
+                .map_err(|error| anyhow!("Failed to make a salt: {error}"))?;
+            println!("Passphrase:");
+            println!("{}", hash_passphrase(&get_hidden_input(), &salt));
+
+            Ok(())
+        }
+        Command::ConvertToDirectory { path } => {
+            let target = DirectoryDatabase::new(path.clone());
+
+            if target.exists() {
+                return Err(anyhow!("{} already holds a board", path.display()));
+            }
+
+            let db = database.read_db()?;
+
+            target
+                .write_db(&db)
+                .with_context(|| anyhow!("Failed to write {}", path.display()))?;
+            println!(
+                "Copied {} epics and {} stories to {}, set \"data_dir\" in the config to use it",
+                db.epics.len(),
+                db.stories.len(),
+                path.display()
+            );
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::model::DB;
+    use chrono::NaiveDate;
+    use std::fs;
+
+    fn args(input: &[&str]) -> Vec<String> {
+        input.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_should_default_to_interactive() {
+        assert_eq!(Command::parse(&[]).unwrap(), Command::Interactive);
+    }
+
+    #[test]
+    fn parse_should_read_epic_bundle_commands() {
+        assert_eq!(
+            Command::parse(&args(&["export-epic", "3", "payments.json"])).unwrap(),
+            Command::ExportEpic {
+                epic_id: 3,
+                path: PathBuf::from("payments.json")
+            }
+        );
+        assert_eq!(
+            Command::parse(&args(&["import-epic", "payments.json"])).unwrap(),
+            Command::ImportEpic {
+                path: PathBuf::from("payments.json")
+            }
+        );
+    }
+
+    #[test]
+    fn parse_should_read_list_command() {
+        assert_eq!(
+            Command::parse(&args(&["list", "epics"])).unwrap(),
+            Command::List(ListOptions {
+                stories: false,
+                sort: SortBy::Id,
+                offset: 0,
+                limit: DEFAULT_LIST_LIMIT,
+            })
+        );
+        assert_eq!(
+            Command::parse(&args(&[
+                "list", "stories", "--limit", "20", "--sort", "status", "--offset", "40"
+            ]))
+            .unwrap(),
+            Command::List(ListOptions {
+                stories: true,
+                sort: SortBy::Status,
+                offset: 40,
+                limit: 20,
+            })
+        );
+        assert!(Command::parse(&args(&["list", "sprints"])).is_err());
+        assert!(Command::parse(&args(&["list", "epics", "--limit"])).is_err());
+        assert!(Command::parse(&args(&["list", "epics", "--sort", "due"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_standup_command() {
+        assert_eq!(
+            Command::parse(&args(&["standup"])).unwrap(),
+            Command::Standup { user: None }
+        );
+        assert_eq!(
+            Command::parse(&args(&["standup", "alice"])).unwrap(),
+            Command::Standup {
+                user: Some("alice".to_owned())
+            }
+        );
+    }
+
+    #[test]
+    fn parse_should_read_cfd_command() {
+        assert_eq!(
+            Command::parse(&args(&["cfd", "flow.csv"])).unwrap(),
+            Command::CumulativeFlow {
+                path: PathBuf::from("flow.csv")
+            }
+        );
+        assert!(Command::parse(&args(&["cfd"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_snapshot_commands() {
+        assert_eq!(
+            Command::parse(&args(&["snapshot", "create", "before-reorg"])).unwrap(),
+            Command::CreateSnapshot {
+                name: "before-reorg".to_owned()
+            }
+        );
+        assert_eq!(
+            Command::parse(&args(&["snapshot", "restore", "before-reorg"])).unwrap(),
+            Command::RestoreSnapshot {
+                name: "before-reorg".to_owned()
+            }
+        );
+        assert_eq!(
+            Command::parse(&args(&["snapshot", "list"])).unwrap(),
+            Command::ListSnapshots
+        );
+        assert!(Command::parse(&args(&["snapshot", "create"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_json_interchange_commands() {
+        assert_eq!(
+            Command::parse(&args(&["export", "json"])).unwrap(),
+            Command::ExportJson {
+                path: PathBuf::from("-")
+            }
+        );
+        assert_eq!(
+            Command::parse(&args(&["import", "json", "board.json"])).unwrap(),
+            Command::ImportJson {
+                path: PathBuf::from("board.json")
+            }
+        );
+        assert!(Command::parse(&args(&["import", "json"])).is_err());
+        assert!(Command::parse(&args(&["export", "csv"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_org_command() {
+        assert_eq!(
+            Command::parse(&args(&["org", "board.org"])).unwrap(),
+            Command::Org {
+                path: PathBuf::from("board.org")
+            }
+        );
+    }
+
+    #[test]
+    fn parse_should_read_obsidian_command() {
+        assert_eq!(
+            Command::parse(&args(&["obsidian", "vault/backlog"])).unwrap(),
+            Command::Obsidian {
+                path: PathBuf::from("vault/backlog")
+            }
+        );
+        assert!(Command::parse(&args(&["obsidian"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_import_jira_command() {
+        assert_eq!(
+            Command::parse(&args(&["import-jira", "backup.xml"])).unwrap(),
+            Command::ImportJira {
+                path: PathBuf::from("backup.xml")
+            }
+        );
+        assert!(Command::parse(&args(&["import-jira"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_graph_command() {
+        assert_eq!(
+            Command::parse(&args(&["graph", "dot"])).unwrap(),
+            Command::Graph {
+                format: GraphFormat::Dot,
+                scope: GraphScope::default()
+            }
+        );
+        assert_eq!(
+            Command::parse(&args(&["graph", "mermaid", "--unresolved", "--epic", "2"])).unwrap(),
+            Command::Graph {
+                format: GraphFormat::Mermaid,
+                scope: GraphScope {
+                    epic: Some(ItemId(2)),
+                    unresolved: true
+                }
+            }
+        );
+        assert!(Command::parse(&args(&["graph", "svg"])).is_err());
+        assert!(Command::parse(&args(&["graph", "dot", "--epic"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_gantt_command() {
+        assert_eq!(
+            Command::parse(&args(&["gantt"])).unwrap(),
+            Command::Gantt {
+                path: PathBuf::from("-")
+            }
+        );
+        assert_eq!(
+            Command::parse(&args(&["gantt", "roadmap.mmd"])).unwrap(),
+            Command::Gantt {
+                path: PathBuf::from("roadmap.mmd")
+            }
+        );
+    }
+
+    #[test]
+    fn parse_should_read_taskwarrior_command() {
+        assert_eq!(
+            Command::parse(&args(&["taskwarrior"])).unwrap(),
+            Command::Taskwarrior { project: None }
+        );
+        assert_eq!(
+            Command::parse(&args(&["taskwarrior", "work"])).unwrap(),
+            Command::Taskwarrior {
+                project: Some("work".to_owned())
+            }
+        );
+    }
+
+    #[test]
+    fn parse_should_read_hash_passphrase_command() {
+        assert_eq!(
+            Command::parse(&args(&["hash-passphrase"])).unwrap(),
+            Command::HashPassphrase
+        );
+    }
+
+    #[test]
+    fn parse_should_read_doctor_command() {
+        assert_eq!(Command::parse(&args(&["doctor"])).unwrap(), Command::Doctor);
+        assert!(Command::parse(&args(&["doctor", "now"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_last_command() {
+        assert_eq!(
+            Command::parse(&args(&["last"])).unwrap(),
+            Command::Last { nth: 1 }
+        );
+        assert_eq!(
+            Command::parse(&args(&["last", "3"])).unwrap(),
+            Command::Last { nth: 3 }
+        );
+        assert!(Command::parse(&args(&["last", "0"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_serve_command() {
+        assert_eq!(
+            Command::parse(&args(&["serve", "unix:/tmp/board.sock"])).unwrap(),
+            Command::Serve {
+                address: "unix:/tmp/board.sock".to_owned()
+            }
+        );
+        assert!(Command::parse(&args(&["serve"])).is_err());
+    }
+
+    #[test]
+    fn take_option_should_remove_the_option_and_its_value() {
+        let mut input = args(&["--remote", "localhost:7878", "standup"]);
+
+        assert_eq!(
+            take_option(&mut input, "--remote").unwrap(),
+            Some("localhost:7878".to_owned())
+        );
+        assert_eq!(input, args(&["standup"]));
+        assert_eq!(take_option(&mut input, "--remote").unwrap(), None);
+        assert!(take_option(&mut args(&["standup", "--remote"]), "--remote").is_err());
+    }
+
+    #[test]
+    fn run_should_write_cumulative_flow_csv() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let _ = database
+            .create_story("".to_owned(), "".to_owned(), None)
+            .unwrap();
+
+        run(
+            Command::CumulativeFlow {
+                path: file.path().to_owned(),
+            },
+            &database,
+            &Config::default(),
+            &Plugins::none(),
+            &|_| true,
+        )
+        .unwrap();
+
+        let csv = std::fs::read_to_string(file.path()).unwrap();
+        assert!(csv.starts_with("date,open,in_progress,resolved,closed\n"));
+        assert!(csv.trim_end().ends_with(",1,0,0,0"));
+    }
+
+    #[test]
+    fn parse_should_fail_on_invalid_arguments() {
+        assert!(Command::parse(&args(&["export-epic", "three", "payments.json"])).is_err());
+        assert!(Command::parse(&args(&["export-epic", "3"])).is_err());
+        assert!(Command::parse(&args(&["unknown"])).is_err());
+    }
+
+    #[test]
+    fn run_should_export_and_import_an_epic() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let source = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = source
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        let _ = source
+            .create_story("Refunds".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        run(
+            Command::ExportEpic {
+                epic_id: epic_id.0,
+                path: file.path().to_owned(),
+            },
+            &source,
+            &Config::default(),
+            &Plugins::none(),
+            &|_| true,
+        )
+        .unwrap();
+
+        let target = JiraDataBase::from_database(Box::new(MockDB::new()));
+
+        run(
+            Command::ImportEpic {
+                path: file.path().to_owned(),
+            },
+            &target,
+            &Config::default(),
+            &Plugins::none(),
+            &|_| true,
+        )
+        .unwrap();
+
+        let data = target.read_db().unwrap();
+
+        assert_eq!(data.epics.len(), 1);
+        assert_eq!(data.stories.len(), 1);
+    }
+
+    #[test]
+    fn take_flag_should_remove_the_flag() {
+        let mut input = args(&["delete-epic", "--yes", "3"]);
+
+        assert!(take_flag(&mut input, &["--yes", "-y"]));
+        assert_eq!(input, args(&["delete-epic", "3"]));
+        assert!(!take_flag(&mut input, &["--yes", "-y"]));
+        assert_eq!(
+            Command::parse(&input).unwrap(),
+            Command::DeleteEpic { epic_id: 3 }
+        );
+    }
+
+    #[test]
+    fn run_should_only_delete_when_confirmed() {
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = database
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        let story_id = database
+            .create_story("Refunds".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let config = Config::default();
+
+        run(
+            Command::DeleteStory {
+                story_id: story_id.0,
+            },
+            &database,
+            &config,
+            &Plugins::none(),
+            &|question| {
+                assert_eq!(question, "Delete STORY-0 'Refunds'?");
+                false
+            },
+        )
+        .unwrap();
+        assert_eq!(database.read_db().unwrap().stories.len(), 1);
+
+        run(
+            Command::DeleteStory {
+                story_id: story_id.0,
+            },
+            &database,
+            &config,
+            &Plugins::none(),
+            &|_| true,
+        )
+        .unwrap();
+
+        let db = database.read_db().unwrap();
+        assert!(db.stories.is_empty());
+        assert!(db.epics[&epic_id.0].stories.is_empty());
+    }
+
+    #[test]
+    fn run_should_not_ask_when_the_config_skips_confirmation() {
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = database
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        let mut config = Config::default();
+        config.confirm.delete_epic = false;
+
+        run(
+            Command::DeleteEpic { epic_id: epic_id.0 },
+            &database,
+            &config,
+            &Plugins::none(),
+            &|_| panic!("deleting epics is not confirmed"),
+        )
+        .unwrap();
+
+        assert!(database.read_db().unwrap().epics.is_empty());
+        assert!(run(
+            Command::DeleteEpic { epic_id: epic_id.0 },
+            &database,
+            &config,
+            &Plugins::none(),
+            &|_| true,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parse_should_read_bulk_status_command() {
+        assert_eq!(
+            Command::parse(&args(&["bulk-status", "closed", "#legacy", "@bob"])).unwrap(),
+            Command::BulkStatus {
+                status: ItemStatus::Closed,
+                filter: Filter {
+                    label: Some("legacy".to_owned()),
+                    assignee: Some("bob".to_owned()),
+                    ..Default::default()
+                }
+            }
+        );
+        assert!(Command::parse(&args(&["bulk-status", "closed"])).is_err());
+        assert!(Command::parse(&args(&["bulk-status", "done", "#legacy"])).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_replace_command() {
+        assert_eq!(
+            Command::parse(&args(&["replace", "checkout", "cart"])).unwrap(),
+            Command::Replace {
+                from: "checkout".to_owned(),
+                to: "cart".to_owned()
+            }
+        );
+        assert!(Command::parse(&args(&["replace", "", "cart"])).is_err());
+        assert!(Command::parse(&args(&["replace", "checkout"])).is_err());
+    }
+
+    #[test]
+    fn run_should_update_matching_items() {
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = database
+            .create_story("Old login".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let other_id = database
+            .create_story("Refunds".to_owned(), "".to_owned(), None)
+            .unwrap();
+
+        run(
+            Command::BulkStatus {
+                status: ItemStatus::Closed,
+                filter: parse_filter("login").unwrap(),
+            },
+            &database,
+            &Config::default(),
+            &Plugins::none(),
+            &|_| panic!("bulk updates are not confirmed by default"),
+        )
+        .unwrap();
+
+        let db = database.read_db().unwrap();
+        assert_eq!(db.stories[&story_id.0].detail.status, ItemStatus::Closed);
+        assert_eq!(db.stories[&other_id.0].detail.status, ItemStatus::Open);
+    }
+
+    #[test]
+    fn run_should_replace_text_in_the_confirmed_items() {
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = database
+            .create_epic("Checkout".to_owned(), "Everything checkout".to_owned())
+            .unwrap();
+        let story_id = database
+            .create_story("Fix checkout".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let other_id = database
+            .create_story("Checkout emails".to_owned(), "".to_owned(), None)
+            .unwrap();
+
+        run(
+            Command::Replace {
+                from: "heckout".to_owned(),
+                to: "art".to_owned(),
+            },
+            &database,
+            &Config::default(),
+            &Plugins::none(),
+            &|question| question != "Replace it in STORY-1 'Checkout emails'?",
+        )
+        .unwrap();
+
+        let db = database.read_db().unwrap();
+        assert_eq!(db.epics[&epic_id.0].detail.name, "Cart");
+        assert_eq!(db.epics[&epic_id.0].detail.description, "Everything cart");
+        assert_eq!(db.stories[&story_id.0].detail.name, "Fix cart");
+        assert_eq!(db.stories[&other_id.0].detail.name, "Checkout emails");
+    }
+
+    #[test]
+    fn run_should_close_the_sprint_and_archive_its_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let date = |day: u32| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let sprint_id = database
+            .create_sprint("Payments".to_owned(), date(1), date(14))
+            .unwrap();
+        let config = Config {
+            reports_dir: Some(dir.path().join("reports")),
+            ..Default::default()
+        };
+        let command = || Command::CloseSprint {
+            sprint_id: sprint_id.0,
+        };
+
+        run(command(), &database, &config, &Plugins::none(), &|_| true).unwrap();
+
+        let report = fs::read_to_string(dir.path().join("reports").join("SPRINT-0.md")).unwrap();
+        assert!(report.starts_with("# SPRINT-0 Payments"));
+        assert!(database.read_db().unwrap().sprints[&0].closed.is_some());
+        assert!(run(command(), &database, &config, &Plugins::none(), &|_| true).is_err());
+    }
+
+    #[test]
+    fn run_should_restore_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = database
+            .create_story("Refunds".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let config = Config {
+            snapshots_dir: Some(dir.path().to_owned()),
+            ..Default::default()
+        };
+        let snapshot = |command: Command, confirm: bool| {
+            run(command, &database, &config, &Plugins::none(), &|_| confirm).unwrap()
+        };
+
+        snapshot(
+            Command::CreateSnapshot {
+                name: "before-reorg".to_owned(),
+            },
+            true,
+        );
+        database.delete_story(story_id, None).unwrap();
+
+        let restore = || Command::RestoreSnapshot {
+            name: "before-reorg".to_owned(),
+        };
+        snapshot(restore(), false);
+        assert!(database.read_db().unwrap().stories.is_empty());
+
+        snapshot(restore(), true);
+        assert_eq!(
+            database.get_story(story_id).unwrap().detail.name,
+            "Refunds".to_owned()
+        );
+    }
+
+    #[test]
+    fn run_should_convert_the_board_to_a_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = database
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        let _ = database
+            .create_story("Refunds".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let command = || Command::ConvertToDirectory {
+            path: dir.path().to_owned(),
+        };
+
+        run(
+            command(),
+            &database,
+            &Config::default(),
+            &Plugins::none(),
+            &|_| true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            JiraDataBase::with_directory(dir.path().to_owned())
+                .read_db()
+                .unwrap(),
+            DB {
+                revision: 1,
+                ..database.read_db().unwrap()
+            }
+        );
+        assert!(run(
+            command(),
+            &database,
+            &Config::default(),
+            &Plugins::none(),
+            &|_| true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parse_should_read_plugin_commands() {
+        assert_eq!(
+            Command::parse(&args(&["run", "triage", "--label", "bug"])).unwrap(),
+            Command::Plugin {
+                name: "triage".to_owned(),
+                args: args(&["--label", "bug"])
+            }
+        );
+        assert!(Command::parse(&args(&["run"])).is_err());
+    }
+}
@@ -0,0 +1,557 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{Days, Weekday};
+use pbkdf2::pbkdf2_hmac;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::calendar::{Calendar, DateFormat, Zone};
+use crate::integrity::constant_time_eq;
+use crate::keys::KeyFormat;
+use crate::model::ItemStatus;
+use crate::templates::StoryTemplate;
+use crate::ui::{HomeLayout, Keymap};
+
+/// User settings read from `~/.config/lgr/config.json`. Every field is
+/// optional so a missing file or key falls back to the defaults.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub wip: WipLimits,
+    pub confirm: Confirmations,
+    /// Keeps the board as one file per item in this directory instead of in
+    /// `database.json`, see `jira-clone convert-to-dir`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<PathBuf>,
+    /// The name changes are attributed to, unless `LGR_USER` is set. Defaults
+    /// to the login name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Boards that ask for a passphrase before the interactive board opens,
+    /// keyed by their `database.json` or data directory. The values are
+    /// hashes from `jira-clone hash-passphrase`, e.g.
+    /// `{ "/home/me/clients/acme/database.json": "pbkdf2-sha256$600000$…" }`.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub passphrases: HashMap<PathBuf, String>,
+    /// `"vim"` or `"emacs"` adds their keys to the interactive board.
+    pub keymap: Keymap,
+    /// `"grouped"` groups the epics on the home page by status.
+    pub home_layout: HomeLayout,
+    /// The time zone dates and times are shown in, `"UTC"` or an offset like
+    /// `"+05:30"`. Defaults to the machine's own.
+    #[serde(skip_serializing_if = "Zone::is_local")]
+    pub timezone: Zone,
+    /// `"iso"` (2024-05-02), `"us"` (05/02/2024) or `"eu"` (02.05.2024).
+    pub date_format: DateFormat,
+    /// The day weeks start on, e.g. `"sunday"`. Defaults to Monday.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub week_start: Option<Weekday>,
+    /// Where the reports of closed sprints are kept. Defaults to `reports` in
+    /// the working directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reports_dir: Option<PathBuf>,
+    /// Where `jira-clone snapshot create` keeps copies of the board. Defaults
+    /// to `snapshots` in the working directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshots_dir: Option<PathBuf>,
+    /// Points per person, e.g. `{ "default": 10, "per_assignee": { "bob": 5 } }`.
+    pub capacity: Capacity,
+    /// Keeps the changes of the interactive board in memory until they are
+    /// saved with `:w`, instead of writing the board after every change.
+    pub explicit_save: bool,
+    /// How much goes into the log in `~/.local/state/lgr`: `"off"`,
+    /// `"error"`, `"warn"`, `"info"` (the default), `"debug"` or `"trace"`.
+    pub log_level: LogLevel,
+    /// The status new epics and stories start in, e.g. `"InProgress"` for a
+    /// board that only tracks started work. Defaults to `"Open"`.
+    pub default_status: ItemStatus,
+    /// How many days an item that isn't done can go unchanged before lists
+    /// mark it stale. Defaults to 30, `0` turns the marker off.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_after_days: Option<u32>,
+    /// How item keys are written, e.g. `{ "story": "OPS", "width": 3 }` for
+    /// `OPS-007`. Defaults to `EPIC-1`, `STORY-1` and `SPRINT-1`.
+    pub keys: KeyFormat,
+    /// Stories to start from by name, e.g. `{ "upgrade": { "name": "Upgrade
+    /// {{customer}}", "checklist": ["Back up {{customer}}"] } }`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, StoryTemplate>,
+    /// The secret `jira-clone serve` asks `--remote` clients for, unless
+    /// `LGR_SERVER_TOKEN` is set. Without one the server only takes
+    /// connections from this machine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_token: Option<String>,
+}
+
+/// How many days of no changes make an item stale when the config doesn't say.
+const STALE_AFTER_DAYS: u32 = 30;
+
+/// The most stories that may be in progress at once. Going over a limit shows a
+/// warning, or asks for confirmation when `enforce` is set.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct WipLimits {
+    pub global: Option<usize>,
+    pub per_epic: Option<usize>,
+    pub per_assignee: Option<usize>,
+    pub enforce: bool,
+}
+
+/// How many points of open and in-progress work each person can carry. The
+/// workload page highlights anyone over theirs.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Capacity {
+    /// Applies to everyone without an entry in `per_assignee`.
+    pub default: Option<u32>,
+    pub per_assignee: HashMap<String, u32>,
+}
+
+impl Capacity {
+    pub fn of(&self, assignee: &str) -> Option<u32> {
+        self.per_assignee.get(assignee).copied().or(self.default)
+    }
+
+    /// How far `points` go over the capacity of `assignee`, if they do.
+    pub fn overage(&self, assignee: &str, points: u32) -> Option<u32> {
+        self.of(assignee)
+            .filter(|&capacity| points > capacity)
+            .map(|capacity| points - capacity)
+    }
+}
+
+/// Which destructive actions ask "are you sure?" first. Deletes, merges and
+/// restoring a snapshot do by default, bulk status updates don't.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Confirmations {
+    pub delete_epic: bool,
+    pub delete_story: bool,
+    pub merge_epic: bool,
+    pub bulk_update: bool,
+    pub restore_snapshot: bool,
+}
+
+impl Default for Confirmations {
+    fn default() -> Self {
+        Self {
+            delete_epic: true,
+            delete_story: true,
+            merge_epic: true,
+            bulk_update: false,
+            restore_snapshot: true,
+        }
+    }
+}
+
+/// How much goes into the log, set with `"log_level"` in the config.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Config {
+    /// Who changes are attributed to: `LGR_USER`, then `user`, then the login
+    /// name.
+    pub fn acting_user(&self) -> String {
+        match (std::env::var("LGR_USER"), &self.user) {
+            (Err(_), Some(user)) => user.clone(),
+            _ => current_user(),
+        }
+    }
+
+    /// The board server token: `LGR_SERVER_TOKEN`, then `server_token`.
+    pub fn server_token(&self) -> Option<String> {
+        std::env::var("LGR_SERVER_TOKEN")
+            .ok()
+            .or_else(|| self.server_token.clone())
+    }
+
+    /// How dates and times are shown, from `timezone`, `date_format` and
+    /// `week_start`.
+    pub fn calendar(&self) -> Calendar {
+        Calendar {
+            zone: self.timezone,
+            format: self.date_format,
+            week_start: self.week_start.unwrap_or(Weekday::Mon),
+        }
+    }
+
+    /// How long an unfinished item can go unchanged before it's stale, `None`
+    /// when the marker is off.
+    pub fn stale_after(&self) -> Option<Days> {
+        match self.stale_after_days.unwrap_or(STALE_AFTER_DAYS) {
+            0 => None,
+            days => Some(Days::new(days.into())),
+        }
+    }
+
+    pub fn reports_dir(&self) -> PathBuf {
+        self.reports_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("reports"))
+    }
+
+    pub fn snapshots_dir(&self) -> PathBuf {
+        self.snapshots_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("snapshots"))
+    }
+
+    /// The passphrase hash of the board at `board`, if it has one. Relative
+    /// paths on either side are resolved against the working directory.
+    pub fn passphrase_hash(&self, board: &Path) -> Option<&str> {
+        let board = absolute(board);
+
+        self.passphrases
+            .iter()
+            .find(|(path, _)| absolute(path) == board)
+            .map(|(_, hash)| hash.as_str())
+    }
+
+    /// `LGR_CONFIG` when set, otherwise `lgr/config.json` in the XDG config
+    /// directory.
+    pub fn path() -> Option<PathBuf> {
+        if let Ok(path) = std::env::var("LGR_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+
+        let config_dir = std::env::var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .ok()?;
+
+        Some(config_dir.join("lgr").join("config.json"))
+    }
+
+    /// The `plugins` directory next to the config file.
+    pub fn plugin_dir() -> Option<PathBuf> {
+        Some(Self::path()?.parent()?.join("plugins"))
+    }
+
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::read(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn read(path: &PathBuf) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| anyhow!("Failed to read config {}", path.display()))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| anyhow!("Failed to parse config {}", path.display()))
+    }
+
+    /// The settings that differ from `other` but are only read when the board
+    /// opens, so changing them needs a restart.
+    pub fn startup_changes(&self, other: &Config) -> Vec<&'static str> {
+        [
+            ("data_dir", self.data_dir != other.data_dir),
+            ("user", self.user != other.user),
+            ("passphrases", self.passphrases != other.passphrases),
+            ("explicit_save", self.explicit_save != other.explicit_save),
+            ("snapshots_dir", self.snapshots_dir != other.snapshots_dir),
+            ("reports_dir", self.reports_dir != other.reports_dir),
+            ("log_level", self.log_level != other.log_level),
+            (
+                "default_status",
+                self.default_status != other.default_status,
+            ),
+            (
+                "stale_after_days",
+                self.stale_after_days != other.stale_after_days,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(setting, _)| setting)
+        .collect()
+    }
+}
+
+/// Notices when the config file changes, so the interactive board can pick up
+/// new settings without a restart.
+pub struct ConfigWatcher {
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let modified = path.as_deref().and_then(modified);
+
+        Self { path, modified }
+    }
+
+    /// The config as it reads now, once after every change to the file. A
+    /// removed file gives the defaults.
+    pub fn poll(&mut self) -> Option<Result<Config>> {
+        let path = self.path.as_ref()?;
+        let modified = modified(path);
+
+        if modified == self.modified {
+            return None;
+        }
+
+        self.modified = modified;
+
+        Some(match path.exists() {
+            true => Config::read(path),
+            false => Ok(Config::default()),
+        })
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).ok()?.modified().ok()
+}
+
+/// The name that assignments, watches and notifications refer to: `LGR_USER`,
+/// falling back to the login name.
+pub fn current_user() -> String {
+    std::env::var("LGR_USER")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "me".to_owned())
+}
+
+/// How many rounds of PBKDF2 a passphrase goes through, so that every guess at
+/// it takes a while.
+const PASSPHRASE_ROUNDS: u32 = 600_000;
+
+/// What goes into `passphrases` for `passphrase`: its PBKDF2-SHA256 with `salt`,
+/// as `pbkdf2-sha256$<rounds>$<salt>$<hash>` in hex. Only meant to keep
+/// passers-by out, the board itself stays readable on disk.
+pub fn hash_passphrase(passphrase: &str, salt: &[u8]) -> String {
+    hash_passphrase_with(passphrase, salt, PASSPHRASE_ROUNDS)
+}
+
+fn hash_passphrase_with(passphrase: &str, salt: &[u8], rounds: u32) -> String {
+    let mut hash = [0; 32];
+
+    pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, rounds, &mut hash);
+    format!("pbkdf2-sha256${rounds}${}${}", to_hex(salt), to_hex(&hash))
+}
+
+/// Whether `passphrase` is the one `hash` was made from. Hashes in any other
+/// form, like the unsalted ones older versions wrote, match no passphrase.
+pub fn verify_passphrase(passphrase: &str, hash: &str) -> bool {
+    let parts = hash.split('$').collect::<Vec<_>>();
+    let ["pbkdf2-sha256", rounds, salt, _] = parts[..] else {
+        return false;
+    };
+    let (Ok(rounds), Some(salt)) = (rounds.parse(), from_hex(salt)) else {
+        return false;
+    };
+
+    constant_time_eq(
+        hash_passphrase_with(passphrase, &salt, rounds).as_bytes(),
+        hash.as_bytes(),
+    )
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|at| u8::from_str_radix(hex.get(at..at + 2)?, 16).ok())
+        .collect()
+}
+
+/// Resolves symlinks too when `path` exists, which a board that wasn't
+/// created yet doesn't.
+fn absolute(path: &Path) -> PathBuf {
+    fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn config_watcher_should_reread_the_file_after_a_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let touch = |content: &str, seconds| {
+            fs::write(&path, content).unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(seconds))
+                .unwrap();
+        };
+
+        touch("{}", 1);
+        let mut watcher = ConfigWatcher::new(Some(path.clone()));
+
+        assert!(watcher.poll().is_none());
+
+        touch(r#"{ "keymap": "vim" }"#, 2);
+        let config = watcher.poll().unwrap().unwrap();
+
+        assert_eq!(config.keymap, Keymap::Vim);
+        assert!(watcher.poll().is_none());
+
+        touch(r#"{ "keymap": "#, 3);
+        assert!(watcher.poll().unwrap().is_err());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.poll().unwrap().unwrap(), Config::default());
+        assert!(ConfigWatcher::new(None).poll().is_none());
+    }
+
+    #[test]
+    fn startup_changes_should_list_settings_that_need_a_restart() {
+        let config = Config {
+            user: Some("alice".to_owned()),
+            keymap: Keymap::Vim,
+            log_level: LogLevel::Debug,
+            default_status: ItemStatus::InProgress,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.startup_changes(&Config::default()),
+            vec!["user", "log_level", "default_status"]
+        );
+        assert!(config.startup_changes(&config).is_empty());
+    }
+
+    #[test]
+    fn read_should_fill_missing_fields_with_defaults() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(br#"{ "wip": { "per_epic": 3, "enforce": true }, "confirm": { "delete_story": false } }"#)
+            .unwrap();
+
+        let config = Config::read(&file.path().to_owned()).unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                wip: WipLimits {
+                    per_epic: Some(3),
+                    enforce: true,
+                    ..Default::default()
+                },
+                confirm: Confirmations {
+                    delete_epic: true,
+                    delete_story: false,
+                    merge_epic: true,
+                    bulk_update: false,
+                    restore_snapshot: true,
+                },
+                data_dir: None,
+                user: None,
+                passphrases: HashMap::new(),
+                keymap: Keymap::Default,
+                home_layout: HomeLayout::Flat,
+                timezone: Zone::Local,
+                date_format: DateFormat::Iso,
+                week_start: None,
+                reports_dir: None,
+                snapshots_dir: None,
+                capacity: Capacity::default(),
+                explicit_save: false,
+                log_level: LogLevel::Info,
+                default_status: ItemStatus::Open,
+                stale_after_days: None,
+                keys: KeyFormat::default(),
+                templates: BTreeMap::new(),
+                server_token: None,
+            }
+        );
+    }
+
+    #[test]
+    fn calendar_should_follow_the_date_settings() {
+        let config: Config = serde_json::from_str(
+            r#"{ "timezone": "+02:00", "date_format": "eu", "week_start": "sunday" }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.calendar(),
+            Calendar {
+                zone: "+02:00".parse().unwrap(),
+                format: DateFormat::Eu,
+                week_start: Weekday::Sun,
+            }
+        );
+        assert_eq!(Config::default().calendar(), Calendar::default());
+    }
+
+    #[test]
+    fn stale_after_should_default_to_thirty_days_and_turn_off_at_zero() {
+        let off: Config = serde_json::from_str(r#"{ "stale_after_days": 0 }"#).unwrap();
+        let week: Config = serde_json::from_str(r#"{ "stale_after_days": 7 }"#).unwrap();
+
+        assert_eq!(Config::default().stale_after(), Some(Days::new(30)));
+        assert_eq!(week.stale_after(), Some(Days::new(7)));
+        assert_eq!(off.stale_after(), None);
+    }
+
+    #[test]
+    fn passphrase_hash_should_match_the_board_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let board = dir.path().join("database.json");
+        fs::write(&board, "{}").unwrap();
+
+        let hash = hash_passphrase_with("hunter2", b"salt", 10);
+        let config = Config {
+            passphrases: HashMap::from([(board.clone(), hash.clone())]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.passphrase_hash(&dir.path().join(".").join("database.json")),
+            Some(hash.as_str())
+        );
+        assert_eq!(config.passphrase_hash(&dir.path().join("other.json")), None);
+
+        fs::remove_file(&board).unwrap();
+        assert!(config.passphrase_hash(&board).is_some());
+    }
+
+    #[test]
+    fn verify_passphrase_should_take_salted_hashes_only() {
+        let hash = hash_passphrase_with("hunter2", b"salt", 10);
+
+        assert!(hash.starts_with("pbkdf2-sha256$10$73616c74$"));
+        assert!(verify_passphrase("hunter2", &hash));
+        assert!(!verify_passphrase("hunter3", &hash));
+        assert_ne!(hash, hash_passphrase_with("hunter2", b"pepper", 10));
+        assert!(!verify_passphrase(
+            "hunter2",
+            &crate::integrity::checksum(b"hunter2")
+        ));
+        assert!(!verify_passphrase("hunter2", "pbkdf2-sha256$10$7$00"));
+    }
+
+    #[test]
+    fn read_should_fail_with_invalid_json() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"{ wip: 3 }").unwrap();
+
+        assert!(Config::read(&file.path().to_owned()).is_err());
+    }
+}
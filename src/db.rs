@@ -1,82 +1,527 @@
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::{
-    fs::{File, OpenOptions},
-    io::{BufReader, BufWriter, Write},
-    path::PathBuf,
+    cell::RefCell, collections::HashMap, fmt::Display, fs, mem, path::PathBuf, str::FromStr,
+    time::SystemTime,
 };
 
+use crate::config::current_user;
+use crate::diff::word_diff;
+use crate::directory_db::DirectoryDatabase;
+use crate::events::{ChangeEvent, EventBus};
+use crate::integrity::{checksum_path, verify, write_verified};
+use crate::keys::story_key;
 use crate::model::*;
+use crate::search::{search, SearchHit};
 
 pub trait DataBase {
     fn read_db(&self) -> Result<DB>;
     fn write_db(&self, database: &DB) -> Result<()>;
+
+    /// Up to `limit` epics after skipping `offset`, in `sort` order. Backends
+    /// that can read single items override this to leave the rest alone.
+    fn list_epics(&self, offset: usize, limit: usize, sort: SortBy) -> Result<Listing<Epic>> {
+        Ok(sorted_page(
+            self.read_db()?.epics.into_values(),
+            offset,
+            limit,
+            sort,
+            |epic| &epic.detail,
+        ))
+    }
+
+    /// Up to `limit` stories after skipping `offset`, in `sort` order.
+    fn list_stories(&self, offset: usize, limit: usize, sort: SortBy) -> Result<Listing<Story>> {
+        Ok(sorted_page(
+            self.read_db()?.stories.into_values(),
+            offset,
+            limit,
+            sort,
+            |story| &story.detail,
+        ))
+    }
+
+    /// The epics and stories that pass `filter`, ranked like `search::search`.
+    /// Backends that can filter where the items are stored override this, the
+    /// rest filter the board in memory.
+    fn search(&self, filter: &Filter) -> Result<Vec<SearchHit>> {
+        Ok(search(&self.read_db()?, filter))
+    }
+
+    /// Writes out what backends holding changes back have kept in memory.
+    /// Backends that write right away have nothing to do.
+    fn persist(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        false
+    }
+
+    /// The revision of the board, see `DB::revision`. Backends that can tell
+    /// without reading the whole board override this.
+    fn revision(&self) -> Result<u64> {
+        Ok(self.read_db()?.revision)
+    }
+}
+
+/// The orders `list_epics` and `list_stories` can return items in, and an epic
+/// can keep its stories in. Ties are broken by ID.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    #[default]
+    Id,
+    Name,
+    /// Open first, closed last.
+    Status,
+    /// Critical first, items without a priority last.
+    Priority,
+}
+
+impl FromStr for SortBy {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        match input {
+            "id" => Ok(Self::Id),
+            "name" => Ok(Self::Name),
+            "status" => Ok(Self::Status),
+            "priority" => Ok(Self::Priority),
+            _ => Err(anyhow!(
+                "Can't sort by {input}, use id, name, status or priority"
+            )),
+        }
+    }
+}
+
+impl Display for SortBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Id => write!(f, "id"),
+            Self::Name => write!(f, "name"),
+            Self::Status => write!(f, "status"),
+            Self::Priority => write!(f, "priority"),
+        }
+    }
+}
+
+/// One page of a listing, with the number of items across all pages.
+#[derive(Debug, PartialEq)]
+pub struct Listing<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+}
+
+pub(crate) fn sorted_page<T>(
+    items: impl Iterator<Item = T>,
+    offset: usize,
+    limit: usize,
+    sort: SortBy,
+    detail: impl Fn(&T) -> &ItemDetail,
+) -> Listing<T> {
+    let items = items.collect::<Vec<_>>();
+    let total = items.len();
+    let status_rank = |status: &ItemStatus| match status {
+        ItemStatus::Open => 0,
+        ItemStatus::InProgress => 1,
+        ItemStatus::Resolved => 2,
+        ItemStatus::Closed => 3,
+    };
+    let items = items
+        .into_iter()
+        .sorted_by(|a, b| {
+            let (a, b) = (detail(a), detail(b));
+            let order = match sort {
+                SortBy::Id => std::cmp::Ordering::Equal,
+                SortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                SortBy::Status => status_rank(&a.status).cmp(&status_rank(&b.status)),
+                SortBy::Priority => {
+                    (a.priority.is_none(), &a.priority).cmp(&(b.priority.is_none(), &b.priority))
+                }
+            };
+
+            order.then(a.id.0.cmp(&b.id.0))
+        })
+        .skip(offset)
+        .take(limit)
+        .collect();
+
+    Listing { items, total }
+}
+
+/// Keeps the last board it read or wrote, so the file is only parsed again
+/// when it changed on disk.
+pub struct JSONFileDatabase {
+    path: PathBuf,
+    cache: RefCell<Option<(Fingerprint, DB)>>,
+}
+
+/// What tells two versions of the file apart without reading it: its size and
+/// modification time, plus the checksum written next to it.
+#[derive(PartialEq)]
+struct Fingerprint {
+    len: u64,
+    modified: SystemTime,
+    checksum: Option<String>,
 }
 
-pub struct JSONFileDatabase(PathBuf);
+impl JSONFileDatabase {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            cache: RefCell::new(None),
+        }
+    }
+
+    fn fingerprint(&self) -> Result<Fingerprint> {
+        let metadata = fs::metadata(&self.path)?;
+
+        Ok(Fingerprint {
+            len: metadata.len(),
+            modified: metadata.modified()?,
+            checksum: fs::read_to_string(checksum_path(&self.path)).ok(),
+        })
+    }
+}
 
 impl DataBase for JSONFileDatabase {
     fn read_db(&self) -> Result<DB> {
-        let file = File::open(&self.0)?;
-        let reader = BufReader::new(file);
+        let fingerprint = self.fingerprint()?;
 
-        Ok(serde_json::from_reader(reader)?)
+        if let Some((cached, db)) = self.cache.borrow().as_ref() {
+            if *cached == fingerprint {
+                return Ok(db.clone());
+            }
+        }
+
+        let content = fs::read(&self.path)?;
+
+        verify(&self.path, &content)?;
+
+        let db: DB = serde_json::from_slice(&content)?;
+
+        *self.cache.borrow_mut() = Some((fingerprint, db.clone()));
+        Ok(db)
     }
 
     fn write_db(&self, database: &DB) -> Result<()> {
-        let file = OpenOptions::new().write(true).truncate(true).open(&self.0)?;
-        let mut writer = BufWriter::new(file);
+        let database = DB {
+            revision: database.revision + 1,
+            ..database.clone()
+        };
+
+        write_verified(&self.path, &serde_json::to_vec_pretty(&database)?)?;
+        *self.cache.borrow_mut() = Some((self.fingerprint()?, database));
 
-        serde_json::to_writer_pretty(&mut writer, database)?;
-        Ok(writer.flush()?)
+        Ok(())
     }
 }
 
-pub mod test_utils {
-    use std::{cell::RefCell, collections::HashMap};
-
-    use super::*;
+/// Keeps the board in memory only, for frontends without a file system, like a
+/// browser playground, and for tests.
+pub struct MemoryDatabase {
+    board: RefCell<DB>,
+}
 
-    pub struct MockDB {
-        last_written_db: RefCell<DB>,
+impl MemoryDatabase {
+    /// An empty board.
+    pub fn new() -> Self {
+        Self::with_board(DB {
+            last_item: ItemType::None,
+            epics: HashMap::new(),
+            stories: HashMap::new(),
+            filters: Vec::new(),
+            notifications: Vec::new(),
+            sprints: HashMap::new(),
+            story_order: HashMap::new(),
+            notes: Vec::new(),
+            revision: 0,
+        })
     }
 
-    impl MockDB {
-        pub fn new() -> Self {
-            Self {
-                last_written_db: RefCell::new(DB {
-                    last_item: ItemType::None,
-                    epics: HashMap::new(),
-                    stories: HashMap::new(),
-                }),
-            }
+    pub fn with_board(board: DB) -> Self {
+        Self {
+            board: RefCell::new(board),
         }
     }
+}
 
-    impl DataBase for MockDB {
-        fn read_db(&self) -> Result<DB> {
-            Ok(self.last_written_db.borrow().clone())
-        }
+impl Default for MemoryDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        fn write_db(&self, database: &DB) -> Result<()> {
-            *self.last_written_db.borrow_mut() = database.clone();
-            Ok(())
-        }
+impl DataBase for MemoryDatabase {
+    fn read_db(&self) -> Result<DB> {
+        Ok(self.board.borrow().clone())
+    }
+
+    fn write_db(&self, database: &DB) -> Result<()> {
+        *self.board.borrow_mut() = DB {
+            revision: database.revision + 1,
+            ..database.clone()
+        };
+        Ok(())
     }
 }
 
+#[cfg(test)]
+pub mod test_utils {
+    pub use super::MemoryDatabase as MockDB;
+}
+
 pub struct JiraDataBase {
     pub database: Box<dyn DataBase>,
+    events: EventBus,
+    user: String,
+    default_status: ItemStatus,
+    stale_after: Option<Days>,
 }
 
 impl JiraDataBase {
     pub fn new(path: PathBuf) -> Self {
-        return JiraDataBase {
-            database: Box::new(JSONFileDatabase(path)),
-        };
+        Self::from_database(Box::new(JSONFileDatabase::new(path)))
+    }
+
+    /// A board kept as one file per item under `root`.
+    pub fn with_directory(root: PathBuf) -> Self {
+        Self::from_database(Box::new(DirectoryDatabase::new(root)))
+    }
+
+    pub fn from_database(database: Box<dyn DataBase>) -> Self {
+        JiraDataBase {
+            database,
+            events: EventBus::default(),
+            user: current_user(),
+            default_status: ItemStatus::default(),
+            stale_after: None,
+        }
+    }
+
+    /// Gives the epics and stories created from now on `status`.
+    pub fn with_default_status(mut self, status: ItemStatus) -> Self {
+        self.default_status = status;
+        self
+    }
+
+    pub fn default_status(&self) -> &ItemStatus {
+        &self.default_status
+    }
+
+    /// Has lists mark the items that aren't done and haven't changed for
+    /// `after` as stale.
+    pub fn with_stale_after(mut self, after: Option<Days>) -> Self {
+        self.stale_after = after;
+        self
+    }
+
+    pub fn stale_after(&self) -> Option<Days> {
+        self.stale_after
+    }
+
+    /// Attributes the changes saved from now on to `user`.
+    pub fn with_user(mut self, user: String) -> Self {
+        self.user = user;
+        self
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Wraps the storage, e.g. to keep writes in memory, keeping the user and
+    /// the subscribers.
+    pub fn map_database(self, wrap: impl FnOnce(Box<dyn DataBase>) -> Box<dyn DataBase>) -> Self {
+        JiraDataBase {
+            database: wrap(self.database),
+            ..self
+        }
+    }
+
+    /// Calls `subscriber` with every change to an epic or story, after the save
+    /// that made it.
+    pub fn subscribe(&self, subscriber: impl Fn(&ChangeEvent) + 'static) {
+        self.events.subscribe(subscriber);
     }
 
     pub fn read_db(&self) -> Result<DB> {
-        Ok(self.database.read_db()?)
+        self.database.read_db()
+    }
+
+    pub fn get_epic(&self, id: ItemId) -> Result<Epic> {
+        self.database
+            .read_db()?
+            .epics
+            .remove(&id.0)
+            .ok_or_else(|| anyhow!("Epic ID: {id:?} was not found."))
+    }
+
+    pub fn get_story(&self, id: ItemId) -> Result<Story> {
+        self.database
+            .read_db()?
+            .stories
+            .remove(&id.0)
+            .ok_or_else(|| anyhow!("Story ID: {id:?} was not found."))
+    }
+
+    /// The stories of an epic, in ID order.
+    pub fn list_stories_for_epic(&self, id: ItemId) -> Result<Vec<Story>> {
+        let mut db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&id.0)
+            .ok_or_else(|| anyhow!("Epic ID: {id:?} was not found."))?;
+
+        Ok(epic
+            .stories
+            .iter()
+            .filter_map(|story_id| db.stories.remove(&story_id.0))
+            .sorted_by_key(|story| story.detail.id.0)
+            .collect())
+    }
+
+    pub fn list_epics(&self, offset: usize, limit: usize, sort: SortBy) -> Result<Listing<Epic>> {
+        self.database.list_epics(offset, limit, sort)
+    }
+
+    pub fn search(&self, filter: &Filter) -> Result<Vec<SearchHit>> {
+        self.database.search(filter)
+    }
+
+    pub fn list_stories(
+        &self,
+        offset: usize,
+        limit: usize,
+        sort: SortBy,
+    ) -> Result<Listing<Story>> {
+        self.database.list_stories(offset, limit, sort)
+    }
+
+    /// The epics with `status`, in ID order.
+    pub fn list_epics_by_status(&self, status: &ItemStatus) -> Result<Vec<Epic>> {
+        Ok(self
+            .database
+            .read_db()?
+            .epics
+            .into_values()
+            .filter(|epic| &epic.detail.status == status)
+            .sorted_by_key(|epic| epic.detail.id.0)
+            .collect())
+    }
+
+    pub fn get_sprint(&self, id: ItemId) -> Result<Sprint> {
+        self.database
+            .read_db()?
+            .sprints
+            .remove(&id.0)
+            .ok_or_else(|| anyhow!("Sprint ID: {id:?} was not found."))
+    }
+
+    /// The notifications sent to `user`, the newest first.
+    pub fn list_notifications(&self, user: &str) -> Result<Vec<Notification>> {
+        Ok(self
+            .database
+            .read_db()?
+            .notifications
+            .into_iter()
+            .filter(|notification| notification.recipient == user)
+            .rev()
+            .collect())
+    }
+
+    /// Writes `db` after bumping the version of every epic and story that
+    /// changed and recording who changed it. Fails without writing if one of
+    /// them was saved by someone else since `db` was read.
+    fn save(&self, db: &mut DB) -> Result<()> {
+        let stored = self.database.read_db()?;
+        let now = Utc::now();
+
+        stamp_changes(&stored.epics, &mut db.epics, &self.user, now)?;
+        stamp_changes(&stored.stories, &mut db.stories, &self.user, now)?;
+
+        self.database.write_db(db)?;
+
+        let events = change_events(&stored.epics, &db.epics, |id| ItemType::Epic { id })
+            .into_iter()
+            .chain(change_events(&stored.stories, &db.stories, |id| {
+                ItemType::Story { id }
+            }))
+            .collect::<Vec<_>>();
+
+        tracing::debug!(user = %self.user, changes = events.len(), "Saved the board");
+        self.events.publish(&events);
+        Ok(())
+    }
+
+    /// Writes out the changes the backend kept in memory, see `persist`.
+    pub fn persist(&self) -> Result<()> {
+        self.database.persist()
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.database.has_unsaved_changes()
+    }
+
+    pub fn revision(&self) -> Result<u64> {
+        self.database.revision()
+    }
+
+    /// Saves a whole board, e.g. one a plugin command returned.
+    pub fn replace_db(&self, mut db: DB) -> Result<()> {
+        self.save(&mut db)
+    }
+
+    /// Writes `snapshot`, taken earlier with `read_db`, back over the board,
+    /// e.g. to restore a saved snapshot. Items changed since then go back to
+    /// how the snapshot has them.
+    pub fn restore(&self, mut snapshot: DB) -> Result<()> {
+        let stored = self.database.read_db()?;
+
+        snapshot.revision = stored.revision;
+
+        for (id, epic) in snapshot.epics.iter_mut() {
+            if let Some(current) = stored.epics.get(id) {
+                epic.detail.version = current.detail.version;
+            }
+        }
+
+        for (id, story) in snapshot.stories.iter_mut() {
+            if let Some(current) = stored.stories.get(id) {
+                story.detail.version = current.detail.version;
+            }
+        }
+
+        self.save(&mut snapshot)
+    }
+
+    /// The current version of each item, to hand back to `check_versions`.
+    pub fn item_versions(&self, items: &[ItemType]) -> Result<Vec<(ItemType, u32)>> {
+        let mut db = self.database.read_db()?;
+
+        items
+            .iter()
+            .map(|item| Ok((item.clone(), item_detail_mut(&mut db, item)?.version)))
+            .collect()
+    }
+
+    /// Fails if any of the items changed since their versions were taken, e.g.
+    /// while a prompt was open.
+    pub fn check_versions(&self, loaded: &[(ItemType, u32)]) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        for (item, version) in loaded {
+            let current = item_detail_mut(&mut db, item)?.version;
+
+            if current != *version {
+                return Err(conflict(&item.key(), current, *version));
+            }
+        }
+
+        Ok(())
     }
 
     pub fn create_epic(&self, name: String, description: String) -> Result<ItemId> {
@@ -85,22 +530,22 @@ impl JiraDataBase {
             None => ItemId(0),
             Some(last_id) => ItemId(last_id + 1),
         };
-        let epic = Epic::new(
+        let mut epic = Epic::new(
             ItemDetail {
                 description,
                 id: epic_id,
                 name,
-                status: ItemStatus::Open,
+                ..Default::default()
             },
             Vec::new(),
         );
+        epic.detail
+            .set_status(self.default_status.clone(), Utc::now());
         let epic_id = db.epics.entry(epic.detail.id.0).or_insert(epic).detail.id;
 
-        db.last_item = ItemType::Epic {
-            id: epic_id.clone(),
-        };
+        db.last_item = ItemType::Epic { id: epic_id };
 
-        self.database.write_db(&db)?;
+        self.save(&mut db)?;
         Ok(epic_id)
     }
 
@@ -110,27 +555,43 @@ impl JiraDataBase {
         description: String,
         epic_id: Option<ItemId>,
     ) -> Result<ItemId> {
+        self.add_story(
+            Story::new(ItemDetail {
+                description,
+                name,
+                status: self.default_status.clone(),
+                ..Default::default()
+            }),
+            epic_id,
+        )
+    }
+
+    /// Stores an already populated story under a fresh ID.
+    pub fn add_story(&self, mut story: Story, epic_id: Option<ItemId>) -> Result<ItemId> {
         let mut db = self.database.read_db()?;
         let story_id = match db.stories.keys().max() {
             None => ItemId(0),
             Some(last_id) => ItemId(last_id + 1),
         };
-        let story = Story::new(ItemDetail {
-            description,
-            id: story_id,
-            name,
-            status: ItemStatus::Open,
-        });
-        let story_id = db
-            .stories
-            .entry(story.detail.id.0)
-            .or_insert(story)
-            .detail
-            .id;
 
-        db.last_item = ItemType::Story {
-            id: story_id.clone(),
-        };
+        story.detail.id = story_id;
+
+        if story.detail.history.is_empty() {
+            let status = story.detail.status.clone();
+            story.detail.set_status(status, Utc::now());
+        }
+
+        db.stories.insert(story_id.0, story);
+        db.last_item = ItemType::Story { id: story_id };
+
+        if let Some(assignee) = db.stories[&story_id.0].detail.assignee.clone() {
+            push_notification(
+                &mut db,
+                assignee,
+                ItemType::Story { id: story_id },
+                format!("{} was assigned to you", story_key(story_id.0)),
+            );
+        }
 
         if let Some(id) = epic_id {
             let epic = db.epics.get(&id.0);
@@ -140,356 +601,2598 @@ impl JiraDataBase {
                 Some(_) => db
                     .epics
                     .entry(id.0)
-                    .and_modify(|epic| epic.stories.push(story_id.clone())),
+                    .and_modify(|epic| epic.stories.push(story_id)),
             };
         }
 
-        self.database.write_db(&db)?;
+        self.save(&mut db)?;
         Ok(story_id)
     }
 
-    pub fn delete_epic(&self, id: ItemId) -> Result<()> {
+    /// Copies every field of the story into a new story in the default status,
+    /// added to the same epic.
+    pub fn clone_story(&self, story_id: ItemId, epic_id: Option<ItemId>) -> Result<ItemId> {
         let mut db = self.database.read_db()?;
+        let mut story = db
+            .stories
+            .get(&story_id.0)
+            .cloned()
+            .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", story_id))?;
+        let clone_id = match db.stories.keys().max() {
+            None => ItemId(0),
+            Some(last_id) => ItemId(last_id + 1),
+        };
 
-        if let ItemType::Epic { id: last_item_id } = db.last_item {
-            if last_item_id.0 == id.0 {
-                db.last_item = ItemType::None;
-            }
+        story.detail.id = clone_id;
+        story.detail.history = Vec::new();
+        story.detail.description_edits = Vec::new();
+        story
+            .detail
+            .set_status(self.default_status.clone(), Utc::now());
+        story.links = Vec::new();
+        story.taskwarrior = None;
+
+        if let Some(id) = epic_id {
+            match db.epics.get_mut(&id.0) {
+                None => return Err(anyhow!("Epic ID: {id:?} was not found")),
+                Some(epic) => epic.stories.push(clone_id),
+            };
         }
 
-        if let Some(epic) = db.epics.get(&id.0) {
-            for story_id in &epic.stories {
-                self.delete_story(*story_id, None)?;
-            }
+        db.stories.insert(clone_id.0, story);
+        db.last_item = ItemType::Story { id: clone_id };
 
-            db = self.database.read_db()?;
-        }
+        self.save(&mut db)?;
+        Ok(clone_id)
+    }
 
-        return match db.epics.remove(&id.0) {
-            Some(_) => Ok(self.database.write_db(&db)?),
-            None => Err(anyhow!("Epic ID: {:?} was not found", id)),
+    /// Creates a new story out of part of an existing one: the selected checklist
+    /// items move over and both stories get linked to each other.
+    pub fn split_story(
+        &self,
+        story_id: ItemId,
+        epic_id: Option<ItemId>,
+        split: StorySplit,
+    ) -> Result<ItemId> {
+        let mut db = self.database.read_db()?;
+        let new_id = match db.stories.keys().max() {
+            None => ItemId(0),
+            Some(last_id) => ItemId(last_id + 1),
         };
+        let original = db
+            .stories
+            .get_mut(&story_id.0)
+            .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", story_id))?;
+
+        if let Some(index) = split
+            .checklist
+            .iter()
+            .find(|index| **index >= original.checklist.len())
+        {
+            return Err(anyhow!("Checklist item {} was not found.", index + 1));
+        }
+
+        let mut new_story = Story::new(ItemDetail {
+            description: split.description,
+            id: new_id,
+            name: split.name,
+            ..Default::default()
+        });
+        new_story
+            .detail
+            .set_status(self.default_status.clone(), Utc::now());
+        let (moved, kept) = original
+            .checklist
+            .drain(..)
+            .enumerate()
+            .partition::<Vec<_>, _>(|(index, _)| split.checklist.contains(index));
+
+        original.checklist = kept.into_iter().map(|(_, item)| item).collect();
+        original.links.push(ItemLink {
+            kind: LinkKind::SplitInto,
+            story_id: new_id,
+        });
+        new_story.checklist = moved.into_iter().map(|(_, item)| item).collect();
+        new_story.links.push(ItemLink {
+            kind: LinkKind::SplitFrom,
+            story_id,
+        });
+
+        if let Some(id) = epic_id {
+            match db.epics.get_mut(&id.0) {
+                None => return Err(anyhow!("Epic ID: {id:?} was not found")),
+                Some(epic) => epic.stories.push(new_id),
+            };
+        }
+
+        db.stories.insert(new_id.0, new_story);
+        db.last_item = ItemType::Story { id: new_id };
+
+        self.save(&mut db)?;
+        Ok(new_id)
     }
 
-    pub fn delete_story(&self, story_id: ItemId, epic_id: Option<ItemId>) -> Result<()> {
+    /// Turns a story into an epic that keeps its name, description and status.
+    /// When `move_checklist` is set every checklist item becomes a story of the new
+    /// epic; links pointing to the old story are dropped since it no longer exists.
+    pub fn promote_story(&self, story_id: ItemId, move_checklist: bool) -> Result<ItemId> {
         let mut db = self.database.read_db()?;
+        let story = db
+            .stories
+            .remove(&story_id.0)
+            .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", story_id))?;
+        let epic_id = match db.epics.keys().max() {
+            None => ItemId(0),
+            Some(last_id) => ItemId(last_id + 1),
+        };
+        let mut epic = Epic::new(
+            ItemDetail {
+                id: epic_id,
+                ..story.detail
+            },
+            Vec::new(),
+        );
 
-        if let ItemType::Story { id: last_item_id } = db.last_item {
-            if last_item_id.0 == story_id.0 {
-                db.last_item = ItemType::None;
-            }
+        for parent in db.epics.values_mut() {
+            parent.stories.retain(|id| *id != story_id);
         }
 
-        if let Some(id) = epic_id {
-            if !db.epics.contains_key(&id.0) {
-                return Err(anyhow!("Epic ID: {id:?} was not found"));
-            }
+        for other in db.stories.values_mut() {
+            other.links.retain(|link| link.story_id != story_id);
+        }
 
-            db
-                .epics
-                .entry(id.0)
-                .and_modify(|epic| {
-                    epic.stories.remove(
-                epic.stories
-                        .iter()
-                        .position(|id| id.0 == story_id.0)
-                        .unwrap()
-                    );
-                });
+        for sprint in db.sprints.values_mut() {
+            sprint.stories.retain(|id| *id != story_id);
         }
 
-        return match db.stories.remove(&story_id.0) {
-            Some(_) => Ok(self.database.write_db(&db)?),
-            None => Err(anyhow!("Story ID: {:?} was not found.", story_id)),
-        };
-    }
+        if move_checklist {
+            let first_story_id = match db.stories.keys().max() {
+                None => 0,
+                Some(last_id) => last_id + 1,
+            };
 
-    pub fn update_epic_status(&self, epic_id: ItemId, status: ItemStatus) -> Result<()> {
-        let mut db = self.database.read_db()?;
-        let epic = db.epics.get(&epic_id.0);
+            for (id, item) in (first_story_id..).zip(story.checklist) {
+                let status = match item.done {
+                    true => ItemStatus::Closed,
+                    false => self.default_status.clone(),
+                };
+
+                let mut child = Story::new(ItemDetail {
+                    description: String::new(),
+                    id: ItemId(id),
+                    name: item.text,
+                    ..Default::default()
+                });
+                child.detail.set_status(status, Utc::now());
 
-        match epic {
-            Some(_) => {
-                db.epics
-                    .entry(epic_id.0)
-                    .and_modify(|epic| epic.detail.status = status);
-                Ok(self.database.write_db(&db)?)
+                db.stories.insert(id, child);
+                epic.stories.push(ItemId(id));
             }
-            None => Err(anyhow!("Epic ID: {:?} was not found.", epic_id)),
         }
+
+        db.epics.insert(epic_id.0, epic);
+        db.last_item = ItemType::Epic { id: epic_id };
+
+        self.save(&mut db)?;
+        Ok(epic_id)
     }
 
-    pub fn update_story_status(&self, story_id: ItemId, status: ItemStatus) -> Result<()> {
+    pub fn add_checklist_item(&self, story_id: ItemId, text: String) -> Result<()> {
         let mut db = self.database.read_db()?;
-        let story = db.stories.get(&story_id.0);
+        let story = db
+            .stories
+            .get_mut(&story_id.0)
+            .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", story_id))?;
 
-        match story {
-            Some(_) => {
-                db.stories
-                    .entry(story_id.0)
-                    .and_modify(|story| story.detail.status = status);
-                Ok(self.database.write_db(&db)?)
-            }
-            None => Err(anyhow!("Story ID: {:?} was not found.", story_id)),
-        }
+        story.checklist.push(ChecklistItem { text, done: false });
+        self.save(&mut db)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::test_utils::MockDB;
-    use super::*;
+    pub fn toggle_checklist_item(&self, story_id: ItemId, index: usize) -> Result<()> {
+        let mut db = self.database.read_db()?;
+        let story = db
+            .stories
+            .get_mut(&story_id.0)
+            .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", story_id))?;
+        let item = story
+            .checklist
+            .get_mut(index)
+            .ok_or_else(|| anyhow!("Checklist item {} was not found.", index + 1))?;
+
+        item.done = !item.done;
+        self.save(&mut db)
+    }
+
+    pub fn delete_epic(&self, id: ItemId) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        if let ItemType::Epic { id: last_item_id } = db.last_item {
+            if last_item_id.0 == id.0 {
+                db.last_item = ItemType::None;
+            }
+        }
+
+        if let Some(epic) = db.epics.get(&id.0) {
+            for story_id in &epic.stories {
+                self.delete_story(*story_id, None)?;
+            }
+
+            db = self.database.read_db()?;
+        }
+
+        match db.epics.remove(&id.0) {
+            Some(_) => Ok(self.save(&mut db)?),
+            None => Err(anyhow!("Epic ID: {:?} was not found", id)),
+        }
+    }
+
+    /// Moves every story of `source_id` into `target_id`, appends the source
+    /// description to the target one and deletes the source epic, all in a single write.
+    pub fn merge_epics(&self, source_id: ItemId, target_id: ItemId) -> Result<()> {
+        if source_id == target_id {
+            return Err(anyhow!("Cannot merge epic ID: {:?} into itself", source_id));
+        }
+
+        let mut db = self.database.read_db()?;
+
+        if !db.epics.contains_key(&target_id.0) {
+            return Err(anyhow!("Epic ID: {:?} was not found", target_id));
+        }
+
+        let source = db
+            .epics
+            .remove(&source_id.0)
+            .ok_or_else(|| anyhow!("Epic ID: {:?} was not found", source_id))?;
+        let target = db.epics.get_mut(&target_id.0).unwrap();
+
+        target.stories.extend(source.stories);
+
+        if !source.detail.description.is_empty() {
+            if !target.detail.description.is_empty() {
+                target.detail.description.push(' ');
+            }
+
+            target
+                .detail
+                .description
+                .push_str(&source.detail.description);
+        }
+
+        if db.last_item == (ItemType::Epic { id: source_id }) {
+            db.last_item = ItemType::Epic { id: target_id };
+        }
+
+        self.save(&mut db)
+    }
+
+    /// Collapses an epic into a single story of `target_id`. The epic's stories are
+    /// deleted and kept only as checklist items (done when resolved or closed).
+    /// Their links and sprints carry over to the new story.
+    pub fn demote_epic(&self, epic_id: ItemId, target_id: ItemId) -> Result<ItemId> {
+        if epic_id == target_id {
+            return Err(anyhow!("Cannot demote epic ID: {:?} into itself", epic_id));
+        }
+
+        let mut db = self.database.read_db()?;
+
+        if !db.epics.contains_key(&target_id.0) {
+            return Err(anyhow!("Epic ID: {:?} was not found", target_id));
+        }
+
+        let epic = db
+            .epics
+            .remove(&epic_id.0)
+            .ok_or_else(|| anyhow!("Epic ID: {:?} was not found", epic_id))?;
+        let story_id = match db.stories.keys().max() {
+            None => ItemId(0),
+            Some(last_id) => ItemId(last_id + 1),
+        };
+        let mut story = Story::new(ItemDetail {
+            id: story_id,
+            ..epic.detail
+        });
+
+        for child_id in &epic.stories {
+            if let Some(child) = db.stories.remove(&child_id.0) {
+                story.checklist.push(ChecklistItem {
+                    text: child.detail.name,
+                    done: matches!(
+                        child.detail.status,
+                        ItemStatus::Resolved | ItemStatus::Closed
+                    ),
+                });
+                story.links.extend(child.links);
+            }
+        }
+
+        // Links between the folded stories would point the story at itself.
+        story.links = repoint_links(story.links, &epic.stories, story_id);
+        story.links.retain(|link| link.story_id != story_id);
+
+        for other in db.stories.values_mut() {
+            other.links = repoint_links(mem::take(&mut other.links), &epic.stories, story_id);
+        }
+
+        for sprint in db.sprints.values_mut() {
+            if sprint.stories.iter().any(|id| epic.stories.contains(id)) {
+                sprint.stories.retain(|id| !epic.stories.contains(id));
+                sprint.stories.push(story_id);
+            }
+        }
+
+        db.stories.insert(story_id.0, story);
+        db.epics
+            .get_mut(&target_id.0)
+            .unwrap()
+            .stories
+            .push(story_id);
+        db.last_item = ItemType::Story { id: story_id };
+
+        self.save(&mut db)?;
+        Ok(story_id)
+    }
+
+    pub fn delete_story(&self, story_id: ItemId, epic_id: Option<ItemId>) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        if let ItemType::Story { id: last_item_id } = db.last_item {
+            if last_item_id.0 == story_id.0 {
+                db.last_item = ItemType::None;
+            }
+        }
+
+        if let Some(id) = epic_id {
+            if !db.epics.contains_key(&id.0) {
+                return Err(anyhow!("Epic ID: {id:?} was not found"));
+            }
+
+            db.epics.entry(id.0).and_modify(|epic| {
+                epic.stories.remove(
+                    epic.stories
+                        .iter()
+                        .position(|id| id.0 == story_id.0)
+                        .unwrap(),
+                );
+            });
+        }
+
+        for story in db.stories.values_mut() {
+            story.links.retain(|link| link.story_id != story_id);
+        }
+
+        for sprint in db.sprints.values_mut() {
+            sprint.stories.retain(|id| *id != story_id);
+        }
+
+        match db.stories.remove(&story_id.0) {
+            Some(_) => Ok(self.save(&mut db)?),
+            None => Err(anyhow!("Story ID: {:?} was not found.", story_id)),
+        }
+    }
+
+    pub fn export_epic(&self, epic_id: ItemId) -> Result<EpicBundle> {
+        let db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&epic_id.0)
+            .ok_or_else(|| anyhow!("Epic ID: {:?} was not found.", epic_id))?;
+        let stories = epic
+            .stories
+            .iter()
+            .map(|story_id| {
+                db.stories
+                    .get(&story_id.0)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", story_id))
+            })
+            .collect::<Result<Vec<Story>>>()?;
+
+        Ok(EpicBundle {
+            epic: epic.clone(),
+            stories,
+        })
+    }
+
+    /// Inserts the bundled epic and its stories under fresh IDs, so bundles coming
+    /// from another board never collide with the items already stored here. Links
+    /// between the bundled stories follow them to their new IDs, links to stories
+    /// outside the bundle are dropped, and so is the Taskwarrior task, which
+    /// stays with the board it came from.
+    pub fn import_epic(&self, bundle: EpicBundle) -> Result<ItemId> {
+        let mut db = self.database.read_db()?;
+        let epic_id = match db.epics.keys().max() {
+            None => ItemId(0),
+            Some(last_id) => ItemId(last_id + 1),
+        };
+        let first_story_id = match db.stories.keys().max() {
+            None => 0,
+            Some(last_id) => last_id + 1,
+        };
+        let new_ids = bundle
+            .stories
+            .iter()
+            .zip(first_story_id..)
+            .map(|(story, new_id)| (story.detail.id.0, ItemId(new_id)))
+            .collect::<HashMap<_, _>>();
+        let mut epic = bundle.epic;
+
+        epic.detail.id = epic_id;
+        epic.stories = Vec::new();
+
+        for mut story in bundle.stories {
+            story.detail.id = new_ids[&story.detail.id.0];
+            story.links = story
+                .links
+                .into_iter()
+                .filter_map(|link| {
+                    Some(ItemLink {
+                        story_id: *new_ids.get(&link.story_id.0)?,
+                        ..link
+                    })
+                })
+                .collect();
+            story.taskwarrior = None;
+            epic.stories.push(story.detail.id);
+            db.stories.insert(story.detail.id.0, story);
+        }
+
+        db.epics.insert(epic_id.0, epic);
+        db.last_item = ItemType::Epic { id: epic_id };
+
+        self.save(&mut db)?;
+        Ok(epic_id)
+    }
+
+    pub fn update_epic_status(&self, epic_id: ItemId, status: ItemStatus) -> Result<()> {
+        self.update_statuses(&[ItemType::Epic { id: epic_id }], status)
+    }
+
+    pub fn update_story_status(&self, story_id: ItemId, status: ItemStatus) -> Result<()> {
+        self.update_statuses(&[ItemType::Story { id: story_id }], status)
+    }
+
+    /// Sets the status of every epic and story in `items` at once, leaving the
+    /// database untouched if any of them is missing.
+    pub fn update_statuses(&self, items: &[ItemType], status: ItemStatus) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        for item in items {
+            let (key, detail) = match item {
+                ItemType::Epic { id } => db
+                    .epics
+                    .get_mut(&id.0)
+                    .map(|epic| (epic.key(), &mut epic.detail))
+                    .ok_or_else(|| anyhow!("Epic ID: {:?} was not found.", id))?,
+                ItemType::Story { id } => db
+                    .stories
+                    .get_mut(&id.0)
+                    .map(|story| (story.key(), &mut story.detail))
+                    .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", id))?,
+                ItemType::None => continue,
+            };
+
+            if detail.status != status {
+                detail.set_status(status.clone(), Utc::now());
+                notify(&mut db, item.clone(), format!("{key} moved to {status}"));
+            }
+        }
+
+        self.save(&mut db)
+    }
+
+    pub fn create_sprint(&self, name: String, start: NaiveDate, end: NaiveDate) -> Result<ItemId> {
+        if end < start {
+            return Err(anyhow!("A sprint cannot end before it starts"));
+        }
+
+        let mut db = self.database.read_db()?;
+        let sprint_id = match db.sprints.keys().max() {
+            None => ItemId(0),
+            Some(last_id) => ItemId(last_id + 1),
+        };
+
+        db.sprints.insert(
+            sprint_id.0,
+            Sprint {
+                id: sprint_id,
+                name,
+                start,
+                end,
+                stories: Vec::new(),
+                closed: None,
+                snapshot: Vec::new(),
+            },
+        );
+
+        self.save(&mut db)?;
+        Ok(sprint_id)
+    }
+
+    /// Commits the story to the sprint, taking it out of any other open sprint.
+    pub fn add_story_to_sprint(&self, sprint_id: ItemId, story_id: ItemId) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        if !db.stories.contains_key(&story_id.0) {
+            return Err(anyhow!("Story ID: {:?} was not found.", story_id));
+        }
+
+        match db.sprints.get(&sprint_id.0) {
+            None => return Err(anyhow!("Sprint ID: {:?} was not found.", sprint_id)),
+            Some(sprint) if sprint.closed.is_some() => {
+                return Err(anyhow!("{} is closed", sprint.key()))
+            }
+            Some(_) => {}
+        }
+
+        for sprint in db
+            .sprints
+            .values_mut()
+            .filter(|sprint| sprint.closed.is_none())
+        {
+            sprint.stories.retain(|id| *id != story_id);
+        }
+
+        db.sprints
+            .get_mut(&sprint_id.0)
+            .unwrap()
+            .stories
+            .push(story_id);
+
+        self.save(&mut db)
+    }
+
+    /// Closes the sprint on `today`, keeping a snapshot of its stories, and
+    /// returns it as it was closed.
+    pub fn close_sprint(&self, sprint_id: ItemId, today: NaiveDate) -> Result<Sprint> {
+        let mut db = self.database.read_db()?;
+        let stories = &db.stories;
+        let sprint = db
+            .sprints
+            .get_mut(&sprint_id.0)
+            .ok_or_else(|| anyhow!("Sprint ID: {:?} was not found.", sprint_id))?;
+
+        if sprint.closed.is_some() {
+            return Err(anyhow!("{} is already closed", sprint.key()));
+        }
+
+        sprint.closed = Some(today);
+        sprint.snapshot = sprint
+            .stories
+            .iter()
+            .filter_map(|id| stories.get(&id.0))
+            .sorted_by_key(|story| story.detail.id.0)
+            .map(SprintStory::of)
+            .collect();
+        let sprint = sprint.clone();
+
+        self.save(&mut db)?;
+        Ok(sprint)
+    }
+
+    /// Has the stories created in `epic_id` from now on start with `labels`.
+    pub fn set_default_labels(&self, epic_id: ItemId, labels: Vec<String>) -> Result<()> {
+        let mut db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get_mut(&epic_id.0)
+            .ok_or_else(|| anyhow!("Epic ID: {:?} was not found.", epic_id))?;
+
+        epic.default_labels = labels;
+        self.save(&mut db)
+    }
+
+    /// Makes every change in `edits` to every item in `items` and saves them at
+    /// once, leaving the database untouched if any item or sprint is missing.
+    pub fn bulk_edit(&self, items: &[ItemType], edits: &[FieldEdit]) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        for edit in edits {
+            if let FieldEdit::MoveToSprint(sprint_id) = edit {
+                match db.sprints.get(&sprint_id.0) {
+                    None => return Err(anyhow!("Sprint ID: {:?} was not found.", sprint_id)),
+                    Some(sprint) if sprint.closed.is_some() => {
+                        return Err(anyhow!("{} is closed", sprint.key()))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for item in items.iter().filter(|item| **item != ItemType::None) {
+            let key = item.key();
+
+            for edit in edits {
+                let detail = item_detail_mut(&mut db, item)?;
+
+                match edit {
+                    FieldEdit::AddLabel(label) => {
+                        if !detail.labels.contains(label) {
+                            detail.labels.push(label.clone());
+                        }
+                    }
+                    FieldEdit::RemoveLabel(label) => detail.labels.retain(|l| l != label),
+                    FieldEdit::SetPriority(priority) => detail.priority = *priority,
+                    FieldEdit::SetAssignee(assignee) => {
+                        let assigned = assignee.is_some() && detail.assignee != *assignee;
+
+                        detail.assignee = assignee.clone();
+                        if let (true, Some(assignee)) = (assigned, assignee) {
+                            push_notification(
+                                &mut db,
+                                assignee.clone(),
+                                item.clone(),
+                                format!("{key} was assigned to you"),
+                            );
+                        }
+                    }
+                    FieldEdit::MoveToSprint(sprint_id) => {
+                        let ItemType::Story { id } = item else {
+                            continue;
+                        };
+
+                        for sprint in db
+                            .sprints
+                            .values_mut()
+                            .filter(|sprint| sprint.closed.is_none())
+                        {
+                            sprint.stories.retain(|story_id| story_id != id);
+                        }
+                        db.sprints.get_mut(&sprint_id.0).unwrap().stories.push(*id);
+                    }
+                }
+            }
+        }
+
+        self.save(&mut db)
+    }
+
+    pub fn set_story_points(&self, story_id: ItemId, points: Option<u32>) -> Result<()> {
+        let mut db = self.database.read_db()?;
+        let story = db
+            .stories
+            .get_mut(&story_id.0)
+            .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", story_id))?;
+
+        story.detail.points = points;
+        self.save(&mut db)
+    }
+
+    /// Records that `story_id` cannot move on until `blocker_id` is done, linking
+    /// both stories to each other.
+    pub fn add_blocker(&self, story_id: ItemId, blocker_id: ItemId) -> Result<()> {
+        if story_id == blocker_id {
+            return Err(anyhow!("Story ID: {:?} cannot block itself", story_id));
+        }
+
+        let mut db = self.database.read_db()?;
+
+        if !db.stories.contains_key(&blocker_id.0) {
+            return Err(anyhow!("Story ID: {:?} was not found.", blocker_id));
+        }
+
+        let story = db
+            .stories
+            .get_mut(&story_id.0)
+            .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", story_id))?;
+        let link = ItemLink {
+            kind: LinkKind::BlockedBy,
+            story_id: blocker_id,
+        };
+
+        if story.links.contains(&link) {
+            return Ok(());
+        }
+
+        story.links.push(link);
+        db.stories
+            .get_mut(&blocker_id.0)
+            .unwrap()
+            .links
+            .push(ItemLink {
+                kind: LinkKind::Blocks,
+                story_id,
+            });
+
+        self.save(&mut db)
+    }
+
+    /// Adds or removes the user from the watchers of the item and returns whether
+    /// they are watching it now.
+    pub fn toggle_watch(&self, item: &ItemType, user: &str) -> Result<bool> {
+        let mut db = self.database.read_db()?;
+        let watchers = &mut item_detail_mut(&mut db, item)?.watchers;
+        let watching = match watchers.iter().position(|watcher| watcher == user) {
+            Some(index) => {
+                watchers.remove(index);
+                false
+            }
+            None => {
+                watchers.push(user.to_owned());
+                true
+            }
+        };
+
+        self.save(&mut db)?;
+        Ok(watching)
+    }
+
+    /// Reminds the user of the open items they are assigned to or watch that are
+    /// due within two days of `today`, once per item and due date. Returns how
+    /// many reminders were added.
+    pub fn add_due_notifications(&self, user: &str, today: NaiveDate) -> Result<usize> {
+        let mut db = self.database.read_db()?;
+        let epics = db.epics.values().map(|epic| {
+            (
+                ItemType::Epic { id: epic.detail.id },
+                epic.key(),
+                &epic.detail,
+            )
+        });
+        let stories = db.stories.values().map(|story| {
+            (
+                ItemType::Story {
+                    id: story.detail.id,
+                },
+                story.key(),
+                &story.detail,
+            )
+        });
+        let reminders: Vec<(ItemType, String)> = epics
+            .chain(stories)
+            .filter(|(_, _, detail)| {
+                detail.assignee.as_deref() == Some(user)
+                    || detail.watchers.iter().any(|watcher| watcher == user)
+            })
+            .filter(|(_, _, detail)| {
+                !matches!(detail.status, ItemStatus::Resolved | ItemStatus::Closed)
+            })
+            .filter_map(|(item, key, detail)| {
+                let due = detail.due.filter(|due| *due <= today + Days::new(2))?;
+                let reminded = db.notifications.iter().any(|notification| {
+                    notification.recipient == user
+                        && notification.item == item
+                        && notification.message.ends_with(&format!("due {due}"))
+                });
+
+                match (reminded, due < today) {
+                    (true, _) => None,
+                    (false, true) => Some((item, format!("{key} was due {due}"))),
+                    (false, false) => Some((item, format!("{key} is due {due}"))),
+                }
+            })
+            .sorted_by(|a, b| a.1.cmp(&b.1))
+            .collect();
+        let added = reminders.len();
+
+        for (item, message) in reminders {
+            push_notification(&mut db, user.to_owned(), item, message);
+        }
+
+        if added > 0 {
+            self.save(&mut db)?;
+        }
+
+        Ok(added)
+    }
+
+    pub fn mark_notification_read(&self, id: u32) -> Result<()> {
+        let mut db = self.database.read_db()?;
+        let notification = db
+            .notifications
+            .iter_mut()
+            .find(|notification| notification.id == id)
+            .ok_or_else(|| anyhow!("Notification {} was not found.", id))?;
+
+        notification.read = true;
+        self.save(&mut db)
+    }
+
+    pub fn mark_notifications_read(&self, user: &str) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        db.notifications
+            .iter_mut()
+            .filter(|notification| notification.recipient == user)
+            .for_each(|notification| notification.read = true);
+        self.save(&mut db)
+    }
+
+    /// Keeps the stories of `epic_id` in `sort` order from now on.
+    pub fn set_story_order(&self, epic_id: ItemId, sort: SortBy) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        if !db.epics.contains_key(&epic_id.0) {
+            return Err(anyhow!("Epic ID: {epic_id:?} was not found"));
+        }
+
+        db.story_order.insert(epic_id.0, sort);
+        self.save(&mut db)
+    }
+
+    /// The epics and stories whose name or description contains `text`, epics
+    /// first and in ID order.
+    pub fn items_mentioning(&self, text: &str) -> Result<Vec<(ItemType, ItemDetail)>> {
+        let db = self.database.read_db()?;
+        let mentions =
+            |detail: &ItemDetail| detail.name.contains(text) || detail.description.contains(text);
+        let epics = db
+            .epics
+            .values()
+            .filter(|epic| mentions(&epic.detail))
+            .sorted_by_key(|epic| epic.detail.id.0)
+            .map(|epic| (ItemType::Epic { id: epic.detail.id }, epic.detail.clone()));
+        let stories = db
+            .stories
+            .values()
+            .filter(|story| mentions(&story.detail))
+            .sorted_by_key(|story| story.detail.id.0)
+            .map(|story| {
+                let item = ItemType::Story {
+                    id: story.detail.id,
+                };
+
+                (item, story.detail.clone())
+            });
+
+        Ok(epics.chain(stories).collect())
+    }
+
+    /// Replaces `from` with `to` in the names and descriptions of `items`, in a
+    /// single write.
+    pub fn replace_text(&self, from: &str, to: &str, items: &[ItemType]) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        for item in items {
+            let detail = item_detail_mut(&mut db, item)?;
+
+            detail.name = detail.name.replace(from, to);
+            detail.description = detail.description.replace(from, to);
+        }
+
+        self.save(&mut db)
+    }
+
+    pub fn add_note(&self, text: String) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        db.notes.push(text);
+        self.save(&mut db)
+    }
+
+    /// Removes the note at `index` and returns it.
+    pub fn delete_note(&self, index: usize) -> Result<String> {
+        let mut db = self.database.read_db()?;
+
+        if index >= db.notes.len() {
+            return Err(anyhow!("There is no note {}", index + 1));
+        }
+
+        let note = db.notes.remove(index);
+        self.save(&mut db)?;
+        Ok(note)
+    }
+
+    /// Stores the filter under the given name, replacing any filter already
+    /// saved with that name.
+    pub fn save_filter(&self, name: String, filter: Filter) -> Result<()> {
+        let mut db = self.database.read_db()?;
+
+        match db.filters.iter_mut().find(|saved| saved.name == name) {
+            Some(saved) => saved.filter = filter,
+            None => db.filters.push(SavedFilter { name, filter }),
+        }
+
+        self.save(&mut db)
+    }
+}
+
+/// What `save` needs to version epics and stories alike.
+trait Versioned: Clone + PartialEq {
+    fn key(&self) -> String;
+    fn detail(&self) -> &ItemDetail;
+    fn detail_mut(&mut self) -> &mut ItemDetail;
+}
+
+impl Versioned for Epic {
+    fn key(&self) -> String {
+        Epic::key(self)
+    }
+
+    fn detail(&self) -> &ItemDetail {
+        &self.detail
+    }
+
+    fn detail_mut(&mut self) -> &mut ItemDetail {
+        &mut self.detail
+    }
+}
+
+impl Versioned for Story {
+    fn key(&self) -> String {
+        Story::key(self)
+    }
+
+    fn detail(&self) -> &ItemDetail {
+        &self.detail
+    }
+
+    fn detail_mut(&mut self) -> &mut ItemDetail {
+        &mut self.detail
+    }
+}
+
+/// Bumps the version of the new and changed items and attributes them, and
+/// any status they just entered, to `user`. Edited descriptions are recorded
+/// with what changed.
+fn stamp_changes<T: Versioned>(
+    stored: &HashMap<u32, T>,
+    items: &mut HashMap<u32, T>,
+    user: &str,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    for (id, item) in items.iter_mut() {
+        let version = match stored.get(id) {
+            None => 1,
+            Some(current) if current != item => {
+                let loaded = item.detail().version;
+
+                if current.detail().version != loaded {
+                    return Err(conflict(&item.key(), current.detail().version, loaded));
+                }
+
+                loaded + 1
+            }
+            Some(_) => continue,
+        };
+        let recorded = stored
+            .get(id)
+            .map_or(0, |current| current.detail().history.len());
+        let edit = stored
+            .get(id)
+            .map(|current| word_diff(&current.detail().description, &item.detail().description))
+            .filter(|diff| !diff.is_empty());
+        let detail = item.detail_mut();
+
+        if let Some(diff) = edit {
+            detail.description_edits.push(DescriptionEdit {
+                at: now,
+                by: Some(user.to_owned()),
+                diff,
+            });
+        }
+
+        detail.version = version;
+        detail.updated_by = Some(user.to_owned());
+        detail.updated_at = Some(now);
+        detail
+            .history
+            .iter_mut()
+            .skip(recorded)
+            .filter(|change| change.by.is_none())
+            .for_each(|change| change.by = Some(user.to_owned()));
+    }
+
+    Ok(())
+}
+
+/// What changed between the stored items and the saved ones, in ID order.
+fn change_events<T: Versioned>(
+    stored: &HashMap<u32, T>,
+    items: &HashMap<u32, T>,
+    item_type: impl Fn(ItemId) -> ItemType,
+) -> Vec<ChangeEvent> {
+    let mut events = Vec::new();
+
+    for id in stored.keys().chain(items.keys()).unique().sorted() {
+        let item = item_type(ItemId(*id));
+
+        match (stored.get(id), items.get(id)) {
+            (None, Some(_)) => events.push(ChangeEvent::ItemCreated { item }),
+            (Some(_), None) => events.push(ChangeEvent::ItemDeleted { item }),
+            (Some(old), Some(new)) if old != new => {
+                let (from, to) = (&old.detail().status, &new.detail().status);
+                // Changing the status also adds to the history and bumps the
+                // version, so compare the rest as if it hadn't happened.
+                let mut unchanged = old.clone();
+                unchanged.detail_mut().status = to.clone();
+                unchanged.detail_mut().history = new.detail().history.clone();
+                unchanged.detail_mut().version = new.detail().version;
+                unchanged.detail_mut().updated_by = new.detail().updated_by.clone();
+                unchanged.detail_mut().updated_at = new.detail().updated_at;
+
+                if from != to {
+                    events.push(ChangeEvent::StatusChanged {
+                        item: item.clone(),
+                        from: from.clone(),
+                        to: to.clone(),
+                    });
+                }
+                if &unchanged != new {
+                    events.push(ChangeEvent::ItemUpdated { item });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+/// `links` with the ones to any of `from` pointing to `to` instead, each link
+/// kept once.
+fn repoint_links(links: Vec<ItemLink>, from: &[ItemId], to: ItemId) -> Vec<ItemLink> {
+    let mut repointed: Vec<ItemLink> = Vec::new();
+
+    for mut link in links {
+        if from.contains(&link.story_id) {
+            link.story_id = to;
+        }
+        if !repointed.contains(&link) {
+            repointed.push(link);
+        }
+    }
+
+    repointed
+}
+
+fn conflict(key: &str, current: u32, loaded: u32) -> anyhow::Error {
+    anyhow!(
+        "{key} was changed elsewhere since it was loaded (version {current}, loaded {loaded}), reload and try again"
+    )
+}
+
+fn item_detail_mut<'a>(db: &'a mut DB, item: &ItemType) -> Result<&'a mut ItemDetail> {
+    match item {
+        ItemType::Epic { id } => db
+            .epics
+            .get_mut(&id.0)
+            .map(|epic| &mut epic.detail)
+            .ok_or_else(|| anyhow!("Epic ID: {:?} was not found.", id)),
+        ItemType::Story { id } => db
+            .stories
+            .get_mut(&id.0)
+            .map(|story| &mut story.detail)
+            .ok_or_else(|| anyhow!("Story ID: {:?} was not found.", id)),
+        ItemType::None => Err(anyhow!("No item was given.")),
+    }
+}
+
+/// Leaves the message for the assignee and the watchers of the item.
+fn notify(db: &mut DB, item: ItemType, message: String) {
+    let recipients: Vec<String> = match item_detail_mut(db, &item) {
+        Ok(detail) => detail
+            .assignee
+            .iter()
+            .chain(detail.watchers.iter())
+            .unique()
+            .cloned()
+            .collect(),
+        Err(_) => return,
+    };
+
+    for recipient in recipients {
+        push_notification(db, recipient, item.clone(), message.clone());
+    }
+}
+
+fn push_notification(db: &mut DB, recipient: String, item: ItemType, message: String) {
+    let id = db
+        .notifications
+        .iter()
+        .map(|notification| notification.id + 1)
+        .max()
+        .unwrap_or_default();
+
+    db.notifications.push(Notification {
+        id,
+        recipient,
+        item,
+        message,
+        read: false,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::MockDB;
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn create_epic_should_work() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let result = db.create_epic(
+            "First Epic".to_owned(),
+            "This is the first test epic".to_owned(),
+        );
+
+        assert!(result.is_ok());
+
+        let id = result.unwrap();
+        let db_state = db.read_db().unwrap();
+        let expected_id = ItemId(0);
+
+        assert_eq!(id, expected_id);
+
+        if let ItemType::Epic { id } = db_state.last_item {
+            assert_eq!(id, expected_id)
+        }
+
+        let epic = db_state.epics.get(&id.0);
+
+        assert!(epic.is_some());
+
+        let epic = epic.unwrap();
+        assert_eq!(epic.detail.name, "First Epic");
+    }
+
+    #[test]
+    fn create_should_use_the_default_status() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()))
+            .with_default_status(ItemStatus::InProgress);
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(
+            db_state.epics[&epic_id.0].detail.status,
+            ItemStatus::InProgress
+        );
+        assert_eq!(
+            db_state.stories[&story_id.0].detail.history[0].status,
+            ItemStatus::InProgress
+        );
+
+        db.update_story_status(story_id, ItemStatus::Resolved)
+            .unwrap();
+        db.add_checklist_item(story_id, "Refunds".to_owned())
+            .unwrap();
+        db.add_checklist_item(story_id, "Receipts".to_owned())
+            .unwrap();
+        let clone_id = db.clone_story(story_id, Some(epic_id)).unwrap();
+        let split_id = db
+            .split_story(
+                story_id,
+                Some(epic_id),
+                StorySplit {
+                    name: "Receipts".to_owned(),
+                    description: String::new(),
+                    checklist: vec![1],
+                },
+            )
+            .unwrap();
+        let promoted_id = db.promote_story(clone_id, true).unwrap();
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(
+            db_state.stories[&split_id.0].detail.status,
+            ItemStatus::InProgress
+        );
+        assert_eq!(db_state.epics[&promoted_id.0].stories.len(), 2);
+        for child in &db_state.epics[&promoted_id.0].stories {
+            assert_eq!(
+                db_state.stories[&child.0].detail.status,
+                ItemStatus::InProgress
+            );
+        }
+    }
+
+    #[test]
+    fn should_fail_when_creating_story_without_epic_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let result = db.create_story(
+            "Failure Story Without Epic".to_owned(),
+            "This story won't be created if the Epic ID is not valid nor found".to_owned(),
+            Some(ItemId(90000)),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Epic ID: {:?} was not found", ItemId(90000))
+        );
+    }
+
+    #[test]
+    fn should_create_story_with_epic_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(
+                "First Epic".to_owned(),
+                "This is the first test epic".to_owned(),
+            )
+            .unwrap();
+        let result = db.create_story(
+            "Story With Epic".to_owned(),
+            "This story will be part of an Epic".to_owned(),
+            Some(epic_id),
+        );
+
+        assert!(result.is_ok());
+
+        let data = db.database.read_db().unwrap();
+        let epic = data.epics.get(&epic_id.0).unwrap();
+
+        assert_eq!(epic.stories.len(), 1);
+        assert_eq!(*epic.stories.first().unwrap(), result.unwrap())
+    }
+
+    #[test]
+    fn should_create_story_without_epic_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let result = db.create_story(
+            "First Story".to_owned(),
+            "This is the first test story".to_owned(),
+            None,
+        );
+
+        assert!(result.is_ok());
+
+        let id = result.unwrap();
+        let db_state = db.read_db().unwrap();
+        let expected_id = ItemId(0);
+
+        assert_eq!(id, expected_id);
+
+        if let ItemType::Story { id } = db_state.last_item {
+            assert_eq!(id, expected_id)
+        }
+
+        let story = db_state.stories.get(&id.0);
+
+        assert!(story.is_some());
+
+        let story = story.unwrap();
+        assert_eq!(story.detail.name, "First Story");
+    }
+
+    #[test]
+    fn should_clone_story_into_the_same_epic() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(
+                "First Epic".to_owned(),
+                "This is the first test epic".to_owned(),
+            )
+            .unwrap();
+        let story_id = db
+            .create_story(
+                "Crash on login".to_owned(),
+                "Happens on iOS".to_owned(),
+                Some(epic_id),
+            )
+            .unwrap();
+        db.update_story_status(story_id, ItemStatus::Resolved)
+            .unwrap();
+
+        let clone_id = db.clone_story(story_id, Some(epic_id)).unwrap();
+        let data = db.read_db().unwrap();
+        let clone = data.stories.get(&clone_id.0).unwrap();
+
+        assert_eq!(clone_id, ItemId(1));
+        assert_eq!(clone.detail.name, "Crash on login");
+        assert_eq!(clone.detail.description, "Happens on iOS");
+        assert_eq!(clone.detail.status, ItemStatus::Open);
+        assert_eq!(
+            data.epics.get(&epic_id.0).unwrap().stories,
+            vec![story_id, clone_id]
+        );
+    }
+
+    #[test]
+    fn should_fail_to_clone_story_with_invalid_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let result = db.clone_story(ItemId(0), None);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Story ID: {:?} was not found.", ItemId(0))
+        );
+    }
+
+    #[test]
+    fn should_split_story_moving_checklist_items() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic("First Epic".to_owned(), "".to_owned())
+            .unwrap();
+        let story_id = db
+            .create_story("Checkout".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        for text in ["Cart", "Card form", "Receipt"] {
+            db.add_checklist_item(story_id, text.to_owned()).unwrap();
+        }
+
+        let new_id = db
+            .split_story(
+                story_id,
+                Some(epic_id),
+                StorySplit {
+                    name: "Payment".to_owned(),
+                    description: "Card handling".to_owned(),
+                    checklist: vec![1, 2],
+                },
+            )
+            .unwrap();
+
+        let data = db.read_db().unwrap();
+        let original = data.stories.get(&story_id.0).unwrap();
+        let new_story = data.stories.get(&new_id.0).unwrap();
+
+        assert_eq!(
+            original.checklist,
+            vec![ChecklistItem {
+                text: "Cart".to_owned(),
+                done: false
+            }]
+        );
+        assert_eq!(
+            new_story
+                .checklist
+                .iter()
+                .map(|item| item.text.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Card form", "Receipt"]
+        );
+        assert_eq!(new_story.detail.description, "Card handling");
+        assert_eq!(
+            original.links,
+            vec![ItemLink {
+                kind: LinkKind::SplitInto,
+                story_id: new_id
+            }]
+        );
+        assert_eq!(
+            new_story.links,
+            vec![ItemLink {
+                kind: LinkKind::SplitFrom,
+                story_id
+            }]
+        );
+        assert_eq!(
+            data.epics.get(&epic_id.0).unwrap().stories,
+            vec![story_id, new_id]
+        );
+    }
+
+    #[test]
+    fn should_fail_to_split_story_with_invalid_checklist_item() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = db
+            .create_story("Checkout".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let result = db.split_story(
+            story_id,
+            None,
+            StorySplit {
+                name: "Payment".to_owned(),
+                description: "".to_owned(),
+                checklist: vec![0],
+            },
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Checklist item 1 was not found."
+        );
+        assert_eq!(db.read_db().unwrap().stories.len(), 1);
+    }
+
+    #[test]
+    fn should_promote_story_to_epic() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let parent_id = db
+            .create_epic("Checkout".to_owned(), "".to_owned())
+            .unwrap();
+        let story_id = db
+            .create_story(
+                "Payments".to_owned(),
+                "Accept cards".to_owned(),
+                Some(parent_id),
+            )
+            .unwrap();
+        let sibling_id = db
+            .split_story(
+                story_id,
+                Some(parent_id),
+                StorySplit {
+                    name: "Refunds".to_owned(),
+                    description: "".to_owned(),
+                    checklist: Vec::new(),
+                },
+            )
+            .unwrap();
+
+        db.update_story_status(story_id, ItemStatus::InProgress)
+            .unwrap();
+        db.add_checklist_item(story_id, "Card form".to_owned())
+            .unwrap();
+        db.add_checklist_item(story_id, "3D Secure".to_owned())
+            .unwrap();
+        db.toggle_checklist_item(story_id, 0).unwrap();
+
+        let epic_id = db.promote_story(story_id, true).unwrap();
+        let data = db.read_db().unwrap();
+        let epic = data.epics.get(&epic_id.0).unwrap();
+
+        assert_eq!(epic_id, ItemId(1));
+        assert_eq!(epic.detail.name, "Payments");
+        assert_eq!(epic.detail.description, "Accept cards");
+        assert_eq!(epic.detail.status, ItemStatus::InProgress);
+        assert!(!data.stories.contains_key(&story_id.0));
+        assert_eq!(
+            data.epics.get(&parent_id.0).unwrap().stories,
+            vec![sibling_id]
+        );
+        assert!(data.stories.get(&sibling_id.0).unwrap().links.is_empty());
+        assert_eq!(epic.stories, vec![ItemId(2), ItemId(3)]);
+        assert_eq!(data.stories.get(&2).unwrap().detail.name, "Card form");
+        assert_eq!(
+            data.stories.get(&2).unwrap().detail.status,
+            ItemStatus::Closed
+        );
+        assert_eq!(
+            data.stories.get(&3).unwrap().detail.status,
+            ItemStatus::Open
+        );
+        assert_eq!(data.last_item, ItemType::Epic { id: epic_id });
+    }
+
+    #[test]
+    fn should_promote_story_without_moving_checklist() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = db
+            .create_story("Payments".to_owned(), "".to_owned(), None)
+            .unwrap();
+        db.add_checklist_item(story_id, "Card form".to_owned())
+            .unwrap();
+
+        let epic_id = db.promote_story(story_id, false).unwrap();
+        let data = db.read_db().unwrap();
+
+        assert!(data.stories.is_empty());
+        assert!(data.epics.get(&epic_id.0).unwrap().stories.is_empty());
+    }
+
+    #[test]
+    fn should_toggle_checklist_item() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = db
+            .create_story("Checkout".to_owned(), "".to_owned(), None)
+            .unwrap();
+
+        db.add_checklist_item(story_id, "Cart".to_owned()).unwrap();
+        db.toggle_checklist_item(story_id, 0).unwrap();
+
+        let data = db.read_db().unwrap();
+        assert!(data.stories.get(&story_id.0).unwrap().checklist[0].done);
+        assert!(db.toggle_checklist_item(story_id, 1).is_err());
+    }
+
+    #[test]
+    fn should_remove_links_to_deleted_story() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = db
+            .create_story("Checkout".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let new_id = db
+            .split_story(
+                story_id,
+                None,
+                StorySplit {
+                    name: "Payment".to_owned(),
+                    description: "".to_owned(),
+                    checklist: Vec::new(),
+                },
+            )
+            .unwrap();
+
+        db.delete_story(new_id, None).unwrap();
+
+        let data = db.read_db().unwrap();
+        assert!(data.stories.get(&story_id.0).unwrap().links.is_empty());
+    }
+
+    #[test]
+    fn should_add_populated_story() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic("First Epic".to_owned(), "".to_owned())
+            .unwrap();
+        let _ = db
+            .create_story("First Story".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let story = Story::new(ItemDetail {
+            name: "Fix login".to_owned(),
+            priority: Some(Priority::High),
+            assignee: Some("alice".to_owned()),
+            labels: vec!["auth".to_owned()],
+            ..Default::default()
+        });
+
+        let story_id = db.add_story(story, Some(epic_id)).unwrap();
+        let data = db.read_db().unwrap();
+        let stored = data.stories.get(&story_id.0).unwrap();
+
+        assert_eq!(story_id, ItemId(1));
+        assert_eq!(stored.detail.id, story_id);
+        assert_eq!(stored.detail.priority, Some(Priority::High));
+        assert_eq!(stored.detail.assignee, Some("alice".to_owned()));
+        assert_eq!(stored.detail.labels, vec!["auth".to_owned()]);
+        assert_eq!(data.epics.get(&epic_id.0).unwrap().stories, vec![story_id]);
+    }
+
+    #[test]
+    fn should_delete_an_epic() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(
+                "First Epic".to_owned(),
+                "This is the first test epic".to_owned(),
+            )
+            .unwrap();
+        let result = db.delete_epic(epic_id);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_fail_when_deleting_an_epic_with_invalid_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let result = db.delete_epic(ItemId(0));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Epic ID: {:?} was not found", ItemId(0))
+        )
+    }
+
+    #[test]
+    fn should_merge_epics() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let target_id = db
+            .create_epic("Checkout".to_owned(), "Pay for the cart.".to_owned())
+            .unwrap();
+        let source_id = db
+            .create_epic("Payments".to_owned(), "Accept cards.".to_owned())
+            .unwrap();
+        let kept_story = db
+            .create_story("Cart page".to_owned(), "".to_owned(), Some(target_id))
+            .unwrap();
+        let moved_story = db
+            .create_story("Card form".to_owned(), "".to_owned(), Some(source_id))
+            .unwrap();
+
+        db.merge_epics(source_id, target_id).unwrap();
+
+        let data = db.read_db().unwrap();
+        let target = data.epics.get(&target_id.0).unwrap();
+
+        assert_eq!(data.epics.len(), 1);
+        assert_eq!(data.stories.len(), 2);
+        assert_eq!(target.stories, vec![kept_story, moved_story]);
+        assert_eq!(target.detail.description, "Pay for the cart. Accept cards.");
+    }
+
+    #[test]
+    fn should_fail_to_merge_epic_into_itself_or_missing_epic() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic("First Epic".to_owned(), "".to_owned())
+            .unwrap();
+
+        let result = db.merge_epics(epic_id, epic_id);
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Cannot merge epic ID: {:?} into itself", epic_id)
+        );
+
+        let result = db.merge_epics(epic_id, ItemId(90000));
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Epic ID: {:?} was not found", ItemId(90000))
+        );
+        assert_eq!(db.read_db().unwrap().epics.len(), 1);
+    }
+
+    #[test]
+    fn should_demote_epic_into_story() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let target_id = db
+            .create_epic("Checkout".to_owned(), "".to_owned())
+            .unwrap();
+        let epic_id = db
+            .create_epic("Receipts".to_owned(), "Email a receipt".to_owned())
+            .unwrap();
+        let done_id = db
+            .create_story("Template".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let _ = db
+            .create_story("Send email".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        db.update_story_status(done_id, ItemStatus::Resolved)
+            .unwrap();
+
+        let story_id = db.demote_epic(epic_id, target_id).unwrap();
+        let data = db.read_db().unwrap();
+        let story = data.stories.get(&story_id.0).unwrap();
+
+        assert_eq!(data.epics.len(), 1);
+        assert_eq!(data.stories.len(), 1);
+        assert_eq!(story.detail.name, "Receipts");
+        assert_eq!(story.detail.description, "Email a receipt");
+        assert_eq!(
+            story.checklist,
+            vec![
+                ChecklistItem {
+                    text: "Template".to_owned(),
+                    done: true
+                },
+                ChecklistItem {
+                    text: "Send email".to_owned(),
+                    done: false
+                }
+            ]
+        );
+        assert_eq!(
+            data.epics.get(&target_id.0).unwrap().stories,
+            vec![story_id]
+        );
+    }
+
+    #[test]
+    fn should_repoint_links_and_sprints_to_the_demoted_story() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let target_id = db
+            .create_epic("Checkout".to_owned(), "".to_owned())
+            .unwrap();
+        let cart_id = db
+            .create_story("Cart".to_owned(), "".to_owned(), Some(target_id))
+            .unwrap();
+        let epic_id = db
+            .create_epic("Receipts".to_owned(), "".to_owned())
+            .unwrap();
+        let template_id = db
+            .create_story("Template".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let email_id = db
+            .create_story("Send email".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let sprint_id = db
+            .create_sprint(
+                "Launch".to_owned(),
+                NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 5, 14).unwrap(),
+            )
+            .unwrap();
+        db.add_blocker(template_id, cart_id).unwrap();
+        db.add_blocker(email_id, template_id).unwrap();
+        db.add_blocker(cart_id, email_id).unwrap();
+        db.add_story_to_sprint(sprint_id, template_id).unwrap();
+
+        let story_id = db.demote_epic(epic_id, target_id).unwrap();
+        let data = db.read_db().unwrap();
+        let link = |kind, story_id| ItemLink { kind, story_id };
+
+        assert_eq!(
+            data.stories[&story_id.0].links,
+            vec![
+                link(LinkKind::BlockedBy, cart_id),
+                link(LinkKind::Blocks, cart_id)
+            ]
+        );
+        assert_eq!(
+            data.stories[&cart_id.0].links,
+            vec![
+                link(LinkKind::Blocks, story_id),
+                link(LinkKind::BlockedBy, story_id)
+            ]
+        );
+        assert_eq!(data.sprints[&sprint_id.0].stories, vec![story_id]);
+    }
+
+    #[test]
+    fn should_fail_to_demote_epic_into_missing_epic() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic("Receipts".to_owned(), "".to_owned())
+            .unwrap();
+
+        assert!(db.demote_epic(epic_id, epic_id).is_err());
+
+        let result = db.demote_epic(epic_id, ItemId(90000));
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Epic ID: {:?} was not found", ItemId(90000))
+        );
+        assert_eq!(db.read_db().unwrap().epics.len(), 1);
+    }
+
+    #[test]
+    fn should_delete_story_without_epic_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = db
+            .create_story(
+                "First Story".to_owned(),
+                "This is the first test story".to_owned(),
+                None,
+            )
+            .unwrap();
+        let result = db.delete_story(story_id, None);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_fail_when_deleting_a_story_with_invalid_epic_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(
+                "First Epic".to_owned(),
+                "This is the first test epic".to_owned(),
+            )
+            .unwrap();
+        let story_id = db
+            .create_story(
+                "Story With Epic".to_owned(),
+                "This story will be part of an Epic".to_owned(),
+                Some(epic_id),
+            )
+            .unwrap();
+        let result = db.delete_story(story_id, Some(ItemId(90000)));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Epic ID: {:?} was not found", ItemId(90000))
+        );
+    }
+
+    #[test]
+    fn should_update_the_epic_status() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(
+                "First Epic".to_owned(),
+                "This is the first test epic".to_owned(),
+            )
+            .unwrap();
+        let result = db.update_epic_status(epic_id, ItemStatus::Resolved);
+
+        assert!(result.is_ok());
+
+        let data = db.database.read_db().unwrap();
+        let epic = data.epics.get(&epic_id.0).unwrap();
+
+        assert_eq!(epic.detail.status, ItemStatus::Resolved);
+    }
+
+    #[test]
+    fn should_fail_to_update_epic_with_invalid_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let result = db.update_epic_status(ItemId(0), ItemStatus::Closed);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Epic ID: {:?} was not found.", ItemId(0))
+        );
+    }
+
+    #[test]
+    fn should_update_story_status() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = db
+            .create_story(
+                "First Story".to_owned(),
+                "This is the first test story".to_owned(),
+                None,
+            )
+            .unwrap();
+        let result = db.update_story_status(story_id, ItemStatus::Resolved);
+
+        assert!(result.is_ok());
+
+        let data = db.database.read_db().unwrap();
+        let story = data.stories.get(&story_id.0).unwrap();
+
+        assert_eq!(story.detail.status, ItemStatus::Resolved);
+    }
+
+    #[test]
+    fn should_fail_to_update_story_status_with_invalid_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let result = db.update_story_status(ItemId(0), ItemStatus::Closed);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Story ID: {:?} was not found.", ItemId(0))
+        );
+    }
+
+    #[test]
+    fn should_export_epic_with_its_stories() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(
+                "First Epic".to_owned(),
+                "This is the first test epic".to_owned(),
+            )
+            .unwrap();
+        let story_id = db
+            .create_story(
+                "Story With Epic".to_owned(),
+                "This story will be part of an Epic".to_owned(),
+                Some(epic_id),
+            )
+            .unwrap();
+        let _ = db
+            .create_story("Loose Story".to_owned(), "".to_owned(), None)
+            .unwrap();
+
+        let bundle = db.export_epic(epic_id).unwrap();
+
+        assert_eq!(bundle.epic.detail.name, "First Epic");
+        assert_eq!(bundle.stories.len(), 1);
+        assert_eq!(bundle.stories[0].detail.id, story_id);
+    }
+
+    #[test]
+    fn should_fail_to_export_epic_with_invalid_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let result = db.export_epic(ItemId(0));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!("Epic ID: {:?} was not found.", ItemId(0))
+        );
+    }
+
+    #[test]
+    fn should_import_epic_with_remapped_ids() {
+        let source = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = source
+            .create_epic("Imported Epic".to_owned(), "".to_owned())
+            .unwrap();
+        let _ = source
+            .create_story("First".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let _ = source
+            .create_story("Second".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let bundle = source.export_epic(epic_id).unwrap();
+
+        let target = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let existing_epic = target
+            .create_epic("Existing".to_owned(), "".to_owned())
+            .unwrap();
+        let _ = target
+            .create_story("Existing".to_owned(), "".to_owned(), Some(existing_epic))
+            .unwrap();
+
+        let imported_id = target.import_epic(bundle).unwrap();
+        let data = target.read_db().unwrap();
+        let epic = data.epics.get(&imported_id.0).unwrap();
+
+        assert_eq!(imported_id, ItemId(1));
+        assert_eq!(data.epics.len(), 2);
+        assert_eq!(data.stories.len(), 3);
+        assert_eq!(epic.detail.id, imported_id);
+        assert_eq!(epic.stories, vec![ItemId(1), ItemId(2)]);
+        assert_eq!(data.stories.get(&1).unwrap().detail.name, "First");
+        assert_eq!(data.stories.get(&2).unwrap().detail.id, ItemId(2));
+        assert_eq!(data.last_item, ItemType::Epic { id: imported_id });
+    }
+
+    #[test]
+    fn should_import_links_between_the_bundled_stories_only() {
+        let source = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let outside_id = source
+            .create_story("Ledger".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let epic_id = source
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        let refunds_id = source
+            .create_story("Refunds".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let receipts_id = source
+            .create_story("Receipts".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        source.add_blocker(refunds_id, receipts_id).unwrap();
+        source.add_blocker(refunds_id, outside_id).unwrap();
+        let mut bundle = source.export_epic(epic_id).unwrap();
+        bundle.stories[0].taskwarrior = Some("6f1c2d3e".to_owned());
+
+        let target = JiraDataBase::from_database(Box::new(MockDB::new()));
+        for name in ["Existing", "Other"] {
+            target
+                .create_story(name.to_owned(), "".to_owned(), None)
+                .unwrap();
+        }
+
+        let imported_id = target.import_epic(bundle).unwrap();
+        let data = target.read_db().unwrap();
+        let epic = &data.epics[&imported_id.0];
+        let (refunds, receipts) = (&data.stories[&2], &data.stories[&3]);
+
+        assert_eq!(epic.stories, vec![ItemId(2), ItemId(3)]);
+        assert_eq!(refunds.detail.name, "Refunds");
+        assert_eq!(
+            refunds.links,
+            vec![ItemLink {
+                kind: LinkKind::BlockedBy,
+                story_id: ItemId(3),
+            }]
+        );
+        assert_eq!(
+            receipts.links,
+            vec![ItemLink {
+                kind: LinkKind::Blocks,
+                story_id: ItemId(2),
+            }]
+        );
+        assert_eq!(refunds.taskwarrior, None);
+    }
+
+    #[test]
+    fn should_look_up_items_by_id() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        let other_id = db.create_epic("Search".to_owned(), "".to_owned()).unwrap();
+        let first = db
+            .create_story("Card form".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let _ = db
+            .create_story("Ranking".to_owned(), "".to_owned(), Some(other_id))
+            .unwrap();
+        let second = db
+            .create_story("Refunds".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        db.update_epic_status(other_id, ItemStatus::InProgress)
+            .unwrap();
+
+        assert_eq!(db.get_epic(epic_id).unwrap().detail.name, "Payments");
+        assert_eq!(db.get_story(second).unwrap().detail.name, "Refunds");
+        assert_eq!(
+            db.list_stories_for_epic(epic_id)
+                .unwrap()
+                .iter()
+                .map(|story| story.detail.id)
+                .collect::<Vec<_>>(),
+            vec![first, second]
+        );
+        assert_eq!(
+            db.list_epics_by_status(&ItemStatus::InProgress)
+                .unwrap()
+                .iter()
+                .map(|epic| epic.detail.id)
+                .collect::<Vec<_>>(),
+            vec![other_id]
+        );
+
+        assert_eq!(
+            db.get_epic(ItemId(999)).unwrap_err().to_string(),
+            "Epic ID: ItemId(999) was not found."
+        );
+        assert!(db.get_story(ItemId(999)).is_err());
+        assert!(db.list_stories_for_epic(ItemId(999)).is_err());
+        assert!(db.get_sprint(ItemId(999)).is_err());
+    }
+
+    #[test]
+    fn should_list_items_a_page_at_a_time() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+
+        for name in ["search", "Payments", "Onboarding", "Billing"] {
+            db.create_story(name.to_owned(), "".to_owned(), None)
+                .unwrap();
+        }
+        db.update_story_status(ItemId(0), ItemStatus::Closed)
+            .unwrap();
+        db.update_story_status(ItemId(3), ItemStatus::InProgress)
+            .unwrap();
+
+        let names = |sort, offset, limit| {
+            db.list_stories(offset, limit, sort)
+                .unwrap()
+                .items
+                .into_iter()
+                .map(|story| story.detail.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(SortBy::Id, 1, 2), vec!["Payments", "Onboarding"]);
+        assert_eq!(
+            names(SortBy::Name, 0, 10),
+            vec!["Billing", "Onboarding", "Payments", "search"]
+        );
+        assert_eq!(
+            names(SortBy::Status, 0, 3),
+            vec!["Payments", "Onboarding", "Billing"]
+        );
+        assert!(names(SortBy::Id, 4, 2).is_empty());
+        assert_eq!(db.list_stories(3, 2, SortBy::Id).unwrap().total, 4);
+        assert_eq!(db.list_epics(0, 10, SortBy::Id).unwrap().total, 0);
+    }
+
+    #[test]
+    fn search_should_let_the_backend_filter() {
+        struct IndexedDB(MockDB);
+
+        impl DataBase for IndexedDB {
+            fn read_db(&self) -> Result<DB> {
+                self.0.read_db()
+            }
+
+            fn write_db(&self, database: &DB) -> Result<()> {
+                self.0.write_db(database)
+            }
+
+            fn search(&self, _filter: &Filter) -> Result<Vec<SearchHit>> {
+                Ok(vec![SearchHit {
+                    item: ItemType::Story { id: ItemId(7) },
+                    key: "STORY-7".to_owned(),
+                    score: 0,
+                }])
+            }
+        }
 
-    #[test]
-    fn create_epic_should_work() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
+        let filter = Filter {
+            status: Some(ItemStatus::Open),
+            ..Default::default()
         };
-        let result = db.create_epic(
-            "First Epic".to_owned(),
-            "This is the first test epic".to_owned(),
+        let in_memory = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = in_memory
+            .create_story("".to_owned(), "".to_owned(), None)
+            .unwrap();
+
+        assert_eq!(
+            in_memory.search(&filter).unwrap(),
+            search(&in_memory.read_db().unwrap(), &filter)
+        );
+        assert_eq!(
+            in_memory.search(&filter).unwrap()[0].item,
+            ItemType::Story { id: story_id }
         );
 
-        assert_eq!(result.is_ok(), true);
+        let indexed = JiraDataBase::from_database(Box::new(IndexedDB(MockDB::new())));
+        assert_eq!(indexed.search(&filter).unwrap()[0].key, "STORY-7");
+    }
 
-        let id = result.unwrap();
-        let db_state = db.read_db().unwrap();
-        let expected_id = ItemId(0);
+    #[test]
+    fn should_publish_what_each_save_changed() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let received = Rc::clone(&events);
 
-        assert_eq!(id, expected_id);
+        db.subscribe(move |event| received.borrow_mut().push(event.clone()));
 
-        match db_state.last_item {
-            ItemType::Epic { id } => assert_eq!(id, expected_id),
-            _ => (),
-        }
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        db.update_story_status(story_id, ItemStatus::InProgress)
+            .unwrap();
+        db.set_story_points(story_id, Some(3)).unwrap();
+        db.delete_epic(epic_id).unwrap();
 
-        let epic = db_state.epics.get(&id.0);
+        let epic = ItemType::Epic { id: epic_id };
+        let story = ItemType::Story { id: story_id };
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                ChangeEvent::ItemCreated { item: epic.clone() },
+                ChangeEvent::ItemUpdated { item: epic.clone() },
+                ChangeEvent::ItemCreated {
+                    item: story.clone()
+                },
+                ChangeEvent::StatusChanged {
+                    item: story.clone(),
+                    from: ItemStatus::Open,
+                    to: ItemStatus::InProgress,
+                },
+                ChangeEvent::ItemUpdated {
+                    item: story.clone()
+                },
+                ChangeEvent::ItemDeleted { item: story },
+                ChangeEvent::ItemDeleted { item: epic },
+            ]
+        );
+    }
 
-        assert!(epic.is_some());
+    #[test]
+    fn should_update_statuses_of_several_items() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let items = vec![
+            ItemType::Epic { id: epic_id },
+            ItemType::Story { id: story_id },
+        ];
 
-        let epic = epic.unwrap();
-        assert_eq!(epic.detail.name, "First Epic");
+        db.update_statuses(&items, ItemStatus::InProgress).unwrap();
+
+        let data = db.read_db().unwrap();
+        assert_eq!(
+            data.epics.get(&epic_id.0).unwrap().detail.status,
+            ItemStatus::InProgress
+        );
+        assert_eq!(
+            data.stories.get(&story_id.0).unwrap().detail.status,
+            ItemStatus::InProgress
+        );
+
+        let result = db.update_statuses(
+            &[
+                ItemType::Story { id: story_id },
+                ItemType::Story { id: ItemId(999) },
+            ],
+            ItemStatus::Closed,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            db.read_db()
+                .unwrap()
+                .stories
+                .get(&story_id.0)
+                .unwrap()
+                .detail
+                .status,
+            ItemStatus::InProgress
+        );
     }
 
     #[test]
-    fn should_fail_when_creating_story_without_epic_id() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
-        let result = db.create_story(
-            "Failure Story Without Epic".to_owned(),
-            "This story won't be created if the Epic ID is not valid nor found".to_owned(),
-            Some(ItemId(90000)),
+    fn should_edit_fields_of_several_items_at_once() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let date = |day: u32| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let sprint_id = db
+            .create_sprint("First".to_owned(), date(1), date(14))
+            .unwrap();
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story = Story::new(ItemDetail {
+            labels: vec!["legacy".to_owned()],
+            ..Default::default()
+        });
+        let story_id = db.add_story(story, Some(epic_id)).unwrap();
+        let items = vec![
+            ItemType::Epic { id: epic_id },
+            ItemType::Story { id: story_id },
+        ];
+
+        db.bulk_edit(
+            &items,
+            &[
+                FieldEdit::AddLabel("billing".to_owned()),
+                FieldEdit::RemoveLabel("legacy".to_owned()),
+                FieldEdit::SetPriority(Some(Priority::High)),
+                FieldEdit::SetAssignee(Some("alice".to_owned())),
+                FieldEdit::MoveToSprint(sprint_id),
+            ],
+        )
+        .unwrap();
+
+        let data = db.read_db().unwrap();
+        for detail in [
+            &data.epics[&epic_id.0].detail,
+            &data.stories[&story_id.0].detail,
+        ] {
+            assert_eq!(detail.labels, vec!["billing".to_owned()]);
+            assert_eq!(detail.priority, Some(Priority::High));
+            assert_eq!(detail.assignee, Some("alice".to_owned()));
+        }
+        assert_eq!(data.sprints[&sprint_id.0].stories, vec![story_id]);
+        assert_eq!(
+            data.notifications
+                .iter()
+                .filter(|notification| notification.recipient == "alice")
+                .count(),
+            2
+        );
+
+        let result = db.bulk_edit(
+            &items,
+            &[
+                FieldEdit::SetAssignee(None),
+                FieldEdit::MoveToSprint(ItemId(9)),
+            ],
         );
 
         assert!(result.is_err());
         assert_eq!(
-            result.err().unwrap().to_string(),
-            format!("Epic ID: {:?} was not found", ItemId(90000))
+            db.read_db().unwrap().stories[&story_id.0].detail.assignee,
+            Some("alice".to_owned())
         );
     }
 
     #[test]
-    fn should_create_story_with_epic_id() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
-        let epic_id = db
-            .create_epic(
-                "First Epic".to_owned(),
-                "This is the first test epic".to_owned(),
+    fn should_notify_watchers_and_assignee_of_status_changes() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .add_story(
+                Story::new(ItemDetail {
+                    assignee: Some("alice".to_owned()),
+                    ..Default::default()
+                }),
+                Some(epic_id),
             )
             .unwrap();
-        let result = db.create_story(
-            "Story With Epic".to_owned(),
-            "This story will be part of an Epic".to_owned(),
-            Some(epic_id),
-        );
-
-        assert!(result.is_ok());
+        let story = ItemType::Story { id: story_id };
+
+        assert!(db.toggle_watch(&story, "bob").unwrap());
+        assert!(db.toggle_watch(&story, "alice").unwrap());
+        assert!(db
+            .toggle_watch(&ItemType::Epic { id: epic_id }, "carol")
+            .unwrap());
+        db.update_story_status(story_id, ItemStatus::InProgress)
+            .unwrap();
+        db.update_story_status(story_id, ItemStatus::InProgress)
+            .unwrap();
 
-        let data = db.database.read_db().unwrap();
-        let epic = data.epics.get(&epic_id.0).unwrap();
+        let notifications = db.read_db().unwrap().notifications;
+        let received = notifications
+            .iter()
+            .map(|n| (n.recipient.as_str(), n.message.as_str()))
+            .collect::<Vec<_>>();
 
-        assert_eq!(epic.stories.len(), 1);
-        assert_eq!(*epic.stories.first().unwrap(), result.unwrap())
+        assert_eq!(
+            received,
+            vec![
+                ("alice", "STORY-0 was assigned to you"),
+                ("alice", "STORY-0 moved to IN PROGRESS"),
+                ("bob", "STORY-0 moved to IN PROGRESS"),
+            ]
+        );
+        assert!(!db.toggle_watch(&story, "bob").unwrap());
+        assert_eq!(
+            db.read_db().unwrap().stories[&story_id.0].detail.watchers,
+            vec!["alice".to_owned()]
+        );
     }
 
     #[test]
-    fn should_create_story_without_epic_id() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
+    fn should_remind_of_due_items_once() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let today = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let due = |days: u64, assignee: &str, status: ItemStatus| {
+            Story::new(ItemDetail {
+                assignee: Some(assignee.to_owned()),
+                due: Some(today + Days::new(days)),
+                status,
+                ..Default::default()
+            })
         };
-        let result = db.create_story(
-            "First Story".to_owned(),
-            "This is the first test story".to_owned(),
-            None,
+        let _ = db
+            .add_story(due(1, "alice", ItemStatus::Open), None)
+            .unwrap();
+        let _ = db
+            .add_story(due(5, "alice", ItemStatus::Open), None)
+            .unwrap();
+        let _ = db
+            .add_story(due(0, "alice", ItemStatus::Closed), None)
+            .unwrap();
+        let _ = db.add_story(due(0, "bob", ItemStatus::Open), None).unwrap();
+
+        assert_eq!(db.add_due_notifications("alice", today).unwrap(), 1);
+        assert_eq!(
+            db.add_due_notifications("alice", today + Days::new(2))
+                .unwrap(),
+            0
         );
 
-        assert_eq!(result.is_ok(), true);
+        let notifications = db.read_db().unwrap().notifications;
+        let reminder = notifications.last().unwrap();
 
-        let id = result.unwrap();
-        let db_state = db.read_db().unwrap();
-        let expected_id = ItemId(0);
+        assert_eq!(reminder.message, "STORY-0 is due 2024-05-02");
+        assert_eq!(reminder.recipient, "alice");
+    }
 
-        assert_eq!(id, expected_id);
+    #[test]
+    fn should_mark_notifications_as_read() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let assigned = |assignee: &str| {
+            Story::new(ItemDetail {
+                assignee: Some(assignee.to_owned()),
+                ..Default::default()
+            })
+        };
+        let _ = db.add_story(assigned("alice"), None).unwrap();
+        let _ = db.add_story(assigned("alice"), None).unwrap();
+        let _ = db.add_story(assigned("bob"), None).unwrap();
 
-        match db_state.last_item {
-            ItemType::Story { id } => assert_eq!(id, expected_id),
-            _ => (),
-        }
+        db.mark_notification_read(0).unwrap();
+        assert!(db.mark_notification_read(9).is_err());
+        assert_eq!(
+            db.read_db()
+                .unwrap()
+                .notifications
+                .iter()
+                .map(|n| n.read)
+                .collect::<Vec<_>>(),
+            vec![true, false, false]
+        );
 
-        let story = db_state.stories.get(&id.0);
+        db.mark_notifications_read("alice").unwrap();
+        assert_eq!(
+            db.read_db()
+                .unwrap()
+                .notifications
+                .iter()
+                .map(|n| n.read)
+                .collect::<Vec<_>>(),
+            vec![true, true, false]
+        );
+    }
 
-        assert!(story.is_some());
+    #[test]
+    fn should_record_description_edits() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new())).with_user("alice".to_owned());
+        let story_id = db
+            .create_story("".to_owned(), "Refund within 30 days".to_owned(), None)
+            .unwrap();
+        let story = ItemType::Story { id: story_id };
 
-        let story = story.unwrap();
-        assert_eq!(story.detail.name, "First Story");
+        db.replace_text("30", "14", std::slice::from_ref(&story))
+            .unwrap();
+        db.replace_text("Refund", "Refund", &[story]).unwrap();
+
+        let edits = db.get_story(story_id).unwrap().detail.description_edits;
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].by, Some("alice".to_owned()));
+        assert_eq!(edits[0].diff, vec!["- 30".to_owned(), "+ 14".to_owned()]);
+
+        let clone_id = db.clone_story(story_id, None).unwrap();
+        assert!(db
+            .get_story(clone_id)
+            .unwrap()
+            .detail
+            .description_edits
+            .is_empty());
     }
 
     #[test]
-    fn should_delete_an_epic() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
-        let epic_id = db
-            .create_epic(
-                "First Epic".to_owned(),
-                "This is the first test epic".to_owned(),
-            )
+    fn should_record_status_history() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
             .unwrap();
-        let result = db.delete_epic(epic_id);
 
-        assert!(result.is_ok());
+        db.update_story_status(story_id, ItemStatus::InProgress)
+            .unwrap();
+        db.update_story_status(story_id, ItemStatus::Closed)
+            .unwrap();
+
+        let data = db.read_db().unwrap();
+        let statuses = |detail: &ItemDetail| {
+            detail
+                .history
+                .iter()
+                .map(|change| change.status.clone())
+                .collect::<Vec<_>>()
+        };
+        let story = &data.stories[&story_id.0].detail;
+
+        assert_eq!(
+            statuses(story),
+            vec![ItemStatus::Open, ItemStatus::InProgress, ItemStatus::Closed]
+        );
+        assert!(story.history[0].at <= story.history[2].at);
+        assert_eq!(
+            statuses(&data.epics[&epic_id.0].detail),
+            vec![ItemStatus::Open]
+        );
+
+        let clone_id = db.clone_story(story_id, Some(epic_id)).unwrap();
+        assert_eq!(
+            statuses(&db.read_db().unwrap().stories[&clone_id.0].detail),
+            vec![ItemStatus::Open]
+        );
     }
 
     #[test]
-    fn should_fail_when_deleting_an_epic_with_invalid_id() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
-        let result = db.delete_epic(ItemId(0));
+    fn should_link_blocked_stories() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        let blocker_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
 
-        assert!(result.is_err());
+        db.add_blocker(story_id, blocker_id).unwrap();
+        db.add_blocker(story_id, blocker_id).unwrap();
+
+        let data = db.read_db().unwrap();
         assert_eq!(
-            result.err().unwrap().to_string(),
-            format!("Epic ID: {:?} was not found", ItemId(0))
-        )
+            data.stories[&story_id.0].links,
+            vec![ItemLink {
+                kind: LinkKind::BlockedBy,
+                story_id: blocker_id
+            }]
+        );
+        assert_eq!(
+            data.stories[&blocker_id.0].links,
+            vec![ItemLink {
+                kind: LinkKind::Blocks,
+                story_id
+            }]
+        );
+        assert!(db.add_blocker(story_id, story_id).is_err());
+        assert!(db.add_blocker(story_id, ItemId(9)).is_err());
     }
 
     #[test]
-    fn should_delete_story_without_epic_id() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
-        let story_id = db
-            .create_story(
-                "First Story".to_owned(),
-                "This is the first test story".to_owned(),
-                None,
-            )
+    fn should_plan_sprints() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let date = |day: u32| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let first = db
+            .create_sprint("First".to_owned(), date(1), date(14))
             .unwrap();
-        let result = db.delete_story(story_id, None);
+        let second = db
+            .create_sprint("Second".to_owned(), date(15), date(28))
+            .unwrap();
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
 
-        assert!(result.is_ok());
+        db.add_story_to_sprint(first, story_id).unwrap();
+        db.add_story_to_sprint(second, story_id).unwrap();
+        db.set_story_points(story_id, Some(5)).unwrap();
+
+        let data = db.read_db().unwrap();
+        assert!(data.sprints[&first.0].stories.is_empty());
+        assert_eq!(data.sprints[&second.0].stories, vec![story_id]);
+        assert_eq!(data.stories[&story_id.0].detail.points, Some(5));
+
+        assert!(db
+            .create_sprint("Backwards".to_owned(), date(14), date(1))
+            .is_err());
+        assert!(db.add_story_to_sprint(ItemId(9), story_id).is_err());
+
+        db.delete_story(story_id, None).unwrap();
+        assert!(db.read_db().unwrap().sprints[&second.0].stories.is_empty());
     }
 
     #[test]
-    fn should_fail_when_deleting_a_story_with_invalid_epic_id() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
-        let epic_id = db
-            .create_epic(
-                "First Epic".to_owned(),
-                "This is the first test epic".to_owned(),
-            )
+    fn should_keep_the_stories_of_closed_sprints() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let date = |day: u32| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let first = db
+            .create_sprint("First".to_owned(), date(1), date(14))
             .unwrap();
-        let story_id = db
-            .create_story(
-                "Story With Epic".to_owned(),
-                "This story will be part of an Epic".to_owned(),
-                Some(epic_id),
-            )
+        let second = db
+            .create_sprint("Second".to_owned(), date(15), date(28))
             .unwrap();
-        let result = db.delete_story(story_id, Some(ItemId(90000)));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        db.add_story_to_sprint(first, story_id).unwrap();
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.err().unwrap().to_string(),
-            format!("Epic ID: {:?} was not found", ItemId(90000))
-        );
+        let closed = db.close_sprint(first, date(14)).unwrap();
+        assert_eq!(closed.closed, Some(date(14)));
+        assert!(db.close_sprint(first, date(15)).is_err());
+        assert!(db.add_story_to_sprint(first, story_id).is_err());
+
+        db.add_story_to_sprint(second, story_id).unwrap();
+
+        let data = db.read_db().unwrap();
+        assert_eq!(data.sprints[&first.0].stories, vec![story_id]);
+        assert_eq!(data.sprints[&second.0].stories, vec![story_id]);
     }
 
     #[test]
-    fn should_update_the_epic_status() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
+    fn should_save_filters_by_name() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let open_bugs = Filter {
+            status: Some(ItemStatus::Open),
+            label: Some("bug".to_owned()),
+            ..Default::default()
+        };
+        let my_open_bugs = Filter {
+            assignee: Some("alice".to_owned()),
+            ..open_bugs.clone()
         };
-        let epic_id = db
-            .create_epic(
-                "First Epic".to_owned(),
-                "This is the first test epic".to_owned(),
-            )
-            .unwrap();
-        let result = db.update_epic_status(epic_id, ItemStatus::Resolved);
 
-        assert!(result.is_ok());
+        db.save_filter("Open bugs".to_owned(), open_bugs.clone())
+            .unwrap();
+        db.save_filter("Mine".to_owned(), open_bugs).unwrap();
+        db.save_filter("Mine".to_owned(), my_open_bugs.clone())
+            .unwrap();
 
-        let data = db.database.read_db().unwrap();
-        let epic = data.epics.get(&epic_id.0).unwrap();
+        let filters = db.read_db().unwrap().filters;
 
-        assert_eq!(epic.detail.status, ItemStatus::Resolved);
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[1].name, "Mine");
+        assert_eq!(filters[1].filter, my_open_bugs);
     }
 
     #[test]
-    fn should_fail_to_update_epic_with_invalid_id() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
-        let result = db.update_epic_status(ItemId(0), ItemStatus::Closed);
+    fn should_bump_versions_of_changed_items() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.err().unwrap().to_string(),
-            format!("Epic ID: {:?} was not found.", ItemId(0))
-        );
+        let state = db.read_db().unwrap();
+        assert_eq!(state.epics[&epic_id.0].detail.version, 2);
+        assert_eq!(state.stories[&story_id.0].detail.version, 1);
+
+        db.update_story_status(story_id, ItemStatus::Resolved)
+            .unwrap();
+
+        let state = db.read_db().unwrap();
+        assert_eq!(state.epics[&epic_id.0].detail.version, 2);
+        assert_eq!(state.stories[&story_id.0].detail.version, 2);
     }
 
     #[test]
-    fn should_update_story_status() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
+    fn should_attribute_changes_to_the_acting_user() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new())).with_user("alice".to_owned());
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
         let story_id = db
-            .create_story(
-                "First Story".to_owned(),
-                "This is the first test story".to_owned(),
-                None,
-            )
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
             .unwrap();
-        let result = db.update_story_status(story_id, ItemStatus::Resolved);
 
-        assert!(result.is_ok());
+        let db = db.with_user("bob".to_owned());
+        db.update_story_status(story_id, ItemStatus::Resolved)
+            .unwrap();
 
-        let data = db.database.read_db().unwrap();
-        let story = data.stories.get(&story_id.0).unwrap();
+        let state = db.read_db().unwrap();
+        let epic = &state.epics[&epic_id.0].detail;
+        let story = &state.stories[&story_id.0].detail;
 
-        assert_eq!(story.detail.status, ItemStatus::Resolved);
+        assert_eq!(epic.updated_by.as_deref(), Some("alice"));
+        assert_eq!(story.updated_by.as_deref(), Some("bob"));
+        assert!(epic.updated_at.unwrap() <= story.updated_at.unwrap());
+        assert_eq!(
+            story
+                .history
+                .iter()
+                .map(|change| change.by.as_deref())
+                .collect::<Vec<_>>(),
+            vec![Some("alice"), Some("bob")]
+        );
     }
 
     #[test]
-    fn should_fail_to_update_story_status_with_invalid_id() {
-        let db = JiraDataBase {
-            database: Box::new(MockDB::new()),
-        };
-        let result = db.update_story_status(ItemId(0), ItemStatus::Closed);
+    fn should_fail_to_save_items_changed_since_they_were_loaded() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        let mut stale = db.read_db().unwrap();
+        let loaded = db
+            .item_versions(&[ItemType::Story { id: story_id }])
+            .unwrap();
+
+        db.update_story_status(story_id, ItemStatus::Closed)
+            .unwrap();
+
+        stale.stories.get_mut(&story_id.0).unwrap().detail.name = "Renamed".to_owned();
+        let error = db.save(&mut stale).unwrap_err();
 
-        assert!(result.is_err());
         assert_eq!(
-            result.err().unwrap().to_string(),
-            format!("Story ID: {:?} was not found.", ItemId(0))
+            error.to_string(),
+            "STORY-0 was changed elsewhere since it was loaded (version 2, loaded 1), reload and try again"
         );
+        assert!(db.check_versions(&loaded).is_err());
+        assert_eq!(db.read_db().unwrap().stories[&story_id.0].detail.name, "");
+        assert!(db
+            .check_versions(
+                &db.item_versions(&[ItemType::Story { id: story_id }])
+                    .unwrap()
+            )
+            .is_ok());
     }
 
     mod database {
@@ -499,7 +3202,7 @@ mod tests {
 
         #[test]
         fn read_db_should_fail_with_invalid_path() {
-            let db = JSONFileDatabase(Path::new("INVALID_PATH").to_owned());
+            let db = JSONFileDatabase::new(Path::new("INVALID_PATH").to_owned());
             assert!(db.read_db().is_err());
         }
 
@@ -513,7 +3216,7 @@ mod tests {
             )
             .unwrap();
 
-            let db = JSONFileDatabase(file.path().to_owned());
+            let db = JSONFileDatabase::new(file.path().to_owned());
 
             assert!(db.read_db().is_err());
         }
@@ -528,7 +3231,7 @@ mod tests {
             )
             .unwrap();
 
-            let db = JSONFileDatabase(file.path().to_owned());
+            let db = JSONFileDatabase::new(file.path().to_owned());
             let result = db.read_db();
 
             assert!(result.is_ok());
@@ -549,13 +3252,14 @@ mod tests {
             )
             .unwrap();
 
-            let db = JSONFileDatabase(file.path().to_owned());
+            let db = JSONFileDatabase::new(file.path().to_owned());
 
             let story = Story::new(ItemDetail {
                 description: "New Test Story".to_owned(),
                 id: ItemId(0),
                 name: "New Test".to_owned(),
                 status: ItemStatus::Open,
+                ..Default::default()
             });
             let epic = Epic::new(
                 ItemDetail {
@@ -563,6 +3267,7 @@ mod tests {
                     id: ItemId(0),
                     name: "New Epic".to_owned(),
                     status: ItemStatus::Open,
+                    ..Default::default()
                 },
                 vec![ItemId(story.detail.id.0)],
             );
@@ -580,12 +3285,55 @@ mod tests {
                 last_item,
                 epics,
                 stories,
+                filters: Vec::new(),
+                notifications: Vec::new(),
+                sprints: HashMap::new(),
+                story_order: HashMap::new(),
+                notes: Vec::new(),
+                revision: 0,
             };
             let write_result = db.write_db(&state);
             let read_result = db.read_db().unwrap();
 
-            assert_eq!(write_result.is_ok(), true);
-            assert_eq!(read_result, state);
+            assert!(write_result.is_ok());
+            assert_eq!(
+                read_result,
+                DB {
+                    revision: 1,
+                    ..state
+                }
+            );
+        }
+
+        #[test]
+        fn read_db_should_pick_up_changes_made_by_others() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("database.json");
+            let db = JSONFileDatabase::new(path.clone());
+            let other = JSONFileDatabase::new(path.clone());
+            let mut state = DB {
+                last_item: ItemType::None,
+                epics: HashMap::new(),
+                stories: HashMap::new(),
+                filters: Vec::new(),
+                notifications: Vec::new(),
+                sprints: HashMap::new(),
+                story_order: HashMap::new(),
+                notes: Vec::new(),
+                revision: 0,
+            };
+
+            db.write_db(&state).unwrap();
+            state.revision = 1;
+            assert_eq!(other.read_db().unwrap(), state);
+            assert_eq!(other.read_db().unwrap(), state);
+            assert_eq!(other.revision().unwrap(), 1);
+
+            state.stories.insert(0, Story::new(ItemDetail::default()));
+            db.write_db(&state).unwrap();
+            state.revision = 2;
+
+            assert_eq!(other.read_db().unwrap(), state);
         }
     }
 }
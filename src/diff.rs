@@ -0,0 +1,65 @@
+/// What changed between two texts, word by word: runs of removed words as
+/// `- words` and runs of added ones as `+ words`, in the order they appear.
+/// Unchanged words are left out to keep the diff short.
+pub fn word_diff(old: &str, new: &str) -> Vec<String> {
+    let old = old.split_whitespace().collect::<Vec<_>>();
+    let new = new.split_whitespace().collect::<Vec<_>>();
+
+    // common[i][j] is the length of the longest common subsequence of
+    // old[i..] and new[j..].
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = match old[i] == new[j] {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+
+    let mut changes: Vec<(char, Vec<&str>)> = Vec::new();
+    let mut push = |sign: char, word| match changes.last_mut() {
+        Some((last, words)) if *last == sign => words.push(word),
+        _ => changes.push((sign, vec![word])),
+    };
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            push(' ', old[i]);
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            push('-', old[i]);
+            i += 1;
+        } else {
+            push('+', new[j]);
+            j += 1;
+        }
+    }
+
+    changes
+        .into_iter()
+        .filter(|(sign, _)| *sign != ' ')
+        .map(|(sign, words)| format!("{sign} {}", words.join(" ")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_diff_should_keep_only_the_changed_runs() {
+        assert_eq!(
+            word_diff("Pay by card", "Pay by card or PayPal"),
+            vec!["+ or PayPal"]
+        );
+        assert_eq!(
+            word_diff("Refund within 30 days by mail", "Refund within 14 days"),
+            vec!["- 30", "+ 14", "- by mail"]
+        );
+        assert_eq!(word_diff("", "New"), vec!["+ New"]);
+        assert!(word_diff("Same  words", "Same words").is_empty());
+    }
+}
@@ -0,0 +1,336 @@
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::db::{sorted_page, DataBase, Listing, SortBy};
+use crate::model::{Epic, ItemType, Notification, SavedFilter, Sprint, Story, DB};
+
+/// Stores every epic and story in its own file, so saving a change only
+/// rewrites the items that changed plus a small index:
+///
+/// ```text
+/// <root>/index.json
+/// <root>/epics/<id>.json
+/// <root>/stories/<id>.json
+/// ```
+pub struct DirectoryDatabase {
+    root: PathBuf,
+    cache: RefCell<Option<(u64, DB)>>,
+}
+
+/// Everything on the board but the epics and stories themselves. `revision`
+/// goes up on every write, which tells readers whether their copy is current.
+#[derive(Deserialize, Serialize)]
+struct Index {
+    revision: u64,
+    last_item: ItemType,
+    epics: Vec<u32>,
+    stories: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    filters: Vec<SavedFilter>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notifications: Vec<Notification>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    sprints: HashMap<u32, Sprint>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    story_order: HashMap<u32, SortBy>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<String>,
+}
+
+impl DirectoryDatabase {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Whether `root` already holds a board.
+    pub fn exists(&self) -> bool {
+        self.index_path().exists()
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.root.join("index.json")
+    }
+
+    fn item_path(&self, kind: &str, id: u32) -> PathBuf {
+        self.root.join(kind).join(format!("{id}.json"))
+    }
+
+    /// A directory without an index holds an empty board at revision 0.
+    fn read_index(&self) -> Result<Index> {
+        if !self.exists() {
+            return Ok(Index {
+                revision: 0,
+                last_item: ItemType::None,
+                epics: Vec::new(),
+                stories: Vec::new(),
+                filters: Vec::new(),
+                notifications: Vec::new(),
+                sprints: HashMap::new(),
+                story_order: HashMap::new(),
+                notes: Vec::new(),
+            });
+        }
+
+        read_json(&self.index_path())
+    }
+}
+
+impl DirectoryDatabase {
+    /// Reads only the items on the requested page when they are listed in ID
+    /// order, which is how the index keeps them.
+    fn list<T: DeserializeOwned>(
+        &self,
+        kind: &str,
+        ids: &[u32],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Listing<T>> {
+        let items = ids
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|id| read_json(&self.item_path(kind, *id)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Listing {
+            items,
+            total: ids.len(),
+        })
+    }
+}
+
+impl DataBase for DirectoryDatabase {
+    fn list_epics(&self, offset: usize, limit: usize, sort: SortBy) -> Result<Listing<Epic>> {
+        if sort != SortBy::Id {
+            let epics = self.read_db()?.epics.into_values();
+            return Ok(sorted_page(epics, offset, limit, sort, |epic| &epic.detail));
+        }
+
+        self.list("epics", &self.read_index()?.epics, offset, limit)
+    }
+
+    fn list_stories(&self, offset: usize, limit: usize, sort: SortBy) -> Result<Listing<Story>> {
+        if sort != SortBy::Id {
+            let stories = self.read_db()?.stories.into_values();
+            return Ok(sorted_page(stories, offset, limit, sort, |story| {
+                &story.detail
+            }));
+        }
+
+        self.list("stories", &self.read_index()?.stories, offset, limit)
+    }
+
+    fn revision(&self) -> Result<u64> {
+        Ok(self.read_index()?.revision)
+    }
+
+    fn read_db(&self) -> Result<DB> {
+        let index = self.read_index()?;
+
+        if let Some((revision, db)) = self.cache.borrow().as_ref() {
+            if *revision == index.revision {
+                return Ok(db.clone());
+            }
+        }
+
+        let epics = index
+            .epics
+            .iter()
+            .map(|id| Ok((*id, read_json::<Epic>(&self.item_path("epics", *id))?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        let stories = index
+            .stories
+            .iter()
+            .map(|id| Ok((*id, read_json::<Story>(&self.item_path("stories", *id))?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        let db = DB {
+            last_item: index.last_item,
+            epics,
+            stories,
+            filters: index.filters,
+            notifications: index.notifications,
+            sprints: index.sprints,
+            story_order: index.story_order,
+            notes: index.notes,
+            revision: index.revision,
+        };
+
+        *self.cache.borrow_mut() = Some((index.revision, db.clone()));
+        Ok(db)
+    }
+
+    /// Writes the changed items first and the index last, so a crash in
+    /// between leaves the previous board in place.
+    fn write_db(&self, database: &DB) -> Result<()> {
+        let revision = self.read_index()?.revision;
+        let previous = self.read_db()?;
+
+        fs::create_dir_all(self.root.join("epics"))?;
+        fs::create_dir_all(self.root.join("stories"))?;
+
+        write_changed(&previous.epics, &database.epics, |id| {
+            self.item_path("epics", id)
+        })?;
+        write_changed(&previous.stories, &database.stories, |id| {
+            self.item_path("stories", id)
+        })?;
+
+        let index = Index {
+            revision: revision + 1,
+            last_item: database.last_item.clone(),
+            epics: database.epics.keys().copied().sorted().collect(),
+            stories: database.stories.keys().copied().sorted().collect(),
+            filters: database.filters.clone(),
+            notifications: database.notifications.clone(),
+            sprints: database.sprints.clone(),
+            story_order: database.story_order.clone(),
+            notes: database.notes.clone(),
+        };
+
+        write_json(&self.index_path(), &index)?;
+        remove_deleted(&previous.epics, &database.epics, |id| {
+            self.item_path("epics", id)
+        })?;
+        remove_deleted(&previous.stories, &database.stories, |id| {
+            self.item_path("stories", id)
+        })?;
+
+        let database = DB {
+            revision: index.revision,
+            ..database.clone()
+        };
+        *self.cache.borrow_mut() = Some((index.revision, database));
+        Ok(())
+    }
+}
+
+fn write_changed<T: PartialEq + Serialize>(
+    previous: &HashMap<u32, T>,
+    items: &HashMap<u32, T>,
+    path: impl Fn(u32) -> PathBuf,
+) -> Result<()> {
+    for (id, item) in items {
+        if previous.get(id) != Some(item) {
+            write_json(&path(*id), item)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_deleted<T>(
+    previous: &HashMap<u32, T>,
+    items: &HashMap<u32, T>,
+    path: impl Fn(u32) -> PathBuf,
+) -> Result<()> {
+    for id in previous.keys().filter(|id| !items.contains_key(id)) {
+        fs::remove_file(path(*id))?;
+    }
+
+    Ok(())
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = fs::read(path).with_context(|| anyhow!("Failed to read {}", path.display()))?;
+
+    serde_json::from_slice(&content).with_context(|| anyhow!("Failed to parse {}", path.display()))
+}
+
+/// Writes through a temporary file, so readers never see half a file.
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let partial = path.with_extension("json.partial");
+
+    fs::write(&partial, serde_json::to_vec_pretty(value)?)?;
+    fs::rename(&partial, path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::JiraDataBase;
+    use crate::model::{ItemId, ItemStatus};
+    use std::time::SystemTime;
+
+    fn modified(path: PathBuf) -> SystemTime {
+        fs::metadata(path).unwrap().modified().unwrap()
+    }
+
+    #[test]
+    fn list_epics_should_only_read_the_requested_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DirectoryDatabase::new(dir.path().to_owned());
+        let db =
+            JiraDataBase::from_database(Box::new(DirectoryDatabase::new(dir.path().to_owned())));
+
+        for name in ["Payments", "Search", "Onboarding"] {
+            db.create_epic(name.to_owned(), "".to_owned()).unwrap();
+        }
+
+        fs::write(dir.path().join("epics/0.json"), "not json").unwrap();
+
+        let page = database.list_epics(1, 5, SortBy::Id).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(
+            page.items
+                .iter()
+                .map(|epic| epic.detail.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Search", "Onboarding"]
+        );
+        assert!(database.list_epics(0, 5, SortBy::Name).is_err());
+    }
+
+    #[test]
+    fn should_only_rewrite_changed_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let db =
+            JiraDataBase::from_database(Box::new(DirectoryDatabase::new(dir.path().to_owned())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let first = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let second = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let untouched = modified(dir.path().join("stories/0.json"));
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        db.update_story_status(second, ItemStatus::Resolved)
+            .unwrap();
+
+        assert_eq!(modified(dir.path().join("stories/0.json")), untouched);
+        assert_ne!(modified(dir.path().join("stories/1.json")), untouched);
+
+        db.delete_story(first, Some(epic_id)).unwrap();
+
+        assert!(!dir.path().join("stories/0.json").exists());
+
+        let state = DirectoryDatabase::new(dir.path().to_owned())
+            .read_db()
+            .unwrap();
+        assert_eq!(state, db.read_db().unwrap());
+        assert_eq!(state.epics[&epic_id.0].stories, vec![ItemId(1)]);
+        assert_eq!(state.stories[&1].detail.status, ItemStatus::Resolved);
+    }
+
+    #[test]
+    fn read_db_should_start_empty_without_an_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DirectoryDatabase::new(dir.path().to_owned());
+
+        assert!(db.read_db().unwrap().epics.is_empty());
+        assert!(!db.exists());
+    }
+}
@@ -0,0 +1,450 @@
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::db::{DataBase, JSONFileDatabase};
+use crate::directory_db::DirectoryDatabase;
+use crate::integrity::{backup_path, check, journal_path};
+use crate::keys::story_key;
+use crate::model::DB;
+
+/// One thing `jira-clone doctor` looked at and what it found. Problems come
+/// with the fix.
+#[derive(Debug, PartialEq)]
+pub struct Finding {
+    pub check: &'static str,
+    pub found: String,
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    fn ok(check: &'static str, found: impl Into<String>) -> Self {
+        Self {
+            check,
+            found: found.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(check: &'static str, found: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            check,
+            found: found.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.fix {
+            None => write!(f, "ok       {}: {}", self.check, self.found),
+            Some(fix) => write!(
+                f,
+                "PROBLEM  {}: {}\n         fix: {fix}",
+                self.check, self.found
+            ),
+        }
+    }
+}
+
+/// Looks over the config at `config_path` and the board it points to, without
+/// changing either.
+pub fn diagnose(config_path: Option<&Path>) -> Vec<Finding> {
+    let (mut findings, config) = check_config(config_path);
+
+    match &config.data_dir {
+        Some(root) => findings.extend(check_directory(root)),
+        None => findings.extend(check_file(Path::new("database.json"), &config)),
+    }
+
+    findings
+}
+
+fn check_config(path: Option<&Path>) -> (Vec<Finding>, Config) {
+    let Some(path) = path.filter(|path| path.exists()) else {
+        return (
+            vec![Finding::ok("config", "none found, using the defaults")],
+            Config::default(),
+        );
+    };
+
+    let config = match Config::read(&path.to_path_buf()) {
+        Ok(config) => config,
+        Err(error) => {
+            let problem = Finding::problem(
+                "config",
+                format!("{error:#}"),
+                format!(
+                    "correct the JSON in {}, or move it away to use the defaults",
+                    path.display()
+                ),
+            );
+            return (vec![problem], Config::default());
+        }
+    };
+    let mut findings = vec![Finding::ok(
+        "config",
+        format!("{} is valid", path.display()),
+    )];
+
+    if let Some(root) = config.data_dir.as_ref().filter(|root| !root.is_dir()) {
+        findings.push(Finding::problem(
+            "config",
+            format!("data_dir {} is not a directory", root.display()),
+            format!(
+                "run jira-clone convert-to-dir {}, or take data_dir out of the config",
+                root.display()
+            ),
+        ));
+    }
+
+    (findings, config)
+}
+
+fn check_file(path: &Path, config: &Config) -> Vec<Finding> {
+    if !path.exists() {
+        return vec![Finding::ok(
+            "board",
+            format!(
+                "no {} yet, it is created with the first change",
+                path.display()
+            ),
+        )];
+    }
+
+    let mut findings = vec![
+        check_permissions(path),
+        check_journal(path),
+        check_board(path),
+        check_backup(path, config),
+    ];
+
+    if let Ok(db) = JSONFileDatabase::new(path.to_path_buf()).read_db() {
+        findings.extend(check_references(&db));
+    }
+
+    findings
+}
+
+fn check_directory(root: &Path) -> Vec<Finding> {
+    if !root.is_dir() {
+        return Vec::new();
+    }
+
+    let mut findings = vec![check_permissions(root)];
+
+    match DirectoryDatabase::new(root.to_path_buf()).read_db() {
+        Ok(db) => {
+            findings.push(Finding::ok(
+                "board",
+                format!("{} is readable", root.display()),
+            ));
+            findings.extend(check_references(&db));
+        }
+        Err(error) => findings.push(Finding::problem(
+            "board",
+            format!("{error:#}"),
+            "restore the directory from version control, or restore a snapshot with \
+             jira-clone snapshot restore <name>",
+        )),
+    }
+
+    findings
+}
+
+/// The board, and the directory its backup and journal are written to, have
+/// to be writable.
+fn check_permissions(path: &Path) -> Finding {
+    let dir = match path.is_dir() {
+        true => path,
+        false => path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new(".")),
+    };
+    let read_only = [path, dir]
+        .into_iter()
+        .unique()
+        .find(|path| fs::metadata(path).is_ok_and(|meta| meta.permissions().readonly()));
+
+    match read_only {
+        Some(read_only) => Finding::problem(
+            "permissions",
+            format!("{} is read-only", read_only.display()),
+            format!("chmod u+w {}", read_only.display()),
+        ),
+        None => Finding::ok("permissions", format!("{} is writable", path.display())),
+    }
+}
+
+/// A journal left behind means a write was cut short and the board is waiting
+/// for it to be finished.
+fn check_journal(path: &Path) -> Finding {
+    let journal = journal_path(path);
+
+    match journal.exists() {
+        true => Finding::problem(
+            "pending write",
+            format!("an interrupted write is waiting in {}", journal.display()),
+            "run jira-clone once, it finishes the write on start",
+        ),
+        false => Finding::ok("pending write", "none"),
+    }
+}
+
+fn check_board(path: &Path) -> Finding {
+    let Err(error) = check(path) else {
+        return Finding::ok("board", format!("{} is intact", path.display()));
+    };
+
+    let fix = match check(&backup_path(path)) {
+        Ok(_) => "run jira-clone and accept restoring the last backup".to_owned(),
+        Err(_) => "restore a snapshot with jira-clone snapshot restore <name>".to_owned(),
+    };
+
+    Finding::problem("board", format!("{error:#}"), fix)
+}
+
+fn check_backup(path: &Path, config: &Config) -> Finding {
+    let backup = backup_path(path);
+
+    if !backup.exists() {
+        return Finding::ok("backup", "none yet, one is kept from the next change");
+    }
+
+    if let Err(error) = check(&backup) {
+        return Finding::problem(
+            "backup",
+            format!("{error:#}"),
+            "make any change to the board to replace it with a good copy",
+        );
+    }
+
+    let written = fs::metadata(&backup)
+        .and_then(|meta| meta.modified())
+        .map(|at| {
+            config
+                .calendar()
+                .date_time(DateTime::<Utc>::from(at))
+                .to_string()
+        });
+
+    match written {
+        Ok(written) => Finding::ok("backup", format!("{} from {written}", backup.display())),
+        Err(_) => Finding::ok("backup", format!("{} is intact", backup.display())),
+    }
+}
+
+/// Every reference to an item the board doesn't have, as a problem each.
+fn check_references(db: &DB) -> Vec<Finding> {
+    let problems = dangling_references(db);
+
+    if problems.is_empty() {
+        return vec![Finding::ok("references", "every item referred to exists")];
+    }
+
+    problems
+        .into_iter()
+        .map(|(found, fix)| Finding::problem("references", found, fix))
+        .collect()
+}
+
+/// The references to missing stories, and stories claimed by more than one
+/// epic, each with what to change in the board to fix it.
+pub fn dangling_references(db: &DB) -> Vec<(String, String)> {
+    let mut problems = Vec::new();
+    let missing = |id: &u32| !db.stories.contains_key(id);
+
+    for epic in db.epics.values().sorted_by_key(|epic| epic.detail.id.0) {
+        for story_id in epic.stories.iter().filter(|id| missing(&id.0)) {
+            problems.push((
+                format!(
+                    "{} lists {}, which doesn't exist",
+                    epic.key(),
+                    story_key(story_id.0)
+                ),
+                format!(
+                    "remove {} from the stories of epic {}",
+                    story_id.0, epic.detail.id.0
+                ),
+            ));
+        }
+    }
+
+    let owners = db
+        .epics
+        .values()
+        .flat_map(|epic| epic.stories.iter().map(move |id| (id.0, epic.detail.id.0)))
+        .into_group_map();
+    for (story_id, epics) in owners.into_iter().sorted() {
+        if epics.len() > 1 {
+            let epics = epics.into_iter().sorted().collect::<Vec<_>>();
+            problems.push((
+                format!(
+                    "{} is in epics {}",
+                    story_key(story_id),
+                    epics.iter().map(|id| id.to_string()).join(", ")
+                ),
+                format!(
+                    "keep {story_id} in the stories of only one of epics {}",
+                    epics.iter().map(|id| id.to_string()).join(", ")
+                ),
+            ));
+        }
+    }
+
+    for sprint in db.sprints.values().sorted_by_key(|sprint| sprint.id.0) {
+        for story_id in sprint.stories.iter().filter(|id| missing(&id.0)) {
+            problems.push((
+                format!(
+                    "{} lists {}, which doesn't exist",
+                    sprint.key(),
+                    story_key(story_id.0)
+                ),
+                format!(
+                    "remove {} from the stories of sprint {}",
+                    story_id.0, sprint.id.0
+                ),
+            ));
+        }
+    }
+
+    for story in db.stories.values().sorted_by_key(|story| story.detail.id.0) {
+        for link in story.links.iter().filter(|link| missing(&link.story_id.0)) {
+            problems.push((
+                format!(
+                    "{} {} {}, which doesn't exist",
+                    story.key(),
+                    link.kind,
+                    story_key(link.story_id.0)
+                ),
+                format!(
+                    "remove the link to {} from story {}",
+                    link.story_id.0, story.detail.id.0
+                ),
+            ));
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use crate::model::{ItemId, ItemLink, LinkKind};
+
+    #[test]
+    fn dangling_references_should_name_every_missing_story() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let other_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        db.create_sprint("".to_owned(), date, date).unwrap();
+
+        let mut state = db.read_db().unwrap();
+
+        assert!(dangling_references(&state).is_empty());
+
+        state
+            .epics
+            .get_mut(&epic_id.0)
+            .unwrap()
+            .stories
+            .push(ItemId(7));
+        state
+            .epics
+            .get_mut(&other_id.0)
+            .unwrap()
+            .stories
+            .push(story_id);
+        state.sprints.get_mut(&0).unwrap().stories.push(ItemId(8));
+        state
+            .stories
+            .get_mut(&story_id.0)
+            .unwrap()
+            .links
+            .push(ItemLink {
+                kind: LinkKind::Blocks,
+                story_id: ItemId(9),
+            });
+
+        assert_eq!(
+            dangling_references(&state)
+                .into_iter()
+                .map(|(found, _)| found)
+                .collect::<Vec<_>>(),
+            vec![
+                "EPIC-0 lists STORY-7, which doesn't exist",
+                "STORY-0 is in epics 0, 1",
+                "SPRINT-0 lists STORY-8, which doesn't exist",
+                "STORY-0 blocks STORY-9, which doesn't exist",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_file_should_point_out_damage_and_pending_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("database.json");
+        let config = Config::default();
+
+        assert!(check_file(&path, &config)[0].fix.is_none());
+
+        fs::write(
+            &path,
+            r#"{ "last_item": { "type": "None" }, "epics": {}, "stories": {} }"#,
+        )
+        .unwrap();
+        let db = JiraDataBase::new(path.clone());
+        db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        db.create_epic("".to_owned(), "".to_owned()).unwrap();
+
+        assert!(check_file(&path, &config)
+            .iter()
+            .all(|finding| finding.fix.is_none()));
+
+        fs::write(&path, "{").unwrap();
+        fs::write(journal_path(&path), "").unwrap();
+
+        let problems = check_file(&path, &config)
+            .into_iter()
+            .filter_map(|finding| Some((finding.check, finding.fix?)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            problems,
+            vec![
+                (
+                    "pending write",
+                    "run jira-clone once, it finishes the write on start".to_owned()
+                ),
+                (
+                    "board",
+                    "run jira-clone and accept restoring the last backup".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_config_should_point_at_invalid_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        assert!(check_config(Some(&path)).0[0].fix.is_none());
+
+        fs::write(&path, "{ \"keymap\": \"nano\" }").unwrap();
+
+        let (findings, config) = check_config(Some(&path));
+        assert!(findings[0].fix.is_some());
+        assert_eq!(config, Config::default());
+    }
+}
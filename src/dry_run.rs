@@ -0,0 +1,236 @@
+use anyhow::Result;
+use itertools::Itertools;
+use std::{cell::RefCell, collections::HashMap, fmt::Display};
+
+use crate::db::DataBase;
+use crate::model::{ItemDetail, DB};
+
+/// Reads from the wrapped database but keeps every write in memory, so a
+/// command can run to the end without touching the board on disk.
+pub struct DryRunDatabase {
+    inner: Box<dyn DataBase>,
+    pending: RefCell<Option<DB>>,
+}
+
+impl DryRunDatabase {
+    pub fn new(inner: Box<dyn DataBase>) -> Self {
+        Self {
+            inner,
+            pending: RefCell::new(None),
+        }
+    }
+}
+
+impl DataBase for DryRunDatabase {
+    fn read_db(&self) -> Result<DB> {
+        match self.pending.borrow().as_ref() {
+            Some(db) => Ok(db.clone()),
+            None => self.inner.read_db(),
+        }
+    }
+
+    fn write_db(&self, database: &DB) -> Result<()> {
+        *self.pending.borrow_mut() = Some(database.clone());
+        Ok(())
+    }
+
+    fn revision(&self) -> Result<u64> {
+        match self.pending.borrow().as_ref() {
+            Some(db) => Ok(db.revision),
+            None => self.inner.revision(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    Create {
+        key: String,
+    },
+    Modify {
+        key: String,
+        fields: Vec<&'static str>,
+    },
+    Remove {
+        key: String,
+    },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Create { key } => write!(f, "create {key}"),
+            Self::Modify { key, fields } => write!(f, "modify {key} ({})", fields.join(", ")),
+            Self::Remove { key } => write!(f, "remove {key}"),
+        }
+    }
+}
+
+/// The epics, stories and sprints that differ between `before` and `after`,
+/// e.g. `modify STORY-3 (status)`.
+pub fn changes(before: &DB, after: &DB) -> Vec<Change> {
+    let epics = compare(
+        &before.epics,
+        &after.epics,
+        |epic| epic.key(),
+        |old, new| {
+            let mut fields = detail_fields(&old.detail, &new.detail);
+            if old.stories != new.stories {
+                fields.push("stories");
+            }
+            fields
+        },
+    );
+    let stories = compare(
+        &before.stories,
+        &after.stories,
+        |story| story.key(),
+        |old, new| {
+            let mut fields = detail_fields(&old.detail, &new.detail);
+            if old.checklist != new.checklist {
+                fields.push("checklist");
+            }
+            if old.links != new.links {
+                fields.push("links");
+            }
+            fields
+        },
+    );
+    let sprints = compare(
+        &before.sprints,
+        &after.sprints,
+        |sprint| sprint.key(),
+        |old, new| {
+            let mut fields = Vec::new();
+            if old.name != new.name {
+                fields.push("name");
+            }
+            if (old.start, old.end) != (new.start, new.end) {
+                fields.push("dates");
+            }
+            if old.stories != new.stories {
+                fields.push("stories");
+            }
+            fields
+        },
+    );
+
+    epics.into_iter().chain(stories).chain(sprints).collect()
+}
+
+fn compare<T>(
+    before: &HashMap<u32, T>,
+    after: &HashMap<u32, T>,
+    key: impl Fn(&T) -> String,
+    modified: impl Fn(&T, &T) -> Vec<&'static str>,
+) -> Vec<Change> {
+    before
+        .keys()
+        .chain(after.keys())
+        .unique()
+        .sorted()
+        .filter_map(|id| match (before.get(id), after.get(id)) {
+            (None, Some(new)) => Some(Change::Create { key: key(new) }),
+            (Some(old), None) => Some(Change::Remove { key: key(old) }),
+            (Some(old), Some(new)) => {
+                let fields = modified(old, new);
+
+                (!fields.is_empty()).then(|| Change::Modify {
+                    key: key(new),
+                    fields,
+                })
+            }
+            (None, None) => None,
+        })
+        .collect()
+}
+
+/// The changed fields of an item. The status history follows the status, so it
+/// isn't listed on its own.
+fn detail_fields(old: &ItemDetail, new: &ItemDetail) -> Vec<&'static str> {
+    [
+        ("name", old.name != new.name),
+        ("description", old.description != new.description),
+        ("status", old.status != new.status),
+        ("priority", old.priority != new.priority),
+        ("assignee", old.assignee != new.assignee),
+        ("labels", old.labels != new.labels),
+        ("due", old.due != new.due),
+        ("watchers", old.watchers != new.watchers),
+        ("points", old.points != new.points),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(field, _)| field)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use crate::model::ItemStatus;
+
+    #[test]
+    fn should_keep_writes_in_memory() {
+        let inner = MockDB::new();
+        let before = inner.read_db().unwrap();
+        let db = JiraDataBase::from_database(Box::new(DryRunDatabase::new(Box::new(inner))));
+
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let _ = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        assert_eq!(db.read_db().unwrap().stories.len(), 1);
+        assert_eq!(
+            changes(&before, &db.read_db().unwrap()),
+            vec![
+                Change::Create {
+                    key: "EPIC-0".to_owned()
+                },
+                Change::Create {
+                    key: "STORY-0".to_owned()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn changes_should_list_modified_fields_and_removals() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let first = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let second = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let before = db.read_db().unwrap();
+
+        db.update_story_status(first, ItemStatus::Resolved).unwrap();
+        db.delete_story(second, Some(epic_id)).unwrap();
+
+        let changes = changes(&before, &db.read_db().unwrap());
+
+        assert_eq!(
+            changes,
+            vec![
+                Change::Modify {
+                    key: "EPIC-0".to_owned(),
+                    fields: vec!["stories"]
+                },
+                Change::Modify {
+                    key: "STORY-0".to_owned(),
+                    fields: vec!["status"]
+                },
+                Change::Remove {
+                    key: "STORY-1".to_owned()
+                },
+            ]
+        );
+        assert_eq!(changes[1].to_string(), "modify STORY-0 (status)");
+        assert_eq!(changes[2].to_string(), "remove STORY-1");
+        assert!(super::changes(&before, &before).is_empty());
+    }
+}
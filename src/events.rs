@@ -0,0 +1,83 @@
+use std::cell::RefCell;
+
+use crate::model::{ItemStatus, ItemType};
+
+/// Something a save changed about an epic or a story.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeEvent {
+    ItemCreated {
+        item: ItemType,
+    },
+    /// Anything but the status changed, e.g. the name, the stories of an epic
+    /// or the checklist of a story.
+    ItemUpdated {
+        item: ItemType,
+    },
+    StatusChanged {
+        item: ItemType,
+        from: ItemStatus,
+        to: ItemStatus,
+    },
+    ItemDeleted {
+        item: ItemType,
+    },
+}
+
+type Subscriber = Box<dyn Fn(&ChangeEvent)>;
+
+/// Hands what every save changed to whoever subscribed, so they don't each
+/// have to compare the board before and after.
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: RefCell<Vec<Subscriber>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&self, subscriber: impl Fn(&ChangeEvent) + 'static) {
+        self.subscribers.borrow_mut().push(Box::new(subscriber));
+    }
+
+    pub fn publish(&self, events: &[ChangeEvent]) {
+        let subscribers = self.subscribers.borrow();
+
+        for event in events {
+            subscribers.iter().for_each(|subscriber| subscriber(event));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ItemId;
+    use std::rc::Rc;
+
+    #[test]
+    fn publish_should_reach_every_subscriber_in_order() {
+        let bus = EventBus::default();
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        for name in ["first", "second"] {
+            let received = Rc::clone(&received);
+            bus.subscribe(move |event| received.borrow_mut().push((name, event.clone())));
+        }
+
+        let created = ChangeEvent::ItemCreated {
+            item: ItemType::Story { id: ItemId(4) },
+        };
+        let deleted = ChangeEvent::ItemDeleted {
+            item: ItemType::Story { id: ItemId(3) },
+        };
+        bus.publish(&[created.clone(), deleted.clone()]);
+
+        assert_eq!(
+            *received.borrow(),
+            vec![
+                ("first", created.clone()),
+                ("second", created),
+                ("first", deleted.clone()),
+                ("second", deleted)
+            ]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::model::EpicBundle;
+
+/// Whether `path` is `-`, which stands for stdin when reading and stdout when
+/// writing.
+pub fn is_stdio(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+/// A writer for `path`, or stdout for `-`.
+pub fn create_output(path: &Path) -> io::Result<Box<dyn Write>> {
+    if is_stdio(path) {
+        return Ok(Box::new(io::stdout().lock()));
+    }
+
+    Ok(Box::new(BufWriter::new(File::create(path)?)))
+}
+
+/// A reader for `path`, or stdin for `-`.
+pub fn open_input(path: &Path) -> io::Result<Box<dyn Read>> {
+    if is_stdio(path) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+
+    Ok(Box::new(BufReader::new(File::open(path)?)))
+}
+
+/// Writes `content` to `path`, or to stdout for `-`.
+pub fn write_output(path: &Path, content: &str) -> Result<()> {
+    let mut writer =
+        create_output(path).with_context(|| format!("Could not create {}", path.display()))?;
+
+    writer.write_all(content.as_bytes())?;
+    Ok(writer.flush()?)
+}
+
+pub fn write_epic_bundle(path: &Path, bundle: &EpicBundle) -> Result<()> {
+    let mut writer = create_output(path)
+        .with_context(|| format!("Could not create bundle file {}", path.display()))?;
+
+    serde_json::to_writer_pretty(&mut writer, bundle)?;
+    Ok(writer.flush()?)
+}
+
+pub fn read_epic_bundle(path: &Path) -> Result<EpicBundle> {
+    let reader = open_input(path)
+        .with_context(|| format!("Could not open bundle file {}", path.display()))?;
+
+    serde_json::from_reader(reader)
+        .with_context(|| format!("{} is not a valid epic bundle", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Epic, ItemDetail, ItemId, ItemStatus, Story};
+
+    #[test]
+    fn epic_bundle_should_round_trip_through_a_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let story = Story::new(ItemDetail {
+            description: "".to_owned(),
+            id: ItemId(4),
+            name: "Refunds".to_owned(),
+            status: ItemStatus::InProgress,
+            ..Default::default()
+        });
+        let bundle = EpicBundle {
+            epic: Epic::new(
+                ItemDetail {
+                    description: "".to_owned(),
+                    id: ItemId(2),
+                    name: "Payments".to_owned(),
+                    status: ItemStatus::Open,
+                    ..Default::default()
+                },
+                vec![ItemId(4)],
+            ),
+            stories: vec![story],
+        };
+
+        write_epic_bundle(file.path(), &bundle).unwrap();
+
+        assert_eq!(read_epic_bundle(file.path()).unwrap(), bundle);
+    }
+
+    #[test]
+    fn read_epic_bundle_should_fail_with_invalid_json() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+
+        write!(file, r#"{{ "epic": {{}} }}"#).unwrap();
+
+        assert!(read_epic_bundle(file.path()).is_err());
+    }
+
+    #[test]
+    fn only_a_dash_should_stand_for_stdio() {
+        assert!(is_stdio(Path::new("-")));
+        assert!(!is_stdio(Path::new("./-")));
+        assert!(!is_stdio(Path::new("bundle.json")));
+    }
+}
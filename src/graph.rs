@@ -0,0 +1,244 @@
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::keys::story_key;
+use crate::model::{ItemId, LinkKind, Story, DB};
+use crate::stats::is_done;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz, e.g. `dot -Tsvg graph.dot`.
+    Dot,
+    /// A Mermaid `graph TD`, ready to embed in Markdown.
+    Mermaid,
+}
+
+impl FromStr for GraphFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        match input {
+            "dot" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            _ => Err(anyhow!("Can't draw a graph as {input}, use dot or mermaid")),
+        }
+    }
+}
+
+/// Which stories take part in the graph.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GraphScope {
+    /// Only the stories of this epic.
+    pub epic: Option<ItemId>,
+    /// Leaves out resolved and closed stories.
+    pub unresolved: bool,
+}
+
+/// One arrow of the graph, drawn from the story that blocks or was split into
+/// the other one.
+struct Edge {
+    from: u32,
+    to: u32,
+    kind: LinkKind,
+}
+
+/// The stories that block or were split from one another, as a graph. Stories
+/// without links in `scope` are left out.
+pub fn dependency_graph(db: &DB, format: GraphFormat, scope: &GraphScope) -> String {
+    let included = |story: &Story| {
+        let in_epic = scope.epic.is_none_or(|epic_id| {
+            db.epics
+                .get(&epic_id.0)
+                .is_some_and(|epic| epic.stories.contains(&story.detail.id))
+        });
+
+        in_epic && !(scope.unresolved && is_done(&story.detail.status))
+    };
+    let stories = db
+        .stories
+        .iter()
+        .filter(|(_, story)| included(story))
+        .collect::<BTreeMap<_, _>>();
+    let edges = stories
+        .iter()
+        .flat_map(|(id, story)| {
+            story
+                .links
+                .iter()
+                .filter(|link| matches!(link.kind, LinkKind::Blocks | LinkKind::SplitInto))
+                .filter(|link| stories.contains_key(&link.story_id.0))
+                .map(|link| Edge {
+                    from: **id,
+                    to: link.story_id.0,
+                    kind: link.kind,
+                })
+        })
+        .collect::<Vec<_>>();
+    let nodes = edges
+        .iter()
+        .flat_map(|edge| [edge.from, edge.to])
+        .sorted()
+        .dedup()
+        .map(|id| stories[&id])
+        .collect::<Vec<_>>();
+
+    match format {
+        GraphFormat::Dot => dot(&nodes, &edges),
+        GraphFormat::Mermaid => mermaid(&nodes, &edges),
+    }
+}
+
+fn dot(nodes: &[&Story], edges: &[Edge]) -> String {
+    let label = |story: &Story| {
+        format!("{} {}", story.key(), story.detail.name)
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+    };
+    let nodes = nodes
+        .iter()
+        .map(|story| format!("    \"{}\" [label=\"{}\"];", story.key(), label(story)));
+    let edges = edges.iter().map(|edge| {
+        let style = match edge.kind {
+            LinkKind::SplitInto => ", style=dashed",
+            _ => "",
+        };
+
+        format!(
+            "    \"{}\" -> \"{}\" [label=\"{}\"{style}];",
+            story_key(edge.from),
+            story_key(edge.to),
+            edge.kind
+        )
+    });
+
+    std::iter::once("digraph dependencies {".to_owned())
+        .chain(nodes)
+        .chain(edges)
+        .chain(std::iter::once("}".to_owned()))
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Node ids are `STORY_<id>`, since Mermaid would read the dash of a key as
+/// part of an arrow.
+fn mermaid(nodes: &[&Story], edges: &[Edge]) -> String {
+    let nodes = nodes.iter().map(|story| {
+        format!(
+            "    STORY_{}[\"{} {}\"]",
+            story.detail.id.0,
+            story.key(),
+            story.detail.name.replace('"', "#quot;")
+        )
+    });
+    let edges = edges.iter().map(|edge| {
+        let arrow = match edge.kind {
+            LinkKind::SplitInto => "-.->",
+            _ => "-->",
+        };
+
+        format!(
+            "    STORY_{} {arrow}|{}| STORY_{}",
+            edge.from, edge.kind, edge.to
+        )
+    });
+
+    std::iter::once("graph TD".to_owned())
+        .chain(nodes)
+        .chain(edges)
+        .map(|line| line + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use crate::model::{ItemStatus, StorySplit};
+
+    /// An epic with STORY-0 blocking STORY-1, a closed STORY-2 blocking STORY-1
+    /// and STORY-4 split from STORY-0, plus STORY-3 outside the epic blocking
+    /// STORY-0.
+    fn board() -> (DB, ItemId) {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story = |name: &str, epic_id: Option<ItemId>| {
+            db.create_story(name.to_owned(), "".to_owned(), epic_id)
+                .unwrap()
+        };
+
+        let infra = story("Infra", Some(epic_id));
+        let deploy = story("Deploy \"v2\"", Some(epic_id));
+        let review = story("Review", Some(epic_id));
+        let outside = story("Budget", None);
+
+        db.add_blocker(deploy, infra).unwrap();
+        db.add_blocker(deploy, review).unwrap();
+        db.add_blocker(infra, outside).unwrap();
+        db.update_story_status(review, ItemStatus::Closed).unwrap();
+
+        db.add_checklist_item(infra, "Write docs".to_owned())
+            .unwrap();
+        db.split_story(
+            infra,
+            Some(epic_id),
+            StorySplit {
+                name: "Docs".to_owned(),
+                description: "".to_owned(),
+                checklist: vec![0],
+            },
+        )
+        .unwrap();
+
+        (db.read_db().unwrap(), epic_id)
+    }
+
+    #[test]
+    fn should_draw_the_graph_as_dot() {
+        let (db, _) = board();
+
+        assert_eq!(
+            dependency_graph(&db, GraphFormat::Dot, &GraphScope::default()),
+            "digraph dependencies {\n    \
+             \"STORY-0\" [label=\"STORY-0 Infra\"];\n    \
+             \"STORY-1\" [label=\"STORY-1 Deploy \\\"v2\\\"\"];\n    \
+             \"STORY-2\" [label=\"STORY-2 Review\"];\n    \
+             \"STORY-3\" [label=\"STORY-3 Budget\"];\n    \
+             \"STORY-4\" [label=\"STORY-4 Docs\"];\n    \
+             \"STORY-0\" -> \"STORY-1\" [label=\"blocks\"];\n    \
+             \"STORY-0\" -> \"STORY-4\" [label=\"split into\", style=dashed];\n    \
+             \"STORY-2\" -> \"STORY-1\" [label=\"blocks\"];\n    \
+             \"STORY-3\" -> \"STORY-0\" [label=\"blocks\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn should_limit_the_mermaid_graph_to_unresolved_stories_of_an_epic() {
+        let (db, epic_id) = board();
+        let scope = GraphScope {
+            epic: Some(epic_id),
+            unresolved: true,
+        };
+
+        assert_eq!(
+            dependency_graph(&db, GraphFormat::Mermaid, &scope),
+            "graph TD\n    \
+             STORY_0[\"STORY-0 Infra\"]\n    \
+             STORY_1[\"STORY-1 Deploy #quot;v2#quot;\"]\n    \
+             STORY_4[\"STORY-4 Docs\"]\n    \
+             STORY_0 -->|blocks| STORY_1\n    \
+             STORY_0 -.->|split into| STORY_4\n"
+        );
+    }
+
+    #[test]
+    fn should_parse_graph_formats() {
+        assert_eq!("dot".parse::<GraphFormat>().unwrap(), GraphFormat::Dot);
+        assert_eq!(
+            "mermaid".parse::<GraphFormat>().unwrap(),
+            GraphFormat::Mermaid
+        );
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+}
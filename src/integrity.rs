@@ -0,0 +1,280 @@
+use anyhow::{anyhow, Context, Result};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::model::DB;
+use crate::snapshots::list_snapshots;
+
+/// The SHA-256 of `content` as lowercase hex.
+pub fn checksum(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Whether `a` and `b` are equal, looking at every byte whichever one they
+/// differ in, so comparing a secret doesn't tell how much of a guess was right.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Where the checksum of `path` is kept, e.g. `database.json.sha256`.
+pub fn checksum_path(path: &Path) -> PathBuf {
+    with_suffix(path, "sha256")
+}
+
+/// Where the last good copy of `path` is kept, e.g. `database.json.bak`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, "bak")
+}
+
+/// Where a write waits until it made it into `path`, e.g. `database.json.journal`.
+pub fn journal_path(path: &Path) -> PathBuf {
+    with_suffix(path, "journal")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Fails when `content` doesn't match the checksum stored next to `path`.
+/// Files written before checksums existed have none and always pass.
+pub fn verify(path: &Path, content: &[u8]) -> Result<()> {
+    let expected = match fs::read_to_string(checksum_path(path)) {
+        Ok(expected) => expected,
+        Err(_) => return Ok(()),
+    };
+
+    if expected.trim() != checksum(content) {
+        return Err(anyhow!(
+            "{} doesn't match its checksum, it may have been truncated",
+            path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that the file at `path` matches its checksum and holds a board.
+pub fn check(path: &Path) -> Result<()> {
+    let content = fs::read(path).with_context(|| anyhow!("Failed to read {}", path.display()))?;
+
+    verify(path, &content)?;
+    serde_json::from_slice::<DB>(&content)
+        .with_context(|| anyhow!("{} is not a valid board", path.display()))?;
+
+    Ok(())
+}
+
+/// Writes `content` to `path` along with its checksum. The current file, if it
+/// is intact, becomes the backup first.
+///
+/// The new board goes to the journal before anything else is touched, so a
+/// write cut short by a crash can be finished with `replay_journal`.
+pub fn write_verified(path: &Path, content: &[u8]) -> Result<()> {
+    let mut journal = File::create(journal_path(path))?;
+
+    journal.write_all(format!("{}\n", checksum(content)).as_bytes())?;
+    journal.write_all(content)?;
+    journal.sync_all()?;
+
+    if check(path).is_ok() {
+        fs::copy(path, backup_path(path))?;
+        if let Ok(checksum) = fs::read(checksum_path(path)) {
+            fs::write(checksum_path(&backup_path(path)), checksum)?;
+        }
+    }
+
+    let partial = with_suffix(path, "partial");
+
+    fs::write(&partial, content)?;
+    fs::rename(&partial, path)?;
+    fs::write(checksum_path(path), checksum(content))?;
+    fs::remove_file(journal_path(path))?;
+
+    Ok(())
+}
+
+/// Finishes the write left in the journal by a crash, if there is one. A
+/// journal that was itself cut short is dropped, as `path` still holds the
+/// board from before that write. Returns whether a write was replayed.
+pub fn replay_journal(path: &Path) -> Result<bool> {
+    let journal = match fs::read(journal_path(path)) {
+        Ok(journal) => journal,
+        Err(_) => return Ok(false),
+    };
+    let entry = journal
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map(|end| (&journal[..end], &journal[end + 1..]))
+        .filter(|(expected, content)| *expected == checksum(content).as_bytes());
+
+    match entry {
+        Some((_, content)) => {
+            write_verified(path, content)
+                .with_context(|| anyhow!("Failed to replay the journal"))?;
+            Ok(true)
+        }
+        None => {
+            fs::remove_file(journal_path(path))?;
+            Ok(false)
+        }
+    }
+}
+
+/// The most recent intact copy of the board at `path`: its backup, or else the
+/// newest snapshot in `snapshots_dir` that still reads as a board. Comes with
+/// a description of it, e.g. `the last backup`.
+pub fn last_good_copy(path: &Path, snapshots_dir: &Path) -> Option<(PathBuf, String)> {
+    let backup = backup_path(path);
+
+    if check(&backup).is_ok() {
+        return Some((backup, "the last backup".to_owned()));
+    }
+
+    list_snapshots(snapshots_dir)
+        .ok()?
+        .into_iter()
+        .rev()
+        .map(|snapshot| {
+            (
+                snapshots_dir.join(format!("{}.json", snapshot.name)),
+                format!("the snapshot \"{}\"", snapshot.name),
+            )
+        })
+        .find(|(copy, _)| check(copy).is_ok())
+}
+
+/// Puts the intact copy at `source` in place of `path`, keeping the damaged
+/// file as `<file>.corrupt`.
+pub fn restore_from(path: &Path, source: &Path) -> Result<()> {
+    check(source).with_context(|| anyhow!("{} can't be restored", source.display()))?;
+
+    if path.exists() {
+        fs::rename(path, with_suffix(path, "corrupt"))?;
+    }
+
+    let content = fs::read(source)?;
+
+    fs::write(path, &content)?;
+    fs::write(checksum_path(path), checksum(&content))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"{ "last_item": { "type": "None" }, "epics": {}, "stories": {} }"#;
+
+    #[test]
+    fn should_detect_truncated_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("database.json");
+
+        fs::write(&path, BOARD).unwrap();
+        assert!(check(&path).is_ok());
+
+        write_verified(&path, BOARD.as_bytes()).unwrap();
+        assert!(check(&path).is_ok());
+
+        fs::write(&path, &BOARD[..20]).unwrap();
+        assert!(check(&path).is_err());
+        assert!(check(&backup_path(&path)).is_ok());
+    }
+
+    #[test]
+    fn should_restore_the_latest_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("database.json");
+        let newer = format!("{BOARD}\n");
+
+        write_verified(&path, BOARD.as_bytes()).unwrap();
+        write_verified(&path, newer.as_bytes()).unwrap();
+        fs::write(&path, &newer[..20]).unwrap();
+
+        restore_from(&path, &backup_path(&path)).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), BOARD);
+        assert!(check(&path).is_ok());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("database.json.corrupt")).unwrap(),
+            &newer[..20]
+        );
+    }
+
+    #[test]
+    fn should_not_back_up_a_damaged_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("database.json");
+
+        write_verified(&path, BOARD.as_bytes()).unwrap();
+        write_verified(&path, BOARD.as_bytes()).unwrap();
+        fs::write(&path, &BOARD[..20]).unwrap();
+        write_verified(&path, BOARD.as_bytes()).unwrap();
+
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), BOARD);
+        let missing = dir.path().join("missing.json");
+        assert!(restore_from(&missing, &backup_path(&missing)).is_err());
+    }
+
+    #[test]
+    fn should_fall_back_to_the_newest_intact_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("database.json");
+        let snapshots = dir.path().join("snapshots");
+
+        fs::write(&path, &BOARD[..20]).unwrap();
+        assert!(last_good_copy(&path, &snapshots).is_none());
+
+        fs::create_dir(&snapshots).unwrap();
+        fs::write(snapshots.join("before-reorg.json"), BOARD).unwrap();
+        fs::write(backup_path(&path), &BOARD[..20]).unwrap();
+
+        let (copy, description) = last_good_copy(&path, &snapshots).unwrap();
+
+        assert_eq!(copy, snapshots.join("before-reorg.json"));
+        assert_eq!(description, "the snapshot \"before-reorg\"");
+
+        restore_from(&path, &copy).unwrap();
+        assert!(check(&path).is_ok());
+
+        write_verified(&path, BOARD.as_bytes()).unwrap();
+        assert_eq!(
+            last_good_copy(&path, &snapshots).unwrap().1,
+            "the last backup"
+        );
+    }
+
+    #[test]
+    fn should_replay_complete_journal_entries_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("database.json");
+        let newer = format!("{BOARD}\n");
+
+        write_verified(&path, BOARD.as_bytes()).unwrap();
+        assert!(!journal_path(&path).exists());
+        assert!(!replay_journal(&path).unwrap());
+
+        let entry = format!("{}\n{newer}", checksum(newer.as_bytes()));
+
+        fs::write(journal_path(&path), &entry[..entry.len() - 10]).unwrap();
+        assert!(!replay_journal(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), BOARD);
+        assert!(!journal_path(&path).exists());
+
+        fs::write(journal_path(&path), &entry).unwrap();
+        fs::write(&path, &newer[..20]).unwrap();
+        assert!(replay_journal(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), newer);
+        assert!(check(&path).is_ok());
+        assert!(!journal_path(&path).exists());
+    }
+}
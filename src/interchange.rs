@@ -0,0 +1,463 @@
+//! The board as other tools read and write it, with `jira-clone export json`
+//! and `jira-clone import json`. Unlike `database.json` the format is
+//! versioned and only changes with a new `version`, so generated boards keep
+//! importing as the storage format moves on. `schema/board-v1.schema.json`
+//! describes version 1.
+//!
+//! Items refer to each other by `key`: an export uses the board's own keys,
+//! like `EPIC-3`, while a generated board may use any string unique among its
+//! items.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::jira_import::ImportSummary;
+use crate::model::{
+    ChecklistItem, Epic, ItemDetail, ItemId, ItemLink, ItemStatus, ItemType, LinkKind, Priority,
+    Story, DB,
+};
+
+/// The version `export_board` writes and the only one `add_board` reads.
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Board {
+    pub version: u32,
+    #[serde(default)]
+    pub epics: Vec<EpicRecord>,
+    #[serde(default)]
+    pub stories: Vec<StoryRecord>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct EpicRecord {
+    pub key: String,
+    #[serde(flatten)]
+    pub fields: Fields,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct StoryRecord {
+    pub key: String,
+    #[serde(flatten)]
+    pub fields: Fields,
+    /// The key of the epic the story is in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epic: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+    /// The keys of the stories that have to be done first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<ChecklistRecord>,
+}
+
+/// What epics and stories both have.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Fields {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default)]
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<PriorityLevel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ChecklistRecord {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Open,
+    InProgress,
+    Resolved,
+    Closed,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityLevel {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl From<&ItemStatus> for Status {
+    fn from(status: &ItemStatus) -> Self {
+        match status {
+            ItemStatus::Open => Status::Open,
+            ItemStatus::InProgress => Status::InProgress,
+            ItemStatus::Resolved => Status::Resolved,
+            ItemStatus::Closed => Status::Closed,
+        }
+    }
+}
+
+impl From<Status> for ItemStatus {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Open => ItemStatus::Open,
+            Status::InProgress => ItemStatus::InProgress,
+            Status::Resolved => ItemStatus::Resolved,
+            Status::Closed => ItemStatus::Closed,
+        }
+    }
+}
+
+impl From<Priority> for PriorityLevel {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Critical => PriorityLevel::Critical,
+            Priority::High => PriorityLevel::High,
+            Priority::Medium => PriorityLevel::Medium,
+            Priority::Low => PriorityLevel::Low,
+        }
+    }
+}
+
+impl From<PriorityLevel> for Priority {
+    fn from(priority: PriorityLevel) -> Self {
+        match priority {
+            PriorityLevel::Critical => Priority::Critical,
+            PriorityLevel::High => Priority::High,
+            PriorityLevel::Medium => Priority::Medium,
+            PriorityLevel::Low => Priority::Low,
+        }
+    }
+}
+
+impl From<&ItemDetail> for Fields {
+    fn from(detail: &ItemDetail) -> Self {
+        Fields {
+            name: detail.name.clone(),
+            description: detail.description.clone(),
+            status: (&detail.status).into(),
+            priority: detail.priority.map(Into::into),
+            assignee: detail.assignee.clone(),
+            labels: detail.labels.clone(),
+            due: detail.due,
+        }
+    }
+}
+
+impl Fields {
+    fn into_detail(self, id: ItemId, now: DateTime<Utc>) -> ItemDetail {
+        let mut detail = ItemDetail {
+            id,
+            name: self.name,
+            description: self.description,
+            priority: self.priority.map(Into::into),
+            assignee: self.assignee,
+            labels: self.labels,
+            due: self.due,
+            ..Default::default()
+        };
+
+        detail.set_status(self.status.into(), now);
+        detail
+    }
+}
+
+/// Every epic and story of `db` in ID order.
+pub fn export_board(db: &DB) -> Board {
+    let epic_of = db
+        .epics
+        .values()
+        .flat_map(|epic| epic.stories.iter().map(move |id| (id.0, epic.key())))
+        .collect::<HashMap<_, _>>();
+    let story_key = |id: &ItemId| db.stories.get(&id.0).map(Story::key);
+
+    Board {
+        version: VERSION,
+        epics: db
+            .epics
+            .values()
+            .sorted_by_key(|epic| epic.detail.id.0)
+            .map(|epic| EpicRecord {
+                key: epic.key(),
+                fields: (&epic.detail).into(),
+            })
+            .collect(),
+        stories: db
+            .stories
+            .values()
+            .sorted_by_key(|story| story.detail.id.0)
+            .map(|story| StoryRecord {
+                key: story.key(),
+                fields: (&story.detail).into(),
+                epic: epic_of.get(&story.detail.id.0).cloned(),
+                points: story.detail.points,
+                blocked_by: story
+                    .links
+                    .iter()
+                    .filter(|link| link.kind == LinkKind::BlockedBy)
+                    .filter_map(|link| story_key(&link.story_id))
+                    .collect(),
+                checklist: story
+                    .checklist
+                    .iter()
+                    .map(|item| ChecklistRecord {
+                        text: item.text.clone(),
+                        done: item.done,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Adds the epics and stories of `board` to `db` under new IDs. Fails without
+/// touching `db` when the version isn't supported, a key is used twice or an
+/// item refers to a key the board doesn't have.
+pub fn add_board(db: &mut DB, board: Board, now: DateTime<Utc>) -> Result<ImportSummary> {
+    if board.version != VERSION {
+        return Err(anyhow!(
+            "Version {} boards aren't supported, only version {VERSION}",
+            board.version
+        ));
+    }
+
+    let first_epic = db.epics.keys().max().map_or(0, |id| id + 1);
+    let first_story = db.stories.keys().max().map_or(0, |id| id + 1);
+    let mut epic_ids = HashMap::new();
+    let mut story_ids = HashMap::new();
+
+    for (id, epic) in (first_epic..).zip(&board.epics) {
+        if epic_ids.insert(epic.key.clone(), ItemId(id)).is_some() {
+            return Err(anyhow!("{} is the key of more than one epic", epic.key));
+        }
+    }
+    for (id, story) in (first_story..).zip(&board.stories) {
+        if story_ids.insert(story.key.clone(), ItemId(id)).is_some() {
+            return Err(anyhow!("{} is the key of more than one story", story.key));
+        }
+    }
+
+    let find = |ids: &HashMap<String, ItemId>, key: &str, of: &str| {
+        ids.get(key)
+            .copied()
+            .ok_or_else(|| anyhow!("{of} refers to {key}, which the board doesn't have"))
+    };
+    let mut epics = HashMap::new();
+    let mut stories = HashMap::new();
+
+    for record in board.epics {
+        let id = epic_ids[record.key.as_str()];
+
+        epics.insert(
+            id.0,
+            Epic::new(record.fields.into_detail(id, now), Vec::new()),
+        );
+    }
+    for record in board.stories {
+        let id = story_ids[record.key.as_str()];
+        let mut detail = record.fields.into_detail(id, now);
+
+        detail.points = record.points;
+
+        let mut story = Story::new(detail);
+
+        for blocker in &record.blocked_by {
+            story.links.push(ItemLink {
+                kind: LinkKind::BlockedBy,
+                story_id: find(&story_ids, blocker, &record.key)?,
+            });
+        }
+        story.checklist = record
+            .checklist
+            .into_iter()
+            .map(|item| ChecklistItem {
+                text: item.text,
+                done: item.done,
+            })
+            .collect();
+
+        if let Some(epic) = &record.epic {
+            let epic_id = find(&epic_ids, epic, &record.key)?;
+
+            epics.get_mut(&epic_id.0).unwrap().stories.push(id);
+        }
+
+        stories.insert(id.0, story);
+    }
+
+    // The board keeps blocking links in pairs.
+    let blocks = stories
+        .values()
+        .flat_map(|story| {
+            story
+                .links
+                .iter()
+                .map(|link| (link.story_id, story.detail.id))
+        })
+        .collect::<Vec<_>>();
+
+    for (blocker, blocked) in blocks {
+        stories.get_mut(&blocker.0).unwrap().links.push(ItemLink {
+            kind: LinkKind::Blocks,
+            story_id: blocked,
+        });
+    }
+
+    let summary = ImportSummary {
+        epics: epics.len(),
+        stories: stories.len(),
+    };
+
+    if let Some(last) = stories.keys().max() {
+        db.last_item = ItemType::Story { id: ItemId(*last) };
+    } else if let Some(last) = epics.keys().max() {
+        db.last_item = ItemType::Epic { id: ItemId(*last) };
+    }
+    db.epics.extend(epics);
+    db.stories.extend(stories);
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, DataBase};
+
+    fn empty_db() -> DB {
+        MockDB::new().read_db().unwrap()
+    }
+
+    fn board() -> Board {
+        serde_json::from_str(
+            r#"{
+                "version": 1,
+                "epics": [{ "key": "payments", "name": "Payments", "status": "in_progress" }],
+                "stories": [
+                    {
+                        "key": "refunds",
+                        "name": "Refunds",
+                        "epic": "payments",
+                        "priority": "high",
+                        "labels": ["billing"],
+                        "due": "2024-05-10",
+                        "points": 3,
+                        "blocked_by": ["receipts"],
+                        "checklist": [{ "text": "Partial refunds", "done": true }]
+                    },
+                    { "key": "receipts", "name": "Receipts" }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn add_board_should_add_items_under_new_ids() {
+        let mut db = empty_db();
+        db.epics
+            .insert(0, Epic::new(ItemDetail::default(), Vec::new()));
+
+        let summary = add_board(&mut db, board(), Utc::now()).unwrap();
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                epics: 1,
+                stories: 2
+            }
+        );
+
+        let epic = &db.epics[&1];
+        let refunds = &db.stories[&0];
+        assert_eq!(epic.detail.name, "Payments");
+        assert_eq!(epic.detail.status, ItemStatus::InProgress);
+        assert_eq!(epic.stories, vec![ItemId(0)]);
+        assert_eq!(refunds.detail.priority, Some(Priority::High));
+        assert_eq!(refunds.detail.points, Some(3));
+        assert_eq!(refunds.checklist.len(), 1);
+        assert_eq!(
+            refunds.links,
+            vec![ItemLink {
+                kind: LinkKind::BlockedBy,
+                story_id: ItemId(1)
+            }]
+        );
+        assert_eq!(
+            db.stories[&1].links,
+            vec![ItemLink {
+                kind: LinkKind::Blocks,
+                story_id: ItemId(0)
+            }]
+        );
+    }
+
+    #[test]
+    fn export_board_should_round_trip() {
+        let mut db = empty_db();
+        add_board(&mut db, board(), Utc::now()).unwrap();
+
+        let exported = export_board(&db);
+        let mut copy = empty_db();
+        add_board(&mut copy, exported, Utc::now()).unwrap();
+
+        assert_eq!(export_board(&copy), export_board(&db));
+        assert_eq!(export_board(&db).stories[0].epic, Some("EPIC-0".to_owned()));
+        assert_eq!(
+            export_board(&db).stories[0].blocked_by,
+            vec!["STORY-1".to_owned()]
+        );
+    }
+
+    #[test]
+    fn add_board_should_reject_other_versions_and_unknown_keys() {
+        let mut db = empty_db();
+        let mut newer = board();
+        newer.version = 2;
+        let mut dangling = board();
+        dangling.stories[0].epic = Some("billing".to_owned());
+
+        assert!(add_board(&mut db, newer, Utc::now()).is_err());
+        assert_eq!(
+            add_board(&mut db, dangling, Utc::now())
+                .unwrap_err()
+                .to_string(),
+            "refunds refers to billing, which the board doesn't have"
+        );
+        assert!(db.epics.is_empty() && db.stories.is_empty());
+    }
+
+    #[test]
+    fn schema_should_list_the_fields_of_an_export() {
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("../schema/board-v1.schema.json")).unwrap();
+        let mut db = empty_db();
+        add_board(&mut db, board(), Utc::now()).unwrap();
+        let exported = serde_json::to_value(export_board(&db)).unwrap();
+        let story_fields = &schema["$defs"]["story"]["properties"];
+
+        for story in exported["stories"].as_array().unwrap() {
+            for field in story.as_object().unwrap().keys() {
+                assert!(
+                    story_fields.get(field).is_some(),
+                    "{field} is not in the schema"
+                );
+            }
+        }
+        assert_eq!(schema["properties"]["version"]["const"], VERSION);
+    }
+}
@@ -1,13 +1,349 @@
-use std::io;
+use anyhow::Result;
+use arboard::Clipboard;
+use std::{
+    cell::RefCell,
+    io::{self, BufRead, Write},
+    mem,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::ui::FilterKey;
+
+thread_local! {
+    // On some platforms (X11, Wayland) the copied text is only available while
+    // the clipboard handle that set it is alive, so it is kept for the whole session.
+    static CLIPBOARD: RefCell<Option<Clipboard>> = const { RefCell::new(None) };
+    // The line a reader thread is still waiting for, when the last call to
+    // `get_user_input_within` gave up before it was typed.
+    static PENDING_LINE: RefCell<Option<Receiver<String>>> = const { RefCell::new(None) };
+    // What was typed so far of the line `get_user_input_within` reads key by
+    // key, and whether it is still on screen.
+    static PARTIAL_LINE: RefCell<(String, bool)> = const { RefCell::new((String::new(), false)) };
+}
 
 pub fn get_user_input() -> String {
-    let mut input = String::new();
+    read_input_line(&mut io::stdin().lock())
+}
+
+/// Reads a line without the spaces around it, the `\r\n` Windows ends lines
+/// with included.
+fn read_input_line(input: &mut impl BufRead) -> String {
+    let mut line = String::new();
+
+    input.read_line(&mut line).unwrap();
+
+    line.trim().to_string()
+}
+
+/// Like `get_user_input`, but gives up after `timeout` and returns `None`. In a
+/// terminal the keys are read one by one and the next call goes on with what
+/// was typed so far, typing it out again if the screen was cleared in between,
+/// so the page can be drawn again under a half typed line. Anywhere else the
+/// read goes on in the background and the next call picks up its line, so
+/// nothing typed in between is lost.
+pub fn get_user_input_within(timeout: Duration) -> Option<String> {
+    use crossterm::terminal;
+    use std::io::IsTerminal;
+
+    if !io::stdin().is_terminal() || terminal::enable_raw_mode().is_err() {
+        return read_line_within(timeout);
+    }
+
+    let line = PARTIAL_LINE.with(|partial| read_keys_within(&mut partial.borrow_mut(), timeout));
+    let _ = terminal::disable_raw_mode();
+
+    if line.is_some() {
+        println!();
+    }
+    line
+}
+
+/// Has the next `get_user_input_within` type out the half typed line again,
+/// for when the screen was cleared.
+pub fn forget_shown_input() {
+    PARTIAL_LINE.with(|partial| partial.borrow_mut().1 = false);
+}
+
+/// Adds the keys pressed within `timeout` to `line`, showing them as they come,
+/// and returns it once enter is pressed. Ctrl-C quits, as it would have had
+/// the terminal not been in raw mode.
+fn read_keys_within((line, shown): &mut (String, bool), timeout: Duration) -> Option<String> {
+    use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use crossterm::terminal;
+
+    let deadline = Instant::now() + timeout;
+    let mut out = io::stdout();
+
+    if !*shown {
+        print!("{line}");
+        *shown = true;
+    }
+
+    loop {
+        let _ = out.flush();
+
+        if !event::poll(deadline.saturating_duration_since(Instant::now())).unwrap_or(false) {
+            return None;
+        }
+
+        match event::read() {
+            Ok(Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            })) => match code {
+                KeyCode::Enter => break,
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    let _ = terminal::disable_raw_mode();
+                    std::process::exit(130);
+                }
+                KeyCode::Char(key) => {
+                    line.push(key);
+                    print!("{key}");
+                }
+                KeyCode::Backspace if line.pop().is_some() => print!("\x08 \x08"),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+
+    *shown = false;
+    Some(mem::take(line).trim().to_owned())
+}
+
+/// Reads a line on a thread of its own, see `get_user_input_within`.
+fn read_line_within(timeout: Duration) -> Option<String> {
+    PENDING_LINE.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        let lines = pending.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::channel();
+
+            thread::spawn(move || sender.send(get_user_input()));
+            receiver
+        });
+
+        match lines.recv_timeout(timeout) {
+            Ok(line) => {
+                *pending = None;
+                Some(line)
+            }
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                *pending = None;
+                Some(String::new())
+            }
+        }
+    })
+}
+
+/// Reads a single key press when stdin is a terminal, without waiting for
+/// enter. Only presses count, as Windows consoles report releases too, which
+/// would otherwise answer the next question as well. Anywhere else, like when
+/// input is piped, it reads a whole line.
+pub fn get_key_press() -> String {
+    match read_key_press() {
+        Some(key) => {
+            println!("{key}");
+            key
+        }
+        None => get_user_input(),
+    }
+}
+
+/// The next key pressed, enter read as an empty string, or `None` when stdin
+/// isn't a terminal the keys can be read from one by one.
+fn read_key_press() -> Option<String> {
+    use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+    use crossterm::terminal;
+    use std::io::IsTerminal;
 
-    io::stdin().read_line(&mut input).unwrap();
+    if !io::stdin().is_terminal() || terminal::enable_raw_mode().is_err() {
+        return None;
+    }
 
-    return input.trim().to_string();
+    let key = loop {
+        match event::read() {
+            Ok(Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            })) => {
+                break match code {
+                    KeyCode::Enter => String::new(),
+                    KeyCode::Char(key) => key.to_string(),
+                    other => other.to_string(),
+                }
+            }
+            Ok(_) => continue,
+            Err(_) => break String::new(),
+        }
+    };
+    let _ = terminal::disable_raw_mode();
+
+    Some(key)
+}
+
+/// Reads a line without showing it, for passphrases. Where keys can't be read
+/// one by one, like when input is piped, it reads a line like `get_user_input`.
+pub fn get_hidden_input() -> String {
+    use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use crossterm::terminal;
+    use std::io::IsTerminal;
+
+    if !io::stdin().is_terminal() || terminal::enable_raw_mode().is_err() {
+        return get_user_input();
+    }
+
+    let mut line = String::new();
+
+    loop {
+        match event::read() {
+            Ok(Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            })) => match code {
+                KeyCode::Enter => break,
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    line.clear();
+                    break;
+                }
+                KeyCode::Char(key) => line.push(key),
+                KeyCode::Backspace => {
+                    line.pop();
+                }
+                _ => continue,
+            },
+            Ok(_) => continue,
+            Err(_) => break,
+        }
+    }
+    let _ = terminal::disable_raw_mode();
+
+    println!();
+    line
+}
+
+/// Reads the next key of a quick filter when stdin is a terminal, and a whole
+/// line anywhere else, like `get_key_press`.
+pub fn get_filter_key() -> FilterKey {
+    use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+    use crossterm::terminal;
+    use std::io::IsTerminal;
+
+    if !io::stdin().is_terminal() || terminal::enable_raw_mode().is_err() {
+        return FilterKey::Line(get_user_input());
+    }
+
+    let key = loop {
+        match event::read() {
+            Ok(Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            })) => match code {
+                KeyCode::Char(key) => break FilterKey::Char(key),
+                KeyCode::Backspace => break FilterKey::Backspace,
+                KeyCode::Enter => break FilterKey::Enter,
+                KeyCode::Esc => break FilterKey::Esc,
+                _ => continue,
+            },
+            Ok(_) => continue,
+            Err(_) => break FilterKey::Esc,
+        }
+    };
+    let _ = terminal::disable_raw_mode();
+
+    key
+}
+
+/// Asks a yes or no `question` answered with a single key, or a whole `yes` or
+/// `no` when input is piped. Enter picks `default`, which the hint shows in
+/// capitals, and anything else asks again.
+pub fn confirm(question: &str, default: bool) -> bool {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+
+    loop {
+        print!("{question} {hint}: ");
+        let _ = io::stdout().flush();
+
+        match parse_answer(&get_key_press(), default) {
+            Some(answer) => return answer,
+            None => println!("Please answer y or n."),
+        }
+    }
+}
+
+/// Reads `y`, `yes`, `n` or `no` in any case. Anything else is `None`, so the
+/// question is asked again rather than taken as a no.
+fn parse_answer(input: &str, default: bool) -> Option<bool> {
+    match input.trim().to_lowercase().as_str() {
+        "" => Some(default),
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
 }
 
+/// Waits for any key when stdin is a terminal, like `get_key_press`, and for a
+/// whole line anywhere else.
 pub fn wait_for_key_press() {
-    io::stdin().read_line(&mut String::new()).unwrap();
+    if read_key_press().is_none() {
+        get_user_input();
+    }
+}
+
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+
+        if clipboard.is_none() {
+            *clipboard = Some(Clipboard::new()?);
+        }
+
+        Ok(clipboard.as_mut().unwrap().set_text(text)?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answer_should_fall_back_to_the_default_on_enter() {
+        assert_eq!(parse_answer("", false), Some(false));
+        assert_eq!(parse_answer("", true), Some(true));
+        assert_eq!(parse_answer("Y", false), Some(true));
+        assert_eq!(parse_answer("n", true), Some(false));
+        assert_eq!(parse_answer("x", false), None);
+    }
+
+    #[test]
+    fn parse_answer_should_ignore_case() {
+        assert_eq!(parse_answer("YES", false), Some(true));
+        assert_eq!(parse_answer(" Yes ", false), Some(true));
+        assert_eq!(parse_answer("No", true), Some(false));
+        assert_eq!(parse_answer("nope", true), None);
+        assert_eq!(parse_answer("yess", false), None);
+    }
+
+    #[test]
+    fn read_input_line_should_drop_windows_line_endings() {
+        let mut input = "3\r\n Refunds \r\n\r\ny\r\n".as_bytes();
+
+        assert_eq!(read_input_line(&mut input), "3");
+        assert_eq!(read_input_line(&mut input), "Refunds");
+        assert_eq!(read_input_line(&mut input), "");
+
+        let answer = read_input_line(&mut input);
+
+        assert_eq!(parse_answer(&answer, false), Some(true));
+        assert_eq!(read_input_line(&mut input).parse::<u32>().ok(), None);
+    }
 }
@@ -0,0 +1,174 @@
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::HashMap;
+
+use super::JiraIssue;
+
+/// Reads the CSV export of the issue navigator. Columns that hold lists, like
+/// `Labels` or `Outward issue link (Blocks)`, repeat once per value, and
+/// sub-tasks point at their parent by its numeric `Parent id`.
+pub fn parse_issues(content: &str) -> Result<Vec<JiraIssue>> {
+    let mut rows = parse_rows(content)?.into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| anyhow!("The CSV backup is empty"))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| column.trim() == name)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>()
+    };
+
+    let key = column("Issue key");
+    if key.is_empty() {
+        return Err(anyhow!("The CSV backup has no \"Issue key\" column"));
+    }
+
+    let (id, parent_id) = (column("Issue id"), column("Parent id"));
+    let (summary, description) = (column("Summary"), column("Description"));
+    let (issue_type, status) = (column("Issue Type"), column("Status"));
+    let (priority, assignee) = (column("Priority"), column("Assignee"));
+    let (due, labels) = (column("Due Date"), column("Labels"));
+    let epic = column("Custom field (Epic Link)");
+    let parent = column("Parent");
+    let blocks = column("Outward issue link (Blocks)");
+
+    let rows = rows
+        .filter(|row| row.iter().any(|field| !field.is_empty()))
+        .collect::<Vec<_>>();
+    let values = |row: &[String], columns: &[usize]| {
+        columns
+            .iter()
+            .filter_map(|index| row.get(*index))
+            .filter(|value| !value.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let value = |row: &[String], columns: &[usize]| values(row, columns).into_iter().next();
+    let keys_by_id = rows
+        .iter()
+        .filter_map(|row| Some((value(row, &id)?, value(row, &key)?)))
+        .collect::<HashMap<_, _>>();
+
+    rows.iter()
+        .map(|row| {
+            let parent = value(row, &parent)
+                .or_else(|| value(row, &parent_id).and_then(|id| keys_by_id.get(&id).cloned()));
+
+            Ok(JiraIssue {
+                key: value(row, &key).ok_or_else(|| anyhow!("An issue has no key"))?,
+                issue_type: value(row, &issue_type).unwrap_or_default(),
+                summary: value(row, &summary).unwrap_or_default(),
+                description: value(row, &description).unwrap_or_default(),
+                status: value(row, &status).unwrap_or_default(),
+                priority: value(row, &priority),
+                assignee: value(row, &assignee),
+                labels: values(row, &labels),
+                due: value(row, &due).and_then(|due| parse_date(&due)),
+                epic: value(row, &epic),
+                parent,
+                blocks: values(row, &blocks),
+            })
+        })
+        .collect()
+}
+
+/// Jira writes dates in the instance's format, `03/May/24 12:00 AM` unless
+/// an admin changed it.
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    ["%d/%b/%y %I:%M %p", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|date| date.date())
+        .or_else(|| {
+            ["%d/%b/%y", "%Y-%m-%d"]
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        })
+}
+
+/// Splits RFC 4180 CSV into rows of fields. Quoted fields may hold commas,
+/// line breaks and doubled quotes.
+fn parse_rows(content: &str) -> Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(anyhow!("The CSV backup ends inside a quoted field"));
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_issues_should_read_repeated_columns_and_parents() {
+        let content = "\u{feff}Summary,Issue key,Issue id,Parent id,Issue Type,Status,Priority,\
+                       Due Date,Labels,Labels,Outward issue link (Blocks),Custom field (Epic Link)\r\n\
+                       Payments,ABC-1,10000,,Epic,To Do,,,,,,\r\n\
+                       \"Refunds, \"\"partial\"\"\",ABC-2,10001,,Story,In Progress,Major,\
+                       03/May/24 12:00 AM,billing,web,ABC-3,ABC-1\r\n\
+                       \"Docs\nand more\",ABC-3,10002,10001,Sub-task,Done,,,,,,\r\n";
+
+        let issues = parse_issues(content).unwrap();
+
+        assert_eq!(issues.len(), 3);
+        assert_eq!(
+            issues[1],
+            JiraIssue {
+                key: "ABC-2".to_owned(),
+                issue_type: "Story".to_owned(),
+                summary: "Refunds, \"partial\"".to_owned(),
+                description: "".to_owned(),
+                status: "In Progress".to_owned(),
+                priority: Some("Major".to_owned()),
+                assignee: None,
+                labels: vec!["billing".to_owned(), "web".to_owned()],
+                due: NaiveDate::from_ymd_opt(2024, 5, 3),
+                epic: Some("ABC-1".to_owned()),
+                parent: None,
+                blocks: vec!["ABC-3".to_owned()],
+            }
+        );
+        assert_eq!(issues[2].summary, "Docs\nand more");
+        assert_eq!(issues[2].parent.as_deref(), Some("ABC-2"));
+    }
+
+    #[test]
+    fn parse_issues_should_fail_without_keys() {
+        assert!(parse_issues("Summary,Status\nPayments,Done\n").is_err());
+        assert!(parse_issues("Issue key\n\"ABC-1\n").is_err());
+    }
+}
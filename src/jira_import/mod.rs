@@ -0,0 +1,261 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+
+use crate::model::{
+    Epic, ItemDetail, ItemId, ItemLink, ItemStatus, ItemType, LinkKind, Priority, Story, DB,
+};
+
+mod csv;
+mod xml;
+
+/// An issue as the Jira backups describe it, before it becomes an epic or a
+/// story.
+#[derive(Debug, Default, PartialEq)]
+pub struct JiraIssue {
+    pub key: String,
+    pub issue_type: String,
+    pub summary: String,
+    pub description: String,
+    pub status: String,
+    pub priority: Option<String>,
+    pub assignee: Option<String>,
+    pub labels: Vec<String>,
+    pub due: Option<NaiveDate>,
+    /// The key of the epic from the Epic Link field.
+    pub epic: Option<String>,
+    /// The key of the parent issue, for sub-tasks and issues under an epic in
+    /// newer projects.
+    pub parent: Option<String>,
+    /// The keys of the issues this one blocks.
+    pub blocks: Vec<String>,
+}
+
+/// What an import added.
+#[derive(Debug, PartialEq)]
+pub struct ImportSummary {
+    pub epics: usize,
+    pub stories: usize,
+}
+
+/// Reads a Jira Server/DC backup, the XML (RSS) or CSV export of the issue
+/// navigator, telling them apart by their first character.
+pub fn parse_backup(content: &str) -> Result<Vec<JiraIssue>> {
+    match content.trim_start().starts_with('<') {
+        true => xml::parse_issues(content),
+        false => csv::parse_issues(content),
+    }
+}
+
+/// Adds `issues` to `db`: epics become epics and everything else stories,
+/// placed in the epic they are linked to, directly or through their parent.
+/// "Blocks" links carry over, links to issues outside the backup are dropped.
+pub fn add_issues(db: &mut DB, issues: Vec<JiraIssue>, now: DateTime<Utc>) -> ImportSummary {
+    let is_epic = |issue: &JiraIssue| issue.issue_type.eq_ignore_ascii_case("epic");
+    let by_key = issues
+        .iter()
+        .map(|issue| (issue.key.as_str(), issue))
+        .collect::<HashMap<_, _>>();
+    let mut next_epic = db.epics.keys().max().map_or(0, |id| id + 1);
+    let mut next_story = db.stories.keys().max().map_or(0, |id| id + 1);
+    let mut ids = HashMap::new();
+
+    for issue in &issues {
+        let id = match is_epic(issue) {
+            true => &mut next_epic,
+            false => &mut next_story,
+        };
+
+        ids.insert(issue.key.as_str(), ItemId(*id));
+        *id += 1;
+    }
+
+    let epic_of = |issue: &JiraIssue| {
+        let linked = |key: &Option<String>| {
+            key.as_deref()
+                .and_then(|key| by_key.get(key))
+                .copied()
+                .filter(|linked| is_epic(linked))
+        };
+        let parent = issue.parent.as_deref().and_then(|key| by_key.get(key));
+
+        linked(&issue.epic)
+            .or_else(|| linked(&issue.parent))
+            .or_else(|| parent.and_then(|parent| linked(&parent.epic)))
+            .map(|epic| ids[epic.key.as_str()])
+    };
+    let mut summary = ImportSummary {
+        epics: 0,
+        stories: 0,
+    };
+
+    for issue in &issues {
+        let mut detail = ItemDetail {
+            id: ids[issue.key.as_str()],
+            name: issue.summary.clone(),
+            description: issue.description.clone(),
+            priority: issue.priority.as_deref().and_then(priority),
+            assignee: issue.assignee.clone(),
+            labels: issue.labels.clone(),
+            due: issue.due,
+            ..Default::default()
+        };
+
+        detail.set_status(status(&issue.status), now);
+
+        if is_epic(issue) {
+            db.epics.insert(detail.id.0, Epic::new(detail, Vec::new()));
+            summary.epics += 1;
+            continue;
+        }
+
+        let story_id = detail.id;
+        let mut story = Story::new(detail);
+
+        for blocked in issue.blocks.iter().filter_map(|key| ids.get(key.as_str())) {
+            story.links.push(ItemLink {
+                kind: LinkKind::Blocks,
+                story_id: *blocked,
+            });
+        }
+
+        if let Some(epic_id) = epic_of(issue) {
+            db.epics
+                .get_mut(&epic_id.0)
+                .expect("epics are added first")
+                .stories
+                .push(story_id);
+        }
+
+        db.stories.insert(story_id.0, story);
+        db.last_item = ItemType::Story { id: story_id };
+        summary.stories += 1;
+    }
+
+    add_blocked_by_links(db);
+    summary
+}
+
+/// Gives every story a story `Blocks` points at the matching `BlockedBy`
+/// link, the way the board keeps them in pairs.
+fn add_blocked_by_links(db: &mut DB) {
+    let pairs = db
+        .stories
+        .values()
+        .flat_map(|story| {
+            story
+                .links
+                .iter()
+                .filter(|link| link.kind == LinkKind::Blocks)
+                .map(|link| (link.story_id, story.detail.id))
+        })
+        .collect::<Vec<_>>();
+
+    for (blocked, blocker) in pairs {
+        let Some(story) = db.stories.get_mut(&blocked.0) else {
+            continue;
+        };
+        let link = ItemLink {
+            kind: LinkKind::BlockedBy,
+            story_id: blocker,
+        };
+
+        if !story.links.contains(&link) {
+            story.links.push(link);
+        }
+    }
+}
+
+/// Jira workflows name their statuses freely, these are the usual ones.
+fn status(name: &str) -> ItemStatus {
+    match name.to_lowercase().as_str() {
+        "in progress" | "in review" | "in development" | "in testing" => ItemStatus::InProgress,
+        "resolved" => ItemStatus::Resolved,
+        "done" | "closed" => ItemStatus::Closed,
+        _ => ItemStatus::Open,
+    }
+}
+
+fn priority(name: &str) -> Option<Priority> {
+    match name.to_lowercase().as_str() {
+        "blocker" | "highest" | "critical" => Some(Priority::Critical),
+        "high" | "major" => Some(Priority::High),
+        "medium" => Some(Priority::Medium),
+        "low" | "lowest" | "minor" | "trivial" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use chrono::TimeZone;
+
+    fn issue(key: &str, issue_type: &str, status: &str) -> JiraIssue {
+        JiraIssue {
+            key: key.to_owned(),
+            issue_type: issue_type.to_owned(),
+            summary: format!("{key} summary"),
+            status: status.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn add_issues_should_place_stories_in_their_epics_and_link_blockers() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        db.create_story("Existing".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let mut state = db.read_db().unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+
+        let mut story = issue("ABC-2", "Story", "In Progress");
+        story.epic = Some("ABC-1".to_owned());
+        story.blocks = vec!["ABC-3".to_owned(), "XYZ-9".to_owned()];
+        story.priority = Some("Major".to_owned());
+        let mut subtask = issue("ABC-3", "Sub-task", "Done");
+        subtask.parent = Some("ABC-2".to_owned());
+        let mut next_gen = issue("ABC-4", "Task", "Backlog");
+        next_gen.parent = Some("ABC-1".to_owned());
+
+        let summary = add_issues(
+            &mut state,
+            vec![issue("ABC-1", "Epic", "To Do"), story, subtask, next_gen],
+            now,
+        );
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                epics: 1,
+                stories: 3
+            }
+        );
+
+        let epic = &state.epics[&0];
+        assert_eq!(epic.detail.name, "ABC-1 summary");
+        assert_eq!(epic.stories, vec![ItemId(1), ItemId(2), ItemId(3)]);
+
+        let story = &state.stories[&1];
+        assert_eq!(story.detail.status, ItemStatus::InProgress);
+        assert_eq!(story.detail.priority, Some(Priority::High));
+        assert_eq!(story.detail.history[0].at, now);
+        assert_eq!(
+            story.links,
+            vec![ItemLink {
+                kind: LinkKind::Blocks,
+                story_id: ItemId(2)
+            }]
+        );
+        assert_eq!(
+            state.stories[&2].links,
+            vec![ItemLink {
+                kind: LinkKind::BlockedBy,
+                story_id: ItemId(1)
+            }]
+        );
+        assert_eq!(state.stories[&2].detail.status, ItemStatus::Closed);
+        assert_eq!(state.stories[&3].detail.status, ItemStatus::Open);
+    }
+}
@@ -0,0 +1,409 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate};
+
+use super::JiraIssue;
+
+/// An XML element with its attributes, child elements and text, which is as
+/// much of XML as the Jira export uses.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The trimmed text of the child `name`, if it has any.
+    fn text_of(&self, name: &str) -> Option<String> {
+        let text = self.child(name)?.text.trim();
+
+        (!text.is_empty()).then(|| text.to_owned())
+    }
+}
+
+/// Reads the XML (RSS) export of the issue navigator, one `<item>` per issue.
+pub fn parse_issues(content: &str) -> Result<Vec<JiraIssue>> {
+    let root = Parser::new(content).document()?;
+    let channel = root
+        .child("channel")
+        .ok_or_else(|| anyhow!("The XML backup has no <channel>, is it a Jira export?"))?;
+
+    channel.children("item").map(issue).collect()
+}
+
+fn issue(item: &Element) -> Result<JiraIssue> {
+    let key = item
+        .text_of("key")
+        .ok_or_else(|| anyhow!("An issue in the XML backup has no key"))?;
+    let epic = item
+        .child("customfields")
+        .into_iter()
+        .flat_map(|fields| fields.children("customfield"))
+        .find(|field| {
+            field.attribute("key") == Some("com.pyxis.greenhopper.jira:gh-epic-link")
+                || field.text_of("customfieldname").as_deref() == Some("Epic Link")
+        })
+        .and_then(|field| field.child("customfieldvalues"))
+        .and_then(|values| values.text_of("customfieldvalue"));
+    let blocks = item
+        .child("issuelinks")
+        .into_iter()
+        .flat_map(|links| links.children("issuelinktype"))
+        .filter(|link_type| link_type.text_of("name").as_deref() == Some("Blocks"))
+        .flat_map(|link_type| link_type.children("outwardlinks"))
+        .flat_map(|links| links.children("issuelink"))
+        .filter_map(|link| link.text_of("issuekey"))
+        .collect();
+
+    Ok(JiraIssue {
+        key,
+        issue_type: item.text_of("type").unwrap_or_default(),
+        summary: item.text_of("summary").unwrap_or_default(),
+        description: item
+            .text_of("description")
+            .map(|html| strip_html(&html))
+            .unwrap_or_default(),
+        status: item.text_of("status").unwrap_or_default(),
+        priority: item.text_of("priority"),
+        assignee: item
+            .child("assignee")
+            .and_then(|assignee| assignee.attribute("username"))
+            .filter(|username| *username != "-1")
+            .map(str::to_owned),
+        labels: item
+            .child("labels")
+            .into_iter()
+            .flat_map(|labels| labels.children("label"))
+            .map(|label| label.text.trim().to_owned())
+            .collect(),
+        due: item.text_of("due").and_then(|due| parse_date(&due)),
+        epic,
+        parent: item.text_of("parent"),
+        blocks,
+    })
+}
+
+/// Due dates look like `Fri, 3 May 2024 00:00:00 +0000`.
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.date_naive())
+}
+
+/// Descriptions come rendered as HTML. Paragraphs and line breaks become
+/// new lines and every other tag is dropped.
+fn strip_html(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim_end_matches('/').trim();
+
+        if tag == "br" || tag == "/p" {
+            text.push('\n');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    decode_entities(&text)
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_owned()
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .map(|end| (&rest[1..end], end))
+            .and_then(|(name, end)| Some((entity(name)?, end)));
+
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = match name.strip_prefix("#x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+
+            char::from_u32(code)
+        }
+    }
+}
+
+/// A small, forgiving XML reader: prologs, comments and doctypes are skipped,
+/// CDATA is taken as text.
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(content: &'a str) -> Self {
+        Self { rest: content }
+    }
+
+    /// The root element.
+    fn document(mut self) -> Result<Element> {
+        let mut root = Element::default();
+
+        self.content(&mut root)?;
+        root.children
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("The XML backup has no elements"))
+    }
+
+    /// Reads text and child elements into `element` until its closing tag,
+    /// or the end of the input for the document itself.
+    fn content(&mut self, element: &mut Element) -> Result<()> {
+        loop {
+            let Some(start) = self.rest.find('<') else {
+                element.text.push_str(&decode_entities(self.rest));
+                self.rest = "";
+                return Ok(());
+            };
+
+            element.text.push_str(&decode_entities(&self.rest[..start]));
+            self.rest = &self.rest[start..];
+
+            if let Some(rest) = self.rest.strip_prefix("<![CDATA[") {
+                let end = rest
+                    .find("]]>")
+                    .ok_or_else(|| anyhow!("Unclosed CDATA in the XML backup"))?;
+
+                element.text.push_str(&rest[..end]);
+                self.rest = &rest[end + 3..];
+            } else if self.rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest.starts_with("<?") || self.rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else if let Some(rest) = self.rest.strip_prefix("</") {
+                let end = rest
+                    .find('>')
+                    .ok_or_else(|| anyhow!("Unclosed tag in the XML backup"))?;
+                let name = rest[..end].trim();
+
+                if name != element.name {
+                    return Err(anyhow!(
+                        "The XML backup closes <{name}> where <{}> is open",
+                        element.name
+                    ));
+                }
+
+                self.rest = &rest[end + 1..];
+                return Ok(());
+            } else {
+                let child = self.element()?;
+                element.children.push(child);
+            }
+        }
+    }
+
+    fn element(&mut self) -> Result<Element> {
+        let end = self
+            .rest
+            .find('>')
+            .ok_or_else(|| anyhow!("Unclosed tag in the XML backup"))?;
+        let tag = &self.rest[1..end];
+        let (tag, empty) = match tag.strip_suffix('/') {
+            Some(tag) => (tag, true),
+            None => (tag, false),
+        };
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let mut element = Element {
+            name: tag[..name_end].to_owned(),
+            attributes: attributes(&tag[name_end..]),
+            ..Default::default()
+        };
+
+        self.rest = &self.rest[end + 1..];
+
+        if !empty {
+            self.content(&mut element)?;
+        }
+
+        Ok(element)
+    }
+
+    fn skip_past(&mut self, end: &str) -> Result<()> {
+        let index = self
+            .rest
+            .find(end)
+            .ok_or_else(|| anyhow!("Unexpected end of the XML backup"))?;
+
+        self.rest = &self.rest[index + end.len()..];
+        Ok(())
+    }
+}
+
+/// Parses `name="value"` pairs, with either kind of quotes.
+fn attributes(mut rest: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+
+    while let Some(equals) = rest.find('=') {
+        let name = rest[..equals].trim().to_owned();
+        let value = rest[equals + 1..].trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(end) = value[1..].find(quote) else {
+            break;
+        };
+
+        attributes.push((name, decode_entities(&value[1..end + 1])));
+        rest = &value[end + 2..];
+    }
+
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BACKUP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- RSS generated by JIRA -->
+<rss version="0.92">
+  <channel>
+    <title>Your Company JIRA</title>
+    <item>
+      <title>[ABC-2] Refunds</title>
+      <key id="10001">ABC-2</key>
+      <summary>Refunds &amp; credits</summary>
+      <type id="10100" iconUrl="x">Story</type>
+      <priority id="3">Major</priority>
+      <status id="3" description="">In Progress</status>
+      <assignee username="alice">Alice Doe</assignee>
+      <description>&lt;p&gt;First&lt;br/&gt;second&lt;/p&gt;</description>
+      <due>Fri, 3 May 2024 00:00:00 +0000</due>
+      <labels>
+        <label>billing</label>
+      </labels>
+      <issuelinks>
+        <issuelinktype id="10000">
+          <name>Blocks</name>
+          <outwardlinks description="blocks">
+            <issuelink><issuekey id="10002">ABC-3</issuekey></issuelink>
+          </outwardlinks>
+          <inwardlinks description="is blocked by">
+            <issuelink><issuekey id="10009">ABC-9</issuekey></issuelink>
+          </inwardlinks>
+        </issuelinktype>
+        <issuelinktype id="10003">
+          <name>Relates</name>
+          <outwardlinks description="relates to">
+            <issuelink><issuekey id="10004">ABC-4</issuekey></issuelink>
+          </outwardlinks>
+        </issuelinktype>
+      </issuelinks>
+      <customfields>
+        <customfield id="customfield_10008" key="com.pyxis.greenhopper.jira:gh-epic-link">
+          <customfieldname>Epic Link</customfieldname>
+          <customfieldvalues><customfieldvalue>ABC-1</customfieldvalue></customfieldvalues>
+        </customfield>
+      </customfields>
+    </item>
+    <item>
+      <key id="10002">ABC-3</key>
+      <summary><![CDATA[Docs <draft>]]></summary>
+      <type>Sub-task</type>
+      <status>Done</status>
+      <assignee username="-1">Unassigned</assignee>
+      <parent id="10001">ABC-2</parent>
+      <due></due>
+    </item>
+  </channel>
+</rss>"#;
+
+    #[test]
+    fn parse_issues_should_read_the_rss_export() {
+        let issues = parse_issues(BACKUP).unwrap();
+
+        assert_eq!(
+            issues,
+            vec![
+                JiraIssue {
+                    key: "ABC-2".to_owned(),
+                    issue_type: "Story".to_owned(),
+                    summary: "Refunds & credits".to_owned(),
+                    description: "First\nsecond".to_owned(),
+                    status: "In Progress".to_owned(),
+                    priority: Some("Major".to_owned()),
+                    assignee: Some("alice".to_owned()),
+                    labels: vec!["billing".to_owned()],
+                    due: NaiveDate::from_ymd_opt(2024, 5, 3),
+                    epic: Some("ABC-1".to_owned()),
+                    parent: None,
+                    blocks: vec!["ABC-3".to_owned()],
+                },
+                JiraIssue {
+                    key: "ABC-3".to_owned(),
+                    issue_type: "Sub-task".to_owned(),
+                    summary: "Docs <draft>".to_owned(),
+                    status: "Done".to_owned(),
+                    parent: Some("ABC-2".to_owned()),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_issues_should_fail_on_broken_xml() {
+        assert!(parse_issues("<rss><channel><item></channel></rss>").is_err());
+        assert!(parse_issues("<rss><item/></rss>").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+/// The prefixes and zero padding item keys are written with.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct KeyFormat {
+    pub epic: String,
+    pub story: String,
+    pub sprint: String,
+    /// How many digits the number is padded to with zeros.
+    pub width: usize,
+}
+
+impl Default for KeyFormat {
+    fn default() -> Self {
+        Self {
+            epic: "EPIC".to_owned(),
+            story: "STORY".to_owned(),
+            sprint: "SPRINT".to_owned(),
+            width: 0,
+        }
+    }
+}
+
+thread_local! {
+    // Keys are written from everywhere, models and messages included, so the
+    // format is kept for the session instead of being handed around.
+    static KEY_FORMAT: RefCell<Option<KeyFormat>> = const { RefCell::new(None) };
+}
+
+/// Writes keys in `format` from now on.
+pub fn set_key_format(format: KeyFormat) {
+    KEY_FORMAT.with(|current| *current.borrow_mut() = Some(format));
+}
+
+/// The format keys are written in.
+pub fn key_format() -> KeyFormat {
+    with_format(KeyFormat::clone)
+}
+
+fn with_format<T>(f: impl FnOnce(&KeyFormat) -> T) -> T {
+    KEY_FORMAT.with(|current| match current.borrow().as_ref() {
+        Some(format) => f(format),
+        None => f(&KeyFormat::default()),
+    })
+}
+
+fn key(prefix: &str, id: u32, width: usize) -> String {
+    format!("{prefix}-{id:0width$}")
+}
+
+pub fn epic_key(id: u32) -> String {
+    with_format(|format| key(&format.epic, id, format.width))
+}
+
+pub fn story_key(id: u32) -> String {
+    with_format(|format| key(&format.story, id, format.width))
+}
+
+pub fn sprint_key(id: u32) -> String {
+    with_format(|format| key(&format.sprint, id, format.width))
+}
+
+/// Reads an ID typed as a number, `7`, or as a key of any kind, `OPS-007`
+/// or `ops-7`.
+pub fn parse_id(input: &str) -> Option<u32> {
+    let number = with_format(|format| {
+        [&format.epic, &format.story, &format.sprint]
+            .into_iter()
+            .find_map(|prefix| {
+                let head = input.get(..prefix.len() + 1)?;
+
+                head.eq_ignore_ascii_case(&format!("{prefix}-"))
+                    .then(|| &input[head.len()..])
+            })
+            .unwrap_or(input)
+    });
+
+    number.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_should_follow_the_format() {
+        assert_eq!(story_key(7), "STORY-7");
+
+        set_key_format(KeyFormat {
+            story: "OPS".to_owned(),
+            width: 3,
+            ..Default::default()
+        });
+
+        assert_eq!(story_key(7), "OPS-007");
+        assert_eq!(epic_key(12), "EPIC-012");
+        assert_eq!(sprint_key(1234), "SPRINT-1234");
+        assert_eq!(parse_id("OPS-007"), Some(7));
+        assert_eq!(parse_id("ops-7"), Some(7));
+        assert_eq!(parse_id("epic-12"), Some(12));
+        assert_eq!(parse_id("7"), Some(7));
+        assert_eq!(parse_id("STORY-7"), None);
+        assert_eq!(parse_id("OPS-"), None);
+    }
+}
@@ -0,0 +1,53 @@
+//! The board behind `jira-clone`. `session::Session` drives it from other
+//! programs, without a terminal.
+//!
+//! Without the default features the terminal, plugins, the board server, the
+//! log file and the Taskwarrior and Todoist syncs are left out, so the board,
+//! `db::MemoryDatabase`, `archive::MemoryArchive` and the navigation build for
+//! targets like wasm32. CI checks that they do with `cargo check --target
+//! wasm32-unknown-unknown --no-default-features`, and runs the tests without
+//! the default features too.
+
+pub mod archive;
+pub mod buffered_db;
+pub mod calendar;
+pub mod capture;
+#[cfg(all(feature = "terminal", feature = "plugins", feature = "integrations"))]
+pub mod cli;
+pub mod config;
+pub mod db;
+pub mod diff;
+pub mod directory_db;
+pub mod doctor;
+pub mod dry_run;
+pub mod events;
+pub mod export;
+pub mod graph;
+pub mod integrity;
+pub mod interchange;
+#[cfg(feature = "terminal")]
+pub mod io_utils;
+pub mod jira_import;
+pub mod keys;
+#[cfg(feature = "logging")]
+pub mod logging;
+pub mod model;
+pub mod navigators;
+pub mod obsidian;
+pub mod org;
+#[cfg(feature = "plugins")]
+pub mod plugins;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod reports;
+pub mod search;
+pub mod session;
+pub mod share;
+pub mod snapshots;
+pub mod stats;
+#[cfg(feature = "integrations")]
+pub mod taskwarrior;
+pub mod templates;
+#[cfg(feature = "integrations")]
+pub mod todoist;
+pub mod ui;
@@ -0,0 +1,91 @@
+use std::{fs, path::PathBuf};
+use tracing::level_filters::LevelFilter;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, Layer};
+
+use crate::config::LogLevel;
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => LevelFilter::OFF,
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// How many days of logs are kept, one file per day.
+const KEPT_LOGS: usize = 7;
+
+/// Where the log is written, `$XDG_STATE_HOME/lgr` or `~/.local/state/lgr`.
+pub fn log_dir() -> Option<PathBuf> {
+    let state_dir = std::env::var("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| {
+            std::env::var("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+        })
+        .ok()?;
+
+    Some(state_dir.join("lgr"))
+}
+
+/// Logs at `level` to `lgr.log` in `log_dir`, starting a new file every day,
+/// and to stderr as well when `verbose`. Nothing is logged to the file when
+/// the directory can't be created. Lines still buffered are written out when
+/// the returned guard is dropped.
+pub fn init(level: LogLevel, verbose: bool) -> Option<WorkerGuard> {
+    let file = log_dir().and_then(|dir| {
+        fs::create_dir_all(&dir).ok()?;
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("lgr")
+            .filename_suffix("log")
+            .max_log_files(KEPT_LOGS)
+            .build(dir)
+            .ok()
+    });
+    let (file, guard) = match file {
+        Some(file) => {
+            let (writer, guard) = tracing_appender::non_blocking(file);
+            (Some(writer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    let _ = tracing_subscriber::registry()
+        .with(file.map(|writer| {
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(LevelFilter::from(level))
+        }))
+        .with(verbose.then(|| {
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(std::io::stderr)
+                .with_filter(LevelFilter::DEBUG.max(level.into()))
+        }))
+        .try_init();
+
+    guard
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_level_should_read_lowercase_names() {
+        let level: LogLevel = serde_json::from_str(r#""debug""#).unwrap();
+
+        assert_eq!(level, LogLevel::Debug);
+        assert_eq!(LevelFilter::from(level), LevelFilter::DEBUG);
+        assert_eq!(LevelFilter::from(LogLevel::default()), LevelFilter::INFO);
+        assert!(serde_json::from_str::<LogLevel>(r#""loud""#).is_err());
+    }
+}
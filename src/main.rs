@@ -1,60 +1,413 @@
-use std::rc::Rc;
+use chrono::Utc;
 use std::path::Path;
+use std::process::ExitCode;
+use std::rc::Rc;
+use std::time::Duration;
+
+use jira_clone::archive::DirectoryArchive;
+use jira_clone::buffered_db::BufferedDatabase;
+use jira_clone::cli::*;
+use jira_clone::db::*;
+use jira_clone::navigators::*;
+use jira_clone::{cli, config, doctor, dry_run, integrity, keys, logging, plugins, remote, ui};
+use ui::{PromptProvider, Renderer};
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let yes = take_flag(&mut args, &["--yes", "-y"]);
+    let dry_run = take_flag(&mut args, &["--dry-run"]);
+    let verbose = take_flag(&mut args, &["--verbose", "-v"]);
+    let options = take_option(&mut args, "--remote")
+        .and_then(|remote| Ok((remote, take_option(&mut args, "--view")?)));
+    let (remote, view) = match options {
+        Ok((remote, view)) => (remote.map(|address| remote::Address::parse(&address)), view),
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let command = match Command::parse(&args) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("{error}");
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // The doctor has to get by with a broken config or board, so it runs before
+    // either is loaded.
+    if command == Command::Doctor {
+        let findings = doctor::diagnose(config::Config::path().as_deref());
+
+        findings.iter().for_each(|finding| println!("{finding}"));
+        return match findings.iter().any(|finding| finding.fix.is_some()) {
+            true => ExitCode::FAILURE,
+            false => ExitCode::SUCCESS,
+        };
+    }
+
+    let config = match config::Config::load() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if verbose && matches!(command, Command::Interactive | Command::Last { .. }) {
+        eprintln!("--verbose only applies to headless commands");
+        return ExitCode::FAILURE;
+    }
+
+    let _log = logging::init(config.log_level, verbose);
+    keys::set_key_format(config.keys.clone());
+    tracing::info!(?command, "Starting");
+    let plugins = match config::Config::plugin_dir() {
+        Some(dir) => plugins::Plugins::load(&dir),
+        None => Ok(plugins::Plugins::none()),
+    };
+    let plugins = match plugins {
+        Ok(plugins) => Rc::new(plugins),
+        Err(error) => {
+            eprintln!("{error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let (board, database) = match (&remote, &config.data_dir) {
+        (Some(address), _) => (
+            None,
+            JiraDataBase::from_database(Box::new(
+                remote::RemoteDatabase::new(address.clone()).with_token(config.server_token()),
+            )),
+        ),
+        (None, Some(root)) => (
+            Some(root.clone()),
+            JiraDataBase::with_directory(root.clone()),
+        ),
+        (None, None) => {
+            let path = Path::new("database.json");
+
+            if !check_database(path, &config.snapshots_dir(), yes && !dry_run) {
+                return ExitCode::FAILURE;
+            }
+
+            (
+                Some(path.to_path_buf()),
+                JiraDataBase::new(path.to_path_buf()),
+            )
+        }
+    };
+
+    if view.is_some() && command != Command::Interactive {
+        eprintln!("--view only applies to the interactive board");
+        return ExitCode::FAILURE;
+    }
+
+    if let Command::Serve { address } = &command {
+        if remote.is_some() || dry_run {
+            eprintln!("serve shares a local board and always writes it");
+            return ExitCode::FAILURE;
+        }
+
+        return match remote::serve(
+            &remote::Address::parse(address),
+            database.database.as_ref(),
+            config.server_token().as_deref(),
+        ) {
+            Ok(_) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("{error:#}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let database = database
+        .with_user(config.acting_user())
+        .with_default_status(config.default_status.clone())
+        .with_stale_after(config.stale_after());
+
+    let start = match &command {
+        Command::Interactive => Some(view.clone().map_or(StartPage::Home, StartPage::View)),
+        Command::Last { nth } => Some(StartPage::Recent(*nth)),
+        _ => None,
+    };
+
+    if let Some(start) = start {
+        if dry_run {
+            eprintln!("--dry-run only applies to headless commands");
+            return ExitCode::FAILURE;
+        }
+
+        if let Some(hash) = board.and_then(|board| config.passphrase_hash(&board)) {
+            if !hash.starts_with("pbkdf2-") {
+                eprintln!("The passphrase of this board was hashed by an older version, hash it again with `jira-clone hash-passphrase`");
+                return ExitCode::FAILURE;
+            }
+            if !unlock(hash, &mut ui::Terminal) {
+                eprintln!("Wrong passphrase");
+                return ExitCode::FAILURE;
+            }
+        }
+
+        let database = match config.explicit_save {
+            true => database.map_database(|inner| Box::new(BufferedDatabase::new(inner))),
+            false => database,
+        };
+
+        run_interactive(
+            with_plugins(database, &plugins),
+            config,
+            start,
+            ui::TerminalPrompts,
+            &mut ui::Terminal,
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    if dry_run && matches!(command, Command::Taskwarrior { .. }) {
+        eprintln!("--dry-run can't preview a sync, it changes Taskwarrior too");
+        return ExitCode::FAILURE;
+    }
+
+    if dry_run {
+        return run_dry(command, database, &config, &plugins);
+    }
+
+    let database = with_plugins(database, &plugins);
+    let confirm = |question: &str| yes || ask_confirmation(question);
+
+    match cli::run(command, &database, &config, &plugins, &confirm) {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(error) => {
+            tracing::error!("{error:#}");
+            eprintln!("{error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the plugin hooks on everything `database` saves.
+fn with_plugins(database: JiraDataBase, plugins: &Rc<plugins::Plugins>) -> JiraDataBase {
+    database.map_database(|inner| Box::new(plugins::PluginDatabase::new(inner, Rc::clone(plugins))))
+}
+
+/// Finishes a write a crash interrupted, then makes sure the board file is
+/// intact, offering to restore the backup, or the newest snapshot, when it
+/// isn't. Returns whether it is safe to go on.
+fn check_database(path: &Path, snapshots_dir: &Path, yes: bool) -> bool {
+    match integrity::replay_journal(path) {
+        Ok(true) => eprintln!("Recovered the last change from the journal"),
+        Ok(false) => {}
+        Err(error) => {
+            eprintln!("{error:#}");
+            return false;
+        }
+    }
+
+    if !path.exists() {
+        return true;
+    }
+
+    let error = match integrity::check(path) {
+        Ok(_) => return true,
+        Err(error) => error,
+    };
+
+    tracing::warn!("{error:#}");
+
+    eprintln!("{error:#}");
+
+    let Some((copy, description)) = integrity::last_good_copy(path, snapshots_dir) else {
+        eprintln!("There is no intact backup or snapshot to restore it from.");
+        return false;
+    };
 
-mod db;
-mod io_utils;
-mod model;
-mod navigators;
-mod ui;
+    if !yes && !ask_confirmation(&format!("Restore {description}?")) {
+        return false;
+    }
+
+    match integrity::restore_from(path, &copy) {
+        Ok(_) => {
+            tracing::warn!("Restored {description}");
+            eprintln!("Restored {description}, the damaged file was kept as .corrupt");
+            true
+        }
+        Err(error) => {
+            eprintln!("{error:#}");
+            false
+        }
+    }
+}
 
-use db::*;
-use io_utils::*;
-use navigators::*;
+/// Runs a headless command against an in-memory copy of the board and prints
+/// what it would have changed.
+fn run_dry(
+    command: Command,
+    database: JiraDataBase,
+    config: &config::Config,
+    plugins: &Rc<plugins::Plugins>,
+) -> ExitCode {
+    let database = database.map_database(|inner| Box::new(dry_run::DryRunDatabase::new(inner)));
+    let database = with_plugins(database, plugins);
+    let result = database.read_db().and_then(|before| {
+        cli::run(command, &database, config, plugins, &|_| true)?;
+        Ok(dry_run::changes(&before, &database.read_db()?))
+    });
+
+    match result {
+        Ok(changes) => {
+            println!("Dry run, nothing was written. This would:");
+            changes.iter().for_each(|change| println!("- {change}"));
+            if changes.is_empty() {
+                println!("- change nothing");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// How many tries `unlock` gives before giving up.
+const PASSPHRASE_ATTEMPTS: usize = 3;
+
+/// Asks for the passphrase of a locked board without showing it as it's
+/// typed. Returns whether it was right.
+fn unlock(hash: &str, renderer: &mut dyn Renderer) -> bool {
+    (0..PASSPHRASE_ATTEMPTS).any(|_| {
+        renderer.show_notice("This board is locked, enter its passphrase:");
+        let passphrase = renderer.read_secret();
+
+        config::verify_passphrase(&passphrase, hash)
+    })
+}
+
+/// Where the interactive board opens, on top of the home page.
+enum StartPage {
+    Home,
+    /// A saved filter or named page, from `--view`.
+    View(String),
+    /// The nth most recently changed item, from `last`.
+    Recent(usize),
+}
 
-fn main() {
-    let database = JiraDataBase::new(Path::new("database.json").to_path_buf());
-    let mut navigator = Navigator::new(Rc::new(database));
+/// How often the page in view is checked for changes made by others.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
 
+/// Waits for the user's input, checking every `REFRESH_INTERVAL` whether
+/// someone else changed the item in view, the config file changed or a held
+/// back delete is due. Returns `None` when any of them did, with `banner` or the navigator's notice saying
+/// what, so the page gets drawn again. The renderer keeps what was typed so
+/// far and goes on with it under the new page.
+fn wait_for_input(
+    navigator: &mut Navigator,
+    renderer: &mut dyn Renderer,
+    banner: &mut Option<String>,
+    watcher: &mut config::ConfigWatcher,
+) -> Option<String> {
     loop {
-        match clearscreen::clear() {
+        match navigator.expire_delete() {
+            Ok(true) => return None,
+            Err(error) => {
+                *banner = Some(format!("{error:#}"));
+                return None;
+            }
+            Ok(false) => {}
+        }
+
+        if let Ok(Some(update)) = navigator.check_for_updates() {
+            *banner = Some(update);
+            return None;
+        }
+
+        match watcher.poll() {
+            Some(Ok(config)) => {
+                navigator.reload_config(config);
+                return None;
+            }
+            Some(Err(error)) => {
+                *banner = Some(format!("Kept the previous config: {error:#}"));
+                return None;
+            }
+            None => {}
+        }
+
+        if let Some(input) = renderer.poll_input(REFRESH_INTERVAL) {
+            return Some(input);
+        }
+    }
+}
+
+/// Drives the board through a frontend: `prompts` asks the questions actions
+/// need and `renderer` shows the pages and reads the input.
+fn run_interactive(
+    database: JiraDataBase,
+    config: config::Config,
+    start: StartPage,
+    prompts: impl PromptProvider + 'static,
+    renderer: &mut dyn Renderer,
+) {
+    let today = config.calendar().today();
+    let mut navigator = Navigator::new(Rc::new(database))
+        .with_archive(DirectoryArchive::new(&config))
+        .with_config(config)
+        .with_prompts(prompts);
+    let startup = navigator
+        .greet(Utc::now())
+        .and_then(|_| match &start {
+            StartPage::Home => Ok(()),
+            StartPage::View(view) => navigator.open_view(view),
+            StartPage::Recent(nth) => navigator.open_recent(*nth),
+        })
+        .and_then(|_| navigator.remind_due_items(today));
+
+    if let Err(error) = startup {
+        renderer.show_error(&format!("Error while loading the board: {error}"));
+    }
+
+    let mut banner = None;
+    let mut watcher = config::ConfigWatcher::new(config::Config::path());
+
+    loop {
+        match renderer.clear() {
             Ok(_) => {
+                if navigator.has_unsaved_changes() {
+                    renderer.show_notice("* Unsaved changes, :w saves them");
+                }
+
+                if let Some(notice) = navigator.take_notice().or_else(|| banner.take()) {
+                    renderer.show_notice(&notice);
+                }
+
                 match navigator.get_current_page() {
                     None => break,
-                    Some(page) => {
-                        match page.draw_page() {
-                            Ok(_) => {
-                                let input = get_user_input();
-                                let action = page.handle_input(input.trim());
-
-                                match action {
-                                    Ok(action) => {
-                                        if let Some(action) = action {
-                                            if let Err(error) = navigator.handle_action(action) {
-                                                println!("Error handling user input: {error}");
-                                                println!("Press any key to continue...");
-                                                wait_for_key_press();
-                                            }
-                                        }
-                                    },
-                                    Err(e) => {
-                                        println!("Error while getting user input: {e}");
-                                        println!("Press any key to continue...");
-                                        wait_for_key_press();
-                                    }
-                                }
-                            },
-                            Err(e) => {
-                                println!("Error while rendering page: {e}");
-                                println!("Press any key to continue...");
-                                wait_for_key_press();
+                    Some(page) => match renderer.draw(page) {
+                        Ok(_) if navigator.is_filtering() => {
+                            let key = renderer.read_filter_key();
+                            navigator.handle_filter_key(key);
+                        }
+                        Ok(_) => {
+                            let Some(input) =
+                                wait_for_input(&mut navigator, renderer, &mut banner, &mut watcher)
+                            else {
+                                continue;
+                            };
+                            if let Err(error) = navigator.handle_input(&input) {
+                                tracing::error!("{error:#}");
+                                renderer.show_error(&format!("Error handling user input: {error}"));
                             }
                         }
-                    }
+                        Err(e) => {
+                            renderer.show_error(&format!("Error while rendering page: {e}"));
+                        }
+                    },
                 }
             }
             Err(_) => {
-                println!("Something went wrong.");
-                wait_for_key_press();
+                renderer.show_error("Something went wrong.");
                 break;
             }
         }
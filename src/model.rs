@@ -1,10 +1,15 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt::Display};
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+use crate::db::SortBy;
+use crate::keys::{epic_key, sprint_key, story_key};
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 pub enum ItemStatus {
     Closed,
     InProgress,
+    #[default]
     Open,
     Resolved,
 }
@@ -22,53 +27,414 @@ impl Display for ItemStatus {
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Action {
-    NavigateToEpicDetail { epic_id: u32 },
-    NavigateToStoryDetail { epic_id: u32, story_id: u32 },
+    NavigateToEpicDetail {
+        epic_id: u32,
+    },
+    NavigateToStoryDetail {
+        epic_id: u32,
+        story_id: u32,
+    },
     NavigateToPreviousPage,
+    ShowKeyHints,
     CreateEpic,
-    UpdateEpicStatus { epic_id: u32 },
-    DeleteEpic { epic_id: u32 },
-    CreateStory { epic_id: u32 },
-    UpdateStoryStatus { story_id: u32 },
-    DeleteStory { epic_id: u32, story_id: u32 },
+    UpdateEpicStatus {
+        epic_id: u32,
+    },
+    DeleteEpic {
+        epic_id: u32,
+    },
+    MergeEpic {
+        epic_id: u32,
+    },
+    DemoteEpic {
+        epic_id: u32,
+    },
+    EditDefaultLabels {
+        epic_id: u32,
+    },
+    CreateStory {
+        epic_id: u32,
+    },
+    CreateStoryFromTemplate {
+        epic_id: u32,
+    },
+    SortStories {
+        epic_id: u32,
+        sort: SortBy,
+    },
+    QuickAddStory {
+        epic_id: Option<u32>,
+        input: String,
+    },
+    UpdateStoryStatus {
+        story_id: u32,
+    },
+    BulkUpdateStatus {
+        items: Vec<ItemType>,
+    },
+    BulkEdit {
+        items: Vec<ItemType>,
+        edits: Vec<FieldEdit>,
+    },
+    DeleteStory {
+        epic_id: u32,
+        story_id: u32,
+    },
+    CloneStory {
+        epic_id: u32,
+        story_id: u32,
+    },
+    SplitStory {
+        epic_id: u32,
+        story_id: u32,
+    },
+    PromoteStory {
+        story_id: u32,
+    },
+    AddChecklistItem {
+        story_id: u32,
+    },
+    ToggleChecklistItem {
+        story_id: u32,
+        index: usize,
+    },
+    AddBlocker {
+        story_id: u32,
+        blocker_id: u32,
+    },
+    CopyEpic {
+        epic_id: u32,
+    },
+    CopyStory {
+        story_id: u32,
+    },
+    NavigateToEpicMarkdown {
+        epic_id: u32,
+    },
+    NavigateToStoryMarkdown {
+        story_id: u32,
+    },
+    CopyText {
+        text: String,
+    },
+    Search {
+        filter: Filter,
+    },
+    SaveFilter {
+        filter: Filter,
+    },
+    PickFilter,
+    ToggleWatch {
+        item: ItemType,
+    },
+    NavigateToNotifications,
+    NavigateToStandup,
+    NavigateToSprints,
+    NavigateToDashboard,
+    NavigateToWorkload,
+    NavigateToLabels,
+    NavigateToSnapshots,
+    NavigateToThisWeek,
+    NavigateToStale,
+    NavigateToBoard,
+    QuickFilter,
+    CreateSnapshot,
+    RestoreSnapshot {
+        name: String,
+    },
+    NavigateToTree,
+    NavigateToSprintDetail {
+        sprint_id: u32,
+    },
+    NavigateToSprintArchive,
+    NavigateToArchivedSprint {
+        sprint_id: u32,
+    },
+    CreateSprint,
+    AddStoryToSprint {
+        sprint_id: u32,
+        story_id: u32,
+    },
+    CloseSprint {
+        sprint_id: u32,
+    },
+    SetStoryPoints {
+        story_id: u32,
+        points: Option<u32>,
+    },
+    OpenNotification {
+        id: u32,
+    },
+    MarkNotificationsRead,
+    NavigateToNotes,
+    AddNote {
+        text: String,
+    },
+    DeleteNote {
+        index: usize,
+    },
+    NoteToStory {
+        index: usize,
+        capture: String,
+    },
     Exit,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Priority {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Critical => write!(f, "CRITICAL"),
+            Self::High => write!(f, "HIGH"),
+            Self::Medium => write!(f, "MEDIUM"),
+            Self::Low => write!(f, "LOW"),
+        }
+    }
+}
+
+/// A change `Action::BulkEdit` makes to every item it's given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldEdit {
+    AddLabel(String),
+    RemoveLabel(String),
+    SetPriority(Option<Priority>),
+    SetAssignee(Option<String>),
+    /// Moves stories into the sprint, epics are left alone.
+    MoveToSprint(ItemId),
+}
+
+impl Display for FieldEdit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AddLabel(label) => write!(f, "add #{label}"),
+            Self::RemoveLabel(label) => write!(f, "remove #{label}"),
+            Self::SetPriority(Some(priority)) => write!(f, "priority {priority}"),
+            Self::SetPriority(None) => write!(f, "no priority"),
+            Self::SetAssignee(Some(assignee)) => write!(f, "assign @{assignee}"),
+            Self::SetAssignee(None) => write!(f, "unassign"),
+            Self::MoveToSprint(id) => write!(f, "move to {}", sprint_key(id.0)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 pub struct ItemId(pub u32);
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct ItemDetail {
     pub description: String,
     pub id: ItemId,
     pub name: String,
     pub status: ItemStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<StatusChange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub description_edits: Vec<DescriptionEdit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+    /// Who saved the last change to the item, and when.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Goes up by one every time the item is saved with changes.
+    #[serde(default)]
+    pub version: u32,
+}
+
+impl ItemDetail {
+    /// Moves the item to `status` and records the transition in its history.
+    pub fn set_status(&mut self, status: ItemStatus, at: DateTime<Utc>) {
+        self.status = status.clone();
+        self.history.push(StatusChange {
+            status,
+            at,
+            by: None,
+        });
+    }
+}
+
+/// A status an item entered, when and by whom. The first entry is the status
+/// it was created with.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StatusChange {
+    pub status: ItemStatus,
+    pub at: DateTime<Utc>,
+    /// Filled in when the change is saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+}
+
+/// A change to the description of an item, recorded when it is saved. `diff`
+/// holds the removed and added words, see `diff::word_diff`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DescriptionEdit {
+    pub at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+    pub diff: Vec<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Epic {
     pub detail: ItemDetail,
     pub stories: Vec<ItemId>,
+    /// The labels stories created in the epic start with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_labels: Vec<String>,
 }
 
 impl Epic {
     pub fn new(detail: ItemDetail, stories: Vec<ItemId>) -> Self {
-        return Epic { detail, stories };
+        Epic {
+            detail,
+            stories,
+            default_labels: Vec::new(),
+        }
+    }
+
+    pub fn key(&self) -> String {
+        epic_key(self.detail.id.0)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub done: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum LinkKind {
+    SplitFrom,
+    SplitInto,
+    BlockedBy,
+    Blocks,
+}
+
+impl Display for LinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SplitFrom => write!(f, "split from"),
+            Self::SplitInto => write!(f, "split into"),
+            Self::BlockedBy => write!(f, "blocked by"),
+            Self::Blocks => write!(f, "blocks"),
+        }
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ItemLink {
+    pub kind: LinkKind,
+    pub story_id: ItemId,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Story {
     pub detail: ItemDetail,
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    #[serde(default)]
+    pub links: Vec<ItemLink>,
+    /// The UUID of the Taskwarrior task the story is synced with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taskwarrior: Option<String>,
 }
 
 impl Story {
     pub fn new(detail: ItemDetail) -> Self {
-        return Story { detail };
+        Story {
+            detail,
+            checklist: Vec::new(),
+            links: Vec::new(),
+            taskwarrior: None,
+        }
+    }
+
+    pub fn key(&self) -> String {
+        story_key(self.detail.id.0)
+    }
+}
+
+/// A time box the stories in `stories` are committed to.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Sprint {
+    pub id: ItemId,
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub stories: Vec<ItemId>,
+    /// The day the sprint was closed. Closed sprints keep their stories as
+    /// they were and take no new ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed: Option<NaiveDate>,
+    /// The stories as they stood when the sprint was closed, kept when they
+    /// change or get deleted later.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshot: Vec<SprintStory>,
+}
+
+impl Sprint {
+    pub fn key(&self) -> String {
+        sprint_key(self.id.0)
     }
 }
 
+/// A story of a closed sprint as it stood on the day the sprint closed.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SprintStory {
+    pub id: ItemId,
+    pub name: String,
+    pub status: ItemStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+}
+
+impl SprintStory {
+    pub fn of(story: &Story) -> Self {
+        Self {
+            id: story.detail.id,
+            name: story.detail.name.clone(),
+            status: story.detail.status.clone(),
+            points: story.detail.points,
+            assignee: story.detail.assignee.clone(),
+        }
+    }
+
+    pub fn key(&self) -> String {
+        story_key(self.id.0)
+    }
+}
+
+/// What moves out of a story when it gets split: the chosen checklist items
+/// (by position) and the description of the new story.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StorySplit {
+    pub name: String,
+    pub description: String,
+    pub checklist: Vec<usize>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(tag = "type")]
 pub enum ItemType {
     Epic { id: ItemId },
@@ -76,9 +442,112 @@ pub enum ItemType {
     None,
 }
 
+impl ItemType {
+    pub fn key(&self) -> String {
+        match self {
+            Self::Epic { id } => epic_key(id.0),
+            Self::Story { id } => story_key(id.0),
+            Self::None => String::new(),
+        }
+    }
+}
+
+/// What a list shows: items have to match every criteria that is set, e.g.
+/// `status:open #bug @alice login`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Filter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ItemStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+}
+
+impl Filter {
+    /// The items assigned to `user`.
+    pub fn assigned_to(user: &str) -> Self {
+        Self {
+            assignee: Some(user.to_owned()),
+            ..Self::default()
+        }
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tokens = Vec::new();
+
+        if let Some(status) = &self.status {
+            tokens.push(match status {
+                ItemStatus::Closed => "status:closed".to_owned(),
+                ItemStatus::InProgress => "status:in-progress".to_owned(),
+                ItemStatus::Open => "status:open".to_owned(),
+                ItemStatus::Resolved => "status:resolved".to_owned(),
+            });
+        }
+        if let Some(label) = &self.label {
+            tokens.push(format!("#{label}"));
+        }
+        if let Some(assignee) = &self.assignee {
+            tokens.push(format!("@{assignee}"));
+        }
+        if let Some(query) = &self.query {
+            tokens.push(query.clone());
+        }
+
+        write!(f, "{}", tokens.join(" "))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SavedFilter {
+    pub name: String,
+    pub filter: Filter,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Notification {
+    pub id: u32,
+    pub recipient: String,
+    pub item: ItemType,
+    pub message: String,
+    pub read: bool,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct DB {
     pub last_item: ItemType,
     pub epics: HashMap<u32, Epic>,
     pub stories: HashMap<u32, Story>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<SavedFilter>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notifications: Vec<Notification>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sprints: HashMap<u32, Sprint>,
+    /// How the epics that don't keep their stories in the order they were
+    /// added sort them, by epic ID.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub story_order: HashMap<u32, SortBy>,
+    /// The lines of the scratchpad, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Goes up on every write, so readers can tell the board changed without
+    /// comparing it and a board server can turn down a write made from an
+    /// older read.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub revision: u64,
+}
+
+fn is_zero(revision: &u64) -> bool {
+    *revision == 0
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EpicBundle {
+    pub epic: Epic,
+    pub stories: Vec<Story>,
 }
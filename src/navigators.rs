@@ -1,371 +1,3175 @@
 use anyhow::{anyhow, Context, Ok, Result};
-use std::rc::Rc;
+use chrono::{DateTime, NaiveDate, Utc};
+use itertools::Itertools;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
+use crate::archive::{Archive, MemoryArchive};
+use crate::capture::parse_capture;
+use crate::config::Config;
 use crate::db::JiraDataBase;
-use crate::model::{Action, ItemId};
-use crate::ui::{EpicDetail, HomePage, Page, Prompts, StoryDetail};
+use crate::events::ChangeEvent;
+#[cfg(feature = "terminal")]
+use crate::io_utils::copy_to_clipboard;
+use crate::keys::{epic_key, set_key_format, sprint_key, story_key};
+use crate::model::{Action, Filter, ItemDetail, ItemId, ItemStatus, ItemType, Story, DB};
+use crate::reports::sprint_report;
+use crate::share::{epic_summary, story_summary};
+use crate::stats::{assignees, label_counts, sprint_loads, summary, wip_violations};
+#[cfg(feature = "terminal")]
+use crate::ui::TerminalPrompts;
+use crate::ui::{
+    ArchivedSprint, Board, Dashboard, DueReminders, EpicDetail, FilterKey, HomeLayout, HomePage,
+    KeyCommand, KeyHints, Labels, MarkdownSnippet, Notes, Notifications, Page, PromptProvider,
+    QuickFilter, SearchResults, Snapshots, SprintArchive, SprintDetail, Sprints, Stale, Standup,
+    StoryDetail, ThisWeek, Tree, Workload,
+};
+
+type Clipboard = Box<dyn Fn(&str) -> Result<()>>;
+
+/// How long a delete waits before it's written, while `z` can call it off.
+const UNDO_WINDOW: Duration = Duration::from_secs(10);
+
+/// A delete held back for `UNDO_WINDOW`, see `Navigator::hold_delete`.
+struct PendingDelete {
+    item: ItemType,
+    /// The epic of a deleted story.
+    epic_id: Option<ItemId>,
+    until: DateTime<Utc>,
+}
 
 pub struct Navigator {
     pages: Vec<Box<dyn Page>>,
-    prompts: Prompts,
+    prompts: Box<dyn PromptProvider>,
+    clipboard: Clipboard,
+    notice: Option<String>,
+    user: String,
+    config: Config,
+    /// The item on the current page, and the board revision and item version
+    /// last checked by `check_for_updates`.
+    seen: RefCell<Option<(ItemType, u64, u32)>>,
+    /// The row the keymap selected on the current page, by position.
+    selected: Option<usize>,
+    /// The items deleted since the last action, as the database reported them.
+    deleted: Rc<RefCell<Vec<ItemType>>>,
+    /// The last delete, written when the next action runs or `UNDO_WINDOW`
+    /// passes, whichever comes first.
+    pending_delete: Option<PendingDelete>,
+    /// Where snapshots and sprint reports go.
+    archive: Rc<dyn Archive>,
     database: Rc<JiraDataBase>,
 }
 
 impl Navigator {
+    /// A navigator that asks on the terminal and copies to the system
+    /// clipboard.
+    #[cfg(feature = "terminal")]
     pub fn new(database: Rc<JiraDataBase>) -> Self {
+        Self::from_prompts(database, TerminalPrompts).with_clipboard(copy_to_clipboard)
+    }
+
+    /// A navigator that asks through `prompts`, for frontends other than the
+    /// terminal. Copying fails until `with_clipboard` gives it a clipboard, and
+    /// snapshots and sprint reports stay in memory until `with_archive` gives
+    /// them a place.
+    pub fn from_prompts(
+        database: Rc<JiraDataBase>,
+        prompts: impl PromptProvider + 'static,
+    ) -> Self {
+        let deleted = Rc::new(RefCell::new(Vec::new()));
+        let inbox = Rc::clone(&deleted);
+
+        database.subscribe(move |event| {
+            if let ChangeEvent::ItemDeleted { item } = event {
+                inbox.borrow_mut().push(item.clone());
+            }
+        });
+
         Self {
-            pages: vec![Box::new(HomePage {
-                db: Rc::clone(&database),
-            })],
-            prompts: Prompts::new(),
+            pages: vec![Box::new(HomePage::new(
+                Rc::clone(&database),
+                HomeLayout::Flat,
+            ))],
+            prompts: Box::new(prompts),
+            clipboard: Box::new(|_| Err(anyhow!("There is no clipboard to copy to"))),
+            notice: None,
+            user: database.user().to_owned(),
+            config: Config::default(),
+            seen: RefCell::new(None),
+            selected: None,
+            deleted,
+            pending_delete: None,
+            archive: Rc::new(MemoryArchive::default()),
             database,
         }
     }
 
-    pub fn get_current_page(&self) -> Option<&Box<dyn Page>> {
-        return self.pages.last();
+    pub fn with_archive(mut self, archive: impl Archive + 'static) -> Self {
+        self.archive = Rc::new(archive);
+        self
+    }
+
+    pub fn with_config(mut self, config: Config) -> Self {
+        if let Some(home) = self.pages.first_mut() {
+            *home = Box::new(HomePage::new(Rc::clone(&self.database), config.home_layout));
+        }
+
+        set_key_format(config.keys.clone());
+        self.config = config;
+        self
+    }
+
+    /// Applies a config that changed while the board was open. The home page
+    /// takes a new layout right away, the other pages when they open next.
+    pub fn reload_config(&mut self, config: Config) {
+        if config.home_layout != self.config.home_layout {
+            if let Some(home) = self.pages.first_mut() {
+                *home = Box::new(HomePage::new(Rc::clone(&self.database), config.home_layout));
+            }
+        }
+
+        let restart = config.startup_changes(&self.config);
+
+        set_key_format(config.keys.clone());
+
+        self.notice = Some(match restart.is_empty() {
+            true => "Reloaded the config".to_owned(),
+            false => format!(
+                "Reloaded the config, restart to apply {}",
+                restart.join(", ")
+            ),
+        });
+        self.config = config;
+    }
+
+    /// Asks the user through `prompts` instead of the terminal.
+    pub fn with_prompts(mut self, prompts: impl PromptProvider + 'static) -> Self {
+        self.prompts = Box::new(prompts);
+        self
+    }
+
+    /// Copies items with `clipboard`.
+    pub fn with_clipboard(mut self, clipboard: impl Fn(&str) -> Result<()> + 'static) -> Self {
+        self.clipboard = Box::new(clipboard);
+        self
+    }
+
+    pub fn get_current_page(&self) -> Option<&dyn Page> {
+        self.pages.last().map(|page| page.as_ref())
+    }
+
+    /// Whether the page in view is reading keys into its quick filter, which
+    /// then go to `handle_filter_key` instead of `handle_input`.
+    pub fn is_filtering(&self) -> bool {
+        self.get_current_page()
+            .and_then(|page| page.quick_filter())
+            .is_some_and(QuickFilter::is_typing)
+    }
+
+    pub fn handle_filter_key(&mut self, key: FilterKey) {
+        if let Some(filter) = self.get_current_page().and_then(|page| page.quick_filter()) {
+            filter.press(key);
+        }
+    }
+
+    /// Leaves a one line overview of the board to show above the first page.
+    pub fn greet(&mut self, now: DateTime<Utc>) -> Result<()> {
+        let db = self.database.read_db()?;
+
+        self.notice = Some(format!(
+            "Board: {}",
+            summary(&db, &self.config.calendar(), now)
+        ));
+        Ok(())
+    }
+
+    /// Shows the items that are due or overdue on top of the home page, if there
+    /// are any.
+    pub fn remind_due_items(&mut self, today: NaiveDate) -> Result<()> {
+        let reminders = DueReminders {
+            today,
+            db: Rc::clone(&self.database),
+            calendar: self.config.calendar(),
+        };
+
+        if reminders.has_items()? {
+            self.pages.push(Box::new(reminders));
+        }
+
+        Ok(())
+    }
+
+    /// Opens the view called `name` on top of the home page: a saved filter's
+    /// results, or one of the dashboard, tree, notes, sprints, standup and
+    /// notifications pages.
+    pub fn open_view(&mut self, name: &str) -> Result<()> {
+        let filter = self
+            .database
+            .read_db()?
+            .filters
+            .into_iter()
+            .find(|saved| saved.name == name);
+
+        if let Some(saved) = filter {
+            return self.handle_action(Action::Search {
+                filter: saved.filter,
+            });
+        }
+
+        let action = match name {
+            "dashboard" => Action::NavigateToDashboard,
+            "workload" => Action::NavigateToWorkload,
+            "labels" => Action::NavigateToLabels,
+            "snapshots" => Action::NavigateToSnapshots,
+            "week" => Action::NavigateToThisWeek,
+            "stale" => Action::NavigateToStale,
+            "board" => Action::NavigateToBoard,
+            "mine" => Action::Search {
+                filter: Filter::assigned_to(&self.user),
+            },
+            "tree" => Action::NavigateToTree,
+            "notes" => Action::NavigateToNotes,
+            "sprints" => Action::NavigateToSprints,
+            "archive" => Action::NavigateToSprintArchive,
+            "standup" => Action::NavigateToStandup,
+            "notifications" => Action::NavigateToNotifications,
+            _ => {
+                return Err(anyhow!(
+                    "There is no saved filter or view called \"{name}\""
+                ))
+            }
+        };
+
+        self.handle_action(action)
+    }
+
+    /// Opens the `nth` most recently changed epic or story, counting from 1.
+    /// Stories outside of any epic have no page, so they aren't counted.
+    pub fn open_recent(&mut self, nth: usize) -> Result<()> {
+        let db = self.database.read_db()?;
+        let epics = db
+            .epics
+            .values()
+            .map(|epic| (ItemType::Epic { id: epic.detail.id }, &epic.detail));
+        let stories = db.stories.values().map(|story| {
+            (
+                ItemType::Story {
+                    id: story.detail.id,
+                },
+                &story.detail,
+            )
+        });
+        let page = epics
+            .chain(stories)
+            .sorted_by(|(a, a_detail), (b, b_detail)| {
+                b_detail
+                    .updated_at
+                    .cmp(&a_detail.updated_at)
+                    .then_with(|| a.key().cmp(&b.key()))
+            })
+            .filter_map(|(item, _)| self.item_page(&db, &item))
+            .nth(nth.saturating_sub(1))
+            .ok_or_else(|| anyhow!("There are fewer than {nth} items to open"))?;
+
+        self.pages.push(page);
+        Ok(())
+    }
+
+    /// The detail page of `item`, if it is on the board and has one.
+    fn item_page(&self, db: &DB, item: &ItemType) -> Option<Box<dyn Page>> {
+        match item {
+            ItemType::Epic { id } if db.epics.contains_key(&id.0) => Some(Box::new(EpicDetail {
+                epic_id: id.0,
+                db: Rc::clone(&self.database),
+                calendar: self.config.calendar(),
+                quick_filter: QuickFilter::default(),
+            })),
+            ItemType::Story { id } => {
+                let epic = db.epics.values().find(|epic| epic.stories.contains(id))?;
+
+                Some(Box::new(StoryDetail {
+                    epic_id: epic.detail.id.0,
+                    story_id: id.0,
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks the WIP limits before `items` move to in progress. Returns the
+    /// limits that would be exceeded, or `None` when they are enforced and the
+    /// user chose not to go over them.
+    fn check_wip_limits(&self, items: &[ItemType]) -> Result<Option<Vec<String>>> {
+        let stories = items
+            .iter()
+            .filter_map(|item| match item {
+                ItemType::Story { id } => Some(*id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let violations = wip_violations(&self.database.read_db()?, &self.config.wip, &stories);
+
+        if self.config.wip.enforce
+            && !violations.is_empty()
+            && !self.prompts.wip_override(&violations)
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(violations))
+    }
+
+    /// Describes how far the assignee of `story_id` is over their capacity in
+    /// `sprint_id`, if they are.
+    fn sprint_overage(&self, sprint_id: u32, story_id: u32) -> Result<Option<String>> {
+        let db = self.database.read_db()?;
+        let (Some(sprint), Some(assignee)) = (
+            db.sprints.get(&sprint_id),
+            db.stories
+                .get(&story_id)
+                .and_then(|story| story.detail.assignee.as_deref()),
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(sprint_loads(&db, sprint)
+            .into_iter()
+            .find(|load| load.assignee == assignee)
+            .and_then(|load| {
+                let capacity = self.config.capacity.of(assignee)?;
+                (load.points > capacity).then(|| {
+                    format!(
+                        "{assignee} is now over capacity: {} of {capacity} points",
+                        load.points
+                    )
+                })
+            }))
+    }
+
+    /// Returns the message left by the last handled action, if any, so it can be
+    /// shown once above the next rendered page.
+    pub fn take_notice(&mut self) -> Option<String> {
+        self.notice.take()
+    }
+
+    /// Handles a line of input on the current page. Several commands can be
+    /// chained with `;`, e.g. `3; c` to open epic 3 and create a story in it,
+    /// each one going to the page the one before left open. The chain stops at
+    /// the first command that fails or that the page doesn't know. Right after
+    /// a delete, `z` takes it back, and `:w` saves a board that only saves on
+    /// request.
+    pub fn handle_input(&mut self, line: &str) -> Result<()> {
+        if self.is_filtering() {
+            self.handle_filter_key(FilterKey::Line(line.to_owned()));
+            return Ok(());
+        }
+
+        if line.trim() == "z" {
+            return self.take_back();
+        }
+
+        self.finish_delete()?;
+
+        if line.trim() == ":w" {
+            return self.save();
+        }
+
+        let commands = line.split(';').map(str::trim).collect::<Vec<_>>();
+        let chained = commands.len() > 1;
+
+        for command in commands {
+            if chained && command.is_empty() {
+                continue;
+            }
+
+            let Some(page) = self.get_current_page() else {
+                return Ok(());
+            };
+            let action = match self.config.keymap.translate(command) {
+                KeyCommand::Page(input) if input == "?" => Some(Action::ShowKeyHints),
+                KeyCommand::Page(input) => page.handle_input(&input)?,
+                step @ (KeyCommand::Next | KeyCommand::Previous) => {
+                    self.move_selection(step == KeyCommand::Next)?;
+                    continue;
+                }
+                KeyCommand::Open => match self
+                    .selected
+                    .and_then(|row| page.rows().ok()?.into_iter().nth(row))
+                {
+                    Some((handle, _)) => page.handle_input(&handle)?,
+                    None => page.handle_input("")?,
+                },
+                KeyCommand::Back if self.pages.len() > 1 => Some(Action::NavigateToPreviousPage),
+                KeyCommand::Back | KeyCommand::Quit => Some(Action::Exit),
+            };
+
+            match action {
+                Some(action) => self.handle_action(action)?,
+                None if chained => {
+                    return Err(anyhow!(
+                        "Stopped at \"{command}\", this page has no such command"
+                    ))
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes out the changes kept in memory when the board only saves on
+    /// request, see `Config::explicit_save`.
+    fn save(&mut self) -> Result<()> {
+        if !self.database.has_unsaved_changes() {
+            self.notice = Some("Nothing to save".to_owned());
+            return Ok(());
+        }
+
+        self.database
+            .persist()
+            .with_context(|| anyhow!("Failed to save the board"))?;
+        self.notice = Some("Saved the board".to_owned());
+        Ok(())
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.database.has_unsaved_changes()
+    }
+
+    /// Calls off the delete held back by `hold_delete`, if its window is still
+    /// open.
+    fn take_back(&mut self) -> Result<()> {
+        let Some(pending) = self
+            .pending_delete
+            .take_if(|pending| Utc::now() < pending.until)
+        else {
+            self.finish_delete()?;
+            self.notice = Some("Nothing to undo".to_owned());
+            return Ok(());
+        };
+
+        self.notice = Some(format!("Kept {}", pending.item.key()));
+        Ok(())
+    }
+
+    /// Moves the row selection of the keymap one row down, or up, and says
+    /// which row it landed on.
+    fn move_selection(&mut self, down: bool) -> Result<()> {
+        let rows = self
+            .get_current_page()
+            .map_or(Ok(Vec::new()), |page| page.rows())?;
+
+        if rows.is_empty() {
+            self.selected = None;
+            return Ok(());
+        }
+
+        let row = match (self.selected, down) {
+            (None, true) => 0,
+            (None, false) => rows.len() - 1,
+            (Some(row), true) => (row + 1).min(rows.len() - 1),
+            (Some(row), false) => row.saturating_sub(1),
+        };
+        let (handle, name) = &rows[row];
+
+        self.selected = Some(row);
+        self.notice = Some(format!("> {handle} {name}"));
+        Ok(())
     }
 
     pub fn handle_action(&mut self, action: Action) -> Result<()> {
+        tracing::info!(?action, "Handling");
+        self.selected = None;
+        self.finish_delete()?;
+
+        let result = self.apply(action);
+
+        self.close_deleted_pages();
+        result
+    }
+
+    /// Holds back deleting `item` until the next action or `UNDO_WINDOW`
+    /// passes, so `z` can call it off before anything is written. Its pages,
+    /// and those of an epic's stories, close right away.
+    fn hold_delete(&mut self, item: ItemType, epic_id: Option<ItemId>) -> Result<()> {
+        let mut closing = vec![item.clone()];
+
+        if let ItemType::Epic { id } = &item {
+            let stories = self.database.get_epic(*id)?.stories;
+
+            closing.extend(stories.into_iter().map(|id| ItemType::Story { id }));
+        }
+
+        self.pages
+            .retain(|page| page_item(page.as_ref()).is_none_or(|item| !closing.contains(&item)));
+        self.notice = Some(format!(
+            "Deleting {} in {}s, z to keep it",
+            item.key(),
+            UNDO_WINDOW.as_secs()
+        ));
+        self.pending_delete = Some(PendingDelete {
+            item,
+            epic_id,
+            until: Utc::now() + UNDO_WINDOW,
+        });
+        Ok(())
+    }
+
+    /// Writes the delete held back by `hold_delete`, if there is one.
+    fn finish_delete(&mut self) -> Result<()> {
+        let Some(pending) = self.pending_delete.take() else {
+            return Ok(());
+        };
+
+        match pending.item {
+            ItemType::Epic { id } => self
+                .database
+                .delete_epic(id)
+                .with_context(|| anyhow!("Failed to delete epic"))?,
+            ItemType::Story { id } => self
+                .database
+                .delete_story(id, pending.epic_id)
+                .with_context(|| anyhow!("Failed to delete story"))?,
+            ItemType::None => {}
+        }
+
+        self.close_deleted_pages();
+        Ok(())
+    }
+
+    /// Writes the held back delete once its `UNDO_WINDOW` has passed, for
+    /// frontends to call while they wait for input. Returns whether it did, so
+    /// the page can be drawn again without the item.
+    pub fn expire_delete(&mut self) -> Result<bool> {
+        if self
+            .pending_delete
+            .as_ref()
+            .is_none_or(|pending| Utc::now() < pending.until)
+        {
+            return Ok(false);
+        }
+
+        self.finish_delete()?;
+        Ok(true)
+    }
+
+    /// Closes the pages of the epics and stories that were deleted since the
+    /// last action, along with their markdown.
+    fn close_deleted_pages(&mut self) {
+        let deleted = self.deleted.take();
+
+        if deleted.is_empty() {
+            return;
+        }
+
+        self.pages
+            .retain(|page| page_item(page.as_ref()).is_none_or(|item| !deleted.contains(&item)));
+    }
+
+    /// Checks whether someone else changed the item on the current page since
+    /// the last check, returning a banner to show above it when they did.
+    /// Changes made from this session aren't reported.
+    pub fn check_for_updates(&self) -> Result<Option<String>> {
+        let Some(item) = self.get_current_page().and_then(page_item) else {
+            self.seen.replace(None);
+            return Ok(None);
+        };
+        let revision = self.database.revision()?;
+
+        // Nothing was written since the last check, so the item is as it was
+        // and the board needn't be read.
+        let unchanged = matches!(
+            &*self.seen.borrow(),
+            Some((seen, seen_revision, _)) if *seen == item && *seen_revision == revision
+        );
+        if unchanged {
+            return Ok(None);
+        }
+
+        let detail = match &item {
+            ItemType::Epic { id } => self.database.get_epic(*id).ok().map(|epic| epic.detail),
+            ItemType::Story { id } => self.database.get_story(*id).ok().map(|story| story.detail),
+            _ => None,
+        };
+        let Some(detail) = detail else {
+            return Ok(None);
+        };
+        let previous = self
+            .seen
+            .replace(Some((item.clone(), revision, detail.version)));
+        let by = detail.updated_by.as_deref().unwrap_or("someone");
+
+        match previous {
+            Some((seen, _, version))
+                if seen == item && version != detail.version && by != self.user =>
+            {
+                Ok(Some(format!("{} was updated by {by}", item.key())))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn apply(&mut self, action: Action) -> Result<()> {
         match action {
             Action::CreateEpic => {
-                let epic = (self.prompts.create_epic)();
+                let epic = self.prompts.create_epic();
 
                 self.database
                     .create_epic(epic.detail.name, epic.detail.description)
                     .with_context(|| anyhow!("Failed to create Epic"))?;
             }
             Action::DeleteEpic { epic_id } => {
-                if (self.prompts.delete_epic)() {
+                let epic = self.database.get_epic(ItemId(epic_id))?;
+
+                if !self.config.confirm.delete_epic || self.prompts.delete_epic(&epic) {
+                    self.hold_delete(
+                        ItemType::Epic {
+                            id: ItemId(epic_id),
+                        },
+                        None,
+                    )?;
+                }
+            }
+            Action::MergeEpic { epic_id } => {
+                if let Some(target_id) = self.prompts.merge_epic() {
+                    let source = self.database.get_epic(ItemId(epic_id))?;
+                    let target = self.database.get_epic(ItemId(target_id))?;
+
+                    if self.config.confirm.merge_epic
+                        && !self.prompts.confirm_merge(&source, &target)
+                    {
+                        return Ok(());
+                    }
+
                     self.database
-                        .delete_epic(ItemId(epic_id))
-                        .with_context(|| anyhow!("Failed to delete epic"))?;
+                        .merge_epics(ItemId(epic_id), ItemId(target_id))
+                        .with_context(|| anyhow!("Failed to merge epic"))?;
+
+                    self.pages.push(Box::new(EpicDetail {
+                        epic_id: target_id,
+                        db: Rc::clone(&self.database),
+                        calendar: self.config.calendar(),
+                        quick_filter: QuickFilter::default(),
+                    }));
+                    self.notice = Some(format!(
+                        "Merged {} into {}",
+                        epic_key(epic_id),
+                        epic_key(target_id)
+                    ));
+                }
+            }
+            Action::DemoteEpic { epic_id } => {
+                let epic = self.database.get_epic(ItemId(epic_id))?;
+
+                if let Some(target_id) = self.prompts.demote_epic(&epic) {
+                    let story_id = self
+                        .database
+                        .demote_epic(ItemId(epic_id), ItemId(target_id))
+                        .with_context(|| anyhow!("Failed to demote epic"))?;
+
+                    self.pages.push(Box::new(EpicDetail {
+                        epic_id: target_id,
+                        db: Rc::clone(&self.database),
+                        calendar: self.config.calendar(),
+                        quick_filter: QuickFilter::default(),
+                    }));
+                    self.notice = Some(format!(
+                        "Turned {} into {} of {}",
+                        epic_key(epic_id),
+                        story_key(story_id.0),
+                        epic_key(target_id)
+                    ));
+                }
+            }
+            Action::Exit => {
+                if self.database.has_unsaved_changes() {
+                    match self.prompts.save_on_exit() {
+                        Some(true) => self
+                            .database
+                            .persist()
+                            .with_context(|| anyhow!("Failed to save the board"))?,
+                        Some(false) => {}
+                        None => return Ok(()),
+                    }
                 }
 
-                if !self.pages.is_empty() {
-                    self.pages.pop();
+                self.pages.clear()
+            }
+            Action::EditDefaultLabels { epic_id } => {
+                let epic = self.database.get_epic(ItemId(epic_id))?;
+
+                if let Some(labels) = self.prompts.default_labels(&epic) {
+                    self.database
+                        .set_default_labels(ItemId(epic_id), labels)
+                        .with_context(|| anyhow!("Failed to set the default labels"))?;
                 }
             }
-            Action::Exit => self.pages.clear(),
             Action::CreateStory { epic_id } => {
-                let story = (self.prompts.create_story)();
+                let defaults = self.database.get_epic(ItemId(epic_id))?.default_labels;
+                let mut story = self.prompts.create_story();
+
+                if !defaults.is_empty() {
+                    story.detail.labels = self.prompts.story_labels(&defaults);
+                }
 
+                story.detail.status = self.database.default_status().clone();
                 self.database
-                    .create_story(
-                        story.detail.name,
-                        story.detail.description,
-                        Some(ItemId(epic_id)),
-                    )
+                    .add_story(story, Some(ItemId(epic_id)))
                     .with_context(|| anyhow!("Failed to create story"))?;
-            },
+            }
+            Action::CreateStoryFromTemplate { epic_id } => {
+                let names = self.config.templates.keys().cloned().collect::<Vec<_>>();
+
+                if names.is_empty() {
+                    self.notice =
+                        Some("No templates yet, add them under templates in the config".to_owned());
+                } else if let Some(index) = self.prompts.pick_template(&names) {
+                    let template = &self.config.templates[&names[index]];
+                    let values = template
+                        .placeholders()
+                        .into_iter()
+                        .map(|placeholder| {
+                            let value = self.prompts.template_value(&placeholder);
+                            (placeholder, value)
+                        })
+                        .collect();
+                    let mut story = template.fill(&values);
+
+                    // The epic's default labels come first, like on quick add.
+                    story.detail.labels = self
+                        .database
+                        .get_epic(ItemId(epic_id))?
+                        .default_labels
+                        .into_iter()
+                        .chain(story.detail.labels)
+                        .unique()
+                        .collect();
+                    story.detail.status = self.database.default_status().clone();
+
+                    let story_id = self
+                        .database
+                        .add_story(story, Some(ItemId(epic_id)))
+                        .with_context(|| anyhow!("Failed to create story"))?;
+
+                    self.notice = Some(format!(
+                        "Created {} from {}",
+                        story_key(story_id.0),
+                        names[index]
+                    ));
+                }
+            }
+            Action::QuickAddStory { epic_id, input } => {
+                let calendar = self.config.calendar();
+                let mut capture = parse_capture(&input, calendar.today(), calendar.format)?;
+                let completed = {
+                    let db = self.database.read_db()?;
+                    let labels = label_counts(&db)
+                        .into_iter()
+                        .map(|(label, _)| label)
+                        .collect::<Vec<_>>();
+                    capture.complete(&labels, &assignees(&db))
+                };
+                let epic_id = capture
+                    .epic_id
+                    .or(epic_id)
+                    .ok_or_else(|| anyhow!("Add ^epic:<id> to pick the epic of the new story"))?;
+                // Quick add asks nothing, so the labels typed join the epic's.
+                let labels = self
+                    .database
+                    .get_epic(ItemId(epic_id))?
+                    .default_labels
+                    .into_iter()
+                    .chain(capture.labels)
+                    .unique()
+                    .collect();
+                let story = Story::new(ItemDetail {
+                    name: capture.name,
+                    priority: capture.priority,
+                    assignee: capture.assignee,
+                    labels,
+                    due: capture.due,
+                    status: self.database.default_status().clone(),
+                    ..Default::default()
+                });
+                let story_id = self
+                    .database
+                    .add_story(story, Some(ItemId(epic_id)))
+                    .with_context(|| anyhow!("Failed to create story"))?;
+
+                let created = format!("Created {}", story_key(story_id.0));
+                self.notice = Some(match completed.is_empty() {
+                    true => created,
+                    false => format!("{created}, completing {}", completed.join(", ")),
+                });
+            }
             Action::DeleteStory { epic_id, story_id } => {
-              if (self.prompts.delete_story)() {
-                self
-                  .database
-                  .delete_story(ItemId(story_id), Some(ItemId(epic_id)))
-                  .with_context(|| anyhow!("Failed to delete story"))?;
-              }
-
-              if !self.pages.is_empty() {
-                self.pages.pop();
-              }
-            },
+                let story = self.database.get_story(ItemId(story_id))?;
+
+                if !self.config.confirm.delete_story || self.prompts.delete_story(&story) {
+                    self.hold_delete(
+                        ItemType::Story {
+                            id: ItemId(story_id),
+                        },
+                        Some(ItemId(epic_id)),
+                    )?;
+                }
+            }
+            Action::CloneStory { epic_id, story_id } => {
+                let clone_id = self
+                    .database
+                    .clone_story(ItemId(story_id), Some(ItemId(epic_id)))
+                    .with_context(|| anyhow!("Failed to clone story"))?;
+
+                self.notice = Some(format!(
+                    "Cloned {} as {}",
+                    story_key(story_id),
+                    story_key(clone_id.0)
+                ));
+            }
+            Action::SplitStory { epic_id, story_id } => {
+                let story = self.database.get_story(ItemId(story_id))?;
+
+                if let Some(split) = self.prompts.split_story(&story) {
+                    let new_id = self
+                        .database
+                        .split_story(ItemId(story_id), Some(ItemId(epic_id)), split)
+                        .with_context(|| anyhow!("Failed to split story"))?;
+
+                    self.notice = Some(format!(
+                        "Split {} into {}",
+                        story_key(story_id),
+                        story_key(new_id.0)
+                    ));
+                }
+            }
+            Action::PromoteStory { story_id } => {
+                let story = self.database.get_story(ItemId(story_id))?;
+
+                if let Some(move_checklist) = self.prompts.promote_story(&story) {
+                    let epic_id = self
+                        .database
+                        .promote_story(ItemId(story_id), move_checklist)
+                        .with_context(|| anyhow!("Failed to promote story"))?;
+
+                    self.pages.push(Box::new(EpicDetail {
+                        epic_id: epic_id.0,
+                        db: Rc::clone(&self.database),
+                        calendar: self.config.calendar(),
+                        quick_filter: QuickFilter::default(),
+                    }));
+                    // Links only join stories, so the ones of the promoted
+                    // story can't follow it.
+                    let dropped = match story.links.len() {
+                        0 => String::new(),
+                        1 => ", dropping its link".to_owned(),
+                        count => format!(", dropping its {count} links"),
+                    };
+                    self.notice = Some(format!(
+                        "Promoted {} to {}{dropped}",
+                        story_key(story_id),
+                        epic_key(epic_id.0)
+                    ));
+                }
+            }
+            Action::AddChecklistItem { story_id } => {
+                let text = self.prompts.add_checklist_item();
+
+                if !text.is_empty() {
+                    self.database
+                        .add_checklist_item(ItemId(story_id), text)
+                        .with_context(|| anyhow!("Failed to add checklist item"))?;
+                }
+            }
+            Action::AddBlocker {
+                story_id,
+                blocker_id,
+            } => {
+                self.database
+                    .add_blocker(ItemId(story_id), ItemId(blocker_id))
+                    .with_context(|| anyhow!("Failed to add blocker"))?;
+
+                self.notice = Some(format!(
+                    "{} is blocked by {}",
+                    story_key(story_id),
+                    story_key(blocker_id)
+                ));
+            }
+            Action::ToggleChecklistItem { story_id, index } => {
+                self.database
+                    .toggle_checklist_item(ItemId(story_id), index)
+                    .with_context(|| anyhow!("Failed to update checklist item"))?;
+            }
             Action::NavigateToEpicDetail { epic_id } => {
-              self.pages.push(Box::new(EpicDetail { epic_id, db: Rc::clone(&self.database) }));
-            },
+                self.pages.push(Box::new(EpicDetail {
+                    epic_id,
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                    quick_filter: QuickFilter::default(),
+                }));
+            }
             Action::NavigateToPreviousPage => {
-              if !self.pages.is_empty() {
-                self.pages.pop();
-              }
-            },
+                if !self.pages.is_empty() {
+                    self.pages.pop();
+                }
+            }
+            Action::ShowKeyHints => {
+                if let Some(page) = self.get_current_page() {
+                    let hints = page
+                        .key_hints()
+                        .into_iter()
+                        .chain(self.config.keymap.key_hints())
+                        .collect();
+
+                    self.pages.push(Box::new(KeyHints { hints }));
+                }
+            }
             Action::NavigateToStoryDetail { epic_id, story_id } => {
-              self.pages.push(Box::new(StoryDetail { epic_id, story_id, db: Rc::clone(&self.database) }))
-            },
+                self.pages.push(Box::new(StoryDetail {
+                    epic_id,
+                    story_id,
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                }))
+            }
             Action::UpdateEpicStatus { epic_id } => {
-              if let Some(status) = (self.prompts.update_status)() {
-                self
-                  .database
-                  .update_epic_status(ItemId(epic_id), status)
-                  .with_context(|| anyhow!("Failed to update epic status"))?;
-              }
-            },
+                let loaded = self.database.item_versions(&[ItemType::Epic {
+                    id: ItemId(epic_id),
+                }])?;
+
+                if let Some(status) = self.prompts.update_status() {
+                    self.database.check_versions(&loaded)?;
+                    self.database
+                        .update_epic_status(ItemId(epic_id), status)
+                        .with_context(|| anyhow!("Failed to update epic status"))?;
+                }
+            }
             Action::UpdateStoryStatus { story_id } => {
-              if let Some(status) = (self.prompts.update_status)() {
-                self
-                  .database
-                  .update_story_status(ItemId(story_id), status)
-                  .with_context(|| anyhow!("Failed to update story status"))?;
-              }
+                let loaded = self.database.item_versions(&[ItemType::Story {
+                    id: ItemId(story_id),
+                }])?;
+
+                if let Some(status) = self.prompts.update_status() {
+                    let violations = match status {
+                        ItemStatus::InProgress => {
+                            match self.check_wip_limits(&[ItemType::Story {
+                                id: ItemId(story_id),
+                            }])? {
+                                Some(violations) => violations,
+                                None => return Ok(()),
+                            }
+                        }
+                        _ => Vec::new(),
+                    };
+
+                    self.database.check_versions(&loaded)?;
+                    self.database
+                        .update_story_status(ItemId(story_id), status)
+                        .with_context(|| anyhow!("Failed to update story status"))?;
+
+                    if !violations.is_empty() {
+                        self.notice =
+                            Some(format!("WIP limit exceeded: {}", violations.join(", ")));
+                    }
+                }
             }
-        };
+            Action::BulkUpdateStatus { items } => {
+                let loaded = self.database.item_versions(&items)?;
+
+                if let Some(status) = self.prompts.update_status() {
+                    if self.config.confirm.bulk_update
+                        && !self.prompts.confirm_bulk_update(items.len(), &status)
+                    {
+                        return Ok(());
+                    }
+
+                    let violations = match status {
+                        ItemStatus::InProgress => match self.check_wip_limits(&items)? {
+                            Some(violations) => violations,
+                            None => return Ok(()),
+                        },
+                        _ => Vec::new(),
+                    };
+
+                    self.database.check_versions(&loaded)?;
+                    self.database
+                        .update_statuses(&items, status.clone())
+                        .with_context(|| anyhow!("Failed to update statuses"))?;
+
+                    let mut notice = format!("Updated {} items to {status}", items.len());
+                    if !violations.is_empty() {
+                        notice
+                            .push_str(&format!(" (WIP limit exceeded: {})", violations.join(", ")));
+                    }
+                    self.notice = Some(notice);
+                }
+            }
+            Action::BulkEdit { items, edits } => {
+                let loaded = self.database.item_versions(&items)?;
 
-        return Ok(());
-    }
+                if self.config.confirm.bulk_update
+                    && !self.prompts.confirm_bulk_edit(items.len(), &edits)
+                {
+                    return Ok(());
+                }
 
-    // Private functions used for testing
-    fn get_page_count(&self) -> usize {
-        self.pages.len()
-    }
+                self.database.check_versions(&loaded)?;
+                self.database
+                    .bulk_edit(&items, &edits)
+                    .with_context(|| anyhow!("Failed to edit the items"))?;
+
+                self.notice = Some(format!(
+                    "Updated {} items: {}",
+                    items.len(),
+                    edits.iter().join(", ")
+                ));
+            }
+            Action::ToggleWatch { item } => {
+                let watching = self
+                    .database
+                    .toggle_watch(&item, &self.user)
+                    .with_context(|| anyhow!("Failed to update watchers"))?;
+                let key = item.key();
+
+                self.notice = Some(match watching {
+                    true => format!("Watching {key}"),
+                    false => format!("Stopped watching {key}"),
+                });
+            }
+            Action::NavigateToNotifications => {
+                self.database
+                    .add_due_notifications(&self.user, self.config.calendar().today())
+                    .with_context(|| anyhow!("Failed to check due dates"))?;
+                self.pages.push(Box::new(Notifications {
+                    user: self.user.clone(),
+                    db: Rc::clone(&self.database),
+                }));
+            }
+            Action::NavigateToStandup => {
+                self.pages.push(Box::new(Standup {
+                    user: self.user.clone(),
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                }));
+            }
+            Action::NavigateToDashboard => {
+                self.pages.push(Box::new(Dashboard {
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                }));
+            }
+            Action::NavigateToWorkload => {
+                self.pages.push(Box::new(Workload {
+                    db: Rc::clone(&self.database),
+                    capacity: self.config.capacity.clone(),
+                }));
+            }
+            Action::NavigateToThisWeek => {
+                self.pages.push(Box::new(ThisWeek {
+                    today: self.config.calendar().today(),
+                    db: Rc::clone(&self.database),
+                    quick_filter: QuickFilter::default(),
+                }));
+            }
+            Action::NavigateToStale => {
+                self.pages.push(Box::new(Stale {
+                    db: Rc::clone(&self.database),
+                    quick_filter: QuickFilter::default(),
+                }));
+            }
+            Action::QuickFilter => {
+                if let Some(filter) = self.get_current_page().and_then(|page| page.quick_filter()) {
+                    filter.start();
+                }
+            }
+            Action::NavigateToBoard => {
+                self.pages
+                    .push(Box::new(Board::new(Rc::clone(&self.database))));
+            }
+            Action::NavigateToLabels => {
+                self.pages.push(Box::new(Labels {
+                    db: Rc::clone(&self.database),
+                }));
+            }
+            Action::NavigateToSnapshots => {
+                self.pages.push(Box::new(Snapshots {
+                    archive: Rc::clone(&self.archive),
+                    calendar: self.config.calendar(),
+                }));
+            }
+            Action::CreateSnapshot => {
+                let name = self.prompts.snapshot_name();
+
+                if !name.is_empty() {
+                    self.archive
+                        .create_snapshot(&name, &self.database.read_db()?)?;
+                    self.notice = Some(format!("Saved the board as \"{name}\""));
+                }
+            }
+            Action::RestoreSnapshot { name } => {
+                let snapshot = self.archive.read_snapshot(&name)?;
+
+                if !self.config.confirm.restore_snapshot || self.prompts.restore_snapshot(&name) {
+                    self.database
+                        .restore(snapshot)
+                        .with_context(|| anyhow!("Failed to restore snapshot"))?;
+                    self.notice = Some(format!("Restored the board to \"{name}\""));
+                }
+            }
+            Action::SortStories { epic_id, sort } => {
+                self.database
+                    .set_story_order(ItemId(epic_id), sort)
+                    .with_context(|| anyhow!("Failed to sort stories"))?;
+
+                self.notice = Some(format!(
+                    "Sorting the stories of {} by {sort}",
+                    epic_key(epic_id)
+                ));
+            }
+            Action::NavigateToNotes => {
+                self.pages.push(Box::new(Notes {
+                    db: Rc::clone(&self.database),
+                }));
+            }
+            Action::AddNote { text } => {
+                self.database
+                    .add_note(text)
+                    .with_context(|| anyhow!("Failed to add note"))?;
+            }
+            Action::DeleteNote { index } => {
+                self.database
+                    .delete_note(index)
+                    .with_context(|| anyhow!("Failed to delete note"))?;
+            }
+            Action::NoteToStory { index, capture } => {
+                let notes = self.database.read_db()?.notes;
+                let note = notes
+                    .get(index)
+                    .ok_or_else(|| anyhow!("There is no note {}", index + 1))?;
+
+                self.apply(Action::QuickAddStory {
+                    epic_id: None,
+                    input: format!("{note} {capture}"),
+                })?;
+                self.database
+                    .delete_note(index)
+                    .with_context(|| anyhow!("Failed to delete note"))?;
+            }
+            Action::NavigateToTree => {
+                self.pages
+                    .push(Box::new(Tree::new(Rc::clone(&self.database))));
+            }
+            Action::NavigateToSprints => {
+                self.pages.push(Box::new(Sprints {
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                }));
+            }
+            Action::NavigateToSprintArchive => {
+                self.pages.push(Box::new(SprintArchive {
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                }));
+            }
+            Action::NavigateToArchivedSprint { sprint_id } => {
+                self.pages.push(Box::new(ArchivedSprint {
+                    sprint_id,
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                }));
+            }
+            Action::NavigateToSprintDetail { sprint_id } => {
+                self.pages.push(Box::new(SprintDetail {
+                    sprint_id,
+                    db: Rc::clone(&self.database),
+                    calendar: self.config.calendar(),
+                    capacity: self.config.capacity.clone(),
+                    quick_filter: QuickFilter::default(),
+                }));
+            }
+            Action::CreateSprint => {
+                if let Some(sprint) = self.prompts.create_sprint(&self.config.calendar()) {
+                    let sprint_id = self
+                        .database
+                        .create_sprint(sprint.name, sprint.start, sprint.end)
+                        .with_context(|| anyhow!("Failed to create sprint"))?;
+
+                    self.notice = Some(format!("Created {}", sprint_key(sprint_id.0)));
+                }
+            }
+            Action::CloseSprint { sprint_id } => {
+                let calendar = self.config.calendar();
+                let sprint = self
+                    .database
+                    .close_sprint(ItemId(sprint_id), calendar.today())?;
+                let report = sprint_report(&self.database.read_db()?, &sprint, &calendar);
+                let place = self.archive.archive_report(&sprint.key(), &report)?;
+
+                self.notice = Some(format!("Closed {}, the report is in {place}", sprint.key()));
+            }
+            Action::AddStoryToSprint {
+                sprint_id,
+                story_id,
+            } => {
+                self.database
+                    .add_story_to_sprint(ItemId(sprint_id), ItemId(story_id))
+                    .with_context(|| anyhow!("Failed to add story to sprint"))?;
+
+                let added = format!("Added {} to {}", story_key(story_id), sprint_key(sprint_id));
+                self.notice = Some(match self.sprint_overage(sprint_id, story_id)? {
+                    Some(over) => format!("{added}, {over}"),
+                    None => added,
+                });
+            }
+            Action::SetStoryPoints { story_id, points } => {
+                self.database
+                    .set_story_points(ItemId(story_id), points)
+                    .with_context(|| anyhow!("Failed to set story points"))?;
+            }
+            Action::OpenNotification { id } => {
+                self.database
+                    .mark_notification_read(id)
+                    .with_context(|| anyhow!("Failed to open notification"))?;
+
+                let db = self.database.read_db()?;
+                let notification = db
+                    .notifications
+                    .iter()
+                    .find(|notification| notification.id == id)
+                    .ok_or_else(|| anyhow!("Notification {} was not found.", id))?;
+
+                if let Some(page) = self.item_page(&db, &notification.item) {
+                    self.pages.push(page);
+                }
+            }
+            Action::MarkNotificationsRead => {
+                self.database
+                    .mark_notifications_read(&self.user)
+                    .with_context(|| anyhow!("Failed to mark notifications as read"))?;
+            }
+            Action::CopyEpic { epic_id } => {
+                let epic = self.database.get_epic(ItemId(epic_id))?;
+
+                (self.clipboard)(&epic_summary(&epic))
+                    .with_context(|| anyhow!("Failed to copy epic to clipboard"))?;
+                self.notice = Some(format!("Copied {} to clipboard", epic.key()));
+            }
+            Action::CopyStory { story_id } => {
+                let story = self.database.get_story(ItemId(story_id))?;
+
+                (self.clipboard)(&story_summary(&story))
+                    .with_context(|| anyhow!("Failed to copy story to clipboard"))?;
+                self.notice = Some(format!("Copied {} to clipboard", story.key()));
+            }
+            Action::NavigateToEpicMarkdown { epic_id } => {
+                self.pages.push(Box::new(MarkdownSnippet {
+                    item: ItemType::Epic {
+                        id: ItemId(epic_id),
+                    },
+                    db: Rc::clone(&self.database),
+                }));
+            }
+            Action::NavigateToStoryMarkdown { story_id } => {
+                self.pages.push(Box::new(MarkdownSnippet {
+                    item: ItemType::Story {
+                        id: ItemId(story_id),
+                    },
+                    db: Rc::clone(&self.database),
+                }));
+            }
+            Action::Search { filter } => {
+                self.pages.push(Box::new(SearchResults {
+                    filter,
+                    db: Rc::clone(&self.database),
+                    quick_filter: QuickFilter::default(),
+                }));
+            }
+            Action::SaveFilter { filter } => {
+                let name = self.prompts.save_filter();
+
+                if !name.is_empty() {
+                    self.database
+                        .save_filter(name.clone(), filter)
+                        .with_context(|| anyhow!("Failed to save filter"))?;
+                    self.notice = Some(format!("Saved filter \"{name}\""));
+                }
+            }
+            Action::PickFilter => {
+                let filters = self.database.read_db()?.filters;
+
+                if filters.is_empty() {
+                    self.notice =
+                        Some("No saved filters yet, save one from the search results".to_owned());
+                } else if let Some(index) = self.prompts.pick_filter(&filters) {
+                    self.pages.push(Box::new(SearchResults {
+                        filter: filters[index].filter.clone(),
+                        db: Rc::clone(&self.database),
+                        quick_filter: QuickFilter::default(),
+                    }));
+                }
+            }
+            Action::CopyText { text } => {
+                (self.clipboard)(&text).with_context(|| anyhow!("Failed to copy to clipboard"))?;
+                self.notice = Some("Copied to clipboard".to_owned());
+            }
+        };
+
+        Ok(())
+    }
+
+    /// How many pages are open, the one in view included.
+    pub fn get_page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    // Private functions used for testing
+    #[cfg(test)]
+    fn set_clipboard(&mut self, clipboard: Clipboard) {
+        self.clipboard = clipboard;
+    }
+}
+
+/// The epic or story a page shows, if it shows one.
+fn page_item(page: &dyn Page) -> Option<ItemType> {
+    let page = page.as_any();
+
+    if let Some(page) = page.downcast_ref::<EpicDetail>() {
+        Some(ItemType::Epic {
+            id: ItemId(page.epic_id),
+        })
+    } else if let Some(page) = page.downcast_ref::<StoryDetail>() {
+        Some(ItemType::Story {
+            id: ItemId(page.story_id),
+        })
+    } else {
+        page.downcast_ref::<MarkdownSnippet>()
+            .map(|page| page.item.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archive::DirectoryArchive,
+        buffered_db::BufferedDatabase,
+        config::{Capacity, Confirmations, WipLimits},
+        db::test_utils::MockDB,
+        model::{
+            Epic, FieldEdit, Filter, ItemDetail, ItemStatus, Priority, Sprint, Story, StorySplit,
+        },
+        templates::StoryTemplate,
+        ui::{delete_epic_question, merge_epic_question, test_utils::MockPrompts, Keymap},
+    };
+
+    #[test]
+    fn should_start_on_home_page() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let nav = Navigator::from_prompts(db, MockPrompts::new());
+
+        assert_eq!(nav.get_page_count(), 1);
+
+        let current_page = nav.get_current_page().unwrap();
+        let home_page = current_page.as_any().downcast_ref::<HomePage>();
+
+        assert!(home_page.is_some());
+    }
+
+    #[test]
+    fn handle_action_should_navigate_pages() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail_page = current_page.as_any().downcast_ref::<EpicDetail>();
+        assert!(epic_detail_page.is_some());
+
+        nav.handle_action(Action::NavigateToStoryDetail {
+            epic_id: 1,
+            story_id: 2,
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 3);
+
+        let current_page = nav.get_current_page().unwrap();
+        let story_detail_page = current_page.as_any().downcast_ref::<StoryDetail>();
+        assert!(story_detail_page.is_some());
+
+        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail_page = current_page.as_any().downcast_ref::<EpicDetail>();
+        assert!(epic_detail_page.is_some());
+
+        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        let current_page = nav.get_current_page().unwrap();
+        let home_page = current_page.as_any().downcast_ref::<HomePage>();
+        assert!(home_page.is_some());
+
+        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
+        assert_eq!(nav.get_page_count(), 0);
+
+        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
+        assert_eq!(nav.get_page_count(), 0);
+    }
+
+    #[test]
+    fn handle_action_should_clear_pages_on_exit() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+            .unwrap();
+        nav.handle_action(Action::NavigateToStoryDetail {
+            epic_id: 1,
+            story_id: 2,
+        })
+        .unwrap();
+        nav.handle_action(Action::Exit).unwrap();
+
+        assert_eq!(nav.get_page_count(), 0);
+    }
+
+    #[test]
+    fn handle_action_should_handle_create_epic() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.create_epic = Box::new(|| {
+            Epic::new(
+                ItemDetail {
+                    name: "name".to_owned(),
+                    description: "description".to_owned(),
+                    id: ItemId(0),
+                    status: ItemStatus::Open,
+                    ..Default::default()
+                },
+                Vec::new(),
+            )
+        });
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::CreateEpic).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.len(), 1);
+
+        let epic = db_state.epics.into_iter().next().unwrap().1;
+        assert_eq!(epic.detail.name, "name".to_owned());
+        assert_eq!(epic.detail.description, "description".to_owned());
+    }
+
+    #[test]
+    fn handle_action_should_handle_update_epic() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.update_status = Box::new(|| Some(ItemStatus::InProgress));
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::UpdateEpicStatus { epic_id: epic_id.0 })
+            .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(
+            db_state.epics.get(&epic_id.0).unwrap().detail.status,
+            ItemStatus::InProgress
+        );
+    }
+
+    #[test]
+    fn handle_action_should_handle_delete_epic() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        db.create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.delete_epic = Box::new(|epic| {
+            assert_eq!(
+                delete_epic_question(epic),
+                "Delete EPIC-0 'Payments' and its story?"
+            );
+            true
+        });
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::DeleteEpic { epic_id: epic_id.0 })
+            .unwrap();
+        nav.finish_delete().unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.len(), 0);
+    }
+
+    #[test]
+    fn handle_action_should_close_the_pages_of_deleted_items() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let answers = Rc::new(RefCell::new(vec![true, false]));
+
+        let mut prompts = MockPrompts::new();
+        prompts.delete_epic = Box::new(move |_| answers.borrow_mut().pop().unwrap());
+
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: epic_id.0 })
+            .unwrap();
+        nav.handle_action(Action::NavigateToStoryDetail {
+            epic_id: epic_id.0,
+            story_id: story_id.0,
+        })
+        .unwrap();
+        nav.handle_action(Action::NavigateToStoryMarkdown {
+            story_id: story_id.0,
+        })
+        .unwrap();
+
+        nav.handle_action(Action::DeleteEpic { epic_id: epic_id.0 })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 4);
+
+        nav.handle_action(Action::DeleteEpic { epic_id: epic_id.0 })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+        assert!(nav
+            .get_current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<HomePage>()
+            .is_some());
+    }
+
+    #[test]
+    fn handle_action_should_handle_merge_epic() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let target_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let source_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let _ = db
+            .create_story("".to_owned(), "".to_owned(), Some(source_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.merge_epic = Box::new(move || Some(target_id.0));
+        prompts.confirm_merge = Box::new(|_, _| true);
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail {
+            epic_id: source_id.0,
+        })
+        .unwrap();
+        nav.handle_action(Action::MergeEpic {
+            epic_id: source_id.0,
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.len(), 1);
+        assert_eq!(db_state.epics.get(&target_id.0).unwrap().stories.len(), 1);
+
+        assert_eq!(nav.get_page_count(), 2);
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail_page = current_page.as_any().downcast_ref::<EpicDetail>();
+        assert_eq!(epic_detail_page.unwrap().epic_id, target_id.0);
+    }
+
+    #[test]
+    fn handle_action_should_keep_both_epics_when_the_merge_is_declined() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let target_id = db.create_epic("Billing".to_owned(), "".to_owned()).unwrap();
+        let source_id = db
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        for _ in 0..2 {
+            db.create_story("".to_owned(), "".to_owned(), Some(source_id))
+                .unwrap();
+        }
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.merge_epic = Box::new(move || Some(target_id.0));
+        prompts.confirm_merge = Box::new(|source, target| {
+            assert_eq!(
+                merge_epic_question(source, target),
+                "Merge EPIC-1 'Payments' and its 2 stories into EPIC-0 'Billing'?"
+            );
+            false
+        });
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail {
+            epic_id: source_id.0,
+        })
+        .unwrap();
+        nav.handle_action(Action::MergeEpic {
+            epic_id: source_id.0,
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.len(), 2);
+        assert_eq!(db_state.epics.get(&source_id.0).unwrap().stories.len(), 2);
+        assert_eq!(nav.get_page_count(), 2);
+        assert_eq!(nav.take_notice(), None);
+    }
+
+    #[test]
+    fn handle_action_should_handle_demote_epic() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let target_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let _ = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.demote_epic = Box::new(move |_| Some(target_id.0));
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: epic_id.0 })
+            .unwrap();
+        nav.handle_action(Action::DemoteEpic { epic_id: epic_id.0 })
+            .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.len(), 1);
+        assert_eq!(db_state.stories.len(), 1);
+
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail_page = current_page.as_any().downcast_ref::<EpicDetail>();
+        assert_eq!(epic_detail_page.unwrap().epic_id, target_id.0);
+    }
+
+    #[test]
+    fn handle_action_should_handle_create_story() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.create_story = Box::new(|| {
+            Story::new(ItemDetail {
+                name: "name".to_owned(),
+                description: "description".to_owned(),
+                id: ItemId(0),
+                status: ItemStatus::Open,
+                ..Default::default()
+            })
+        });
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::CreateStory { epic_id: epic_id.0 })
+            .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.stories.len(), 1);
+
+        let story = db_state.stories.into_iter().next().unwrap().1;
+        assert_eq!(story.detail.name, "name".to_owned());
+        assert_eq!(story.detail.description, "description".to_owned());
+    }
+
+    #[test]
+    fn new_stories_should_start_with_the_default_labels_of_their_epic() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let mut prompts = MockPrompts::new();
+        prompts.default_labels = Box::new(|_| Some(vec!["billing".to_owned()]));
+        prompts.create_story = Box::new(|| Story::new(ItemDetail::default()));
+        prompts.story_labels = Box::new(|defaults| {
+            assert_eq!(defaults, ["billing".to_owned()]);
+            vec!["billing".to_owned(), "ui".to_owned()]
+        });
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_prompts(prompts);
+
+        nav.handle_action(Action::EditDefaultLabels { epic_id: epic_id.0 })
+            .unwrap();
+        nav.handle_action(Action::CreateStory { epic_id: epic_id.0 })
+            .unwrap();
+        nav.handle_action(Action::QuickAddStory {
+            epic_id: Some(epic_id.0),
+            input: "Refunds #urgent".to_owned(),
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics[&epic_id.0].default_labels, ["billing"]);
+        assert_eq!(db_state.stories[&0].detail.labels, ["billing", "ui"]);
+        assert_eq!(db_state.stories[&1].detail.labels, ["billing", "urgent"]);
+    }
+
+    #[test]
+    fn handle_action_should_handle_quick_add_story() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        nav.handle_action(Action::QuickAddStory {
+            epic_id: Some(epic_id.0),
+            input: "Fix login".to_owned(),
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        let story = db_state.stories.get(&0).unwrap();
+        assert_eq!(story.detail.name, "Fix login");
+        assert_eq!(story.detail.description, "");
+        assert_eq!(
+            db_state.epics.get(&epic_id.0).unwrap().stories,
+            vec![ItemId(0)]
+        );
+        assert_eq!(nav.take_notice(), Some("Created STORY-0".to_owned()));
+    }
+
+    #[test]
+    fn handle_action_should_capture_story_fields() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let _ = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        nav.handle_action(Action::QuickAddStory {
+            epic_id: None,
+            input: "Fix login redirect !high @alice #auth ^epic:1".to_owned(),
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        let story = db_state.stories.get(&0).unwrap();
+        assert_eq!(story.detail.name, "Fix login redirect");
+        assert_eq!(story.detail.priority, Some(Priority::High));
+        assert_eq!(story.detail.assignee, Some("alice".to_owned()));
+        assert_eq!(story.detail.labels, vec!["auth".to_owned()]);
+        assert_eq!(
+            db_state.epics.get(&epic_id.0).unwrap().stories,
+            vec![ItemId(0)]
+        );
+        nav.take_notice();
+
+        nav.handle_action(Action::QuickAddStory {
+            epic_id: Some(epic_id.0),
+            input: "Log out @al #AU".to_owned(),
+        })
+        .unwrap();
+
+        let story = db.get_story(ItemId(1)).unwrap();
+        assert_eq!(story.detail.assignee, Some("alice".to_owned()));
+        assert_eq!(story.detail.labels, vec!["auth".to_owned()]);
+        assert_eq!(
+            nav.take_notice(),
+            Some("Created STORY-1, completing @al to @alice, #AU to #auth".to_owned())
+        );
+    }
+
+    #[test]
+    fn handle_action_should_fail_to_capture_story_without_epic() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        assert!(nav
+            .handle_action(Action::QuickAddStory {
+                epic_id: None,
+                input: "Fix login".to_owned(),
+            })
+            .is_err());
+        assert!(db.read_db().unwrap().stories.is_empty());
+    }
+
+    #[test]
+    fn handle_action_should_handle_update_story() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.update_status = Box::new(|| Some(ItemStatus::InProgress));
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::UpdateStoryStatus {
+            story_id: story_id.0,
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(
+            db_state.stories.get(&story_id.0).unwrap().detail.status,
+            ItemStatus::InProgress
+        );
+    }
+
+    #[test]
+    fn handle_action_should_handle_delete_story() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.delete_story = Box::new(|_| true);
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::DeleteStory {
+            epic_id: epic_id.0,
+            story_id: story_id.0,
+        })
+        .unwrap();
+        nav.finish_delete().unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.stories.len(), 0);
+    }
+
+    #[test]
+    fn quick_filter_should_narrow_the_rows_of_the_page() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        db.create_epic("Checkout".to_owned(), "".to_owned())
+            .unwrap();
+        let billing = db.create_epic("Billing".to_owned(), "".to_owned()).unwrap();
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+        let rows = |nav: &Navigator| nav.get_current_page().unwrap().rows().unwrap().len();
+
+        nav.handle_input("/").unwrap();
+        assert!(nav.is_filtering());
+
+        for key in "bil".chars() {
+            nav.handle_filter_key(FilterKey::Char(key));
+        }
+        nav.handle_filter_key(FilterKey::Enter);
+
+        assert!(!nav.is_filtering());
+        assert_eq!(rows(&nav), 1);
+
+        nav.handle_input(&billing.0.to_string()).unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        nav.handle_input("p").unwrap();
+        nav.handle_input("/").unwrap();
+        nav.handle_input("").unwrap();
+
+        assert!(!nav.is_filtering());
+        assert_eq!(rows(&nav), 2);
+    }
+
+    #[test]
+    fn reload_config_should_apply_the_new_keymap_and_layout() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+
+        nav.reload_config(Config {
+            keymap: Keymap::Vim,
+            home_layout: HomeLayout::Grouped,
+            ..Config::default()
+        });
+
+        assert_eq!(nav.take_notice(), Some("Reloaded the config".to_owned()));
+        assert_eq!(nav.config.keymap.translate(":q"), KeyCommand::Back);
+        assert_eq!(nav.get_page_count(), 1);
+
+        nav.reload_config(Config {
+            user: Some("alice".to_owned()),
+            ..Config::default()
+        });
+
+        assert_eq!(
+            nav.take_notice(),
+            Some("Reloaded the config, restart to apply user".to_owned())
+        );
+    }
+
+    #[test]
+    fn handle_input_should_save_on_request_and_ask_before_exit() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(
+            BufferedDatabase::new(Box::new(MockDB::new())),
+        )));
+        db.create_epic("Checkout".to_owned(), "".to_owned())
+            .unwrap();
+
+        let mut prompts = MockPrompts::new();
+        prompts.save_on_exit = Box::new(|| None);
+
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_prompts(prompts);
+
+        assert!(nav.has_unsaved_changes());
+
+        nav.handle_action(Action::Exit).unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        nav.handle_input(":w").unwrap();
+        assert_eq!(nav.take_notice(), Some("Saved the board".to_owned()));
+        assert!(!nav.has_unsaved_changes());
+
+        nav.handle_input(":w").unwrap();
+        assert_eq!(nav.take_notice(), Some("Nothing to save".to_owned()));
+
+        nav.handle_action(Action::Exit).unwrap();
+        assert_eq!(nav.get_page_count(), 0);
+    }
+
+    #[test]
+    fn handle_input_should_call_off_a_delete_right_after_it() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("Refunds".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut prompts = MockPrompts::new();
+        prompts.delete_story = Box::new(|_| true);
+
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_prompts(prompts);
+        let delete = || Action::DeleteStory {
+            epic_id: epic_id.0,
+            story_id: story_id.0,
+        };
+
+        nav.handle_action(delete()).unwrap();
+        assert_eq!(
+            nav.take_notice(),
+            Some("Deleting STORY-0 in 10s, z to keep it".to_owned())
+        );
+        assert!(db.read_db().unwrap().stories.contains_key(&story_id.0));
+        assert!(!nav.expire_delete().unwrap());
+
+        nav.handle_input("z").unwrap();
+        assert_eq!(nav.take_notice(), Some("Kept STORY-0".to_owned()));
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.stories[&story_id.0].detail.name, "Refunds");
+        assert_eq!(db_state.epics[&epic_id.0].stories, vec![story_id]);
+
+        nav.handle_action(delete()).unwrap();
+        nav.handle_input("?").unwrap();
+        assert!(db.read_db().unwrap().stories.is_empty());
+
+        nav.handle_input("z").unwrap();
+        assert_eq!(nav.take_notice(), Some("Nothing to undo".to_owned()));
+    }
+
+    #[test]
+    fn expire_delete_should_write_the_delete_once_its_window_passed() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        db.create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut prompts = MockPrompts::new();
+        prompts.delete_epic = Box::new(|_| true);
+
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: epic_id.0 })
+            .unwrap();
+        nav.handle_action(Action::DeleteEpic { epic_id: epic_id.0 })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+        assert!(!nav.expire_delete().unwrap());
+        assert_eq!(db.read_db().unwrap().epics.len(), 1);
+
+        nav.pending_delete.as_mut().unwrap().until = Utc::now();
+        assert!(nav.expire_delete().unwrap());
+
+        let db_state = db.read_db().unwrap();
+        assert!(db_state.epics.is_empty());
+        assert!(db_state.stories.is_empty());
+    }
+
+    #[test]
+    fn handle_action_should_create_stories_from_templates() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        db.set_default_labels(epic_id, vec!["support".to_owned()])
+            .unwrap();
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        nav.handle_action(Action::CreateStoryFromTemplate { epic_id: epic_id.0 })
+            .unwrap();
+        assert_eq!(
+            nav.take_notice(),
+            Some("No templates yet, add them under templates in the config".to_owned())
+        );
+
+        let mut prompts = MockPrompts::new();
+        prompts.pick_template = Box::new(|names| names.iter().position(|name| name == "upgrade"));
+        prompts.template_value = Box::new(|placeholder| match placeholder {
+            "customer" => "Acme".to_owned(),
+            _ => "2.0".to_owned(),
+        });
+        nav = nav
+            .with_config(Config {
+                templates: [
+                    ("bug".to_owned(), StoryTemplate::default()),
+                    (
+                        "upgrade".to_owned(),
+                        StoryTemplate {
+                            name: "Upgrade {{customer}} to {{version}}".to_owned(),
+                            checklist: vec!["Back up {{customer}}".to_owned()],
+                            labels: vec!["upgrade".to_owned()],
+                            ..Default::default()
+                        },
+                    ),
+                ]
+                .into(),
+                ..Config::default()
+            })
+            .with_prompts(prompts);
+
+        nav.handle_action(Action::CreateStoryFromTemplate { epic_id: epic_id.0 })
+            .unwrap();
+
+        let story = db.read_db().unwrap().stories[&0].clone();
+
+        assert_eq!(
+            nav.take_notice(),
+            Some("Created STORY-0 from upgrade".to_owned())
+        );
+        assert_eq!(story.detail.name, "Upgrade Acme to 2.0");
+        assert_eq!(story.checklist[0].text, "Back up Acme");
+        assert_eq!(story.detail.labels, vec!["support", "upgrade"]);
+        assert!(db
+            .get_epic(epic_id)
+            .unwrap()
+            .stories
+            .contains(&story.detail.id));
+    }
+
+    #[test]
+    fn handle_action_should_handle_clone_story() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("name".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        nav.handle_action(Action::CloneStory {
+            epic_id: epic_id.0,
+            story_id: story_id.0,
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.stories.len(), 2);
+        assert_eq!(db_state.epics.get(&epic_id.0).unwrap().stories.len(), 2);
+        assert_eq!(
+            nav.take_notice(),
+            Some("Cloned STORY-0 as STORY-1".to_owned())
+        );
+    }
+
+    #[test]
+    fn handle_action_should_handle_split_story() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("name".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.add_checklist_item = Box::new(|| "Card form".to_owned());
+        prompts.split_story = Box::new(|story| {
+            assert_eq!(story.checklist.len(), 1);
+
+            Some(StorySplit {
+                name: "Payment".to_owned(),
+                description: "".to_owned(),
+                checklist: vec![0],
+            })
+        });
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::AddChecklistItem {
+            story_id: story_id.0,
+        })
+        .unwrap();
+        nav.handle_action(Action::SplitStory {
+            epic_id: epic_id.0,
+            story_id: story_id.0,
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.stories.len(), 2);
+        assert!(db_state.stories.get(&0).unwrap().checklist.is_empty());
+        assert_eq!(db_state.stories.get(&1).unwrap().checklist.len(), 1);
+        assert_eq!(
+            nav.take_notice(),
+            Some("Split STORY-0 into STORY-1".to_owned())
+        );
+    }
+
+    #[test]
+    fn handle_action_should_handle_promote_story() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("name".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let blocker_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        db.add_blocker(story_id, blocker_id).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let mut prompts = MockPrompts::new();
+        prompts.promote_story = Box::new(|_| Some(false));
+
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: epic_id.0 })
+            .unwrap();
+        nav.handle_action(Action::NavigateToStoryDetail {
+            epic_id: epic_id.0,
+            story_id: story_id.0,
+        })
+        .unwrap();
+        nav.handle_action(Action::PromoteStory {
+            story_id: story_id.0,
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.len(), 2);
+        assert_eq!(db_state.stories.len(), 1);
+        assert!(db_state.stories[&blocker_id.0].links.is_empty());
+        assert_eq!(
+            nav.take_notice().as_deref(),
+            Some("Promoted STORY-0 to EPIC-1, dropping its link")
+        );
+
+        assert_eq!(nav.get_page_count(), 3);
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail_page = current_page.as_any().downcast_ref::<EpicDetail>();
+        assert_eq!(epic_detail_page.unwrap().epic_id, 1);
+    }
+
+    #[test]
+    fn handle_action_should_handle_toggle_checklist_item() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        db.add_checklist_item(story_id, "Cart".to_owned()).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        nav.handle_action(Action::ToggleChecklistItem {
+            story_id: story_id.0,
+            index: 0,
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert!(db_state.stories.get(&story_id.0).unwrap().checklist[0].done);
+    }
+
+    #[test]
+    fn handle_action_should_copy_epic_summary_to_clipboard() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+        let copied = Rc::new(RefCell::new(String::new()));
+        let sink = Rc::clone(&copied);
+
+        nav.set_clipboard(Box::new(move |text| {
+            *sink.borrow_mut() = text.to_owned();
+            Ok(())
+        }));
+
+        nav.handle_action(Action::CopyEpic { epic_id: epic_id.0 })
+            .unwrap();
+
+        assert_eq!(
+            *copied.borrow(),
+            "[EPIC-0] Payments (OPEN) - jira-clone://epic/0"
+        );
+        assert_eq!(
+            nav.take_notice(),
+            Some("Copied EPIC-0 to clipboard".to_owned())
+        );
+        assert_eq!(nav.take_notice(), None);
+    }
+
+    #[test]
+    fn handle_action_should_copy_story_summary_to_clipboard() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("Fix login".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+        let copied = Rc::new(RefCell::new(String::new()));
+        let sink = Rc::clone(&copied);
+
+        nav.set_clipboard(Box::new(move |text| {
+            *sink.borrow_mut() = text.to_owned();
+            Ok(())
+        }));
+
+        nav.handle_action(Action::CopyStory {
+            story_id: story_id.0,
+        })
+        .unwrap();
+
+        assert_eq!(
+            *copied.borrow(),
+            "[STORY-0] Fix login (OPEN) - jira-clone://story/0"
+        );
+    }
+
+    #[test]
+    fn handle_action_should_fail_to_copy_missing_story() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+        nav.set_clipboard(Box::new(|_| Ok(())));
+
+        assert!(nav
+            .handle_action(Action::CopyStory { story_id: 999 })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_navigate_to_markdown_snippet() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+
+        nav.handle_action(Action::NavigateToStoryMarkdown { story_id: 1 })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let snippet_page = current_page.as_any().downcast_ref::<MarkdownSnippet>();
+        assert!(snippet_page.is_some());
+    }
+
+    #[test]
+    fn handle_action_should_copy_text_to_clipboard() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+        let copied = Rc::new(RefCell::new(String::new()));
+        let sink = Rc::clone(&copied);
+
+        nav.set_clipboard(Box::new(move |text| {
+            *sink.borrow_mut() = text.to_owned();
+            Ok(())
+        }));
+
+        nav.handle_action(Action::CopyText {
+            text: "### [STORY-0] Fix login".to_owned(),
+        })
+        .unwrap();
+
+        assert_eq!(*copied.borrow(), "### [STORY-0] Fix login");
+        assert_eq!(nav.take_notice(), Some("Copied to clipboard".to_owned()));
+    }
+
+    #[test]
+    fn handle_action_should_navigate_to_search_results() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+
+        let filter = Filter {
+            query: Some("login".to_owned()),
+            ..Default::default()
+        };
+
+        nav.handle_action(Action::Search {
+            filter: filter.clone(),
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let search_page = current_page.as_any().downcast_ref::<SearchResults>();
+        assert!(search_page.is_some());
+        assert_eq!(search_page.unwrap().filter, filter);
+    }
+
+    #[test]
+    fn handle_action_should_bulk_update_statuses() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let first = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let second = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+        let mut prompts = MockPrompts::new();
+        prompts.update_status = Box::new(|| Some(ItemStatus::Resolved));
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::BulkUpdateStatus {
+            items: vec![
+                ItemType::Story { id: first },
+                ItemType::Story { id: second },
+            ],
+        })
+        .unwrap();
+
+        let stories = db.read_db().unwrap().stories;
+        assert!(stories
+            .values()
+            .all(|story| story.detail.status == ItemStatus::Resolved));
+        assert_eq!(
+            nav.take_notice(),
+            Some("Updated 2 items to RESOLVED".to_owned())
+        );
+    }
+
+    #[test]
+    fn handle_action_should_bulk_edit_fields() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        let items = vec![ItemType::Story { id: story_id }];
+        let edits = vec![
+            FieldEdit::AddLabel("billing".to_owned()),
+            FieldEdit::SetPriority(Some(Priority::Low)),
+        ];
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_config(Config {
+                confirm: Confirmations {
+                    bulk_update: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        let mut prompts = MockPrompts::new();
+        prompts.confirm_bulk_edit = Box::new(|count, edits| count == 1 && edits.len() == 2);
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::BulkEdit { items, edits })
+            .unwrap();
+
+        let story = &db.read_db().unwrap().stories[&story_id.0];
+        assert_eq!(story.detail.labels, vec!["billing".to_owned()]);
+        assert_eq!(story.detail.priority, Some(Priority::Low));
+        assert_eq!(
+            nav.take_notice(),
+            Some("Updated 1 items: add #billing, priority LOW".to_owned())
+        );
+    }
+
+    #[test]
+    fn handle_action_should_save_and_pick_filters() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let filter = Filter {
+            status: Some(ItemStatus::Open),
+            label: Some("bug".to_owned()),
+            ..Default::default()
+        };
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        nav.handle_action(Action::PickFilter).unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+        assert!(nav.take_notice().is_some());
+
+        let mut prompts = MockPrompts::new();
+        prompts.save_filter = Box::new(|| "My open bugs".to_owned());
+        prompts.pick_filter = Box::new(|filters| {
+            assert_eq!(filters[0].name, "My open bugs");
+            Some(0)
+        });
+        nav = nav.with_prompts(prompts);
+
+        nav.handle_action(Action::SaveFilter {
+            filter: filter.clone(),
+        })
+        .unwrap();
+        assert_eq!(db.read_db().unwrap().filters[0].filter, filter);
+        assert_eq!(
+            nav.take_notice(),
+            Some("Saved filter \"My open bugs\"".to_owned())
+        );
+
+        nav.handle_action(Action::PickFilter).unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let search_page = current_page.as_any().downcast_ref::<SearchResults>();
+        assert_eq!(search_page.unwrap().filter, filter);
+    }
+
+    #[test]
+    fn handle_action_should_toggle_watch_and_open_notifications() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+        let story = ItemType::Story { id: story_id };
+
+        nav.handle_action(Action::ToggleWatch {
+            item: story.clone(),
+        })
+        .unwrap();
+        assert_eq!(nav.take_notice(), Some("Watching STORY-0".to_owned()));
+
+        db.update_story_status(story_id, ItemStatus::Resolved)
+            .unwrap();
+
+        nav.handle_action(Action::NavigateToNotifications).unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page
+            .as_any()
+            .downcast_ref::<Notifications>()
+            .is_some());
+
+        nav.handle_action(Action::OpenNotification { id: 0 })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 3);
+        assert!(db.read_db().unwrap().notifications[0].read);
+
+        let current_page = nav.get_current_page().unwrap();
+        let story_page = current_page.as_any().downcast_ref::<StoryDetail>();
+        assert_eq!(story_page.unwrap().story_id, story_id.0);
+
+        nav.handle_action(Action::ToggleWatch { item: story })
+            .unwrap();
+        assert_eq!(
+            nav.take_notice(),
+            Some("Stopped watching STORY-0".to_owned())
+        );
+    }
+
+    #[test]
+    fn remind_due_items_should_only_show_reminders_when_something_is_due() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let today = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+        nav.remind_due_items(today).unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        let _ = db
+            .add_story(
+                Story::new(ItemDetail {
+                    due: Some(today),
+                    ..Default::default()
+                }),
+                None,
+            )
+            .unwrap();
+
+        nav.remind_due_items(today).unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page
+            .as_any()
+            .downcast_ref::<DueReminders>()
+            .is_some());
+    }
+
+    #[test]
+    fn greet_should_leave_a_board_summary() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let _ = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+        nav.greet(Utc::now()).unwrap();
+
+        assert_eq!(
+            nav.take_notice(),
+            Some("Board: 1 open, 0 in progress, 0 resolved this week, 0 blocked".to_owned())
+        );
+    }
+
+    #[test]
+    fn handle_action_should_add_blocker() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        let blocker_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        nav.handle_action(Action::AddBlocker {
+            story_id: story_id.0,
+            blocker_id: blocker_id.0,
+        })
+        .unwrap();
+        assert_eq!(
+            nav.take_notice(),
+            Some("STORY-0 is blocked by STORY-1".to_owned())
+        );
+        assert!(nav
+            .handle_action(Action::AddBlocker {
+                story_id: story_id.0,
+                blocker_id: 9,
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_navigate_to_standup() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+
+        nav.handle_action(Action::NavigateToStandup).unwrap();
+        assert_eq!(nav.get_page_count(), 2);
 
-    fn set_prompts(&mut self, prompts: Prompts) {
-        self.prompts = prompts;
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page.as_any().downcast_ref::<Standup>().is_some());
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{
-        db::test_utils::MockDB,
-        model::{Epic, ItemDetail, ItemId, ItemStatus, Story},
-    };
 
     #[test]
-    fn should_start_on_home_page() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
+    fn handle_action_should_plan_sprints() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+        let mut prompts = MockPrompts::new();
+        prompts.create_sprint = Box::new(|| {
+            Some(Sprint {
+                id: ItemId(0),
+                name: "Sprint 1".to_owned(),
+                start: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+                end: NaiveDate::from_ymd_opt(2024, 5, 14).unwrap(),
+                stories: Vec::new(),
+                closed: None,
+                snapshot: Vec::new(),
+            })
         });
-        let nav = Navigator::new(db);
+        nav = nav.with_prompts(prompts);
 
-        assert_eq!(nav.get_page_count(), 1);
+        nav.handle_action(Action::NavigateToSprints).unwrap();
+        nav.handle_action(Action::CreateSprint).unwrap();
+        assert_eq!(nav.take_notice(), Some("Created SPRINT-0".to_owned()));
 
-        let current_page = nav.get_current_page().unwrap();
-        let home_page = current_page.as_any().downcast_ref::<HomePage>();
+        nav.handle_action(Action::NavigateToSprintDetail { sprint_id: 0 })
+            .unwrap();
+        nav.handle_action(Action::AddStoryToSprint {
+            sprint_id: 0,
+            story_id: story_id.0,
+        })
+        .unwrap();
+        nav.handle_action(Action::SetStoryPoints {
+            story_id: story_id.0,
+            points: Some(3),
+        })
+        .unwrap();
+
+        let data = db.read_db().unwrap();
+        assert_eq!(data.sprints[&0].stories, vec![story_id]);
+        assert_eq!(data.stories[&story_id.0].detail.points, Some(3));
+        assert_eq!(nav.get_page_count(), 3);
 
-        assert_eq!(home_page.is_some(), true);
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page
+            .as_any()
+            .downcast_ref::<SprintDetail>()
+            .is_some());
     }
 
     #[test]
-    fn handle_action_should_navigate_pages() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
+    fn handle_action_should_warn_when_sprint_stories_exceed_capacity() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let sprint_id = db.create_sprint("Payments".to_owned(), date, date).unwrap();
+        let story_ids = [3, 5, 2].map(|points| {
+            db.add_story(
+                Story::new(ItemDetail {
+                    assignee: Some("bob".to_owned()),
+                    points: Some(points),
+                    ..Default::default()
+                }),
+                None,
+            )
+            .unwrap()
         });
 
-        let mut nav = Navigator::new(db);
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_config(Config {
+                capacity: Capacity {
+                    default: Some(8),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        let mut add = |story_id: ItemId| {
+            nav.handle_action(Action::AddStoryToSprint {
+                sprint_id: sprint_id.0,
+                story_id: story_id.0,
+            })
+            .unwrap();
+            nav.take_notice().unwrap()
+        };
+
+        assert_eq!(add(story_ids[0]), "Added STORY-0 to SPRINT-0");
+        assert_eq!(add(story_ids[1]), "Added STORY-1 to SPRINT-0");
+        assert_eq!(
+            add(story_ids[2]),
+            "Added STORY-2 to SPRINT-0, bob is now over capacity: 10 of 8 points"
+        );
+    }
 
-        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+    #[test]
+    fn handle_action_should_create_and_restore_snapshots() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let story_id = db
+            .create_story("Refunds".to_owned(), "".to_owned(), None)
             .unwrap();
-        assert_eq!(nav.get_page_count(), 2);
 
-        let current_page = nav.get_current_page().unwrap();
-        let epic_detail_page = current_page.as_any().downcast_ref::<EpicDetail>();
-        assert_eq!(epic_detail_page.is_some(), true);
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+        let mut prompts = MockPrompts::new();
+        prompts.snapshot_name = Box::new(|| "before-reorg".to_owned());
+        prompts.restore_snapshot = Box::new(|name| name == "before-reorg");
+        nav = nav.with_prompts(prompts);
 
-        nav.handle_action(Action::NavigateToStoryDetail {
-            epic_id: 1,
-            story_id: 2,
+        nav.handle_action(Action::NavigateToSnapshots).unwrap();
+        nav.handle_action(Action::CreateSnapshot).unwrap();
+        assert_eq!(
+            nav.take_notice(),
+            Some("Saved the board as \"before-reorg\"".to_owned())
+        );
+
+        db.delete_story(story_id, None).unwrap();
+        nav.handle_action(Action::RestoreSnapshot {
+            name: "before-reorg".to_owned(),
         })
         .unwrap();
-        assert_eq!(nav.get_page_count(), 3);
 
-        let current_page = nav.get_current_page().unwrap();
-        let story_detail_page = current_page.as_any().downcast_ref::<StoryDetail>();
-        assert_eq!(story_detail_page.is_some(), true);
+        assert!(db.get_story(story_id).is_ok());
+        assert!(nav
+            .handle_action(Action::RestoreSnapshot {
+                name: "missing".to_owned(),
+            })
+            .is_err());
+    }
 
-        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
+    #[test]
+    fn handle_action_should_close_sprints_with_a_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let sprint_id = db.create_sprint("Payments".to_owned(), date, date).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_archive(
+            DirectoryArchive::new(&Config {
+                reports_dir: Some(dir.path().to_owned()),
+                ..Default::default()
+            }),
+        );
+
+        nav.handle_action(Action::CloseSprint {
+            sprint_id: sprint_id.0,
+        })
+        .unwrap();
+
+        let report = dir.path().join("SPRINT-0.md");
+        assert_eq!(
+            nav.take_notice(),
+            Some(format!(
+                "Closed SPRINT-0, the report is in {}",
+                report.display()
+            ))
+        );
+        assert!(report.exists());
+        assert!(nav
+            .handle_action(Action::CloseSprint {
+                sprint_id: sprint_id.0,
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_navigate_to_dashboard() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::from_prompts(db, MockPrompts::new());
+
+        nav.handle_action(Action::NavigateToDashboard).unwrap();
         assert_eq!(nav.get_page_count(), 2);
 
         let current_page = nav.get_current_page().unwrap();
-        let epic_detail_page = current_page.as_any().downcast_ref::<EpicDetail>();
-        assert_eq!(epic_detail_page.is_some(), true);
+        assert!(current_page.as_any().downcast_ref::<Dashboard>().is_some());
 
-        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
-        assert_eq!(nav.get_page_count(), 1);
+        nav.handle_action(Action::NavigateToTree).unwrap();
 
         let current_page = nav.get_current_page().unwrap();
-        let home_page = current_page.as_any().downcast_ref::<HomePage>();
-        assert_eq!(home_page.is_some(), true);
+        assert!(current_page.as_any().downcast_ref::<Tree>().is_some());
 
-        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
-        assert_eq!(nav.get_page_count(), 0);
+        nav.handle_action(Action::NavigateToWorkload).unwrap();
 
-        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
-        assert_eq!(nav.get_page_count(), 0);
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page.as_any().downcast_ref::<Workload>().is_some());
+
+        nav.handle_action(Action::NavigateToLabels).unwrap();
+
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page.as_any().downcast_ref::<Labels>().is_some());
     }
 
     #[test]
-    fn handle_action_should_clear_pages_on_exit() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
-        });
+    fn handle_action_should_turn_notes_into_stories() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
 
-        let mut nav = Navigator::new(db);
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
 
-        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+        nav.handle_action(Action::NavigateToNotes).unwrap();
+        nav.handle_input("+ Refunds take a week #billing").unwrap();
+        nav.handle_input("+ Lunch on friday").unwrap();
+        assert!(nav.handle_input("s1").is_err());
+
+        nav.handle_input(&format!("s1 ^epic:{}", epic_id.0))
             .unwrap();
-        nav.handle_action(Action::NavigateToStoryDetail {
-            epic_id: 1,
-            story_id: 2,
-        })
-        .unwrap();
-        nav.handle_action(Action::Exit).unwrap();
 
-        assert_eq!(nav.get_page_count(), 0);
+        let db_state = db.read_db().unwrap();
+        let story = &db_state.stories[&0];
+        assert_eq!(story.detail.name, "Refunds take a week");
+        assert_eq!(story.detail.labels, vec!["billing".to_owned()]);
+        assert_eq!(db_state.notes, vec!["Lunch on friday".to_owned()]);
     }
 
     #[test]
-    fn handle_action_should_handle_create_epic() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
-        });
-
-        let mut nav = Navigator::new(Rc::clone(&db));
+    fn handle_action_should_warn_when_exceeding_wip_limits() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let first = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        let second = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        db.update_story_status(first, ItemStatus::InProgress)
+            .unwrap();
 
-        let mut prompts = Prompts::new();
-        prompts.create_epic = Box::new(|| {
-            Epic::new(
-                ItemDetail {
-                    name: "name".to_owned(),
-                    description: "description".to_owned(),
-                    id: ItemId(0),
-                    status: ItemStatus::Open,
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_config(Config {
+                wip: WipLimits {
+                    global: Some(1),
+                    ..Default::default()
                 },
-                Vec::new(),
-            )
-        });
+                ..Default::default()
+            });
 
-        nav.set_prompts(prompts);
+        let mut prompts = MockPrompts::new();
+        prompts.update_status = Box::new(|| Some(ItemStatus::InProgress));
+        prompts.wip_override = Box::new(|_| panic!("limits are not enforced"));
 
-        nav.handle_action(Action::CreateEpic).unwrap();
+        nav = nav.with_prompts(prompts);
 
-        let db_state = db.read_db().unwrap();
-        assert_eq!(db_state.epics.len(), 1);
+        nav.handle_action(Action::UpdateStoryStatus { story_id: second.0 })
+            .unwrap();
 
-        let epic = db_state.epics.into_iter().next().unwrap().1;
-        assert_eq!(epic.detail.name, "name".to_owned());
-        assert_eq!(epic.detail.description, "description".to_owned());
+        assert_eq!(
+            db.read_db()
+                .unwrap()
+                .stories
+                .get(&second.0)
+                .unwrap()
+                .detail
+                .status,
+            ItemStatus::InProgress
+        );
+        assert_eq!(
+            nav.take_notice(),
+            Some("WIP limit exceeded: 2 stories would be in progress (limit 1)".to_owned())
+        );
     }
 
     #[test]
-    fn handle_action_should_handle_update_epic() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
-        });
-        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+    fn handle_action_should_ask_before_exceeding_enforced_wip_limits() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let first = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        let second = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        let third = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        db.update_story_status(first, ItemStatus::InProgress)
+            .unwrap();
 
-        let mut nav = Navigator::new(Rc::clone(&db));
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_config(Config {
+                wip: WipLimits {
+                    global: Some(1),
+                    enforce: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
 
-        let mut prompts = Prompts::new();
+        let answers = Rc::new(RefCell::new(vec![true, false]));
+        let mut prompts = MockPrompts::new();
         prompts.update_status = Box::new(|| Some(ItemStatus::InProgress));
+        prompts.wip_override = Box::new(move |_| answers.borrow_mut().pop().unwrap());
 
-        nav.set_prompts(prompts);
+        nav = nav.with_prompts(prompts);
 
-        nav.handle_action(Action::UpdateEpicStatus { epic_id: epic_id.0 })
+        nav.handle_action(Action::UpdateStoryStatus { story_id: second.0 })
             .unwrap();
 
         let db_state = db.read_db().unwrap();
         assert_eq!(
-            db_state.epics.get(&epic_id.0).unwrap().detail.status,
+            db_state.stories.get(&second.0).unwrap().detail.status,
+            ItemStatus::Open
+        );
+
+        nav.handle_action(Action::BulkUpdateStatus {
+            items: vec![
+                ItemType::Story { id: second },
+                ItemType::Story { id: third },
+            ],
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(
+            db_state.stories.get(&third.0).unwrap().detail.status,
             ItemStatus::InProgress
         );
+        assert_eq!(
+            nav.take_notice(),
+            Some(
+                "Updated 2 items to IN PROGRESS (WIP limit exceeded: 3 stories would be in progress (limit 1))"
+                    .to_owned()
+            )
+        );
     }
 
     #[test]
-    fn handle_action_should_handle_delete_epic() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
-        });
+    fn handle_action_should_show_the_key_hints_of_the_current_page() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+        let expected = nav.get_current_page().unwrap().key_hints();
+
+        nav.handle_action(Action::ShowKeyHints).unwrap();
+
+        let overlay = nav
+            .get_current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<KeyHints>()
+            .unwrap();
+
+        assert_eq!(overlay.hints, expected);
+        assert_eq!(nav.get_page_count(), 2);
+    }
+
+    #[test]
+    fn handle_action_should_skip_confirmations_turned_off_in_the_config() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
         let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
 
-        let mut nav = Navigator::new(Rc::clone(&db));
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_config(Config {
+                confirm: Confirmations {
+                    delete_epic: false,
+                    delete_story: false,
+                    merge_epic: false,
+                    bulk_update: false,
+                    restore_snapshot: false,
+                },
+                ..Default::default()
+            });
 
-        let mut prompts = Prompts::new();
-        prompts.delete_epic = Box::new(|| true);
+        let mut prompts = MockPrompts::new();
+        prompts.delete_story = Box::new(|_| panic!("deleting stories is not confirmed"));
+        prompts.delete_epic = Box::new(|_| panic!("deleting epics is not confirmed"));
 
-        nav.set_prompts(prompts);
+        nav = nav.with_prompts(prompts);
 
+        nav.handle_action(Action::DeleteStory {
+            epic_id: epic_id.0,
+            story_id: story_id.0,
+        })
+        .unwrap();
         nav.handle_action(Action::DeleteEpic { epic_id: epic_id.0 })
             .unwrap();
+        nav.finish_delete().unwrap();
 
         let db_state = db.read_db().unwrap();
-        assert_eq!(db_state.epics.len(), 0);
+        assert!(db_state.stories.is_empty());
+        assert!(db_state.epics.is_empty());
     }
 
     #[test]
-    fn handle_action_should_handle_create_story() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
+    fn handle_action_should_confirm_bulk_updates_when_configured() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_config(Config {
+                confirm: Confirmations {
+                    bulk_update: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+        let mut prompts = MockPrompts::new();
+        prompts.update_status = Box::new(|| Some(ItemStatus::Resolved));
+        prompts.confirm_bulk_update = Box::new(|count, status| {
+            assert_eq!((count, status), (1, &ItemStatus::Resolved));
+            false
         });
-        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
 
-        let mut nav = Navigator::new(Rc::clone(&db));
+        nav = nav.with_prompts(prompts);
 
-        let mut prompts = Prompts::new();
-        prompts.create_story = Box::new(|| {
-            Story::new(ItemDetail {
-                name: "name".to_owned(),
-                description: "description".to_owned(),
-                id: ItemId(0),
-                status: ItemStatus::Open,
-            })
+        nav.handle_action(Action::BulkUpdateStatus {
+            items: vec![ItemType::Story { id: story_id }],
+        })
+        .unwrap();
+
+        assert_eq!(
+            db.read_db()
+                .unwrap()
+                .stories
+                .get(&story_id.0)
+                .unwrap()
+                .detail
+                .status,
+            ItemStatus::Open
+        );
+        assert_eq!(nav.take_notice(), None);
+    }
+
+    #[test]
+    fn handle_action_should_fail_when_the_story_changed_during_the_prompt() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        let elsewhere = Rc::clone(&db);
+        let mut prompts = MockPrompts::new();
+        prompts.update_status = Box::new(move || {
+            elsewhere
+                .update_story_status(story_id, ItemStatus::Closed)
+                .unwrap();
+            Some(ItemStatus::InProgress)
         });
 
-        nav.set_prompts(prompts);
+        nav = nav.with_prompts(prompts);
 
-        nav.handle_action(Action::CreateStory { epic_id: epic_id.0 })
+        assert!(nav
+            .handle_action(Action::UpdateStoryStatus {
+                story_id: story_id.0,
+            })
+            .is_err());
+        assert_eq!(
+            db.read_db().unwrap().stories[&story_id.0].detail.status,
+            ItemStatus::Closed
+        );
+    }
+
+    #[test]
+    fn check_for_updates_should_report_changes_made_by_others() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new())).with_user("alice".to_owned());
+        let db = Rc::new(db);
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        assert_eq!(nav.check_for_updates().unwrap(), None);
+
+        nav.handle_action(Action::NavigateToStoryDetail {
+            epic_id: 0,
+            story_id: story_id.0,
+        })
+        .unwrap();
+
+        assert_eq!(nav.check_for_updates().unwrap(), None);
+
+        db.update_story_status(story_id, ItemStatus::InProgress)
             .unwrap();
 
-        let db_state = db.read_db().unwrap();
-        assert_eq!(db_state.stories.len(), 1);
+        assert_eq!(nav.check_for_updates().unwrap(), None);
 
-        let story = db_state.stories.into_iter().next().unwrap().1;
-        assert_eq!(story.detail.name, "name".to_owned());
-        assert_eq!(story.detail.description, "description".to_owned());
+        let mut state = db.read_db().unwrap();
+        let detail = &mut state.stories.get_mut(&story_id.0).unwrap().detail;
+        detail.version += 1;
+        detail.updated_by = Some("bob".to_owned());
+        db.database.write_db(&state).unwrap();
+
+        assert_eq!(
+            nav.check_for_updates().unwrap(),
+            Some("STORY-0 was updated by bob".to_owned())
+        );
+        assert_eq!(nav.check_for_updates().unwrap(), None);
     }
 
     #[test]
-    fn handle_action_should_handle_update_story() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
-        });
-        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
-        let story_id = db
-            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+    fn open_view_should_open_saved_filters_and_named_pages() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let filter = Filter {
+            status: Some(ItemStatus::Open),
+            ..Default::default()
+        };
+        db.save_filter("my-open-bugs".to_owned(), filter.clone())
             .unwrap();
 
-        let mut nav = Navigator::new(Rc::clone(&db));
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
 
-        let mut prompts = Prompts::new();
-        prompts.update_status = Box::new(|| Some(ItemStatus::InProgress));
+        nav.open_view("my-open-bugs").unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        let search_page = current_page.as_any().downcast_ref::<SearchResults>();
+        assert_eq!(search_page.unwrap().filter, filter);
 
-        nav.set_prompts(prompts);
+        nav.open_view("dashboard").unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page.as_any().downcast_ref::<Dashboard>().is_some());
 
-        nav.handle_action(Action::UpdateStoryStatus {
-            story_id: story_id.0,
-        })
-        .unwrap();
+        nav.open_view("mine").unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        let search_page = current_page.as_any().downcast_ref::<SearchResults>();
+        assert_eq!(search_page.unwrap().filter, Filter::assigned_to(db.user()));
 
-        let db_state = db.read_db().unwrap();
         assert_eq!(
-            db_state.stories.get(&story_id.0).unwrap().detail.status,
-            ItemStatus::InProgress
+            nav.open_view("nope").unwrap_err().to_string(),
+            "There is no saved filter or view called \"nope\""
         );
+        assert_eq!(nav.get_page_count(), 4);
     }
 
     #[test]
-    fn handle_action_should_handle_delete_story() {
-        let db = Rc::new(JiraDataBase {
-            database: Box::new(MockDB::new()),
-        });
+    fn open_recent_should_open_the_nth_most_recently_changed_item() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
         let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
         let story_id = db
             .create_story("".to_owned(), "".to_owned(), Some(epic_id))
             .unwrap();
+        db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+        db.update_story_status(story_id, ItemStatus::InProgress)
+            .unwrap();
 
-        let mut nav = Navigator::new(Rc::clone(&db));
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
 
-        let mut prompts = Prompts::new();
-        prompts.delete_story = Box::new(|| true);
+        nav.open_recent(1).unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        let story_page = current_page.as_any().downcast_ref::<StoryDetail>();
+        assert_eq!(story_page.unwrap().story_id, story_id.0);
 
-        nav.set_prompts(prompts);
+        nav.open_recent(2).unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        let epic_page = current_page.as_any().downcast_ref::<EpicDetail>();
+        assert_eq!(epic_page.unwrap().epic_id, epic_id.0);
 
-        nav.handle_action(Action::DeleteStory {
-            epic_id: epic_id.0,
-            story_id: story_id.0,
-        })
-        .unwrap();
+        assert!(nav.open_recent(3).is_err());
+    }
 
-        let db_state = db.read_db().unwrap();
-        assert_eq!(db_state.stories.len(), 0);
+    #[test]
+    fn handle_input_should_run_chained_commands_in_order() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story_id = db
+            .create_story("".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let mut nav = Navigator::from_prompts(Rc::clone(&db), MockPrompts::new());
+
+        nav.handle_input("0; 0 ;").unwrap();
+        assert_eq!(nav.get_page_count(), 3);
+        let current_page = nav.get_current_page().unwrap();
+        let story_page = current_page.as_any().downcast_ref::<StoryDetail>();
+        assert_eq!(story_page.unwrap().story_id, story_id.0);
+
+        assert_eq!(
+            nav.handle_input("p; zz; p").unwrap_err().to_string(),
+            "Stopped at \"zz\", this page has no such command"
+        );
+        assert_eq!(nav.get_page_count(), 2);
+
+        nav.handle_input("zz").unwrap();
+        nav.handle_input("?").unwrap();
+        assert_eq!(nav.get_page_count(), 3);
+    }
+
+    #[test]
+    fn handle_input_should_move_through_rows_with_the_vim_keymap() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        db.create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        let epic_id = db.create_epic("Auth".to_owned(), "".to_owned()).unwrap();
+
+        let mut nav =
+            Navigator::from_prompts(Rc::clone(&db), MockPrompts::new()).with_config(Config {
+                keymap: Keymap::Vim,
+                ..Default::default()
+            });
+
+        nav.handle_input("j; j; j").unwrap();
+        assert_eq!(nav.take_notice(), Some("> 1 Auth".to_owned()));
+
+        nav.handle_input("l").unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        let epic_page = current_page.as_any().downcast_ref::<EpicDetail>();
+        assert_eq!(epic_page.unwrap().epic_id, epic_id.0);
+
+        nav.handle_input(":q").unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        nav.handle_input("k").unwrap();
+        assert_eq!(nav.take_notice(), Some("> 1 Auth".to_owned()));
+        nav.handle_input("k").unwrap();
+        assert_eq!(nav.take_notice(), Some("> 0 Payments".to_owned()));
+
+        nav.handle_input(":0").unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+        nav.handle_input("h").unwrap();
+        nav.handle_input("h").unwrap();
+        assert_eq!(nav.get_page_count(), 0);
     }
 }
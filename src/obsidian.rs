@@ -0,0 +1,348 @@
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use std::{fs, path::Path};
+
+use crate::keys::{key_format, story_key};
+use crate::model::{Epic, ItemDetail, Story, DB};
+
+/// The frontmatter line that marks a note as written by `write_vault`, so a
+/// later export only ever removes its own notes.
+const MARKER: &str = "source: jira-clone";
+
+/// One Markdown note per epic and story, named after its key, e.g.
+/// `STORY-4.md`.
+pub struct Note {
+    pub file_name: String,
+    pub content: String,
+}
+
+/// Every epic and story as an Obsidian note. Frontmatter carries the status,
+/// labels (as tags) and links, and epics and their stories link to each other
+/// with wiki-links.
+pub fn vault_notes(db: &DB) -> Vec<Note> {
+    let epic_of = |story: &Story| {
+        db.epics
+            .values()
+            .find(|epic| epic.stories.contains(&story.detail.id))
+    };
+    let epics = db
+        .epics
+        .values()
+        .sorted_by_key(|epic| epic.detail.id.0)
+        .map(|epic| epic_note(epic, db));
+    let stories = db
+        .stories
+        .values()
+        .sorted_by_key(|story| story.detail.id.0)
+        .map(|story| story_note(story, epic_of(story), db));
+
+    epics.chain(stories).collect()
+}
+
+/// Writes the notes into `dir`, replacing the ones an earlier export left and
+/// removing those of items that are gone. Other files, notes the user wrote
+/// under a key's name included, are left alone.
+pub fn write_vault(dir: &Path, db: &DB) -> Result<usize> {
+    let notes = vault_notes(db);
+
+    fs::create_dir_all(dir).with_context(|| anyhow!("Failed to create {}", dir.display()))?;
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let stale = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                is_note_name(name) && !notes.iter().any(|note| note.file_name == name)
+            })
+            && is_exported(&path);
+
+        if stale {
+            fs::remove_file(&path)?;
+        }
+    }
+
+    for note in &notes {
+        let path = dir.join(&note.file_name);
+
+        fs::write(&path, &note.content)
+            .with_context(|| anyhow!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(notes.len())
+}
+
+/// Whether `name` looks like a note this module writes, e.g. `EPIC-3.md`.
+fn is_note_name(name: &str) -> bool {
+    let Some(stem) = name.strip_suffix(".md") else {
+        return false;
+    };
+
+    let format = key_format();
+
+    [format.epic, format.story].iter().any(|prefix| {
+        stem.strip_prefix(prefix.as_str())
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|id| id.parse::<u32>().is_ok())
+    })
+}
+
+/// Whether the note at `path` carries `MARKER` in its frontmatter.
+fn is_exported(path: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(path) else {
+        return false;
+    };
+    let mut lines = content.lines();
+
+    lines.next() == Some("---")
+        && lines
+            .take_while(|line| *line != "---")
+            .any(|line| line == MARKER)
+}
+
+fn epic_note(epic: &Epic, db: &DB) -> Note {
+    let mut content = frontmatter(&epic.key(), "epic", &epic.detail, &[]);
+    let stories = epic
+        .stories
+        .iter()
+        .filter_map(|id| db.stories.get(&id.0))
+        .map(|story| {
+            format!(
+                "- {} ({})",
+                wiki_link(&story.key(), &story.detail.name),
+                story.detail.status
+            )
+        })
+        .collect::<Vec<_>>();
+
+    body(&mut content, &epic.detail);
+    if !stories.is_empty() {
+        content.push_str("\n## Stories\n\n");
+        content.push_str(&stories.join("\n"));
+        content.push('\n');
+    }
+
+    Note {
+        file_name: format!("{}.md", epic.key()),
+        content,
+    }
+}
+
+fn story_note(story: &Story, epic: Option<&Epic>, db: &DB) -> Note {
+    let mut properties = Vec::new();
+
+    if let Some(epic) = epic {
+        properties.push(format!("epic: {}", yaml(&format!("[[{}]]", epic.key()))));
+    }
+
+    let links = story
+        .links
+        .iter()
+        .filter(|link| db.stories.contains_key(&link.story_id.0))
+        .map(|link| {
+            format!(
+                "  - {}",
+                yaml(&format!("{} [[{}]]", link.kind, story_key(link.story_id.0)))
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if !links.is_empty() {
+        properties.push(format!("links:\n{}", links.join("\n")));
+    }
+
+    let mut content = frontmatter(&story.key(), "story", &story.detail, &properties);
+
+    body(&mut content, &story.detail);
+    if let Some(epic) = epic {
+        content.push_str(&format!(
+            "\nPart of {}\n",
+            wiki_link(&epic.key(), &epic.detail.name)
+        ));
+    }
+    if !story.checklist.is_empty() {
+        content.push('\n');
+        story.checklist.iter().for_each(|item| {
+            content.push_str(&format!(
+                "- [{}] {}\n",
+                if item.done { "x" } else { " " },
+                item.text
+            ))
+        });
+    }
+
+    Note {
+        file_name: format!("{}.md", story.key()),
+        content,
+    }
+}
+
+fn frontmatter(key: &str, kind: &str, detail: &ItemDetail, extra: &[String]) -> String {
+    let mut lines = vec![
+        "---".to_owned(),
+        format!("key: {key}"),
+        format!("type: {kind}"),
+        MARKER.to_owned(),
+        format!("status: {}", yaml(&detail.status.to_string())),
+        format!("aliases:\n  - {}", yaml(&detail.name)),
+    ];
+
+    if !detail.labels.is_empty() {
+        let tags = detail
+            .labels
+            .iter()
+            .map(|label| format!("  - {}", yaml(label)))
+            .join("\n");
+
+        lines.push(format!("tags:\n{tags}"));
+    }
+    if let Some(assignee) = &detail.assignee {
+        lines.push(format!("assignee: {}", yaml(assignee)));
+    }
+    if let Some(priority) = &detail.priority {
+        lines.push(format!("priority: {priority}"));
+    }
+    if let Some(due) = detail.due {
+        lines.push(format!("due: {due}"));
+    }
+    if let Some(points) = detail.points {
+        lines.push(format!("points: {points}"));
+    }
+
+    lines.extend(extra.iter().cloned());
+    lines.push("---".to_owned());
+
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+fn body(content: &mut String, detail: &ItemDetail) {
+    content.push_str(&format!("# {}\n", detail.name));
+
+    if !detail.description.is_empty() {
+        content.push_str(&format!("\n{}\n", detail.description));
+    }
+}
+
+fn wiki_link(key: &str, name: &str) -> String {
+    format!("[[{key}|{}]]", name.replace(['|', '[', ']'], " "))
+}
+
+/// A quoted YAML string. JSON strings are valid YAML, so they take care of the
+/// escaping.
+fn yaml(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use crate::model::ItemStatus;
+
+    fn board() -> DB {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic("Payments".to_owned(), "Take money.".to_owned())
+            .unwrap();
+        let refunds = db
+            .create_story("Refunds: v2".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let infra = db
+            .create_story("Infra".to_owned(), "".to_owned(), None)
+            .unwrap();
+
+        db.add_blocker(refunds, infra).unwrap();
+        db.update_story_status(refunds, ItemStatus::InProgress)
+            .unwrap();
+        db.add_checklist_item(refunds, "Partial refunds".to_owned())
+            .unwrap();
+
+        let mut state = db.read_db().unwrap();
+        state.stories.get_mut(&refunds.0).unwrap().detail.labels = vec!["billing".to_owned()];
+        state
+    }
+
+    #[test]
+    fn vault_notes_should_link_epics_and_stories() {
+        let notes = vault_notes(&board());
+
+        assert_eq!(
+            notes
+                .iter()
+                .map(|note| note.file_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["EPIC-0.md", "STORY-0.md", "STORY-1.md"]
+        );
+        assert_eq!(
+            notes[0].content,
+            "---\n\
+             key: EPIC-0\n\
+             type: epic\n\
+             source: jira-clone\n\
+             status: \"OPEN\"\n\
+             aliases:\n  - \"Payments\"\n\
+             ---\n\
+             # Payments\n\
+             \n\
+             Take money.\n\
+             \n\
+             ## Stories\n\
+             \n\
+             - [[STORY-0|Refunds: v2]] (IN PROGRESS)\n"
+        );
+        assert_eq!(
+            notes[1].content,
+            "---\n\
+             key: STORY-0\n\
+             type: story\n\
+             source: jira-clone\n\
+             status: \"IN PROGRESS\"\n\
+             aliases:\n  - \"Refunds: v2\"\n\
+             tags:\n  - \"billing\"\n\
+             epic: \"[[EPIC-0]]\"\n\
+             links:\n  - \"blocked by [[STORY-1]]\"\n\
+             ---\n\
+             # Refunds: v2\n\
+             \n\
+             Part of [[EPIC-0|Payments]]\n\
+             \n\
+             - [ ] Partial refunds\n"
+        );
+    }
+
+    #[test]
+    fn write_vault_should_replace_old_notes_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut gone = board();
+        gone.stories.get_mut(&1).unwrap().detail.id.0 = 9;
+        fs::write(
+            dir.path().join("STORY-9.md"),
+            &story_note(&gone.stories[&1], None, &gone).content,
+        )
+        .unwrap();
+        fs::write(dir.path().join("STORY-0.md"), "old").unwrap();
+        fs::write(dir.path().join("Ideas.md"), "mine").unwrap();
+        fs::write(
+            dir.path().join("STORY-99.md"),
+            "---\ntype: story\n---\n# Written by hand\n",
+        )
+        .unwrap();
+
+        assert_eq!(write_vault(dir.path(), &board()).unwrap(), 3);
+
+        assert!(!dir.path().join("STORY-9.md").exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("STORY-99.md")).unwrap(),
+            "---\ntype: story\n---\n# Written by hand\n"
+        );
+        assert!(dir.path().join("EPIC-0.md").exists());
+        assert_ne!(
+            fs::read_to_string(dir.path().join("STORY-0.md")).unwrap(),
+            "old"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Ideas.md")).unwrap(),
+            "mine"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use chrono::NaiveDate;
+use itertools::Itertools;
+
+use crate::model::{ItemDetail, ItemStatus, Story, DB};
+
+/// The board as an Org file: epics are top-level headings, their stories the
+/// entries below them, and stories without an epic are gathered under a
+/// heading of their own. Statuses become TODO keywords and item keys the `ID`
+/// property.
+pub fn board_org(db: &DB) -> String {
+    let mut lines = vec![
+        "#+TITLE: Board".to_owned(),
+        "#+TODO: TODO IN-PROGRESS | RESOLVED CLOSED".to_owned(),
+    ];
+
+    for epic in db.epics.values().sorted_by_key(|epic| epic.detail.id.0) {
+        lines.push(String::new());
+        entry(&mut lines, 1, &epic.key(), &epic.detail);
+
+        for story in epic.stories.iter().filter_map(|id| db.stories.get(&id.0)) {
+            story_entry(&mut lines, story);
+        }
+    }
+
+    let without_epic = db
+        .stories
+        .values()
+        .filter(|story| {
+            !db.epics
+                .values()
+                .any(|epic| epic.stories.contains(&story.detail.id))
+        })
+        .sorted_by_key(|story| story.detail.id.0)
+        .collect::<Vec<_>>();
+
+    if !without_epic.is_empty() {
+        lines.push(String::new());
+        lines.push("* Stories without an epic".to_owned());
+        without_epic
+            .into_iter()
+            .for_each(|story| story_entry(&mut lines, story));
+    }
+
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+fn story_entry(lines: &mut Vec<String>, story: &Story) {
+    entry(lines, 2, &story.key(), &story.detail);
+
+    let indent = " ".repeat(3);
+    lines.extend(story.checklist.iter().map(|item| {
+        format!(
+            "{indent}- [{}] {}",
+            if item.done { "X" } else { " " },
+            item.text
+        )
+    }));
+}
+
+/// A heading with its deadline, properties and description, the body indented
+/// past the stars so a line of the description can't start a heading.
+fn entry(lines: &mut Vec<String>, level: usize, key: &str, detail: &ItemDetail) {
+    let indent = " ".repeat(level + 1);
+    let mut heading = format!(
+        "{} {} {}",
+        "*".repeat(level),
+        keyword(&detail.status),
+        detail.name
+    );
+
+    if !detail.labels.is_empty() {
+        heading.push_str(&format!(
+            " :{}:",
+            detail.labels.iter().map(|label| tag(label)).join(":")
+        ));
+    }
+
+    lines.push(heading);
+
+    if let Some(due) = detail.due {
+        lines.push(format!("{indent}DEADLINE: {}", timestamp(due)));
+    }
+
+    lines.push(format!("{indent}:PROPERTIES:"));
+    lines.push(format!("{indent}:ID: {key}"));
+    if let Some(assignee) = &detail.assignee {
+        lines.push(format!("{indent}:ASSIGNEE: {assignee}"));
+    }
+    if let Some(priority) = &detail.priority {
+        lines.push(format!("{indent}:PRIORITY_LEVEL: {priority}"));
+    }
+    if let Some(points) = detail.points {
+        lines.push(format!("{indent}:POINTS: {points}"));
+    }
+    lines.push(format!("{indent}:END:"));
+
+    lines.extend(
+        detail
+            .description
+            .lines()
+            .map(|line| format!("{indent}{line}").trim_end().to_owned()),
+    );
+}
+
+fn keyword(status: &ItemStatus) -> &'static str {
+    match status {
+        ItemStatus::Open => "TODO",
+        ItemStatus::InProgress => "IN-PROGRESS",
+        ItemStatus::Resolved => "RESOLVED",
+        ItemStatus::Closed => "CLOSED",
+    }
+}
+
+/// Org tags only take letters, digits, `_`, `@`, `#` and `%`.
+fn tag(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| match c {
+            c if c.is_alphanumeric() || "_@#%".contains(c) => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn timestamp(date: NaiveDate) -> String {
+    date.format("<%Y-%m-%d %a>").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use crate::model::Priority;
+
+    #[test]
+    fn board_org_should_nest_stories_under_their_epics() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(
+                "Payments".to_owned(),
+                "Take money.\n* not a heading".to_owned(),
+            )
+            .unwrap();
+        let story_id = db
+            .create_story("Refunds".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+        let _ = db
+            .create_story("Loose end".to_owned(), "".to_owned(), None)
+            .unwrap();
+        db.update_story_status(story_id, ItemStatus::InProgress)
+            .unwrap();
+        db.add_checklist_item(story_id, "Partial refunds".to_owned())
+            .unwrap();
+
+        let mut state = db.read_db().unwrap();
+        let refunds = &mut state.stories.get_mut(&story_id.0).unwrap().detail;
+        refunds.labels = vec!["billing".to_owned(), "follow-up".to_owned()];
+        refunds.due = NaiveDate::from_ymd_opt(2024, 5, 3);
+        refunds.assignee = Some("alice".to_owned());
+        refunds.priority = Some(Priority::High);
+
+        assert_eq!(
+            board_org(&state),
+            "#+TITLE: Board\n\
+             #+TODO: TODO IN-PROGRESS | RESOLVED CLOSED\n\
+             \n\
+             * TODO Payments\n  \
+             :PROPERTIES:\n  \
+             :ID: EPIC-0\n  \
+             :END:\n  \
+             Take money.\n  \
+             * not a heading\n\
+             ** IN-PROGRESS Refunds :billing:follow_up:\n   \
+             DEADLINE: <2024-05-03 Fri>\n   \
+             :PROPERTIES:\n   \
+             :ID: STORY-0\n   \
+             :ASSIGNEE: alice\n   \
+             :PRIORITY_LEVEL: HIGH\n   \
+             :END:\n   \
+             - [ ] Partial refunds\n\
+             \n\
+             * Stories without an epic\n\
+             ** TODO Loose end\n   \
+             :PROPERTIES:\n   \
+             :ID: STORY-1\n   \
+             :END:\n"
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use rhai::{
+    serde::{from_dynamic, to_dynamic},
+    Array, Dynamic, Engine, EvalAltResult, Map, Scope, AST,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fs, path::Path, rc::Rc};
+
+use crate::db::{DataBase, JiraDataBase, Listing, SortBy};
+use crate::model::{Epic, Filter, ItemDetail, Story, DB};
+use crate::search::SearchHit;
+
+/// How many operations a single hook or command may run before it is stopped,
+/// so a runaway loop in a script can't hang the board.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Rhai scripts from the plugin directory, e.g. `~/.config/lgr/plugins/`.
+///
+/// A script takes part by defining any of these functions:
+///
+/// - `on_save(kind, item)` runs for every epic (`kind == "epic"`) or story that
+///   is about to be saved with changes. It may return the item with fields
+///   changed, or `throw` a message to reject the change.
+/// - `command_<name>(board, args)` adds `jira-clone run <name> [args]`. It may
+///   return a changed board to save it.
+pub struct Plugins {
+    engine: Engine,
+    scripts: Vec<Script>,
+}
+
+struct Script {
+    name: String,
+    ast: AST,
+}
+
+impl Plugins {
+    pub fn none() -> Self {
+        Self {
+            engine: Engine::new(),
+            scripts: Vec::new(),
+        }
+    }
+
+    /// Compiles every `.rhai` file in `dir`, in file name order. A missing
+    /// directory means no plugins.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut plugins = Self::none();
+
+        plugins.engine.set_max_operations(MAX_OPERATIONS);
+
+        if !dir.exists() {
+            return Ok(plugins);
+        }
+
+        let paths = fs::read_dir(dir)
+            .with_context(|| anyhow!("Failed to read plugins from {}", dir.display()))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+
+        for path in paths
+            .into_iter()
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "rhai")
+            })
+            .sorted()
+        {
+            let source = fs::read_to_string(&path)?;
+            plugins.add(&path.display().to_string(), &source)?;
+        }
+
+        Ok(plugins)
+    }
+
+    fn add(&mut self, name: &str, source: &str) -> Result<()> {
+        let ast = self
+            .engine
+            .compile(source)
+            .map_err(|error| anyhow!("Failed to compile plugin {name}: {error}"))?;
+
+        self.scripts.push(Script {
+            name: name.to_owned(),
+            ast,
+        });
+        Ok(())
+    }
+
+    fn defining<'a>(&'a self, function: &'a str, arity: usize) -> impl Iterator<Item = &'a Script> {
+        self.scripts.iter().filter(move |script| {
+            script
+                .ast
+                .iter_functions()
+                .any(|f| f.name == function && f.params.len() == arity)
+        })
+    }
+
+    /// The names of the commands the scripts add, sorted.
+    pub fn commands(&self) -> Vec<String> {
+        self.scripts
+            .iter()
+            .flat_map(|script| script.ast.iter_functions())
+            .filter_map(|f| f.name.strip_prefix("command_").map(str::to_owned))
+            .sorted()
+            .collect()
+    }
+
+    /// Runs the `on_save` hooks on an item about to be saved, letting each one
+    /// change it in turn. Its id, version, history and attribution stay as
+    /// they were.
+    pub fn on_save(&self, kind: &str, key: &str, detail: &mut ItemDetail) -> Result<()> {
+        for script in self.defining("on_save", 2) {
+            let item = item_map(detail)?;
+            let result = self
+                .engine
+                .call_fn::<Dynamic>(
+                    &mut Scope::new(),
+                    &script.ast,
+                    "on_save",
+                    (kind.to_owned(), item),
+                )
+                .map_err(|error| script_error(&script.name, key, *error))?;
+
+            if result.is_unit() {
+                continue;
+            }
+
+            let changed: ItemDetail = from_script(&result).map_err(|error| {
+                anyhow!("Plugin {} returned an invalid {key}: {error}", script.name)
+            })?;
+
+            *detail = ItemDetail {
+                id: detail.id,
+                version: detail.version,
+                history: detail.history.clone(),
+                updated_by: detail.updated_by.clone(),
+                updated_at: detail.updated_at,
+                ..changed
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Runs `command_<name>` with the board and `args`, saving the board it
+    /// returns, if any.
+    pub fn run_command(&self, name: &str, args: &[String], database: &JiraDataBase) -> Result<()> {
+        let function = format!("command_{name}");
+        let script = self.defining(&function, 2).next().ok_or_else(|| {
+            anyhow!(
+                "No plugin adds a command named {name}, the plugins add: {}",
+                self.commands().join(", ")
+            )
+        })?;
+        let board = to_script(&database.read_db()?)?;
+        let args: Array = args.iter().cloned().map(Dynamic::from).collect();
+        let result = self
+            .engine
+            .call_fn::<Dynamic>(&mut Scope::new(), &script.ast, &function, (board, args))
+            .map_err(|error| script_error(&script.name, name, *error))?;
+
+        if result.is_unit() {
+            return Ok(());
+        }
+
+        let board: DB = from_script(&result).map_err(|error| {
+            anyhow!("Plugin {} returned an invalid board: {error}", script.name)
+        })?;
+
+        database.replace_db(board)
+    }
+}
+
+/// An item as a script sees it: every field is there, unset ones as `()` or
+/// an empty array, so hooks don't have to check before using them.
+fn item_map(detail: &ItemDetail) -> Result<Dynamic> {
+    let mut item = to_script(detail)?.cast::<Map>();
+
+    for field in ["labels", "watchers"] {
+        item.entry(field.into())
+            .or_insert_with(|| Dynamic::from_array(Array::new()));
+    }
+    for field in ["priority", "assignee", "due", "points"] {
+        item.entry(field.into()).or_insert(Dynamic::UNIT);
+    }
+
+    Ok(Dynamic::from_map(item))
+}
+
+/// Hands a value to a script. Goes through JSON, which turns the numeric ids
+/// the board is keyed by into the string keys script maps need.
+fn to_script<T: Serialize>(value: &T) -> Result<Dynamic> {
+    to_dynamic(serde_json::to_value(value)?).map_err(|error| anyhow!("{error}"))
+}
+
+/// Reads back what a script returned, the other way around.
+fn from_script<T: DeserializeOwned>(value: &Dynamic) -> Result<T> {
+    let json: serde_json::Value = from_dynamic(value).map_err(|error| anyhow!("{error}"))?;
+
+    Ok(serde_json::from_value(json)?)
+}
+
+fn script_error(script: &str, subject: &str, error: EvalAltResult) -> anyhow::Error {
+    match error {
+        EvalAltResult::ErrorRuntime(message, _) => {
+            anyhow!("Plugin {script} rejected {subject}: {message}")
+        }
+        error => anyhow!("Plugin {script} failed on {subject}: {error}"),
+    }
+}
+
+/// Runs the plugin hooks on everything that changed before handing the board to
+/// the wrapped database.
+pub struct PluginDatabase {
+    inner: Box<dyn DataBase>,
+    plugins: Rc<Plugins>,
+}
+
+impl PluginDatabase {
+    pub fn new(inner: Box<dyn DataBase>, plugins: Rc<Plugins>) -> Self {
+        Self { inner, plugins }
+    }
+}
+
+impl DataBase for PluginDatabase {
+    fn read_db(&self) -> Result<DB> {
+        self.inner.read_db()
+    }
+
+    fn list_epics(&self, offset: usize, limit: usize, sort: SortBy) -> Result<Listing<Epic>> {
+        self.inner.list_epics(offset, limit, sort)
+    }
+
+    fn list_stories(&self, offset: usize, limit: usize, sort: SortBy) -> Result<Listing<Story>> {
+        self.inner.list_stories(offset, limit, sort)
+    }
+
+    fn search(&self, filter: &Filter) -> Result<Vec<SearchHit>> {
+        self.inner.search(filter)
+    }
+
+    fn persist(&self) -> Result<()> {
+        self.inner.persist()
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        self.inner.has_unsaved_changes()
+    }
+
+    fn revision(&self) -> Result<u64> {
+        self.inner.revision()
+    }
+
+    fn write_db(&self, database: &DB) -> Result<()> {
+        let stored = self.inner.read_db()?;
+        let mut database = database.clone();
+
+        for (id, epic) in database.epics.iter_mut() {
+            if stored.epics.get(id) != Some(epic) {
+                let key = epic.key();
+                self.plugins.on_save("epic", &key, &mut epic.detail)?;
+            }
+        }
+
+        for (id, story) in database.stories.iter_mut() {
+            if stored.stories.get(id) != Some(story) {
+                let key = story.key();
+                self.plugins.on_save("story", &key, &mut story.detail)?;
+            }
+        }
+
+        self.inner.write_db(&database)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+
+    fn plugins(sources: &[&str]) -> Rc<Plugins> {
+        let mut plugins = Plugins::none();
+
+        for (index, source) in sources.iter().enumerate() {
+            plugins.add(&format!("plugin{index}.rhai"), source).unwrap();
+        }
+
+        Rc::new(plugins)
+    }
+
+    fn database(plugins: Rc<Plugins>) -> JiraDataBase {
+        JiraDataBase::from_database(Box::new(PluginDatabase::new(
+            Box::new(MockDB::new()),
+            plugins,
+        )))
+    }
+
+    #[test]
+    fn on_save_hooks_should_change_items() {
+        let db = database(plugins(&[
+            r#"
+            fn on_save(kind, item) {
+                if kind == "story" && item.name.starts_with("Bug:") && !item.labels.contains("bug") {
+                    item.labels.push("bug");
+                    return item;
+                }
+            }
+            "#,
+            r#"
+            fn on_save(kind, item) {
+                item.id = 42;
+                item.name.trim();
+                item
+            }
+            "#,
+        ]));
+
+        let story_id = db
+            .create_story("Bug: login fails ".to_owned(), "".to_owned(), None)
+            .unwrap();
+
+        let story = db.read_db().unwrap().stories[&story_id.0].clone();
+        assert_eq!(story.detail.labels, vec!["bug".to_owned()]);
+        assert_eq!(story.detail.name, "Bug: login fails");
+        assert_eq!(story.detail.id, story_id);
+    }
+
+    #[test]
+    fn on_save_hooks_should_reject_changes() {
+        let db = database(plugins(&[r#"
+            fn on_save(kind, item) {
+                if kind == "epic" && item.name == "" {
+                    throw "epics need a name";
+                }
+            }
+            "#]));
+
+        let error = db.create_epic("".to_owned(), "".to_owned()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Plugin plugin0.rhai rejected EPIC-0: epics need a name"
+        );
+        assert!(db.read_db().unwrap().epics.is_empty());
+        assert!(db.create_epic("Payments".to_owned(), "".to_owned()).is_ok());
+    }
+
+    #[test]
+    fn run_command_should_save_the_returned_board() {
+        let plugins = plugins(&[r#"
+            fn command_close_all(board, args) {
+                for id in board.stories.keys() {
+                    board.stories[id].detail.status = args[0];
+                }
+                board
+            }
+
+            fn command_count(board, args) {
+                print(board.stories.len());
+            }
+            "#]);
+        let db = database(Rc::clone(&plugins));
+        let story_id = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        assert_eq!(plugins.commands(), vec!["close_all", "count"]);
+
+        plugins.run_command("count", &[], &db).unwrap();
+        plugins
+            .run_command("close_all", &["Closed".to_owned()], &db)
+            .unwrap();
+
+        assert_eq!(
+            db.read_db().unwrap().stories[&story_id.0].detail.status,
+            crate::model::ItemStatus::Closed
+        );
+        assert!(plugins.run_command("missing", &[], &db).is_err());
+    }
+}
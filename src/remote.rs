@@ -0,0 +1,497 @@
+use anyhow::{anyhow, Context, Result};
+use std::{
+    fmt::Display,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::db::DataBase;
+use crate::integrity::constant_time_eq;
+use crate::model::DB;
+
+/// How long the server waits on a client that connected but hasn't finished
+/// its request, since it answers one connection at a time.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where a board server listens: `host:port`, or `unix:<path>` for a unix
+/// socket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Address {
+    Tcp(String),
+    Unix(PathBuf),
+}
+
+impl Address {
+    pub fn parse(address: &str) -> Self {
+        match address.strip_prefix("unix:") {
+            Some(path) => Self::Unix(PathBuf::from(path)),
+            None => Self::Tcp(address.to_owned()),
+        }
+    }
+}
+
+impl Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(address) => write!(f, "{address}"),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// The routes the server answers: `GET /board`, `PUT /board` and
+/// `GET /revision`, each on a connection of its own.
+const BOARD: &str = "/board";
+const REVISION: &str = "/revision";
+
+/// The parts of an HTTP request or response the board looks at: its first
+/// line, its headers and its body.
+struct Message {
+    start: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Message {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A board another process owns, reached through `jira-clone serve`. Every
+/// read and write is a round trip, so all clients see each other's changes.
+pub struct RemoteDatabase {
+    address: Address,
+    token: Option<String>,
+}
+
+impl RemoteDatabase {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            token: None,
+        }
+    }
+
+    /// Sends `token` along with every request, for servers started with one.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Sends `method` on `path` and returns the body of a successful answer.
+    /// The server's message for any other answer is the error.
+    fn request(&self, method: &str, path: &str, body: &[u8]) -> Result<Vec<u8>> {
+        let failed = || anyhow!("Failed to reach the board server at {}", self.address);
+        let mut headers = vec![
+            ("Host", self.address.to_string()),
+            ("Content-Type", "application/json".to_owned()),
+        ];
+
+        if let Some(token) = &self.token {
+            headers.push(("Authorization", format!("Bearer {token}")));
+        }
+
+        let request = (format!("{method} {path} HTTP/1.1"), headers, body);
+        let response = match &self.address {
+            Address::Tcp(address) => {
+                exchange(TcpStream::connect(address).with_context(failed)?, request)
+            }
+            #[cfg(unix)]
+            Address::Unix(path) => exchange(
+                std::os::unix::net::UnixStream::connect(path).with_context(failed)?,
+                request,
+            ),
+            #[cfg(not(unix))]
+            Address::Unix(_) => Err(anyhow!("Unix sockets aren't supported on this system")),
+        }?;
+
+        match response.start.split_whitespace().nth(1) {
+            Some(status) if status.starts_with('2') => Ok(response.body),
+            Some(_) => Err(anyhow!(String::from_utf8_lossy(&response.body).into_owned())),
+            None => Err(anyhow!("The board server sent an invalid answer")),
+        }
+    }
+}
+
+impl DataBase for RemoteDatabase {
+    fn read_db(&self) -> Result<DB> {
+        let body = self.request("GET", BOARD, &[])?;
+
+        serde_json::from_slice(&body).context("The board server sent an invalid board")
+    }
+
+    fn write_db(&self, database: &DB) -> Result<()> {
+        self.request("PUT", BOARD, &serde_json::to_vec(database)?)?;
+        Ok(())
+    }
+
+    /// Asks for the revision alone, so polling for changes doesn't send the
+    /// whole board each time.
+    fn revision(&self) -> Result<u64> {
+        let body = self.request("GET", REVISION, &[])?;
+
+        serde_json::from_slice(&body).context("The board server sent an invalid revision")
+    }
+}
+
+type Outgoing<'a> = (String, Vec<(&'a str, String)>, &'a [u8]);
+
+/// Sends one request on `stream` and reads the response, which ends the
+/// connection.
+fn exchange(mut stream: impl Read + Write, request: Outgoing) -> Result<Message> {
+    write_message(&mut stream, request)?;
+
+    read_message(&mut BufReader::new(stream)).context("The board server sent an invalid answer")
+}
+
+fn write_message(stream: &mut impl Write, (start, headers, body): Outgoing) -> Result<()> {
+    write!(stream, "{start}\r\n")?;
+    for (name, value) in headers {
+        write!(stream, "{name}: {value}\r\n")?;
+    }
+    write!(
+        stream,
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn read_message(stream: &mut impl BufRead) -> Result<Message> {
+    let read_line = |stream: &mut dyn BufRead| -> Result<String> {
+        let mut line = String::new();
+        stream.read_line(&mut line)?;
+        Ok(line.trim_end_matches(['\r', '\n']).to_owned())
+    };
+    let start = read_line(stream)?;
+    let mut headers = Vec::new();
+
+    loop {
+        let line = read_line(stream)?;
+
+        if line.is_empty() {
+            break;
+        }
+
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid HTTP header: {line}"))?;
+        headers.push((name.trim().to_owned(), value.trim().to_owned()));
+    }
+
+    let mut message = Message {
+        start,
+        headers,
+        body: Vec::new(),
+    };
+
+    // Both ends always say how long the body is, so a message without it has
+    // none, like a GET from curl.
+    if let Some(length) = message.header("Content-Length") {
+        message.body = vec![0; length.parse().context("Invalid Content-Length")?];
+        stream.read_exact(&mut message.body)?;
+    }
+
+    Ok(message)
+}
+
+/// Serves `database` on `address` until the process is stopped. Connections
+/// are answered one at a time, so a write can't land between the check of
+/// another and its save. Without a `token` only this machine can connect.
+///
+/// The server speaks plain HTTP, so off this machine the token and the board
+/// travel unencrypted. Reach it through an SSH tunnel or a TLS proxy there.
+pub fn serve(address: &Address, database: &dyn DataBase, token: Option<&str>) -> Result<()> {
+    match address {
+        Address::Tcp(address) => {
+            let listener = TcpListener::bind(address)
+                .with_context(|| anyhow!("Failed to listen on {address}"))?;
+            let local = listener.local_addr()?;
+
+            if !local.ip().is_loopback() {
+                if token.is_none() {
+                    return Err(anyhow!(
+                        "Serving on {local} lets other machines in, set LGR_SERVER_TOKEN or \
+                         `server_token` in the config, or listen on 127.0.0.1"
+                    ));
+                }
+
+                eprintln!(
+                    "Warning: {local} is served without TLS, so the token and the board can \
+                     be read on the way. Put a TLS proxy or an SSH tunnel in front of it."
+                );
+            }
+
+            println!("Serving the board on http://{local}");
+            for stream in listener.incoming() {
+                let stream = stream?;
+
+                stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+                report(answer(stream, database, token));
+            }
+        }
+        #[cfg(unix)]
+        Address::Unix(path) => {
+            use std::os::unix::net::{UnixListener, UnixStream};
+
+            // A socket left behind by a server that didn't shut down cleanly
+            // would make the bind fail, unless something still answers on it.
+            if path.exists() && UnixStream::connect(path).is_err() {
+                std::fs::remove_file(path)?;
+            }
+
+            let listener = UnixListener::bind(path)
+                .with_context(|| anyhow!("Failed to listen on {}", path.display()))?;
+
+            println!("Serving the board on {}", path.display());
+            for stream in listener.incoming() {
+                let stream = stream?;
+
+                stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+                report(answer(stream, database, token));
+            }
+        }
+        #[cfg(not(unix))]
+        Address::Unix(_) => return Err(anyhow!("Unix sockets aren't supported on this system")),
+    }
+
+    Ok(())
+}
+
+/// A client that went away shouldn't stop the server.
+fn report(result: Result<()>) {
+    if let Err(error) = result {
+        eprintln!("{error:#}");
+    }
+}
+
+fn answer(
+    mut stream: impl Read + Write,
+    database: &dyn DataBase,
+    token: Option<&str>,
+) -> Result<()> {
+    let request = read_message(&mut BufReader::new(&mut stream))?;
+    let (status, content_type, body) = match respond(&request, database, token) {
+        Ok(body) => (200, "application/json", body),
+        Err((status, message)) => (status, "text/plain; charset=utf-8", message.into_bytes()),
+    };
+
+    write_message(
+        &mut stream,
+        (
+            format!("HTTP/1.1 {status} {}", reason(status)),
+            vec![("Content-Type", content_type.to_owned())],
+            &body,
+        ),
+    )
+}
+
+/// The body of the answer to `request`, or its status and message when it
+/// fails.
+fn respond(
+    request: &Message,
+    database: &dyn DataBase,
+    token: Option<&str>,
+) -> std::result::Result<Vec<u8>, (u16, String)> {
+    let failed = |error: anyhow::Error| (500, format!("{error:#}"));
+    let sent = request
+        .header("Authorization")
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let allowed = token.is_none_or(|token| {
+        sent.is_some_and(|sent| constant_time_eq(sent.as_bytes(), token.as_bytes()))
+    });
+
+    if !allowed {
+        return Err((401, "The board server turned down the token".to_owned()));
+    }
+
+    let mut start = request.start.split_whitespace();
+
+    match (start.next(), start.next()) {
+        (Some("GET"), Some(BOARD)) => {
+            let board = database.read_db().map_err(failed)?;
+
+            serde_json::to_vec(&board).map_err(|error| failed(error.into()))
+        }
+        (Some("GET"), Some(REVISION)) => {
+            let revision = database.revision().map_err(failed)?;
+
+            serde_json::to_vec(&revision).map_err(|error| failed(error.into()))
+        }
+        (Some("PUT"), Some(BOARD)) => {
+            let board: DB = serde_json::from_slice(&request.body)
+                .map_err(|error| (400, format!("The board sent is invalid: {error}")))?;
+            let stored = database.read_db().map_err(failed)?;
+
+            check_write(&stored, &board).map_err(|error| (409, error.to_string()))?;
+            database.write_db(&board).map_err(failed)?;
+            Ok(b"null".to_vec())
+        }
+        (Some(_), Some(BOARD | REVISION)) => Err((405, "Method not allowed".to_owned())),
+        _ => Err((404, "Not found".to_owned())),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Clients write back the whole board they read, so a write made from an older
+/// revision would undo whatever other clients saved in between: their items,
+/// notes, filters, sprints and notifications alike.
+fn check_write(stored: &DB, incoming: &DB) -> Result<()> {
+    if incoming.revision != stored.revision {
+        return Err(anyhow!(
+            "The board was changed by someone else in the meantime, reload and try again"
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+
+    #[test]
+    fn address_should_tell_unix_sockets_apart() {
+        assert_eq!(
+            Address::parse("127.0.0.1:7878"),
+            Address::Tcp("127.0.0.1:7878".to_owned())
+        );
+        assert_eq!(
+            Address::parse("unix:/tmp/board.sock"),
+            Address::Unix(PathBuf::from("/tmp/board.sock"))
+        );
+    }
+
+    #[test]
+    fn check_write_should_reject_writes_made_from_an_older_revision() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        db.create_story("Refunds".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let loaded = db.read_db().unwrap();
+        let stored = DB {
+            revision: loaded.revision + 1,
+            notes: vec!["Ask finance".to_owned()],
+            ..loaded.clone()
+        };
+
+        assert!(check_write(&stored, &stored).is_ok());
+        assert_eq!(
+            check_write(&stored, &loaded).unwrap_err().to_string(),
+            "The board was changed by someone else in the meantime, reload and try again"
+        );
+    }
+
+    #[test]
+    fn serve_should_keep_other_machines_out_without_a_token() {
+        let database = MockDB::new();
+        let error = serve(&Address::parse("0.0.0.0:0"), &database, None).unwrap_err();
+
+        assert!(error.to_string().contains("set LGR_SERVER_TOKEN"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn answer_should_serve_reads_and_writes() {
+        use std::os::unix::net::UnixStream;
+
+        let database = MockDB::new();
+        let send = |method: &str, path: &str, body: Vec<u8>, token: Option<&str>| {
+            let (client, server) = UnixStream::pair().unwrap();
+            let mut headers = vec![("Host", "localhost".to_owned())];
+
+            if let Some(token) = token {
+                headers.push(("Authorization", format!("Bearer {token}")));
+            }
+
+            let start = format!("{method} {path} HTTP/1.1");
+            let handle = std::thread::spawn(move || {
+                let response = exchange(client, (start, headers, &body)).unwrap();
+                (response.start, String::from_utf8(response.body).unwrap())
+            });
+
+            answer(server, &database, Some("s3cret")).unwrap();
+            handle.join().unwrap()
+        };
+        let write = |board: &DB| {
+            let (start, _) = send(
+                "PUT",
+                "/board",
+                serde_json::to_vec(board).unwrap(),
+                Some("s3cret"),
+            );
+            start
+        };
+        let read = || {
+            let (_, body) = send("GET", "/board", Vec::new(), Some("s3cret"));
+            serde_json::from_str::<DB>(&body).unwrap()
+        };
+        let board = JiraDataBase::from_database(Box::new(MockDB::new()));
+        board
+            .create_story("Refunds".to_owned(), "".to_owned(), None)
+            .unwrap();
+        // Read from the other board, but written as if from this one.
+        let board = DB {
+            revision: 0,
+            ..board.read_db().unwrap()
+        };
+
+        assert_eq!(
+            send("GET", "/board", Vec::new(), None),
+            (
+                "HTTP/1.1 401 Unauthorized".to_owned(),
+                "The board server turned down the token".to_owned()
+            )
+        );
+        assert_eq!(
+            send("GET", "/board", Vec::new(), Some("s3cre")).0,
+            "HTTP/1.1 401 Unauthorized"
+        );
+        assert_eq!(
+            send("GET", "/boards", Vec::new(), Some("s3cret")).0,
+            "HTTP/1.1 404 Not Found"
+        );
+        assert_eq!(write(&board), "HTTP/1.1 200 OK");
+
+        let first = read();
+        assert_eq!(first.revision, 1);
+        assert_eq!(first.stories, board.stories);
+
+        // Another client adds a note after the first one read the board.
+        let mut other = first.clone();
+        other.notes.push("Ask finance".to_owned());
+        assert_eq!(write(&other), "HTTP/1.1 200 OK");
+
+        let mut stale = first;
+        stale.stories.get_mut(&0).unwrap().detail.name = "Full refunds".to_owned();
+
+        assert_eq!(write(&stale), "HTTP/1.1 409 Conflict");
+        assert_eq!(
+            send("GET", "/revision", Vec::new(), Some("s3cret")),
+            ("HTTP/1.1 200 OK".to_owned(), "2".to_owned())
+        );
+
+        let stored = read();
+        assert_eq!(stored.notes, vec!["Ask finance"]);
+        assert_eq!(stored.stories[&0].detail.name, "Refunds");
+    }
+}
@@ -0,0 +1,542 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Days, Local, NaiveDate, Utc};
+use itertools::Itertools;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::calendar::Calendar;
+use crate::model::{ItemDetail, ItemStatus, LinkKind, Sprint, SprintStory, Story, DB};
+use crate::stats::{is_blocked, is_done, sprint_stories};
+
+/// What the user closed yesterday, is working on and is stuck on, as plain text
+/// ready to paste into a chat.
+pub fn standup(db: &DB, user: &str, calendar: &Calendar, now: DateTime<Utc>) -> String {
+    let today = now.with_timezone(&calendar.zone).date_naive();
+    let yesterday = today - Days::new(1);
+    let mine = db
+        .stories
+        .values()
+        .filter(|story| story.detail.assignee.as_deref() == Some(user))
+        .sorted_by_key(|story| story.detail.id.0)
+        .collect::<Vec<_>>();
+
+    let closed = mine
+        .iter()
+        .filter(|story| is_done(&story.detail.status))
+        .filter(|story| {
+            story.detail.history.last().is_some_and(|change| {
+                change.at.with_timezone(&calendar.zone).date_naive() == yesterday
+            })
+        })
+        .map(|story| format!("- {} {}", story.key(), story.detail.name));
+    let in_progress = mine
+        .iter()
+        .filter(|story| story.detail.status == ItemStatus::InProgress)
+        .filter(|story| !is_blocked(db, story))
+        .map(|story| format!("- {} {}", story.key(), story.detail.name));
+    let blocked = mine
+        .iter()
+        .filter(|story| is_blocked(db, story))
+        .map(|story| {
+            format!(
+                "- {} {} (blocked by {})",
+                story.key(),
+                story.detail.name,
+                open_blockers(db, story).join(", ")
+            )
+        });
+
+    [
+        format!("Standup {} (@{user})", calendar.date(today)),
+        section("Yesterday", closed),
+        section("Today", in_progress),
+        section("Blocked", blocked),
+    ]
+    .join("\n\n")
+}
+
+/// How many stories were in each status at the end of a day.
+#[derive(Debug, PartialEq)]
+pub struct FlowDay {
+    pub date: NaiveDate,
+    pub open: usize,
+    pub in_progress: usize,
+    pub resolved: usize,
+    pub closed: usize,
+}
+
+/// Replays the status history of every story to count them per status for each
+/// day from the first recorded change up to `today`. Stories without a history
+/// count with their current status on every day.
+pub fn cumulative_flow(db: &DB, today: NaiveDate) -> Vec<FlowDay> {
+    let first_day = db
+        .stories
+        .values()
+        .filter_map(|story| story.detail.history.first())
+        .map(|change| local_date(change.at))
+        .min()
+        .unwrap_or(today)
+        .min(today);
+
+    first_day
+        .iter_days()
+        .take_while(|date| *date <= today)
+        .map(|date| {
+            let mut day = FlowDay {
+                date,
+                open: 0,
+                in_progress: 0,
+                resolved: 0,
+                closed: 0,
+            };
+
+            for status in db
+                .stories
+                .values()
+                .filter_map(|story| status_on(&story.detail, date))
+            {
+                match status {
+                    ItemStatus::Open => day.open += 1,
+                    ItemStatus::InProgress => day.in_progress += 1,
+                    ItemStatus::Resolved => day.resolved += 1,
+                    ItemStatus::Closed => day.closed += 1,
+                }
+            }
+
+            day
+        })
+        .collect()
+}
+
+pub fn cumulative_flow_csv(flow: &[FlowDay]) -> String {
+    let rows = flow.iter().map(|day| {
+        format!(
+            "{},{},{},{},{}",
+            day.date, day.open, day.in_progress, day.resolved, day.closed
+        )
+    });
+
+    std::iter::once("date,open,in_progress,resolved,closed".to_owned())
+        .chain(rows)
+        .map(|row| row + "\n")
+        .collect()
+}
+
+/// A Mermaid `gantt` diagram of the sprints and of the epics with a due date,
+/// ready to embed in Markdown. Epics start the day they were created and are
+/// tagged `active` or `done` by their status.
+pub fn gantt(db: &DB) -> String {
+    let mut lines = vec![
+        "gantt".to_owned(),
+        "    title Roadmap".to_owned(),
+        "    dateFormat YYYY-MM-DD".to_owned(),
+    ];
+
+    let sprints = db
+        .sprints
+        .values()
+        .sorted_by_key(|sprint| (sprint.start, sprint.id.0))
+        .map(|sprint| {
+            format!(
+                "    {} :{}, {}, {}",
+                gantt_name(&sprint.name),
+                sprint.key(),
+                sprint.start,
+                sprint.end
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if !sprints.is_empty() {
+        lines.push("    section Sprints".to_owned());
+        lines.extend(sprints);
+    }
+
+    let epics = db
+        .epics
+        .values()
+        .sorted_by_key(|epic| epic.detail.id.0)
+        .filter_map(|epic| {
+            let due = epic.detail.due?;
+            let start = epic
+                .detail
+                .history
+                .first()
+                .map_or(due, |change| local_date(change.at).min(due));
+            let tag = match epic.detail.status {
+                ItemStatus::Open => "",
+                ItemStatus::InProgress => "active, ",
+                ItemStatus::Resolved | ItemStatus::Closed => "done, ",
+            };
+
+            Some(format!(
+                "    {} :{tag}{}, {start}, {due}",
+                gantt_name(&epic.detail.name),
+                epic.key()
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    if !epics.is_empty() {
+        lines.push("    section Epics".to_owned());
+        lines.extend(epics);
+    }
+
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+/// A Markdown retrospective of `sprint`: the stories it finished, the ones
+/// carried over and the open blockers still holding them up.
+pub fn sprint_report(db: &DB, sprint: &Sprint, calendar: &Calendar) -> String {
+    let stories = sprint_stories(db, sprint);
+    let (completed, carried_over): (Vec<&SprintStory>, Vec<&SprintStory>) =
+        stories.iter().partition(|story| is_done(&story.status));
+    let points = |stories: &[&SprintStory]| -> u32 {
+        stories
+            .iter()
+            .map(|story| story.points.unwrap_or_default())
+            .sum()
+    };
+    let line = |story: &SprintStory| match story.points {
+        Some(points) => format!("- {} {} ({points} pts)", story.key(), story.name),
+        None => format!("- {} {}", story.key(), story.name),
+    };
+
+    let mut dates = format!(
+        "{} - {}",
+        calendar.date(sprint.start),
+        calendar.date(sprint.end)
+    );
+    if let Some(closed) = sprint.closed {
+        dates.push_str(&format!(", closed {}", calendar.date(closed)));
+    }
+
+    let blockers = carried_over.iter().filter_map(|story| {
+        let blockers = open_blockers(db, db.stories.get(&story.id.0)?);
+
+        (!blockers.is_empty()).then(|| {
+            format!(
+                "- {} {} is blocked by {}",
+                story.key(),
+                story.name,
+                blockers.join(", ")
+            )
+        })
+    });
+
+    [
+        format!("# {} {}", sprint.key(), sprint.name),
+        dates,
+        format!(
+            "{} of {} points completed, {} of {} stories done.",
+            points(&completed),
+            points(&completed) + points(&carried_over),
+            completed.len(),
+            stories.len()
+        ),
+        markdown_section("Completed", completed.iter().map(|story| line(story))),
+        markdown_section(
+            "Carried over",
+            carried_over
+                .iter()
+                .map(|story| format!("{}, {}", line(story), story.status)),
+        ),
+        markdown_section("Blockers", blockers),
+    ]
+    .join("\n\n")
+        + "\n"
+}
+
+/// Writes `report` to `<dir>/<name>.md`, creating `dir` if needed.
+pub fn archive_report(dir: &Path, name: &str, report: &str) -> Result<PathBuf> {
+    let path = dir.join(format!("{name}.md"));
+
+    fs::create_dir_all(dir).with_context(|| anyhow!("Failed to create {}", dir.display()))?;
+    fs::write(&path, report).with_context(|| anyhow!("Failed to write {}", path.display()))?;
+
+    Ok(path)
+}
+
+/// Mermaid reads `:` as the end of a task name and `#`/`;` as the start of a
+/// comment or entity, so they can't appear in one.
+fn gantt_name(name: &str) -> String {
+    let name = name.replace([':', '#', ';'], " ");
+
+    match name.trim() {
+        "" => "Untitled".to_owned(),
+        name => name.to_owned(),
+    }
+}
+
+/// The status the item had at the end of `date`, or nothing if it didn't exist yet.
+fn status_on(detail: &ItemDetail, date: NaiveDate) -> Option<ItemStatus> {
+    if detail.history.is_empty() {
+        return Some(detail.status.clone());
+    }
+
+    detail
+        .history
+        .iter()
+        .take_while(|change| local_date(change.at) <= date)
+        .last()
+        .map(|change| change.status.clone())
+}
+
+fn local_date(at: DateTime<Utc>) -> NaiveDate {
+    at.with_timezone(&Local).date_naive()
+}
+
+fn open_blockers(db: &DB, story: &Story) -> Vec<String> {
+    story
+        .links
+        .iter()
+        .filter(|link| link.kind == LinkKind::BlockedBy)
+        .filter_map(|link| db.stories.get(&link.story_id.0))
+        .filter(|blocker| !is_done(&blocker.detail.status))
+        .map(|blocker| blocker.key())
+        .collect()
+}
+
+fn markdown_section(title: &str, lines: impl Iterator<Item = String>) -> String {
+    let lines = lines.collect::<Vec<_>>();
+
+    match lines.is_empty() {
+        true => format!("## {title}\n\n- nothing"),
+        false => format!("## {title}\n\n{}", lines.join("\n")),
+    }
+}
+
+fn section(title: &str, lines: impl Iterator<Item = String>) -> String {
+    let lines = lines.collect::<Vec<_>>();
+
+    match lines.is_empty() {
+        true => format!("{title}:\n- nothing"),
+        false => format!("{title}:\n{}", lines.join("\n")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::DateFormat;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use crate::model::{ItemDetail, StatusChange};
+    use chrono::TimeZone;
+
+    #[test]
+    fn standup_should_list_closed_in_progress_and_blocked_stories() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story = |name: &str, assignee: &str, status: ItemStatus| {
+            Story::new(ItemDetail {
+                name: name.to_owned(),
+                assignee: Some(assignee.to_owned()),
+                status,
+                ..Default::default()
+            })
+        };
+        let now = Local.with_ymd_and_hms(2024, 5, 2, 9, 30, 0).unwrap();
+        let yesterday = Local.with_ymd_and_hms(2024, 5, 1, 17, 0, 0).unwrap();
+
+        let closed = db
+            .add_story(story("Fix login", "alice", ItemStatus::Closed), None)
+            .unwrap();
+        let _ = db
+            .add_story(story("Old work", "alice", ItemStatus::Closed), None)
+            .unwrap();
+        let _ = db
+            .add_story(story("Billing", "alice", ItemStatus::InProgress), None)
+            .unwrap();
+        let blocked = db
+            .add_story(story("Deploy", "alice", ItemStatus::InProgress), None)
+            .unwrap();
+        let blocker = db
+            .add_story(story("Infra", "bob", ItemStatus::Open), None)
+            .unwrap();
+        db.add_blocker(blocked, blocker).unwrap();
+
+        let mut state = db.read_db().unwrap();
+        state.stories.get_mut(&closed.0).unwrap().detail.history = vec![StatusChange {
+            status: ItemStatus::Closed,
+            by: None,
+            at: yesterday.into(),
+        }];
+
+        assert_eq!(
+            standup(&state, "alice", &Calendar::default(), now.into()),
+            "Standup 2024-05-02 (@alice)\n\n\
+             Yesterday:\n- STORY-0 Fix login\n\n\
+             Today:\n- STORY-2 Billing\n\n\
+             Blocked:\n- STORY-3 Deploy (blocked by STORY-4)"
+        );
+        assert!(standup(&state, "carol", &Calendar::default(), now.into())
+            .contains("Today:\n- nothing"));
+    }
+
+    #[test]
+    fn standup_should_count_days_on_the_given_calendar() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let closed = db
+            .add_story(
+                Story::new(ItemDetail {
+                    name: "Fix login".to_owned(),
+                    assignee: Some("alice".to_owned()),
+                    status: ItemStatus::Closed,
+                    ..Default::default()
+                }),
+                None,
+            )
+            .unwrap();
+
+        let mut state = db.read_db().unwrap();
+        state.stories.get_mut(&closed.0).unwrap().detail.history = vec![StatusChange {
+            status: ItemStatus::Closed,
+            by: None,
+            at: Utc.with_ymd_and_hms(2024, 5, 1, 20, 0, 0).unwrap(),
+        }];
+
+        let india = Calendar {
+            zone: "+05:30".parse().unwrap(),
+            format: DateFormat::Eu,
+            ..Default::default()
+        };
+        let utc = Calendar {
+            zone: "UTC".parse().unwrap(),
+            ..Default::default()
+        };
+        let now = Utc.with_ymd_and_hms(2024, 5, 3, 4, 0, 0).unwrap();
+        let report = standup(&state, "alice", &india, now);
+
+        assert!(report.starts_with("Standup 03.05.2024"));
+        assert!(report.contains("Yesterday:\n- STORY-0"));
+        assert!(standup(&state, "alice", &utc, now).contains("Yesterday:\n- nothing"));
+    }
+
+    #[test]
+    fn sprint_report_should_split_completed_and_carried_over_stories() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let date = |day: u32| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let sprint_id = db
+            .create_sprint("Payments".to_owned(), date(1), date(14))
+            .unwrap();
+        let story = |name: &str, status: ItemStatus, points: Option<u32>| {
+            let story_id = db
+                .add_story(
+                    Story::new(ItemDetail {
+                        name: name.to_owned(),
+                        status,
+                        points,
+                        ..Default::default()
+                    }),
+                    None,
+                )
+                .unwrap();
+            db.add_story_to_sprint(sprint_id, story_id).unwrap();
+            story_id
+        };
+
+        story("Refunds", ItemStatus::Closed, Some(3));
+        let blocked = story("Invoices", ItemStatus::InProgress, Some(5));
+        story("Receipts", ItemStatus::Open, None);
+        let blocker = db
+            .create_story("Tax rules".to_owned(), "".to_owned(), None)
+            .unwrap();
+        db.add_blocker(blocked, blocker).unwrap();
+
+        let sprint = db.close_sprint(sprint_id, date(15)).unwrap();
+
+        assert_eq!(
+            sprint_report(&db.read_db().unwrap(), &sprint, &Calendar::default()),
+            "# SPRINT-0 Payments\n\n\
+             2024-05-01 - 2024-05-14, closed 2024-05-15\n\n\
+             3 of 8 points completed, 1 of 3 stories done.\n\n\
+             ## Completed\n\n- STORY-0 Refunds (3 pts)\n\n\
+             ## Carried over\n\n\
+             - STORY-1 Invoices (5 pts), IN PROGRESS\n\
+             - STORY-2 Receipts, OPEN\n\n\
+             ## Blockers\n\n- STORY-1 Invoices is blocked by STORY-3\n"
+        );
+    }
+
+    #[test]
+    fn cumulative_flow_should_replay_status_history() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let at = |day: u32| -> DateTime<Utc> {
+            Local
+                .with_ymd_and_hms(2024, 5, day, 12, 0, 0)
+                .unwrap()
+                .into()
+        };
+        let change = |status: ItemStatus, day: u32| StatusChange {
+            status,
+            by: None,
+            at: at(day),
+        };
+        let story = |history: Vec<StatusChange>| {
+            Story::new(ItemDetail {
+                status: history.last().unwrap().status.clone(),
+                history,
+                ..Default::default()
+            })
+        };
+
+        let _ = db
+            .add_story(
+                story(vec![
+                    change(ItemStatus::Open, 1),
+                    change(ItemStatus::InProgress, 2),
+                    change(ItemStatus::Closed, 3),
+                ]),
+                None,
+            )
+            .unwrap();
+        let _ = db
+            .add_story(story(vec![change(ItemStatus::Open, 2)]), None)
+            .unwrap();
+
+        let today = NaiveDate::from_ymd_opt(2024, 5, 3).unwrap();
+        let flow = cumulative_flow(&db.read_db().unwrap(), today);
+
+        assert_eq!(
+            cumulative_flow_csv(&flow),
+            "date,open,in_progress,resolved,closed\n\
+             2024-05-01,1,0,0,0\n\
+             2024-05-02,1,1,0,0\n\
+             2024-05-03,1,0,0,1\n"
+        );
+    }
+
+    #[test]
+    fn gantt_should_chart_sprints_and_epics_with_due_dates() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let date = |day: u32| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+
+        let payments = db
+            .create_epic("Payments: v2".to_owned(), "".to_owned())
+            .unwrap();
+        let search = db.create_epic("Search".to_owned(), "".to_owned()).unwrap();
+        let _ = db.create_epic("Someday".to_owned(), "".to_owned()).unwrap();
+        db.create_sprint("Sprint 1".to_owned(), date(1), date(14))
+            .unwrap();
+
+        let mut state = db.read_db().unwrap();
+        for (id, due) in [(payments, date(20)), (search, date(31))] {
+            let epic = state.epics.get_mut(&id.0).unwrap();
+            epic.detail.due = Some(due);
+            epic.detail.history[0].at =
+                Local.with_ymd_and_hms(2024, 5, 2, 12, 0, 0).unwrap().into();
+        }
+        state.epics.get_mut(&search.0).unwrap().detail.status = ItemStatus::Closed;
+
+        assert_eq!(
+            gantt(&state),
+            "gantt\n    \
+             title Roadmap\n    \
+             dateFormat YYYY-MM-DD\n    \
+             section Sprints\n    \
+             Sprint 1 :SPRINT-0, 2024-05-01, 2024-05-14\n    \
+             section Epics\n    \
+             Payments  v2 :EPIC-0, 2024-05-02, 2024-05-20\n    \
+             Search :done, EPIC-1, 2024-05-02, 2024-05-31\n"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use anyhow::{anyhow, Result};
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use itertools::Itertools;
+
+use crate::model::{Filter, ItemDetail, ItemStatus, ItemType, DB};
+
+#[derive(Debug, PartialEq)]
+pub struct SearchHit {
+    pub item: ItemType,
+    pub key: String,
+    pub score: i64,
+}
+
+/// Reads a filter from tokens: `status:<status>`, `#label` and `@assignee` fill
+/// the matching fields and every other word becomes part of the text query.
+pub fn parse_filter(input: &str) -> Result<Filter> {
+    let mut filter = Filter::default();
+    let mut query = Vec::new();
+
+    for token in input.split_whitespace() {
+        if let Some(status) = token.strip_prefix("status:") {
+            filter.status = Some(parse_status(status)?);
+        } else if let Some(label) = token.strip_prefix('#').filter(|l| !l.is_empty()) {
+            filter.label = Some(label.to_owned());
+        } else if let Some(assignee) = token.strip_prefix('@').filter(|a| !a.is_empty()) {
+            filter.assignee = Some(assignee.to_owned());
+        } else {
+            query.push(token);
+        }
+    }
+
+    if !query.is_empty() {
+        filter.query = Some(query.join(" "));
+    }
+
+    Ok(filter)
+}
+
+pub fn parse_status(input: &str) -> Result<ItemStatus> {
+    match input.to_lowercase().as_str() {
+        "open" => Ok(ItemStatus::Open),
+        "in-progress" | "inprogress" | "wip" => Ok(ItemStatus::InProgress),
+        "resolved" => Ok(ItemStatus::Resolved),
+        "closed" => Ok(ItemStatus::Closed),
+        _ => Err(anyhow!("{input} is not a valid status")),
+    }
+}
+
+/// Returns the epics and stories that pass the filter. With a text query the
+/// key, name and labels are fuzzy matched and the best matches come first,
+/// otherwise epics come before stories in ID order.
+pub fn search(db: &DB, filter: &Filter) -> Vec<SearchHit> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let score = |key: &str, detail: &ItemDetail| {
+        if !matches(filter, detail) {
+            return None;
+        }
+
+        match &filter.query {
+            Some(query) => std::iter::once(key)
+                .chain(std::iter::once(detail.name.as_str()))
+                .chain(detail.labels.iter().map(|label| label.as_str()))
+                .filter_map(|text| matcher.fuzzy_match(text, query))
+                .max(),
+            None => Some(0),
+        }
+    };
+
+    let epics = db.epics.values().filter_map(|epic| {
+        let key = epic.key();
+
+        score(&key, &epic.detail).map(|score| SearchHit {
+            item: ItemType::Epic { id: epic.detail.id },
+            key,
+            score,
+        })
+    });
+    let stories = db.stories.values().filter_map(|story| {
+        let key = story.key();
+
+        score(&key, &story.detail).map(|score| SearchHit {
+            item: ItemType::Story {
+                id: story.detail.id,
+            },
+            key,
+            score,
+        })
+    });
+
+    epics
+        .chain(stories)
+        .sorted_by_key(|hit| {
+            let (is_story, id) = match hit.item {
+                ItemType::Epic { id } => (false, id.0),
+                ItemType::Story { id } => (true, id.0),
+                ItemType::None => (true, u32::MAX),
+            };
+
+            (-hit.score, is_story, id)
+        })
+        .collect()
+}
+
+fn matches(filter: &Filter, detail: &ItemDetail) -> bool {
+    let status = filter
+        .status
+        .as_ref()
+        .is_none_or(|status| &detail.status == status);
+    let label = filter.label.as_ref().is_none_or(|label| {
+        detail
+            .labels
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(label))
+    });
+    let assignee = filter.assignee.as_ref().is_none_or(|assignee| {
+        detail
+            .assignee
+            .as_ref()
+            .is_some_and(|existing| existing.eq_ignore_ascii_case(assignee))
+    });
+
+    status && label && assignee
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use crate::model::ItemId;
+
+    fn board() -> DB {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic("Authentication".to_owned(), "".to_owned())
+            .unwrap();
+        let _ = db
+            .create_story(
+                "Fix login redirect".to_owned(),
+                "".to_owned(),
+                Some(epic_id),
+            )
+            .unwrap();
+        let _ = db
+            .create_story("Billing page".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        db.read_db().unwrap()
+    }
+
+    fn query(text: &str) -> Filter {
+        Filter {
+            query: Some(text.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn search_should_rank_best_matches_first() {
+        let hits = search(&board(), &query("login"));
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].item, ItemType::Story { id: ItemId(0) });
+    }
+
+    #[test]
+    fn search_should_match_partial_and_misspelled_queries() {
+        let db = board();
+
+        assert_eq!(search(&db, &query("auth"))[0].key, "EPIC-0");
+        assert_eq!(search(&db, &query("lgn rdrct"))[0].key, "STORY-0");
+        assert_eq!(search(&db, &query("story-1"))[0].key, "STORY-1");
+        assert!(search(&db, &query("zzz")).is_empty());
+    }
+
+    #[test]
+    fn search_should_match_labels() {
+        let mut db = board();
+        db.stories.get_mut(&1).unwrap().detail.labels = vec!["payments".to_owned()];
+
+        assert_eq!(search(&db, &query("paymnts"))[0].key, "STORY-1");
+    }
+
+    #[test]
+    fn search_should_apply_every_filter_field() {
+        let mut db = board();
+        let story = &mut db.stories.get_mut(&0).unwrap().detail;
+        story.labels = vec!["bug".to_owned()];
+        story.assignee = Some("alice".to_owned());
+
+        let keys = |filter: &Filter| {
+            search(&db, filter)
+                .into_iter()
+                .map(|hit| hit.key)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            keys(&Filter::default()),
+            vec!["EPIC-0", "STORY-0", "STORY-1"]
+        );
+        assert_eq!(
+            keys(&parse_filter("status:open #BUG @alice").unwrap()),
+            vec!["STORY-0"]
+        );
+        assert!(keys(&parse_filter("status:closed #bug").unwrap()).is_empty());
+        assert!(keys(&parse_filter("@alice billing").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn parse_filter_should_fill_every_field() {
+        let filter = parse_filter("login status:in-progress #bug @alice redirect").unwrap();
+
+        assert_eq!(
+            filter,
+            Filter {
+                status: Some(ItemStatus::InProgress),
+                label: Some("bug".to_owned()),
+                assignee: Some("alice".to_owned()),
+                query: Some("login redirect".to_owned()),
+            }
+        );
+        assert_eq!(
+            filter.to_string(),
+            "status:in-progress #bug @alice login redirect"
+        );
+        assert!(parse_filter("status:done").is_err());
+    }
+}
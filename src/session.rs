@@ -0,0 +1,126 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::rc::Rc;
+
+use crate::archive::Archive;
+use crate::config::Config;
+use crate::db::JiraDataBase;
+use crate::model::Action;
+use crate::navigators::Navigator;
+use crate::ui::{KeyHint, PromptProvider};
+
+/// The board driven without a terminal, for programs that embed it and for
+/// end-to-end tests. It takes actions, or input lines like the interactive
+/// board does, and answers with what the page in view shows.
+pub struct Session {
+    navigator: Navigator,
+}
+
+/// What the page in view shows, as data.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PageState {
+    /// The page as the terminal would draw it.
+    pub text: String,
+    /// The rows the page lists, as the input that opens each one and its name.
+    pub rows: Vec<(String, String)>,
+    pub hints: Vec<KeyHint>,
+    /// The message the last action left, e.g. `Saved the board`.
+    pub notice: Option<String>,
+    /// How many pages are open, the one in view included.
+    pub depth: usize,
+}
+
+impl Session {
+    /// A session on the home page of `database`. `prompts` answers the
+    /// questions actions ask, like the name of a new epic. Snapshots and sprint
+    /// reports stay in memory, see `with_archive`.
+    pub fn new(
+        database: JiraDataBase,
+        config: Config,
+        prompts: impl PromptProvider + 'static,
+    ) -> Self {
+        let navigator = Navigator::from_prompts(Rc::new(database), prompts).with_config(config);
+        #[cfg(feature = "terminal")]
+        let navigator = navigator.with_clipboard(crate::io_utils::copy_to_clipboard);
+
+        Self { navigator }
+    }
+
+    /// Keeps snapshots and sprint reports in `archive` instead of in memory.
+    pub fn with_archive(mut self, archive: impl Archive + 'static) -> Self {
+        self.navigator = self.navigator.with_archive(archive);
+        self
+    }
+
+    pub fn dispatch(&mut self, action: Action) -> Result<Option<PageState>> {
+        self.navigator.handle_action(action)?;
+        self.state()
+    }
+
+    /// Handles `line` like the interactive board, keymap and chains included.
+    pub fn input(&mut self, line: &str) -> Result<Option<PageState>> {
+        self.navigator.handle_input(line)?;
+        self.state()
+    }
+
+    /// Writes a delete once the time to take it back with `z` has passed, see
+    /// `Navigator::expire_delete`. Returns whether it did.
+    pub fn expire_delete(&mut self) -> Result<bool> {
+        self.navigator.expire_delete()
+    }
+
+    /// The page in view, or `None` once the last page was closed.
+    pub fn state(&mut self) -> Result<Option<PageState>> {
+        let notice = self.navigator.take_notice();
+        let depth = self.navigator.get_page_count();
+        let Some(page) = self.navigator.get_current_page() else {
+            return Ok(None);
+        };
+        let mut text = Vec::new();
+
+        page.draw_page(&mut text)?;
+
+        Ok(Some(PageState {
+            text: String::from_utf8_lossy(&text).into_owned(),
+            rows: page.rows()?,
+            hints: page.key_hints(),
+            notice,
+            depth,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::ui::test_utils::MockPrompts;
+
+    #[test]
+    fn should_drive_the_navigation_without_a_terminal() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic("Checkout".to_owned(), "".to_owned())
+            .unwrap();
+        let mut session = Session::new(db, Config::default(), MockPrompts::new());
+
+        let home = session.state().unwrap().unwrap();
+
+        assert!(home.text.contains("Checkout"));
+        assert_eq!(
+            home.rows,
+            vec![(epic_id.0.to_string(), "Checkout".to_owned())]
+        );
+        assert_eq!(home.depth, 1);
+
+        let epic = session.input(&epic_id.0.to_string()).unwrap().unwrap();
+
+        assert!(epic.text.contains("EPIC"));
+        assert_eq!(epic.depth, 2);
+
+        let back = session.dispatch(Action::NavigateToPreviousPage).unwrap();
+
+        assert_eq!(back.unwrap().depth, 1);
+        assert_eq!(session.dispatch(Action::Exit).unwrap(), None);
+    }
+}
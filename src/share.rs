@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use crate::model::{Epic, ItemDetail, ItemStatus, Story};
+
+pub fn epic_summary(epic: &Epic) -> String {
+    item_summary(
+        &epic.key(),
+        &epic.detail.name,
+        &epic.detail.status.to_string(),
+        "epic",
+        epic.detail.id.0,
+    )
+}
+
+pub fn story_summary(story: &Story) -> String {
+    item_summary(
+        &story.key(),
+        &story.detail.name,
+        &story.detail.status.to_string(),
+        "story",
+        story.detail.id.0,
+    )
+}
+
+pub fn epic_markdown(epic: &Epic, stories: &HashMap<u32, Story>) -> String {
+    let mut markdown = item_markdown(&epic.key(), &epic.detail);
+
+    let checklist = epic
+        .stories
+        .iter()
+        .filter_map(|id| stories.get(&id.0))
+        .map(|story| {
+            let done = matches!(
+                story.detail.status,
+                ItemStatus::Resolved | ItemStatus::Closed
+            );
+
+            format!(
+                "- [{}] {} {}",
+                if done { "x" } else { " " },
+                story.key(),
+                story.detail.name
+            )
+        })
+        .collect::<Vec<String>>();
+
+    if !checklist.is_empty() {
+        markdown.push_str("\n\n");
+        markdown.push_str(&checklist.join("\n"));
+    }
+
+    markdown
+}
+
+pub fn story_markdown(story: &Story) -> String {
+    let mut markdown = item_markdown(&story.key(), &story.detail);
+
+    if !story.checklist.is_empty() {
+        let checklist = story
+            .checklist
+            .iter()
+            .map(|item| format!("- [{}] {}", if item.done { "x" } else { " " }, item.text))
+            .collect::<Vec<String>>();
+
+        markdown.push_str("\n\n");
+        markdown.push_str(&checklist.join("\n"));
+    }
+
+    markdown
+}
+
+fn item_markdown(key: &str, detail: &ItemDetail) -> String {
+    let mut markdown = format!(
+        "### [{key}] {}\n\n{}",
+        detail.name,
+        status_badge(&detail.status)
+    );
+
+    if !detail.description.is_empty() {
+        markdown.push_str("\n\n");
+        markdown.push_str(&detail.description);
+    }
+
+    markdown
+}
+
+fn status_badge(status: &ItemStatus) -> String {
+    let color = match status {
+        ItemStatus::Open => "blue",
+        ItemStatus::InProgress => "yellow",
+        ItemStatus::Resolved => "green",
+        ItemStatus::Closed => "lightgrey",
+    };
+    let label = status.to_string();
+
+    format!(
+        "![{label}](https://img.shields.io/badge/status-{}-{color})",
+        label.replace('-', "--").replace(' ', "%20")
+    )
+}
+
+fn item_summary(key: &str, name: &str, status: &str, kind: &str, id: u32) -> String {
+    format!("[{key}] {name} ({status}) - jira-clone://{kind}/{id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ChecklistItem, ItemDetail, ItemId, ItemStatus};
+
+    #[test]
+    fn epic_summary_should_include_key_name_status_and_reference() {
+        let epic = Epic::new(
+            ItemDetail {
+                description: "".to_owned(),
+                id: ItemId(3),
+                name: "Payments".to_owned(),
+                status: ItemStatus::InProgress,
+                ..Default::default()
+            },
+            Vec::new(),
+        );
+
+        assert_eq!(
+            epic_summary(&epic),
+            "[EPIC-3] Payments (IN PROGRESS) - jira-clone://epic/3"
+        );
+    }
+
+    #[test]
+    fn epic_markdown_should_render_stories_as_checklist() {
+        let mut stories = HashMap::new();
+        let done = Story::new(ItemDetail {
+            description: "".to_owned(),
+            id: ItemId(0),
+            name: "Add card form".to_owned(),
+            status: ItemStatus::Closed,
+            ..Default::default()
+        });
+        let todo = Story::new(ItemDetail {
+            description: "".to_owned(),
+            id: ItemId(1),
+            name: "Refunds".to_owned(),
+            status: ItemStatus::InProgress,
+            ..Default::default()
+        });
+
+        stories.insert(0, done);
+        stories.insert(1, todo);
+
+        let epic = Epic::new(
+            ItemDetail {
+                description: "Take money from customers".to_owned(),
+                id: ItemId(3),
+                name: "Payments".to_owned(),
+                status: ItemStatus::InProgress,
+                ..Default::default()
+            },
+            vec![ItemId(0), ItemId(1)],
+        );
+
+        assert_eq!(
+            epic_markdown(&epic, &stories),
+            "### [EPIC-3] Payments\n\n\
+             ![IN PROGRESS](https://img.shields.io/badge/status-IN%20PROGRESS-yellow)\n\n\
+             Take money from customers\n\n\
+             - [x] STORY-0 Add card form\n\
+             - [ ] STORY-1 Refunds"
+        );
+    }
+
+    #[test]
+    fn story_markdown_should_skip_empty_description() {
+        let story = Story::new(ItemDetail {
+            description: "".to_owned(),
+            id: ItemId(7),
+            name: "Fix login redirect".to_owned(),
+            status: ItemStatus::Open,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            story_markdown(&story),
+            "### [STORY-7] Fix login redirect\n\n\
+             ![OPEN](https://img.shields.io/badge/status-OPEN-blue)"
+        );
+    }
+
+    #[test]
+    fn story_markdown_should_render_checklist() {
+        let mut story = Story::new(ItemDetail {
+            description: "Users land on a blank page".to_owned(),
+            id: ItemId(7),
+            name: "Fix login redirect".to_owned(),
+            status: ItemStatus::Resolved,
+            ..Default::default()
+        });
+        story.checklist = vec![
+            ChecklistItem {
+                text: "Reproduce".to_owned(),
+                done: true,
+            },
+            ChecklistItem {
+                text: "Add test".to_owned(),
+                done: false,
+            },
+        ];
+
+        assert_eq!(
+            story_markdown(&story),
+            "### [STORY-7] Fix login redirect\n\n\
+             ![RESOLVED](https://img.shields.io/badge/status-RESOLVED-green)\n\n\
+             Users land on a blank page\n\n\
+             - [x] Reproduce\n\
+             - [ ] Add test"
+        );
+    }
+
+    #[test]
+    fn story_summary_should_include_key_name_status_and_reference() {
+        let story = Story::new(ItemDetail {
+            description: "".to_owned(),
+            id: ItemId(7),
+            name: "Fix login redirect".to_owned(),
+            status: ItemStatus::Open,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            story_summary(&story),
+            "[STORY-7] Fix login redirect (OPEN) - jira-clone://story/7"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::model::DB;
+
+/// A named copy of the whole board, kept as `<name>.json` in the snapshots
+/// directory. The file is a plain board, so it can be opened as one too.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Saves `board` as the snapshot `name`. Existing snapshots are never
+/// overwritten.
+pub fn create_snapshot(dir: &Path, name: &str, board: &DB) -> Result<PathBuf> {
+    let path = snapshot_path(dir, name)?;
+
+    if path.exists() {
+        return Err(anyhow!("There already is a snapshot called \"{name}\""));
+    }
+
+    fs::create_dir_all(dir).with_context(|| anyhow!("Failed to create {}", dir.display()))?;
+    fs::write(&path, serde_json::to_vec_pretty(board)?)
+        .with_context(|| anyhow!("Failed to write {}", path.display()))?;
+
+    Ok(path)
+}
+
+pub fn read_snapshot(dir: &Path, name: &str) -> Result<DB> {
+    let path = snapshot_path(dir, name)?;
+    let content =
+        fs::read(&path).with_context(|| anyhow!("There is no snapshot called \"{name}\""))?;
+
+    serde_json::from_slice(&content)
+        .with_context(|| anyhow!("The snapshot \"{name}\" is not a valid board"))
+}
+
+/// The snapshots in `dir`, oldest first. A missing directory has none.
+pub fn list_snapshots(dir: &Path) -> Result<Vec<Snapshot>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+
+    for entry in fs::read_dir(dir).with_context(|| anyhow!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+
+        let Some(name) = path
+            .extension()
+            .filter(|extension| *extension == "json")
+            .and(path.file_stem())
+            .and_then(|name| name.to_str())
+        else {
+            continue;
+        };
+
+        snapshots.push(Snapshot {
+            name: name.to_owned(),
+            created_at: fs::metadata(&path)?.modified()?.into(),
+        });
+    }
+
+    Ok(snapshots
+        .into_iter()
+        .sorted_by(|a, b| (a.created_at, &a.name).cmp(&(b.created_at, &b.name)))
+        .collect())
+}
+
+fn snapshot_path(dir: &Path, name: &str) -> Result<PathBuf> {
+    if name.trim().is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(anyhow!("\"{name}\" can't be used as a snapshot name"));
+    }
+
+    Ok(dir.join(format!("{name}.json")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+
+    #[test]
+    fn should_create_list_and_read_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshots = dir.path().join("snapshots");
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let empty = db.read_db().unwrap();
+        db.create_story("Refunds".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let board = db.read_db().unwrap();
+
+        assert!(list_snapshots(&snapshots).unwrap().is_empty());
+
+        let path = create_snapshot(&snapshots, "before-reorg", &board).unwrap();
+
+        assert_eq!(path, snapshots.join("before-reorg.json"));
+        assert!(create_snapshot(&snapshots, "before-reorg", &empty).is_err());
+        assert!(create_snapshot(&snapshots, "../escape", &board).is_err());
+        assert_eq!(
+            list_snapshots(&snapshots)
+                .unwrap()
+                .into_iter()
+                .map(|snapshot| snapshot.name)
+                .collect::<Vec<_>>(),
+            vec!["before-reorg".to_owned()]
+        );
+        assert_eq!(read_snapshot(&snapshots, "before-reorg").unwrap(), board);
+        assert!(read_snapshot(&snapshots, "missing").is_err());
+    }
+}
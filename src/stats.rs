@@ -0,0 +1,868 @@
+use chrono::{DateTime, Days, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::calendar::Calendar;
+use crate::config::WipLimits;
+use crate::model::{ItemDetail, ItemId, ItemStatus, LinkKind, Sprint, SprintStory, Story, DB};
+
+/// How the stories of the board are doing right now.
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    pub open: usize,
+    pub in_progress: usize,
+    pub resolved_this_week: usize,
+    pub blocked: usize,
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} open, {} in progress, {} resolved this week, {} blocked",
+            self.open, self.in_progress, self.resolved_this_week, self.blocked
+        )
+    }
+}
+
+pub fn summary(db: &DB, calendar: &Calendar, now: DateTime<Utc>) -> Summary {
+    let week_start = start_of_week(calendar, now);
+    let mut summary = Summary::default();
+
+    for story in db.stories.values() {
+        match story.detail.status {
+            ItemStatus::Open => summary.open += 1,
+            ItemStatus::InProgress => summary.in_progress += 1,
+            ItemStatus::Resolved | ItemStatus::Closed => {
+                let resolved_at = story.detail.history.last().map(|change| change.at);
+
+                if resolved_at.is_some_and(|at| at >= week_start) {
+                    summary.resolved_this_week += 1;
+                }
+            }
+        }
+
+        if is_blocked(db, story) {
+            summary.blocked += 1;
+        }
+    }
+
+    summary
+}
+
+/// The unfinished work of one assignee.
+#[derive(Debug, PartialEq)]
+pub struct AssigneeLoad {
+    pub assignee: String,
+    pub open: usize,
+    pub in_progress: usize,
+    /// The points of the open and in-progress items.
+    pub points: u32,
+}
+
+/// What every assignee has open or in progress, epics and stories alike, by
+/// name. People with only finished work don't show up.
+pub fn assignee_loads(db: &DB) -> Vec<AssigneeLoad> {
+    loads(
+        db.epics
+            .values()
+            .map(|epic| &epic.detail)
+            .chain(db.stories.values().map(|story| &story.detail)),
+    )
+}
+
+/// What every assignee has open or in progress in `sprint`, by name.
+pub fn sprint_loads(db: &DB, sprint: &Sprint) -> Vec<AssigneeLoad> {
+    loads(
+        sprint
+            .stories
+            .iter()
+            .filter_map(|id| db.stories.get(&id.0))
+            .map(|story| &story.detail),
+    )
+}
+
+fn loads<'a>(details: impl Iterator<Item = &'a ItemDetail>) -> Vec<AssigneeLoad> {
+    let mut loads: BTreeMap<&str, AssigneeLoad> = BTreeMap::new();
+
+    for detail in details.filter(|detail| !is_done(&detail.status)) {
+        let Some(assignee) = detail.assignee.as_deref() else {
+            continue;
+        };
+
+        let load = loads.entry(assignee).or_insert_with(|| AssigneeLoad {
+            assignee: assignee.to_owned(),
+            open: 0,
+            in_progress: 0,
+            points: 0,
+        });
+
+        match detail.status {
+            ItemStatus::InProgress => load.in_progress += 1,
+            _ => load.open += 1,
+        }
+        load.points += detail.points.unwrap_or_default();
+    }
+
+    loads.into_values().collect()
+}
+
+/// Every label on the board with how many epics and stories carry it, by name.
+pub fn label_counts(db: &DB) -> Vec<(String, usize)> {
+    db.epics
+        .values()
+        .map(|epic| &epic.detail)
+        .chain(db.stories.values().map(|story| &story.detail))
+        .flat_map(|detail| detail.labels.iter().cloned())
+        .counts()
+        .into_iter()
+        .sorted()
+        .collect()
+}
+
+/// Everyone epics or stories are assigned to, by name.
+pub fn assignees(db: &DB) -> Vec<String> {
+    db.epics
+        .values()
+        .map(|epic| &epic.detail)
+        .chain(db.stories.values().map(|story| &story.detail))
+        .filter_map(|detail| detail.assignee.clone())
+        .sorted()
+        .dedup()
+        .collect()
+}
+
+/// How many of a set of stories are in each status.
+#[derive(Debug, Default, PartialEq)]
+pub struct StatusCounts {
+    pub open: usize,
+    pub in_progress: usize,
+    pub resolved: usize,
+    pub closed: usize,
+}
+
+/// Compact, for badges: `[O:4 P:2 R:1 C:3]`.
+impl Display for StatusCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[O:{} P:{} R:{} C:{}]",
+            self.open, self.in_progress, self.resolved, self.closed
+        )
+    }
+}
+
+pub fn status_counts<'a>(stories: impl Iterator<Item = &'a Story>) -> StatusCounts {
+    let mut counts = StatusCounts::default();
+
+    for story in stories {
+        match story.detail.status {
+            ItemStatus::Open => counts.open += 1,
+            ItemStatus::InProgress => counts.in_progress += 1,
+            ItemStatus::Resolved => counts.resolved += 1,
+            ItemStatus::Closed => counts.closed += 1,
+        }
+    }
+
+    counts
+}
+
+/// How much work a set of stories adds up to.
+#[derive(Debug, Default, PartialEq)]
+pub struct Rollup {
+    pub stories: usize,
+    pub points: u32,
+    /// The points of the resolved and closed stories.
+    pub done_points: u32,
+}
+
+/// For badges: `3 stories, 5/8 pts`, the done points first.
+impl Display for Rollup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.stories {
+            1 => write!(f, "1 story")?,
+            stories => write!(f, "{stories} stories")?,
+        }
+
+        write!(f, ", {}/{} pts", self.done_points, self.points)
+    }
+}
+
+pub fn rollup<'a>(stories: impl Iterator<Item = &'a Story>) -> Rollup {
+    let mut rollup = Rollup::default();
+
+    for story in stories {
+        let points = story.detail.points.unwrap_or(0);
+
+        rollup.stories += 1;
+        rollup.points += points;
+        if is_done(&story.detail.status) {
+            rollup.done_points += points;
+        }
+    }
+
+    rollup
+}
+
+/// A story is blocked while it is not done and any of its blockers is not done
+/// either.
+pub fn is_blocked(db: &DB, story: &Story) -> bool {
+    !is_done(&story.detail.status)
+        && story
+            .links
+            .iter()
+            .filter(|link| link.kind == LinkKind::BlockedBy)
+            .filter_map(|link| db.stories.get(&link.story_id.0))
+            .any(|blocker| !is_done(&blocker.detail.status))
+}
+
+pub fn is_done(status: &ItemStatus) -> bool {
+    matches!(status, ItemStatus::Resolved | ItemStatus::Closed)
+}
+
+/// An item is stale when it isn't done and its last change is more than
+/// `after` ago. Items that never changed aren't stale.
+pub fn is_stale(
+    status: &ItemStatus,
+    last_change: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    after: Days,
+) -> bool {
+    !is_done(status)
+        && last_change
+            .zip(now.checked_sub_days(after))
+            .is_some_and(|(changed, cutoff)| changed < cutoff)
+}
+
+/// The points of a sprint: what got done by its last day out of what was
+/// committed to it.
+#[derive(Debug, PartialEq)]
+pub struct SprintPoints {
+    pub sprint_id: ItemId,
+    pub completed: u32,
+    pub committed: u32,
+}
+
+/// The points of a sprint. Closed sprints count their stories as they stood
+/// on closing.
+pub fn sprint_points(db: &DB, sprint: &Sprint) -> SprintPoints {
+    if sprint.closed.is_some() && !sprint.snapshot.is_empty() {
+        let points = |story: &SprintStory| story.points.unwrap_or_default();
+
+        return SprintPoints {
+            sprint_id: sprint.id,
+            completed: sprint
+                .snapshot
+                .iter()
+                .filter(|story| is_done(&story.status))
+                .map(points)
+                .sum(),
+            committed: sprint.snapshot.iter().map(points).sum(),
+        };
+    }
+
+    let stories = sprint
+        .stories
+        .iter()
+        .filter_map(|id| db.stories.get(&id.0))
+        .collect::<Vec<_>>();
+    let points = |story: &&Story| story.detail.points.unwrap_or_default();
+
+    SprintPoints {
+        sprint_id: sprint.id,
+        completed: stories
+            .iter()
+            .filter(|story| done_by(story, sprint.end))
+            .map(points)
+            .sum(),
+        committed: stories.iter().map(points).sum(),
+    }
+}
+
+/// The stories of the sprint, as they stood on closing for a closed sprint
+/// and as they are now otherwise.
+pub fn sprint_stories(db: &DB, sprint: &Sprint) -> Vec<SprintStory> {
+    if sprint.closed.is_some() && !sprint.snapshot.is_empty() {
+        return sprint.snapshot.clone();
+    }
+
+    sprint
+        .stories
+        .iter()
+        .filter_map(|id| db.stories.get(&id.0))
+        .sorted_by_key(|story| story.detail.id.0)
+        .map(SprintStory::of)
+        .collect()
+}
+
+/// The points completed in every sprint that ended before `today`, oldest first.
+pub fn velocity(db: &DB, today: NaiveDate) -> Vec<SprintPoints> {
+    db.sprints
+        .values()
+        .filter(|sprint| sprint.end < today)
+        .sorted_by_key(|sprint| (sprint.end, sprint.id.0))
+        .map(|sprint| sprint_points(db, sprint))
+        .collect()
+}
+
+/// The average of the points completed in the last `window` sprints.
+pub fn rolling_average(velocity: &[SprintPoints], window: usize) -> Option<f64> {
+    let recent = &velocity[velocity.len().saturating_sub(window)..];
+
+    match recent.is_empty() {
+        true => None,
+        false => {
+            let total: u32 = recent.iter().map(|sprint| sprint.completed).sum();
+
+            Some(f64::from(total) / recent.len() as f64)
+        }
+    }
+}
+
+/// Whether the story was done by the end of `day`. Stories without a history
+/// only have their current status to go by.
+fn done_by(story: &Story, day: NaiveDate) -> bool {
+    if !is_done(&story.detail.status) {
+        return false;
+    }
+
+    match story.detail.history.last() {
+        Some(change) => change.at.with_timezone(&Local).date_naive() <= day,
+        None => true,
+    }
+}
+
+/// Time from creation to done, from the history of a story that is done now.
+pub fn lead_time(story: &Story) -> Option<Duration> {
+    let created = story.detail.history.first()?.at;
+
+    Some(done_at(story)? - created)
+}
+
+/// Time from first starting work to done, from the history of a story that is
+/// done now.
+pub fn cycle_time(story: &Story) -> Option<Duration> {
+    let started = story
+        .detail
+        .history
+        .iter()
+        .find(|change| change.status == ItemStatus::InProgress)?
+        .at;
+
+    Some(done_at(story)? - started)
+}
+
+fn done_at(story: &Story) -> Option<DateTime<Utc>> {
+    if !is_done(&story.detail.status) {
+        return None;
+    }
+
+    story
+        .detail
+        .history
+        .last()
+        .filter(|change| is_done(&change.status))
+        .map(|change| change.at)
+}
+
+/// Average and percentiles of a set of lead or cycle times.
+#[derive(Debug, PartialEq)]
+pub struct TimeStats {
+    pub count: usize,
+    pub average: Duration,
+    pub p50: Duration,
+    pub p85: Duration,
+}
+
+impl TimeStats {
+    pub fn new(mut times: Vec<Duration>) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+
+        times.sort();
+        let total = times
+            .iter()
+            .fold(Duration::zero(), |total, time| total + *time);
+
+        Some(TimeStats {
+            count: times.len(),
+            average: total / times.len() as i32,
+            p50: percentile(&times, 50),
+            p85: percentile(&times, 85),
+        })
+    }
+}
+
+impl Display for TimeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "avg {}, p50 {}, p85 {} ({} stories)",
+            days(self.average),
+            days(self.p50),
+            days(self.p85),
+            self.count
+        )
+    }
+}
+
+/// Nearest-rank percentile of sorted times.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+
+    sorted[rank - 1]
+}
+
+fn days(duration: Duration) -> String {
+    format!("{:.1}d", duration.num_minutes() as f64 / (24.0 * 60.0))
+}
+
+/// Lead and cycle time statistics of the given stories.
+pub fn flow_times<'a>(
+    stories: impl Iterator<Item = &'a Story> + Clone,
+) -> (Option<TimeStats>, Option<TimeStats>) {
+    (
+        TimeStats::new(stories.clone().filter_map(lead_time).collect()),
+        TimeStats::new(stories.filter_map(cycle_time).collect()),
+    )
+}
+
+/// The WIP limits that would be exceeded if the `starting` stories moved to
+/// in progress, described for the user.
+pub fn wip_violations(db: &DB, limits: &WipLimits, starting: &[ItemId]) -> Vec<String> {
+    let in_progress = db
+        .stories
+        .values()
+        .filter(|story| {
+            story.detail.status == ItemStatus::InProgress || starting.contains(&story.detail.id)
+        })
+        .collect::<Vec<_>>();
+    let mut violations = Vec::new();
+
+    if let Some(limit) = limits.global {
+        if in_progress.len() > limit {
+            violations.push(format!(
+                "{} stories would be in progress (limit {limit})",
+                in_progress.len()
+            ));
+        }
+    }
+
+    if let Some(limit) = limits.per_epic {
+        for epic in db
+            .epics
+            .values()
+            .filter(|epic| starting.iter().any(|id| epic.stories.contains(id)))
+            .sorted_by_key(|epic| epic.detail.id.0)
+        {
+            let count = in_progress
+                .iter()
+                .filter(|story| epic.stories.contains(&story.detail.id))
+                .count();
+
+            if count > limit {
+                violations.push(format!(
+                    "{} would have {count} stories in progress (limit {limit})",
+                    epic.key()
+                ));
+            }
+        }
+    }
+
+    if let Some(limit) = limits.per_assignee {
+        let assignees = starting
+            .iter()
+            .filter_map(|id| db.stories.get(&id.0))
+            .filter_map(|story| story.detail.assignee.as_deref())
+            .unique()
+            .sorted();
+
+        for assignee in assignees {
+            let count = in_progress
+                .iter()
+                .filter(|story| story.detail.assignee.as_deref() == Some(assignee))
+                .count();
+
+            if count > limit {
+                violations.push(format!(
+                    "@{assignee} would have {count} stories in progress (limit {limit})"
+                ));
+            }
+        }
+    }
+
+    violations
+}
+
+/// Midnight of the first day of the week `now` falls in on `calendar`.
+fn start_of_week(calendar: &Calendar, now: DateTime<Utc>) -> DateTime<Utc> {
+    let first_day = calendar.start_of_week(now.with_timezone(&calendar.zone).date_naive());
+
+    calendar
+        .zone
+        .from_local_datetime(&first_day.and_time(NaiveTime::MIN))
+        .earliest()
+        .map_or(now, |midnight| midnight.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use crate::model::StatusChange;
+    use chrono::Weekday;
+
+    fn at(month: u32, day: u32, hour: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2024, month, day, hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn status_counts_should_show_as_a_badge() {
+        let stories = [
+            ItemStatus::Open,
+            ItemStatus::InProgress,
+            ItemStatus::Open,
+            ItemStatus::Closed,
+        ]
+        .into_iter()
+        .map(|status| {
+            Story::new(ItemDetail {
+                status,
+                ..Default::default()
+            })
+        })
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            status_counts(stories.iter()).to_string(),
+            "[O:2 P:1 R:0 C:1]"
+        );
+    }
+
+    #[test]
+    fn rollup_should_add_up_stories_and_points() {
+        let stories = [
+            (ItemStatus::Resolved, Some(3)),
+            (ItemStatus::Open, Some(5)),
+            (ItemStatus::Open, None),
+        ]
+        .into_iter()
+        .map(|(status, points)| {
+            Story::new(ItemDetail {
+                status,
+                points,
+                ..Default::default()
+            })
+        })
+        .collect::<Vec<_>>();
+
+        assert_eq!(rollup(stories.iter()).to_string(), "3 stories, 3/8 pts");
+        assert_eq!(rollup(stories[..1].iter()).to_string(), "1 story, 3/3 pts");
+    }
+
+    #[test]
+    fn summary_should_count_stories_by_status() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let ids = (0..6)
+            .map(|_| db.create_story("".to_owned(), "".to_owned(), None).unwrap())
+            .collect::<Vec<_>>();
+        db.add_blocker(ids[0], ids[1]).unwrap();
+        db.add_blocker(ids[2], ids[3]).unwrap();
+
+        let mut state = db.read_db().unwrap();
+        let mut resolve = |id: ItemId, status: ItemStatus, resolved_at: DateTime<Local>| {
+            state.stories.get_mut(&id.0).unwrap().detail.status = status.clone();
+            state
+                .stories
+                .get_mut(&id.0)
+                .unwrap()
+                .detail
+                .history
+                .push(StatusChange {
+                    status,
+                    by: None,
+                    at: resolved_at.into(),
+                });
+        };
+        // Wednesday 2024-05-01 is in the same week as Monday 2024-04-29.
+        resolve(ids[3], ItemStatus::Resolved, at(5, 1, 12));
+        resolve(ids[4], ItemStatus::Closed, at(5, 2, 12));
+        resolve(ids[5], ItemStatus::Closed, at(4, 28, 12));
+        state.stories.get_mut(&ids[1].0).unwrap().detail.status = ItemStatus::InProgress;
+
+        let calendar = Calendar::default();
+
+        assert_eq!(
+            summary(&state, &calendar, at(5, 3, 9).into()),
+            Summary {
+                open: 2,
+                in_progress: 1,
+                resolved_this_week: 2,
+                blocked: 1,
+            }
+        );
+        assert_eq!(
+            summary(&state, &calendar, at(5, 3, 9).into()).to_string(),
+            "2 open, 1 in progress, 2 resolved this week, 1 blocked"
+        );
+
+        // Weeks starting on Sunday take in Sunday 2024-04-28 too.
+        let sunday_weeks = Calendar {
+            week_start: Weekday::Sun,
+            ..calendar
+        };
+        assert_eq!(
+            summary(&state, &sunday_weeks, at(5, 3, 9).into()).resolved_this_week,
+            3
+        );
+    }
+
+    #[test]
+    fn assignee_loads_should_add_up_unfinished_work_per_person() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let assigned = |assignee: &str, status: ItemStatus, points: u32| {
+            Story::new(ItemDetail {
+                assignee: Some(assignee.to_owned()),
+                status,
+                points: Some(points),
+                ..Default::default()
+            })
+        };
+        db.add_story(assigned("bob", ItemStatus::Open, 3), None)
+            .unwrap();
+        db.add_story(assigned("bob", ItemStatus::InProgress, 5), None)
+            .unwrap();
+        db.add_story(assigned("bob", ItemStatus::Closed, 8), None)
+            .unwrap();
+        db.add_story(assigned("alice", ItemStatus::Open, 2), None)
+            .unwrap();
+        db.add_story(assigned("carol", ItemStatus::Resolved, 1), None)
+            .unwrap();
+        db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        assert_eq!(
+            assignee_loads(&db.read_db().unwrap()),
+            vec![
+                AssigneeLoad {
+                    assignee: "alice".to_owned(),
+                    open: 1,
+                    in_progress: 0,
+                    points: 2,
+                },
+                AssigneeLoad {
+                    assignee: "bob".to_owned(),
+                    open: 1,
+                    in_progress: 1,
+                    points: 8,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn label_counts_should_count_epics_and_stories_per_label() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let labelled = |labels: &[&str]| {
+            Story::new(ItemDetail {
+                labels: labels.iter().map(|label| label.to_string()).collect(),
+                ..Default::default()
+            })
+        };
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        db.add_story(labelled(&["billing", "bug"]), None).unwrap();
+        db.add_story(labelled(&["bug"]), None).unwrap();
+        db.add_story(labelled(&[]), None).unwrap();
+
+        let mut state = db.read_db().unwrap();
+        state.epics.get_mut(&epic_id.0).unwrap().detail.labels = vec!["billing".to_owned()];
+
+        assert_eq!(
+            label_counts(&state),
+            vec![("billing".to_owned(), 2), ("bug".to_owned(), 2)]
+        );
+    }
+
+    #[test]
+    fn velocity_should_count_points_done_by_the_end_of_each_past_sprint() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let date = |day: u32| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let story = |points: u32, status: ItemStatus| {
+            Story::new(ItemDetail {
+                points: Some(points),
+                status,
+                ..Default::default()
+            })
+        };
+        let first = db
+            .create_sprint("First".to_owned(), date(1), date(14))
+            .unwrap();
+        let second = db
+            .create_sprint("Second".to_owned(), date(15), date(28))
+            .unwrap();
+        let current = db
+            .create_sprint("Current".to_owned(), date(29), date(31))
+            .unwrap();
+
+        for (sprint, points, status) in [
+            (first, 3, ItemStatus::Closed),
+            (first, 5, ItemStatus::Open),
+            (second, 8, ItemStatus::Resolved),
+            (second, 2, ItemStatus::Closed),
+            (current, 1, ItemStatus::Closed),
+        ] {
+            let story_id = db.add_story(story(points, status), None).unwrap();
+            db.add_story_to_sprint(sprint, story_id).unwrap();
+        }
+
+        let mut state = db.read_db().unwrap();
+        state
+            .stories
+            .values_mut()
+            .for_each(|story| story.detail.history.clear());
+        // Closed a day after the second sprint ended.
+        state.stories.get_mut(&3).unwrap().detail.history = vec![StatusChange {
+            status: ItemStatus::Closed,
+            by: None,
+            at: Local
+                .with_ymd_and_hms(2024, 5, 29, 12, 0, 0)
+                .unwrap()
+                .into(),
+        }];
+
+        let velocity = velocity(&state, date(30));
+
+        assert_eq!(
+            velocity,
+            vec![
+                SprintPoints {
+                    sprint_id: first,
+                    completed: 3,
+                    committed: 8,
+                },
+                SprintPoints {
+                    sprint_id: second,
+                    completed: 8,
+                    committed: 10,
+                },
+            ]
+        );
+        assert_eq!(rolling_average(&velocity, 3), Some(5.5));
+        assert_eq!(rolling_average(&velocity, 1), Some(8.0));
+        assert_eq!(rolling_average(&[], 3), None);
+    }
+
+    #[test]
+    fn flow_times_should_measure_lead_and_cycle_time() {
+        let change = |status: ItemStatus, day: u32| StatusChange {
+            status,
+            by: None,
+            at: Local
+                .with_ymd_and_hms(2024, 5, day, 12, 0, 0)
+                .unwrap()
+                .into(),
+        };
+        let story = |history: Vec<StatusChange>| {
+            Story::new(ItemDetail {
+                status: history.last().unwrap().status.clone(),
+                history,
+                ..Default::default()
+            })
+        };
+        let stories = [
+            story(vec![
+                change(ItemStatus::Open, 1),
+                change(ItemStatus::InProgress, 2),
+                change(ItemStatus::Closed, 4),
+            ]),
+            story(vec![
+                change(ItemStatus::Open, 1),
+                change(ItemStatus::InProgress, 5),
+                change(ItemStatus::Resolved, 6),
+            ]),
+            story(vec![
+                change(ItemStatus::Open, 1),
+                change(ItemStatus::Closed, 2),
+            ]),
+            story(vec![
+                change(ItemStatus::Open, 1),
+                change(ItemStatus::InProgress, 2),
+            ]),
+        ];
+
+        assert_eq!(lead_time(&stories[0]), Some(Duration::days(3)));
+        assert_eq!(cycle_time(&stories[0]), Some(Duration::days(2)));
+        assert_eq!(cycle_time(&stories[2]), None);
+        assert_eq!(lead_time(&stories[3]), None);
+
+        let (lead, cycle) = flow_times(stories.iter());
+
+        assert_eq!(
+            lead,
+            Some(TimeStats {
+                count: 3,
+                average: Duration::days(3),
+                p50: Duration::days(3),
+                p85: Duration::days(5),
+            })
+        );
+        assert_eq!(
+            cycle.unwrap().to_string(),
+            "avg 1.5d, p50 1.0d, p85 2.0d (2 stories)"
+        );
+    }
+
+    #[test]
+    fn wip_violations_should_check_every_limit() {
+        let db = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let story = |assignee: &str, status: ItemStatus| {
+            Story::new(ItemDetail {
+                assignee: Some(assignee.to_owned()),
+                status,
+                ..Default::default()
+            })
+        };
+        let _ = db
+            .add_story(story("alice", ItemStatus::InProgress), Some(epic_id))
+            .unwrap();
+        let _ = db
+            .add_story(story("bob", ItemStatus::InProgress), None)
+            .unwrap();
+        let starting = db
+            .add_story(story("alice", ItemStatus::Open), Some(epic_id))
+            .unwrap();
+        let state = db.read_db().unwrap();
+
+        let limits = WipLimits {
+            global: Some(2),
+            per_epic: Some(1),
+            per_assignee: Some(1),
+            enforce: false,
+        };
+
+        assert_eq!(
+            wip_violations(&state, &limits, &[starting]),
+            vec![
+                "3 stories would be in progress (limit 2)",
+                "EPIC-0 would have 2 stories in progress (limit 1)",
+                "@alice would have 2 stories in progress (limit 1)",
+            ]
+        );
+        assert!(wip_violations(&state, &WipLimits::default(), &[starting]).is_empty());
+        assert!(wip_violations(
+            &state,
+            &WipLimits {
+                global: Some(3),
+                per_epic: Some(2),
+                per_assignee: Some(2),
+                enforce: true,
+            },
+            &[starting]
+        )
+        .is_empty());
+    }
+}
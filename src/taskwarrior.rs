@@ -0,0 +1,428 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::model::{ItemDetail, ItemId, ItemStatus, Story, DB};
+use crate::stats::is_done;
+
+/// How Taskwarrior writes dates in `task export`, always in UTC.
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// A task as `task export` prints it and `task import` reads it. Attributes the
+/// board doesn't know about are kept as they are.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Task {
+    pub uuid: String,
+    pub description: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Set while the task is being worked on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
+/// What a sync changed on either side.
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncSummary {
+    pub stories_created: usize,
+    pub stories_updated: usize,
+    /// The tasks to hand to `task import`, new ones included.
+    pub tasks: Vec<Task>,
+}
+
+/// Runs the `task` binary, limited to `project` when given.
+pub struct Taskwarrior {
+    project: Option<String>,
+}
+
+impl Taskwarrior {
+    pub fn new(project: Option<String>) -> Self {
+        Self { project }
+    }
+
+    pub fn export(&self) -> Result<Vec<Task>> {
+        let mut command = Command::new("task");
+
+        command.arg("rc.confirmation=off");
+        if let Some(project) = &self.project {
+            command.arg(format!("project:{project}"));
+        }
+
+        let output = command
+            .arg("export")
+            .output()
+            .context("Failed to run task, is Taskwarrior installed?")?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "task export failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        serde_json::from_slice(&output.stdout).context("task export printed invalid JSON")
+    }
+
+    pub fn import(&self, tasks: &[Task]) -> Result<()> {
+        if tasks.is_empty() {
+            return Ok(());
+        }
+
+        let mut child = Command::new("task")
+            .args(["rc.confirmation=off", "import"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .context("Failed to run task, is Taskwarrior installed?")?;
+
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(&serde_json::to_vec(tasks)?)?;
+
+        if !child.wait()?.success() {
+            return Err(anyhow!("task import failed"));
+        }
+
+        Ok(())
+    }
+
+    /// Syncs the board with Taskwarrior both ways, see [`sync`].
+    pub fn sync(&self, db: &mut DB, now: DateTime<Utc>) -> Result<SyncSummary> {
+        Ok(sync(db, self.export()?, self.project.as_deref(), now))
+    }
+}
+
+/// Brings `db` and `tasks` in line with each other:
+///
+/// - a story and the task it is linked to take on whichever side changed last,
+/// - pending tasks without a story become stories without an epic,
+/// - stories that aren't done and have no task get one, in `project` if given.
+///
+/// Stories only carry their name, status, due date and labels over.
+pub fn sync(
+    db: &mut DB,
+    tasks: Vec<Task>,
+    project: Option<&str>,
+    now: DateTime<Utc>,
+) -> SyncSummary {
+    let mut summary = SyncSummary::default();
+    let mut tasks = tasks
+        .into_iter()
+        .map(|task| (task.uuid.clone(), task))
+        .collect::<HashMap<_, _>>();
+
+    for story in db.stories.values_mut() {
+        let Some(uuid) = story.taskwarrior.clone() else {
+            continue;
+        };
+        let Some(task) = tasks.remove(&uuid) else {
+            // Never made it into Taskwarrior, so it is pushed again.
+            summary
+                .tasks
+                .push(story_task(story, uuid, project, None, now));
+            continue;
+        };
+
+        if task.status == "deleted" {
+            continue;
+        }
+
+        let task_changed_last = match (
+            parse_date(task.modified.as_deref()),
+            story.detail.updated_at,
+        ) {
+            (Some(modified), Some(updated)) => modified > updated,
+            (modified, _) => modified.is_some(),
+        };
+
+        if task_changed_last {
+            if apply_task(&mut story.detail, &task, now) {
+                summary.stories_updated += 1;
+            }
+        } else {
+            let pushed = story_task(story, uuid, project, Some(&task), now);
+
+            if pushed != task {
+                summary.tasks.push(pushed);
+            }
+        }
+    }
+
+    for story in db.stories.values_mut() {
+        if story.taskwarrior.is_none() && !is_done(&story.detail.status) {
+            let uuid = story_uuid(story);
+
+            story.taskwarrior = Some(uuid.clone());
+            summary
+                .tasks
+                .push(story_task(story, uuid, project, None, now));
+        }
+    }
+
+    let mut new_tasks = tasks
+        .into_values()
+        .filter(|task| task.status == "pending")
+        .collect::<Vec<_>>();
+    new_tasks.sort_by(|a, b| a.uuid.cmp(&b.uuid));
+
+    for task in new_tasks {
+        let id = db.stories.keys().max().map_or(0, |last_id| last_id + 1);
+        let mut detail = ItemDetail {
+            id: ItemId(id),
+            ..Default::default()
+        };
+
+        apply_task(&mut detail, &task, now);
+
+        let mut story = Story::new(detail);
+        story.taskwarrior = Some(task.uuid);
+        db.stories.insert(id, story);
+        summary.stories_created += 1;
+    }
+
+    summary
+}
+
+/// Takes the task's name, status, due date and tags over, returning whether
+/// anything changed.
+fn apply_task(detail: &mut ItemDetail, task: &Task, now: DateTime<Utc>) -> bool {
+    let before = detail.clone();
+    let status = match (task.status.as_str(), &task.start) {
+        ("completed", _) if is_done(&detail.status) => detail.status.clone(),
+        ("completed", _) => ItemStatus::Closed,
+        (_, Some(_)) => ItemStatus::InProgress,
+        _ => ItemStatus::Open,
+    };
+
+    detail.name = task.description.clone();
+    detail.due = parse_date(task.due.as_deref()).map(|due| due.with_timezone(&Local).date_naive());
+    detail.labels = task.tags.clone();
+
+    if detail.status != status || detail.history.is_empty() {
+        detail.set_status(status, now);
+    }
+
+    *detail != before
+}
+
+/// The task `story` should be, keeping whatever else `existing` has.
+fn story_task(
+    story: &Story,
+    uuid: String,
+    project: Option<&str>,
+    existing: Option<&Task>,
+    now: DateTime<Utc>,
+) -> Task {
+    let mut task = existing.cloned().unwrap_or_else(|| Task {
+        uuid,
+        description: String::new(),
+        status: String::new(),
+        due: None,
+        tags: Vec::new(),
+        start: None,
+        modified: None,
+        other: HashMap::new(),
+    });
+
+    if existing.is_none() {
+        if let Some(project) = project {
+            task.other.insert("project".to_owned(), project.into());
+        }
+    }
+
+    task.description = story.detail.name.clone();
+    task.status = match is_done(&story.detail.status) {
+        true => "completed".to_owned(),
+        false => "pending".to_owned(),
+    };
+    task.due = story.detail.due.map(format_date);
+    task.tags = story.detail.labels.clone();
+    task.start = match story.detail.status {
+        ItemStatus::InProgress => task
+            .start
+            .or_else(|| Some(now.format(DATE_FORMAT).to_string())),
+        _ => None,
+    };
+
+    task
+}
+
+fn parse_date(date: Option<&str>) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(date?, DATE_FORMAT)
+        .ok()
+        .map(|date| date.and_utc())
+}
+
+/// Due dates are local midnight, the way `task add due:2024-05-10` sets them.
+fn format_date(date: NaiveDate) -> String {
+    let midnight = date.and_hms_opt(0, 0, 0).expect("midnight exists");
+    let midnight = Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map_or(midnight.and_utc(), |midnight| midnight.with_timezone(&Utc));
+
+    midnight.format(DATE_FORMAT).to_string()
+}
+
+/// A UUID made from the story's ID and when it was created, so a story gets
+/// the same one however often a sync is retried.
+fn story_uuid(story: &Story) -> String {
+    let created = story.detail.history.first().map(|change| change.at);
+    let hash = format!(
+        "{:x}",
+        Sha256::digest(format!("jira-clone/{}/{created:?}", story.key()))
+    );
+
+    format!(
+        "{}-{}-8{}-a{}-{}",
+        &hash[0..8],
+        &hash[8..12],
+        &hash[13..16],
+        &hash[17..20],
+        &hash[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+    use chrono::TimeZone;
+
+    fn task(uuid: &str, description: &str, status: &str) -> Task {
+        Task {
+            uuid: uuid.to_owned(),
+            description: description.to_owned(),
+            status: status.to_owned(),
+            due: None,
+            tags: Vec::new(),
+            start: None,
+            modified: None,
+            other: HashMap::new(),
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 2, 9, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn sync_should_turn_pending_tasks_into_stories() {
+        let mut db = JiraDataBase::from_database(Box::new(MockDB::new()))
+            .read_db()
+            .unwrap();
+        let mut started = task("b", "Renew passport", "pending");
+        started.start = Some("20240501T080000Z".to_owned());
+        started.due = Some("20240510T120000Z".to_owned());
+        started.tags = vec!["personal".to_owned()];
+
+        let summary = sync(
+            &mut db,
+            vec![
+                task("a", "Call the bank", "pending"),
+                started,
+                task("c", "Old chore", "completed"),
+                task("d", "Dropped", "deleted"),
+            ],
+            None,
+            now(),
+        );
+
+        assert_eq!(summary.stories_created, 2);
+        assert!(summary.tasks.is_empty());
+
+        let bank = &db.stories[&0];
+        assert_eq!(bank.detail.name, "Call the bank");
+        assert_eq!(bank.detail.status, ItemStatus::Open);
+        assert_eq!(bank.taskwarrior.as_deref(), Some("a"));
+        assert_eq!(bank.detail.history.len(), 1);
+
+        let passport = &db.stories[&1].detail;
+        assert_eq!(passport.status, ItemStatus::InProgress);
+        assert_eq!(passport.due, NaiveDate::from_ymd_opt(2024, 5, 10));
+        assert_eq!(passport.labels, vec!["personal".to_owned()]);
+    }
+
+    #[test]
+    fn sync_should_create_tasks_for_open_stories() {
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let open = database
+            .create_story("Billing".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let closed = database
+            .create_story("Done already".to_owned(), "".to_owned(), None)
+            .unwrap();
+        database
+            .update_story_status(closed, ItemStatus::Closed)
+            .unwrap();
+        database
+            .update_story_status(open, ItemStatus::InProgress)
+            .unwrap();
+        let mut db = database.read_db().unwrap();
+
+        let summary = sync(&mut db, Vec::new(), Some("work"), now());
+        let uuid = db.stories[&open.0].taskwarrior.clone().unwrap();
+
+        assert_eq!(db.stories[&closed.0].taskwarrior, None);
+        assert_eq!(summary.tasks.len(), 1);
+        assert_eq!(summary.tasks[0].uuid, uuid);
+        assert_eq!(summary.tasks[0].description, "Billing");
+        assert_eq!(summary.tasks[0].status, "pending");
+        assert_eq!(summary.tasks[0].start.as_deref(), Some("20240502T093000Z"));
+        assert_eq!(summary.tasks[0].other["project"], "work");
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(uuid, story_uuid(&db.stories[&open.0]));
+    }
+
+    #[test]
+    fn sync_should_keep_whichever_side_changed_last() {
+        let database = JiraDataBase::from_database(Box::new(MockDB::new()));
+        let story_id = database
+            .create_story("Billing".to_owned(), "".to_owned(), None)
+            .unwrap();
+        let mut db = database.read_db().unwrap();
+        let story = db.stories.get_mut(&story_id.0).unwrap();
+        story.taskwarrior = Some("a".to_owned());
+        story.detail.updated_at = Some(now());
+
+        let mut newer = task("a", "Billing v2", "completed");
+        newer.modified = Some("20240502T100000Z".to_owned());
+        newer
+            .other
+            .insert("urgency".to_owned(), serde_json::json!(4.2));
+
+        let summary = sync(&mut db, vec![newer.clone()], None, now());
+
+        assert_eq!(summary.stories_updated, 1);
+        assert!(summary.tasks.is_empty());
+        assert_eq!(db.stories[&story_id.0].detail.name, "Billing v2");
+        assert_eq!(db.stories[&story_id.0].detail.status, ItemStatus::Closed);
+
+        let story = db.stories.get_mut(&story_id.0).unwrap();
+        story.detail.name = "Billing v3".to_owned();
+        story.detail.updated_at = Some(Utc.with_ymd_and_hms(2024, 5, 2, 11, 0, 0).unwrap());
+
+        let summary = sync(&mut db, vec![newer], None, now());
+
+        assert_eq!(summary.stories_updated, 0);
+        assert_eq!(summary.tasks.len(), 1);
+        assert_eq!(summary.tasks[0].description, "Billing v3");
+        assert_eq!(summary.tasks[0].status, "completed");
+        assert_eq!(summary.tasks[0].other["urgency"], 4.2);
+    }
+}
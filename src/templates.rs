@@ -0,0 +1,126 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::model::{ChecklistItem, ItemDetail, Story};
+
+/// A story to start from, set under `"templates"` in the config. Placeholders
+/// like `{{customer}}` in its name, description and checklist are asked for
+/// each time it's used.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct StoryTemplate {
+    pub name: String,
+    pub description: String,
+    pub checklist: Vec<String>,
+    pub labels: Vec<String>,
+}
+
+impl StoryTemplate {
+    /// The placeholders of the template, in the order they first appear.
+    pub fn placeholders(&self) -> Vec<String> {
+        [&self.name, &self.description]
+            .into_iter()
+            .chain(&self.checklist)
+            .flat_map(|text| placeholders_in(text))
+            .unique()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// The story the template makes with `values` in place of its
+    /// placeholders. Placeholders without a value are kept as they are.
+    pub fn fill(&self, values: &HashMap<String, String>) -> Story {
+        let mut story = Story::new(ItemDetail {
+            name: substitute(&self.name, values),
+            description: substitute(&self.description, values),
+            labels: self.labels.clone(),
+            ..Default::default()
+        });
+
+        story.checklist = self
+            .checklist
+            .iter()
+            .map(|text| ChecklistItem {
+                text: substitute(text, values),
+                done: false,
+            })
+            .collect();
+
+        story
+    }
+}
+
+/// The names between `{{` and `}}` in `text`, spaces around them left out.
+fn placeholders_in(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+
+    while let Some((_, after)) = rest.split_once("{{") {
+        let Some((name, after)) = after.split_once("}}") else {
+            break;
+        };
+
+        if !name.trim().is_empty() {
+            names.push(name.trim());
+        }
+        rest = after;
+    }
+
+    names
+}
+
+fn substitute(text: &str, values: &HashMap<String, String>) -> String {
+    let mut filled = String::new();
+    let mut rest = text;
+
+    while let Some((before, after)) = rest.split_once("{{") {
+        let Some((name, after)) = after.split_once("}}") else {
+            break;
+        };
+
+        filled.push_str(before);
+        match values.get(name.trim()) {
+            Some(value) => filled.push_str(value),
+            None => filled.push_str(&format!("{{{{{name}}}}}")),
+        }
+        rest = after;
+    }
+
+    filled + rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_should_put_the_values_in_place_of_the_placeholders() {
+        let template = StoryTemplate {
+            name: "Upgrade {{ customer }} to {{version}}".to_owned(),
+            description: "{{customer}} runs {{old}}, {{ not closed".to_owned(),
+            checklist: vec!["Back up {{customer}}".to_owned(), "Notify".to_owned()],
+            labels: vec!["upgrade".to_owned()],
+        };
+
+        assert_eq!(template.placeholders(), vec!["customer", "version", "old"]);
+
+        let values = HashMap::from([
+            ("customer".to_owned(), "Acme".to_owned()),
+            ("version".to_owned(), "2.0".to_owned()),
+        ]);
+        let story = template.fill(&values);
+
+        assert_eq!(story.detail.name, "Upgrade Acme to 2.0");
+        assert_eq!(story.detail.description, "Acme runs {{old}}, {{ not closed");
+        assert_eq!(story.detail.labels, vec!["upgrade"]);
+        assert_eq!(
+            story
+                .checklist
+                .iter()
+                .map(|item| item.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Back up Acme", "Notify"]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::model::{Epic, EpicBundle, ItemDetail, ItemStatus, Priority, Story};
+
+const API_URL: &str = "https://api.todoist.com/api/v1";
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Task {
+    pub content: String,
+    #[serde(default)]
+    pub description: String,
+    pub project_id: String,
+    /// From 1 (normal) to 4 (urgent), the reverse of the p1-p4 the app shows.
+    pub priority: u8,
+    pub due: Option<Due>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Due {
+    /// `2024-05-03`, or with a time of day when the task has one.
+    pub date: String,
+}
+
+/// One page of a listing, with the cursor of the next one if there is more.
+#[derive(Deserialize)]
+struct Page<T> {
+    results: Vec<T>,
+    next_cursor: Option<String>,
+}
+
+/// Reads projects and tasks from the Todoist API through `curl`, so the board
+/// doesn't need an HTTP client of its own.
+pub struct Todoist {
+    token: String,
+}
+
+impl Todoist {
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+
+    pub fn projects(&self) -> Result<Vec<Project>> {
+        self.list("projects")
+    }
+
+    /// The tasks that aren't done yet.
+    pub fn tasks(&self) -> Result<Vec<Task>> {
+        self.list("tasks")
+    }
+
+    fn list<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let url = match &cursor {
+                Some(cursor) => format!("{API_URL}/{path}?cursor={cursor}"),
+                None => format!("{API_URL}/{path}"),
+            };
+            let page: Page<T> = serde_json::from_slice(&self.get(&url)?)
+                .with_context(|| anyhow!("Todoist sent an invalid list of {path}"))?;
+
+            items.extend(page.results);
+            cursor = page.next_cursor;
+
+            if cursor.is_none() {
+                return Ok(items);
+            }
+        }
+    }
+
+    /// The token goes to `curl` on stdin rather than as an argument, where
+    /// anyone listing processes could read it.
+    fn get(&self, url: &str) -> Result<Vec<u8>> {
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--header", "@-", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run curl, is it installed?")?;
+
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(format!("Authorization: Bearer {}\n", self.token).as_bytes())?;
+
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Failed to reach Todoist: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(output.stdout)
+    }
+}
+
+/// An epic bundle per project, holding its tasks as open stories, ready for
+/// [`JiraDataBase::import_epic`](crate::db::JiraDataBase::import_epic).
+pub fn bundles(projects: Vec<Project>, tasks: Vec<Task>, now: DateTime<Utc>) -> Vec<EpicBundle> {
+    let open = |name: String, description: String| {
+        let mut detail = ItemDetail {
+            name,
+            description,
+            ..Default::default()
+        };
+
+        detail.set_status(ItemStatus::Open, now);
+        detail
+    };
+
+    projects
+        .into_iter()
+        .map(|project| {
+            let stories = tasks
+                .iter()
+                .filter(|task| task.project_id == project.id)
+                .map(|task| {
+                    let mut detail = open(task.content.clone(), task.description.clone());
+
+                    detail.priority = priority(task.priority);
+                    detail.labels = task.labels.clone();
+                    detail.due = task.due.as_ref().and_then(|due| {
+                        NaiveDate::parse_from_str(due.date.get(..10)?, "%Y-%m-%d").ok()
+                    });
+
+                    Story::new(detail)
+                })
+                .collect();
+
+            EpicBundle {
+                epic: Epic::new(open(project.name, String::new()), Vec::new()),
+                stories,
+            }
+        })
+        .collect()
+}
+
+/// Todoist's normal priority means none was set.
+fn priority(priority: u8) -> Option<Priority> {
+    match priority {
+        4 => Some(Priority::Critical),
+        3 => Some(Priority::High),
+        2 => Some(Priority::Medium),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn bundles_should_turn_projects_into_epics_and_tasks_into_stories() {
+        let projects: Page<Project> = serde_json::from_str(
+            r#"{ "results": [
+                { "id": "p1", "name": "Home", "color": "red" },
+                { "id": "p2", "name": "Garden" }
+            ], "next_cursor": null }"#,
+        )
+        .unwrap();
+        let tasks: Page<Task> = serde_json::from_str(
+            r#"{ "results": [
+                { "id": "t1", "content": "Fix the sink", "project_id": "p1", "priority": 4,
+                  "due": { "date": "2024-05-03T18:00:00", "is_recurring": false },
+                  "labels": ["plumbing"] },
+                { "id": "t2", "content": "Buy paint", "description": "White", "project_id": "p1",
+                  "priority": 1, "due": null }
+            ], "next_cursor": null }"#,
+        )
+        .unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+
+        let bundles = bundles(projects.results, tasks.results, now);
+
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(bundles[0].epic.detail.name, "Home");
+        assert_eq!(bundles[0].epic.detail.history[0].at, now);
+        assert!(bundles[1].stories.is_empty());
+
+        let sink = &bundles[0].stories[0].detail;
+        assert_eq!(sink.name, "Fix the sink");
+        assert_eq!(sink.priority, Some(Priority::Critical));
+        assert_eq!(sink.due, NaiveDate::from_ymd_opt(2024, 5, 3));
+        assert_eq!(sink.labels, vec!["plumbing".to_owned()]);
+        assert_eq!(sink.status, ItemStatus::Open);
+
+        let paint = &bundles[0].stories[1].detail;
+        assert_eq!(paint.description, "White");
+        assert_eq!(paint.priority, None);
+        assert_eq!(paint.due, None);
+    }
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use super::KeyHint;
+
+/// Which keys the board answers to on top of the ones each page lists, set
+/// with `"keymap"` in the config.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Keymap {
+    #[default]
+    Default,
+    /// `j`/`k` to move through the rows of a list, `l` or enter to open the
+    /// selected one, `h` or `:q` to go back, doubled keys like `dd` for the
+    /// page keys and `:` to type page input that the keymap would take.
+    Vim,
+    /// `C-n`/`C-p` to move through the rows of a list, enter to open the
+    /// selected one, `C-s` to search, `C-g` to go back, `C-x C-c` to quit and
+    /// `M-x` to type page input. Control keys can be typed as they are or
+    /// spelled out, e.g. `C-n`.
+    Emacs,
+}
+
+/// What a line of input means once the keymap has translated it.
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyCommand {
+    /// Input for the page, in its own keys.
+    Page(String),
+    /// Selects the next row of the list on the page.
+    Next,
+    /// Selects the previous row of the list on the page.
+    Previous,
+    /// Opens the selected row, or passes enter on to the page when no row is
+    /// selected.
+    Open,
+    /// Goes back a page, or quits from the first one.
+    Back,
+    /// Quits the board from any page.
+    Quit,
+}
+
+impl Keymap {
+    /// The keys the keymap adds, for the `?` overlay.
+    pub fn key_hints(&self) -> Vec<KeyHint> {
+        match self {
+            Self::Default => Vec::new(),
+            Self::Vim => vec![
+                KeyHint::new("j / k", "select next / previous row"),
+                KeyHint::new("l / enter", "open selected row"),
+                KeyHint::new("h / :q", "back"),
+                KeyHint::new("dd / yy", "d / y on this page"),
+                KeyHint::new(":input", "input for the page"),
+            ],
+            Self::Emacs => vec![
+                KeyHint::new("C-n / C-p", "select next / previous row"),
+                KeyHint::new("enter", "open selected row"),
+                KeyHint::new("C-s text", "search"),
+                KeyHint::new("C-g", "back"),
+                KeyHint::new("C-x C-c", "quit"),
+                KeyHint::new("M-x input", "input for the page"),
+            ],
+        }
+    }
+
+    pub fn translate(&self, input: &str) -> KeyCommand {
+        match self {
+            Self::Default => KeyCommand::Page(input.to_owned()),
+            Self::Vim => vim(input),
+            Self::Emacs => emacs(input),
+        }
+    }
+}
+
+fn vim(input: &str) -> KeyCommand {
+    match input {
+        "j" => KeyCommand::Next,
+        "k" => KeyCommand::Previous,
+        "l" | "" => KeyCommand::Open,
+        "h" | ":q" => KeyCommand::Back,
+        "dd" | "yy" => KeyCommand::Page(input[..1].to_owned()),
+        input => match input.strip_prefix(':') {
+            Some(command) => KeyCommand::Page(command.trim().to_owned()),
+            None => KeyCommand::Page(input.to_owned()),
+        },
+    }
+}
+
+fn emacs(input: &str) -> KeyCommand {
+    // Terminals pass most control keys through as their control characters,
+    // so `\u{e}` is C-n. Spelling them out works everywhere, C-s included,
+    // which many terminals keep for flow control.
+    let input = input
+        .replace('\u{e}', "C-n")
+        .replace('\u{10}', "C-p")
+        .replace('\u{13}', "C-s ")
+        .replace('\u{7}', "C-g")
+        .replace('\u{18}', "C-x ")
+        .replace('\u{3}', "C-c");
+
+    match input.trim() {
+        "C-n" => KeyCommand::Next,
+        "C-p" => KeyCommand::Previous,
+        "" | "C-m" | "C-j" => KeyCommand::Open,
+        "C-g" => KeyCommand::Back,
+        "C-x C-c" | "C-xC-c" => KeyCommand::Quit,
+        input => {
+            if let Some(query) = input.strip_prefix("C-s") {
+                return KeyCommand::Page(format!("/ {}", query.trim()));
+            }
+
+            match input.strip_prefix("M-x") {
+                Some(command) => KeyCommand::Page(command.trim().to_owned()),
+                None => KeyCommand::Page(input.to_owned()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translate_should_pass_input_through_by_default() {
+        assert_eq!(
+            Keymap::Default.translate("j"),
+            KeyCommand::Page("j".to_owned())
+        );
+        assert_eq!(
+            Keymap::Default.translate(""),
+            KeyCommand::Page("".to_owned())
+        );
+    }
+
+    #[test]
+    fn translate_should_read_vim_keys() {
+        let vim = Keymap::Vim;
+
+        assert_eq!(vim.translate("j"), KeyCommand::Next);
+        assert_eq!(vim.translate("k"), KeyCommand::Previous);
+        assert_eq!(vim.translate(""), KeyCommand::Open);
+        assert_eq!(vim.translate(":q"), KeyCommand::Back);
+        assert_eq!(vim.translate("dd"), KeyCommand::Page("d".to_owned()));
+        assert_eq!(vim.translate(":u3"), KeyCommand::Page("u3".to_owned()));
+        assert_eq!(vim.translate(": j"), KeyCommand::Page("j".to_owned()));
+        assert_eq!(
+            vim.translate("/ #bug"),
+            KeyCommand::Page("/ #bug".to_owned())
+        );
+    }
+
+    #[test]
+    fn translate_should_read_emacs_keys() {
+        let emacs = Keymap::Emacs;
+
+        assert_eq!(emacs.translate("C-n"), KeyCommand::Next);
+        assert_eq!(emacs.translate("\u{e}"), KeyCommand::Next);
+        assert_eq!(emacs.translate("\u{10}"), KeyCommand::Previous);
+        assert_eq!(emacs.translate(""), KeyCommand::Open);
+        assert_eq!(emacs.translate("C-g"), KeyCommand::Back);
+        assert_eq!(emacs.translate("\u{18}\u{3}"), KeyCommand::Quit);
+        assert_eq!(
+            emacs.translate("C-s #bug login"),
+            KeyCommand::Page("/ #bug login".to_owned())
+        );
+        assert_eq!(
+            emacs.translate("\u{13}login"),
+            KeyCommand::Page("/ login".to_owned())
+        );
+        assert_eq!(emacs.translate("M-x u3"), KeyCommand::Page("u3".to_owned()));
+        assert_eq!(emacs.translate("c"), KeyCommand::Page("c".to_owned()));
+    }
+}
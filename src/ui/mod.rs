@@ -1,5 +1,9 @@
+mod keymap;
 mod pages;
 mod prompts;
+mod renderer;
 
+pub use keymap::*;
 pub use pages::*;
 pub use prompts::*;
+pub use renderer::*;
@@ -0,0 +1,310 @@
+use anyhow::Result;
+use itertools::Itertools;
+use std::any::Any;
+use std::cell::Cell;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::db::JiraDataBase;
+use crate::model::{Action, ItemStatus, ItemType, Priority, DB};
+
+use super::all_items;
+use super::item_list::{ItemList, ItemRow};
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::page_helpers::{get_column_string, last_change};
+use super::{Page, QuickFilter};
+
+/// The status columns of the board, left to right.
+const COLUMNS: [ItemStatus; 4] = [
+    ItemStatus::Open,
+    ItemStatus::InProgress,
+    ItemStatus::Resolved,
+    ItemStatus::Closed,
+];
+
+const COLUMN_WIDTH: usize = 22;
+
+/// What the rows of the board are grouped by.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Lanes {
+    /// A lane per assignee, the unassigned stories last.
+    #[default]
+    Assignee,
+    /// A lane per priority band, the most severe first, for triage.
+    Priority,
+}
+
+/// Every story in a column per status, split into swimlanes by assignee or
+/// by priority. `l` switches between the two.
+pub struct Board {
+    pub db: Rc<JiraDataBase>,
+    pub lanes: Cell<Lanes>,
+    pub quick_filter: QuickFilter,
+}
+
+impl Board {
+    pub fn new(db: Rc<JiraDataBase>) -> Self {
+        Self {
+            db,
+            lanes: Cell::new(Lanes::default()),
+            quick_filter: QuickFilter::default(),
+        }
+    }
+
+    /// The title and rows of every lane, numbered across the board in the
+    /// order they are drawn.
+    fn swimlanes(&self, db: &DB) -> Vec<(String, ItemList)> {
+        let stories = all_items(db)
+            .filter(|(item, ..)| matches!(item, ItemType::Story { .. }))
+            .collect::<Vec<_>>();
+        let lanes: Vec<(String, Vec<_>)> = match self.lanes.get() {
+            Lanes::Priority => [
+                Some(Priority::Critical),
+                Some(Priority::High),
+                Some(Priority::Medium),
+                Some(Priority::Low),
+                None,
+            ]
+            .into_iter()
+            .filter_map(|priority| {
+                let lane = stories
+                    .iter()
+                    .filter(|(.., detail)| detail.priority == priority)
+                    .collect::<Vec<_>>();
+
+                match priority {
+                    Some(priority) => Some((priority.to_string(), lane)),
+                    // Every band shows even when empty, the unprioritized
+                    // stories only when there are some.
+                    None => (!lane.is_empty()).then(|| ("NO PRIORITY".to_owned(), lane)),
+                }
+            })
+            .collect(),
+            Lanes::Assignee => stories
+                .iter()
+                .map(|(.., detail)| detail.assignee.clone())
+                .unique()
+                .sorted_by_key(|assignee| (assignee.is_none(), assignee.clone()))
+                .map(|assignee| {
+                    let title = match &assignee {
+                        Some(assignee) => format!("@{assignee}"),
+                        None => "UNASSIGNED".to_owned(),
+                    };
+                    let lane = stories
+                        .iter()
+                        .filter(|(.., detail)| detail.assignee == assignee)
+                        .collect();
+
+                    (title, lane)
+                })
+                .collect(),
+        };
+        let mut handle = 0;
+
+        lanes
+            .into_iter()
+            .map(|(title, lane)| {
+                let rows = lane
+                    .into_iter()
+                    .sorted_by_key(|(.., detail)| {
+                        (
+                            COLUMNS.iter().position(|status| *status == detail.status),
+                            detail.id.0,
+                        )
+                    })
+                    .map(|(item, epic_id, key, detail)| {
+                        handle += 1;
+
+                        ItemRow {
+                            handle: handle.to_string(),
+                            item: item.clone(),
+                            epic_id: *epic_id,
+                            name: format!("{key} {}", detail.name),
+                            status: detail.status.clone(),
+                            updated_at: last_change(detail),
+                            due: detail.due,
+                            badge: None,
+                        }
+                    })
+                    .collect();
+
+                (title, ItemList::new(rows).filtered(&self.quick_filter))
+            })
+            .collect()
+    }
+
+    fn items(&self) -> Result<ItemList> {
+        let rows = self
+            .swimlanes(&self.db.read_db()?)
+            .into_iter()
+            .flat_map(|(_, list)| list.rows)
+            .collect();
+
+        Ok(ItemList::new(rows))
+    }
+}
+
+impl Page for Board {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+
+        writeln!(
+            out,
+            "------------------------------ BOARD ------------------------------"
+        )?;
+        writeln!(
+            out,
+            "{}",
+            COLUMNS
+                .iter()
+                .map(|status| get_column_string(&status.to_string().to_uppercase(), COLUMN_WIDTH))
+                .join(" | ")
+        )?;
+        self.quick_filter.draw(out)?;
+
+        for (title, list) in self.swimlanes(&db_state) {
+            let columns = COLUMNS
+                .iter()
+                .map(|status| {
+                    list.rows
+                        .iter()
+                        .filter(|row| row.status == *status)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let depth = columns.iter().map(Vec::len).max().unwrap_or_default();
+
+            writeln!(out)?;
+            writeln!(out, "{title} ({})", list.rows.len())?;
+
+            for line in 0..depth {
+                let cells = columns.iter().map(|column| {
+                    let cell = column
+                        .get(line)
+                        .map(|row| format!("{} {}", row.handle, row.name))
+                        .unwrap_or_default();
+
+                    get_column_string(&cell, COLUMN_WIDTH)
+                });
+
+                writeln!(out, "{}", cells.collect::<Vec<_>>().join(" | ").trim_end())?;
+            }
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        let other = match self.lanes.get() {
+            Lanes::Assignee => "lanes by priority",
+            Lanes::Priority => "lanes by assignee",
+        };
+
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            // Only changes how the page draws, so there is no action to hand
+            // to the navigator.
+            Binding::fallible("l", other, || {
+                self.lanes.set(match self.lanes.get() {
+                    Lanes::Assignee => Lanes::Priority,
+                    Lanes::Priority => Lanes::Assignee,
+                });
+                Ok(None)
+            }),
+            Binding::new("/", "filter", || Action::QuickFilter),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        Ok(self.items()?.handle_input(input))
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self.items()?.labels())
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new(":#:", "open story"),
+            KeyHint::new("u:#: / s:#:", "update story"),
+        ]
+    }
+
+    fn quick_filter(&self) -> Option<&QuickFilter> {
+        Some(&self.quick_filter)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::model::{ItemDetail, Story};
+
+    #[test]
+    fn draw_page_should_group_the_stories_by_assignee_or_priority() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let mut ids = Vec::new();
+
+        for (name, assignee, priority, status) in [
+            (
+                "Outage",
+                Some("bob"),
+                Some(Priority::Critical),
+                ItemStatus::InProgress,
+            ),
+            ("Typo", Some("alice"), Some(Priority::Low), ItemStatus::Open),
+            ("Refunds", None, Some(Priority::Critical), ItemStatus::Open),
+        ] {
+            let story = Story::new(ItemDetail {
+                name: name.to_owned(),
+                assignee: assignee.map(str::to_owned),
+                priority,
+                status,
+                ..Default::default()
+            });
+            ids.push(db.add_story(story, Some(epic_id)).unwrap());
+        }
+
+        let page = Board::new(db);
+        let draw = || {
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let by_assignee = draw();
+        assert!(by_assignee.find("@alice (1)").unwrap() < by_assignee.find("@bob (1)").unwrap());
+        assert!(by_assignee.contains("UNASSIGNED (1)\n3 STORY-2 Refunds"));
+        assert_eq!(
+            page.handle_input("2").unwrap(),
+            Some(Action::NavigateToStoryDetail {
+                epic_id: epic_id.0,
+                story_id: ids[0].0
+            })
+        );
+
+        assert_eq!(page.handle_input("l").unwrap(), None);
+
+        let by_priority = draw();
+        assert!(by_priority.contains("CRITICAL (2)\n1 STORY-2 Refunds"));
+        assert!(by_priority.contains("| 2 STORY-0 Outage"));
+        assert!(by_priority.contains("HIGH (0)"));
+        assert!(by_priority.contains("LOW (1)"));
+        assert!(!by_priority.contains("NO PRIORITY"));
+        assert_eq!(
+            page.handle_input("u3").unwrap(),
+            Some(Action::UpdateStoryStatus { story_id: ids[1].0 })
+        );
+    }
+}
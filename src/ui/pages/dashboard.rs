@@ -0,0 +1,99 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::any::Any;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use crate::calendar::Calendar;
+use crate::db::JiraDataBase;
+use crate::model::Action;
+use crate::stats::{flow_times, summary, TimeStats};
+
+use super::key_hints::{print_key_hints, Binding};
+use super::sprints::print_velocity;
+use super::Page;
+
+pub(super) fn print_flow_times(
+    out: &mut dyn Write,
+    lead: Option<TimeStats>,
+    cycle: Option<TimeStats>,
+) -> io::Result<()> {
+    let describe = |stats: Option<TimeStats>| match stats {
+        Some(stats) => stats.to_string(),
+        None => "no finished stories yet".to_owned(),
+    };
+
+    writeln!(out, "lead time:  {}", describe(lead))?;
+    writeln!(out, "cycle time: {}", describe(cycle))
+}
+
+pub struct Dashboard {
+    pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
+}
+
+impl Page for Dashboard {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+        let (lead, cycle) = flow_times(db_state.stories.values());
+
+        writeln!(
+            out,
+            "---------------------------- DASHBOARD ----------------------------"
+        )?;
+        writeln!(out, "{}", summary(&db_state, &self.calendar, Utc::now()))?;
+        writeln!(out)?;
+        print_flow_times(out, lead, cycle)?;
+        print_velocity(out, &db_state, self.calendar.today())?;
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![Binding::new("p", "previous", || {
+            Action::NavigateToPreviousPage
+        })]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+
+    #[test]
+    fn draw_page_should_not_throw_error() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let _ = db.create_story("".to_owned(), "".to_owned(), None).unwrap();
+
+        let page = Dashboard {
+            db,
+            calendar: Calendar::default(),
+        };
+        assert!(page.draw_page(&mut io::sink()).is_ok());
+    }
+
+    #[test]
+    fn handle_input_should_return_the_correct_actions() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+        let page = Dashboard {
+            db,
+            calendar: Calendar::default(),
+        };
+
+        assert_eq!(
+            page.handle_input("p").unwrap(),
+            Some(Action::NavigateToPreviousPage)
+        );
+        assert_eq!(page.handle_input("j983f2j").unwrap(), None);
+    }
+}
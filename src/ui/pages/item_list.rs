@@ -0,0 +1,290 @@
+use chrono::{DateTime, Days, Local, NaiveDate, Utc};
+use std::io::{self, Write};
+
+use crate::model::{Action, ItemStatus, ItemType};
+use crate::stats::is_stale;
+
+use super::page_helpers::{get_column_string, relative_date, relative_time};
+
+/// One row of a list page. `handle` is what gets typed to pick the row: the item
+/// ID on the board pages and the row number on result pages.
+pub struct ItemRow {
+    pub handle: String,
+    pub item: ItemType,
+    pub epic_id: Option<u32>,
+    pub name: String,
+    pub status: ItemStatus,
+    /// When the item last changed, shown as an age like `2 days ago`.
+    pub updated_at: Option<DateTime<Utc>>,
+    pub due: Option<NaiveDate>,
+    /// Shown at the end of the row, like the status counts of an epic's stories.
+    pub badge: Option<String>,
+}
+
+impl ItemRow {
+    /// What to say after the status: how far off the due date is, whether the
+    /// item went stale and the badge.
+    fn notes(&self, today: NaiveDate, stale: bool) -> Vec<String> {
+        self.due
+            .map(|due| format!("due {}", relative_date(due, today)))
+            .into_iter()
+            .chain(stale.then(|| "stale".to_owned()))
+            .chain(self.badge.clone())
+            .collect()
+    }
+}
+
+/// The rows shared by every page that lists epics or stories, together with the
+/// keys that act on them: `<handle>` opens a row, `u<handle>` or `s<handle>`
+/// updates its status, `d<handle>` deletes it and `U` updates the status of
+/// every row.
+pub struct ItemList {
+    pub rows: Vec<ItemRow>,
+    /// How long a row that isn't done can go unchanged before it's marked stale.
+    pub stale_after: Option<Days>,
+}
+
+impl ItemList {
+    pub fn new(rows: Vec<ItemRow>) -> Self {
+        Self {
+            rows,
+            stale_after: None,
+        }
+    }
+
+    /// Marks the rows that aren't done and haven't changed for `after` as
+    /// stale. `None` marks nothing.
+    pub fn marking_stale(mut self, after: Option<Days>) -> Self {
+        self.stale_after = after;
+        self
+    }
+
+    pub fn draw(&self, out: &mut dyn Write, handle_title: &str) -> io::Result<()> {
+        let now = Utc::now();
+        let today = Local::now().date_naive();
+
+        writeln!(
+            out,
+            "{}|               name               |      status      |    updated    ",
+            get_column_string(&format!("     {handle_title}"), 12)
+        )?;
+
+        for row in &self.rows {
+            let updated = row
+                .updated_at
+                .map(|at| relative_time(at, now))
+                .unwrap_or_default();
+            let line = format!(
+                "{} | {} | {} | {}",
+                get_column_string(&row.handle, 11),
+                get_column_string(&row.name, 32),
+                get_column_string(&row.status.to_string(), 17),
+                get_column_string(&updated, 13)
+            );
+
+            let stale = self
+                .stale_after
+                .is_some_and(|after| is_stale(&row.status, row.updated_at, now, after));
+
+            match row.notes(today, stale).join(" ") {
+                notes if notes.is_empty() => writeln!(out, "{}", line.trim_end())?,
+                notes => writeln!(out, "{line} {notes}")?,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn handle_input(&self, input: &str) -> Option<Action> {
+        if input == "U" {
+            if self.rows.is_empty() {
+                return None;
+            }
+
+            return Some(Action::BulkUpdateStatus {
+                items: self.rows.iter().map(|row| row.item.clone()).collect(),
+            });
+        }
+
+        let (key, row) = match self.find(input) {
+            Some(row) => (None, row),
+            None => {
+                let (key, handle) = row_command(input)?;
+                (Some(key), self.find(handle)?)
+            }
+        };
+
+        match (key, &row.item) {
+            (None, ItemType::Epic { id }) => Some(Action::NavigateToEpicDetail { epic_id: id.0 }),
+            (None, ItemType::Story { id }) => {
+                row.epic_id.map(|epic_id| Action::NavigateToStoryDetail {
+                    epic_id,
+                    story_id: id.0,
+                })
+            }
+            (Some('u' | 's'), ItemType::Epic { id }) => {
+                Some(Action::UpdateEpicStatus { epic_id: id.0 })
+            }
+            (Some('u' | 's'), ItemType::Story { id }) => {
+                Some(Action::UpdateStoryStatus { story_id: id.0 })
+            }
+            (Some('d'), ItemType::Epic { id }) => Some(Action::DeleteEpic { epic_id: id.0 }),
+            (Some('d'), ItemType::Story { id }) => row.epic_id.map(|epic_id| Action::DeleteStory {
+                epic_id,
+                story_id: id.0,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The handle and name of every row.
+    pub fn labels(&self) -> Vec<(String, String)> {
+        self.rows
+            .iter()
+            .map(|row| (row.handle.clone(), row.name.clone()))
+            .collect()
+    }
+
+    /// The row with `handle`, or the row of the item with that key, e.g.
+    /// `STORY-7`.
+    fn find(&self, handle: &str) -> Option<&ItemRow> {
+        self.rows
+            .iter()
+            .find(|row| row.handle == handle)
+            .or_else(|| {
+                self.rows
+                    .iter()
+                    .find(|row| row.item.key().eq_ignore_ascii_case(handle))
+            })
+    }
+}
+
+/// Splits input like `d3` into the key and the handle it applies to.
+fn row_command(input: &str) -> Option<(char, &str)> {
+    let key = input.chars().next()?;
+    let handle = input[key.len_utf8()..].trim();
+
+    (matches!(key, 'u' | 's' | 'd') && !handle.is_empty()).then_some((key, handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ItemId;
+
+    fn list() -> ItemList {
+        ItemList::new(vec![
+            ItemRow {
+                handle: "1".to_owned(),
+                item: ItemType::Epic { id: ItemId(4) },
+                epic_id: None,
+                name: "Auth".to_owned(),
+                status: ItemStatus::Open,
+                updated_at: None,
+                due: None,
+                badge: None,
+            },
+            ItemRow {
+                handle: "2".to_owned(),
+                item: ItemType::Story { id: ItemId(7) },
+                epic_id: Some(4),
+                name: "Fix login".to_owned(),
+                status: ItemStatus::InProgress,
+                updated_at: None,
+                due: None,
+                badge: None,
+            },
+            ItemRow {
+                handle: "3".to_owned(),
+                item: ItemType::Story { id: ItemId(8) },
+                epic_id: None,
+                name: "Orphan".to_owned(),
+                status: ItemStatus::Open,
+                updated_at: None,
+                due: None,
+                badge: None,
+            },
+        ])
+    }
+
+    #[test]
+    fn handle_input_should_act_on_the_picked_row() {
+        let list = list();
+
+        assert_eq!(
+            list.handle_input("1"),
+            Some(Action::NavigateToEpicDetail { epic_id: 4 })
+        );
+        assert_eq!(
+            list.handle_input("2"),
+            Some(Action::NavigateToStoryDetail {
+                epic_id: 4,
+                story_id: 7
+            })
+        );
+        assert_eq!(list.handle_input("3"), None);
+        assert_eq!(
+            list.handle_input("u1"),
+            Some(Action::UpdateEpicStatus { epic_id: 4 })
+        );
+        assert_eq!(
+            list.handle_input("u3"),
+            Some(Action::UpdateStoryStatus { story_id: 8 })
+        );
+        assert_eq!(
+            list.handle_input("s2"),
+            Some(Action::UpdateStoryStatus { story_id: 7 })
+        );
+        assert_eq!(
+            list.handle_input("d1"),
+            Some(Action::DeleteEpic { epic_id: 4 })
+        );
+        assert_eq!(
+            list.handle_input("d 2"),
+            Some(Action::DeleteStory {
+                epic_id: 4,
+                story_id: 7
+            })
+        );
+        assert_eq!(list.handle_input("d3"), None);
+        assert_eq!(list.handle_input("u9"), None);
+        assert_eq!(list.handle_input("x1"), None);
+        assert_eq!(list.handle_input("4"), None);
+        assert_eq!(
+            list.handle_input("u story-8"),
+            Some(Action::UpdateStoryStatus { story_id: 8 })
+        );
+    }
+
+    #[test]
+    fn handle_input_should_bulk_update_every_row() {
+        assert_eq!(
+            list().handle_input("U"),
+            Some(Action::BulkUpdateStatus {
+                items: vec![
+                    ItemType::Epic { id: ItemId(4) },
+                    ItemType::Story { id: ItemId(7) },
+                    ItemType::Story { id: ItemId(8) },
+                ]
+            })
+        );
+        assert_eq!(ItemList::new(Vec::new()).handle_input("U"), None);
+    }
+
+    #[test]
+    fn draw_should_mark_rows_unchanged_for_too_long_as_stale() {
+        let mut list = list().marking_stale(Some(Days::new(30)));
+        list.rows[0].updated_at = Some(Utc::now() - chrono::Duration::days(45));
+        list.rows[1].updated_at = Some(Utc::now() - chrono::Duration::days(3));
+        list.rows[2].updated_at = Some(Utc::now() - chrono::Duration::days(45));
+        list.rows[2].status = ItemStatus::Closed;
+        let mut out = Vec::new();
+
+        list.draw(&mut out, "id").unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let stale: Vec<_> = out.lines().filter(|line| line.ends_with("stale")).collect();
+
+        assert_eq!(stale.len(), 1);
+        assert!(stale[0].contains("Auth"));
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::any::Any;
+use std::io::{self, Write};
+
+use crate::model::Action;
+
+use super::page_helpers::get_column_string;
+use super::Page;
+
+/// How wide the footer gets before the hints wrap onto the next line.
+const FOOTER_WIDTH: usize = 100;
+
+/// A key (or input pattern, e.g. `u:id:`) a page reacts to and what it does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct KeyHint {
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+impl KeyHint {
+    pub const fn new(keys: &'static str, description: &'static str) -> Self {
+        Self { keys, description }
+    }
+}
+
+/// One of the fixed keys of a page: the hint that describes it and how to
+/// build the action it stands for. Pages list them in `Page::bindings`, which
+/// is where both the footer and the input dispatch come from.
+pub struct Binding<'a> {
+    pub hint: KeyHint,
+    inputs: Vec<&'static str>,
+    action: Box<dyn Fn() -> Result<Option<Action>> + 'a>,
+}
+
+impl<'a> Binding<'a> {
+    pub fn new(
+        keys: &'static str,
+        description: &'static str,
+        action: impl Fn() -> Action + 'a,
+    ) -> Self {
+        Self::fallible(keys, description, move || Ok(Some(action())))
+    }
+
+    /// A binding whose action may fail, or turn out to be nothing to do.
+    pub fn fallible(
+        keys: &'static str,
+        description: &'static str,
+        action: impl Fn() -> Result<Option<Action>> + 'a,
+    ) -> Self {
+        Self {
+            hint: KeyHint::new(keys, description),
+            inputs: vec![keys],
+            action: Box::new(action),
+        }
+    }
+
+    /// Answers to `inputs` rather than the keys in the hint, e.g. `""` for
+    /// enter.
+    pub fn on(mut self, inputs: &[&'static str]) -> Self {
+        self.inputs = inputs.to_vec();
+        self
+    }
+
+    pub fn matches(&self, input: &str) -> bool {
+        self.inputs.contains(&input)
+    }
+
+    pub fn action(&self) -> Result<Option<Action>> {
+        (self.action)()
+    }
+}
+
+/// The footer of a page: its hints as `[keys] description`, joined with `|`
+/// and wrapped at `FOOTER_WIDTH`.
+pub fn footer_lines(hints: &[KeyHint]) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for hint in hints.iter().chain([&KeyHint::new("?", "all keys")]) {
+        let entry = format!("[{}] {}", hint.keys, hint.description);
+
+        match lines.last_mut() {
+            Some(line) if line.len() + entry.len() + 3 <= FOOTER_WIDTH => {
+                line.push_str(" | ");
+                line.push_str(&entry);
+            }
+            _ => lines.push(entry),
+        }
+    }
+
+    lines
+}
+
+pub(super) fn print_key_hints(out: &mut dyn Write, hints: &[KeyHint]) -> io::Result<()> {
+    for line in footer_lines(hints) {
+        writeln!(out, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// The `?` overlay: every key the page below it accepts, one per line.
+pub struct KeyHints {
+    pub hints: Vec<KeyHint>,
+}
+
+impl Page for KeyHints {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let width = self
+            .hints
+            .iter()
+            .map(|hint| hint.keys.len())
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            out,
+            "------------------------------ KEYS ------------------------------"
+        )?;
+
+        for hint in &self.hints {
+            writeln!(
+                out,
+                "  {}  {}",
+                get_column_string(hint.keys, width),
+                hint.description
+            )?;
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        Vec::new()
+    }
+
+    /// Any input closes the overlay.
+    fn handle_other_input(&self, _input: &str) -> Result<Option<Action>> {
+        Ok(Some(Action::NavigateToPreviousPage))
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new("enter", "back")]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn footer_lines_should_wrap_hints_and_add_the_overlay_key() {
+        assert_eq!(footer_lines(&[]), vec!["[?] all keys".to_owned()]);
+
+        let hints = [
+            KeyHint::new("p", "previous"),
+            KeyHint::new("c", "create epic"),
+            KeyHint::new(
+                "+ story !prio @who #label ^epic:id due:day",
+                "capture story",
+            ),
+            KeyHint::new("/ status:s #label @who text", "search"),
+        ];
+
+        assert_eq!(
+            footer_lines(&hints),
+            vec![
+                "[p] previous | [c] create epic | [+ story !prio @who #label ^epic:id due:day] capture story"
+                    .to_owned(),
+                "[/ status:s #label @who text] search | [?] all keys".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn bindings_should_match_their_inputs() {
+        let binding = Binding::new("enter", "continue", || Action::NavigateToPreviousPage);
+
+        assert!(binding.matches("enter"));
+        assert!(!binding.matches(""));
+
+        let binding = binding.on(&["", "p"]);
+
+        assert!(binding.matches(""));
+        assert!(binding.matches("p"));
+        assert!(!binding.matches("enter"));
+        assert_eq!(binding.hint, KeyHint::new("enter", "continue"));
+        assert_eq!(
+            binding.action().unwrap(),
+            Some(Action::NavigateToPreviousPage)
+        );
+    }
+
+    #[test]
+    fn handle_input_should_go_back() {
+        let page = KeyHints {
+            hints: vec![KeyHint::new("p", "previous")],
+        };
+
+        assert_eq!(
+            page.handle_input("x").unwrap(),
+            Some(Action::NavigateToPreviousPage)
+        );
+        assert_eq!(
+            page.handle_input("").unwrap(),
+            Some(Action::NavigateToPreviousPage)
+        );
+    }
+}
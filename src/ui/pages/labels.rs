@@ -0,0 +1,130 @@
+use anyhow::Result;
+use std::any::Any;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::db::JiraDataBase;
+use crate::model::{Action, Filter};
+use crate::stats::label_counts;
+
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::Page;
+
+/// Every label on the board with how many items carry it. Picking one lists
+/// those items.
+pub struct Labels {
+    pub db: Rc<JiraDataBase>,
+}
+
+impl Labels {
+    fn labels(&self) -> Result<Vec<(String, usize)>> {
+        Ok(label_counts(&self.db.read_db()?))
+    }
+}
+
+impl Page for Labels {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let labels = self.labels()?;
+
+        writeln!(
+            out,
+            "----------------------------- LABELS -----------------------------"
+        )?;
+
+        if labels.is_empty() {
+            writeln!(out, "Nothing is labelled yet.")?;
+        }
+
+        for (index, (label, count)) in labels.iter().enumerate() {
+            writeln!(out, "{:>3}  #{label} ({count})", index + 1)?;
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![Binding::new("p", "previous", || {
+            Action::NavigateToPreviousPage
+        })]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        let Ok(number) = input.trim().parse::<usize>() else {
+            return Ok(None);
+        };
+        let labels = self.labels()?;
+
+        Ok(number
+            .checked_sub(1)
+            .and_then(|index| labels.into_iter().nth(index))
+            .map(|(label, _)| Action::Search {
+                filter: Filter {
+                    label: Some(label),
+                    ..Filter::default()
+                },
+            }))
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(":#:", "list items with label")]
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .labels()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, _))| ((index + 1).to_string(), label))
+            .collect())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::model::{ItemDetail, Story};
+
+    #[test]
+    fn handle_input_should_search_for_the_picked_label() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        for labels in [vec!["bug"], vec!["billing", "bug"]] {
+            let story = Story::new(ItemDetail {
+                labels: labels.into_iter().map(str::to_owned).collect(),
+                ..Default::default()
+            });
+            db.add_story(story, None).unwrap();
+        }
+
+        let page = Labels { db };
+        let mut out = Vec::new();
+        page.draw_page(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("  1  #billing (1)\n"));
+        assert!(out.contains("  2  #bug (2)\n"));
+        assert_eq!(
+            page.handle_input("2").unwrap(),
+            Some(Action::Search {
+                filter: Filter {
+                    label: Some("bug".to_owned()),
+                    ..Filter::default()
+                }
+            })
+        );
+        assert_eq!(page.handle_input("3").unwrap(), None);
+        assert_eq!(
+            page.handle_input("p").unwrap(),
+            Some(Action::NavigateToPreviousPage)
+        );
+    }
+}
@@ -1,68 +1,289 @@
 use anyhow::anyhow;
 use anyhow::Result;
+use chrono::{NaiveDate, Utc};
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::any::Any;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::{self, Write};
 use std::rc::Rc;
 
-use crate::db::JiraDataBase;
-use crate::model::Action;
-
+use crate::calendar::Calendar;
+use crate::capture::parse_field_edits;
+use crate::db::{sorted_page, JiraDataBase, SortBy};
+use crate::keys::{parse_id, story_key};
+use crate::model::{Action, Epic, Filter, ItemDetail, ItemId, ItemStatus, ItemType, Story, DB};
+use crate::reports::standup;
+use crate::search::parse_filter;
+use crate::share::{epic_markdown, story_markdown};
+use crate::stats::{flow_times, rollup, status_counts};
+
+mod board;
+mod dashboard;
+mod item_list;
+mod key_hints;
+mod labels;
+mod notes;
 mod page_helpers;
+mod quick_filter;
+mod snapshots;
+mod sprint_archive;
+mod sprints;
+mod stale;
+mod this_week;
+mod tree;
+mod workload;
+pub use board::*;
+pub use dashboard::*;
+use item_list::*;
+pub use key_hints::*;
+pub use labels::*;
+pub use notes::*;
 use page_helpers::*;
+pub use quick_filter::*;
+pub use snapshots::*;
+pub use sprint_archive::*;
+pub use sprints::*;
+pub use stale::*;
+pub use this_week::*;
+pub use tree::*;
+pub use workload::*;
 
 pub trait Page {
-    fn draw_page(&self) -> Result<()>;
-    fn handle_input(&self, input: &str) -> Result<Option<Action>>;
+    /// Draws the page into `out`, which is whatever the frontend shows it on.
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()>;
+    /// The fixed keys of the page and the actions they stand for.
+    fn bindings(&self) -> Vec<Binding<'_>>;
+    /// Handles the input no binding matched, like ids and captures.
+    fn handle_other_input(&self, _input: &str) -> Result<Option<Action>> {
+        Ok(None)
+    }
+    /// Describes the input `handle_other_input` accepts.
+    fn input_hints(&self) -> Vec<KeyHint> {
+        Vec::new()
+    }
+    /// The handle and name of each row the page lists, for keymaps that move
+    /// a selection through them.
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(Vec::new())
+    }
+    /// What `/` types into, on the pages that list items.
+    fn quick_filter(&self) -> Option<&QuickFilter> {
+        None
+    }
     fn as_any(&self) -> &dyn Any;
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match self
+            .bindings()
+            .iter()
+            .find(|binding| binding.matches(input))
+        {
+            Some(binding) => binding.action(),
+            None => self.handle_other_input(input),
+        }
+    }
+
+    /// The keys `handle_input` accepts, shown in the footer and the `?` overlay.
+    fn key_hints(&self) -> Vec<KeyHint> {
+        self.bindings()
+            .into_iter()
+            .map(|binding| binding.hint)
+            .chain(self.input_hints())
+            .collect()
+    }
+}
+
+/// How the home page lays out the epics, set with `"home_layout"` in the
+/// config.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HomeLayout {
+    /// One table of every epic.
+    #[default]
+    Flat,
+    /// A table per status, with the closed epics folded away until `C`.
+    Grouped,
 }
 
 pub struct HomePage {
     pub db: Rc<JiraDataBase>,
+    pub layout: HomeLayout,
+    /// Whether the grouped layout unfolds the closed epics.
+    pub show_closed: Cell<bool>,
+    pub quick_filter: QuickFilter,
 }
 
-impl Page for HomePage {
-    fn draw_page(&self) -> Result<()> {
-        println!("----------------------------- EPICS -----------------------------");
-        println!("     id     |               name               |      status      ");
-
-        let db = self.db.read_db()?;
+impl HomePage {
+    pub fn new(db: Rc<JiraDataBase>, layout: HomeLayout) -> Self {
+        Self {
+            db,
+            layout,
+            show_closed: Cell::new(false),
+            quick_filter: QuickFilter::default(),
+        }
+    }
 
-        db.epics
-            .iter()
-            .sorted_by(|(first_key, _), (second_key, _)| first_key.cmp(second_key))
-            .for_each(|(id, epic)| {
-                println!(
-                    "{} | {} | {}",
-                    get_column_string(&id.to_string(), 11),
-                    get_column_string(&epic.detail.name, 32),
-                    get_column_string(&epic.detail.status.to_string(), 17)
-                )
-            });
+    /// A row per epic, in ID order.
+    fn epic_list(&self) -> Result<ItemList> {
+        let epics = self.db.list_epics(0, usize::MAX, SortBy::Id)?.items;
 
-        println!();
-        println!();
+        Ok(epic_list(&epics, &stories_by_id(&self.db)?).filtered(&self.quick_filter))
+    }
 
-        println!("[q] quit | [c] create epic | [:id:] navigate to epic");
+    /// The epic groups the grouped layout shows, open first and closed last,
+    /// with their rows left out while folded.
+    fn visible_groups(&self) -> Result<Vec<(ItemStatus, ItemList, bool)>> {
+        let stories = stories_by_id(&self.db)?;
+        let mut groups = Vec::new();
+
+        for status in [
+            ItemStatus::Open,
+            ItemStatus::InProgress,
+            ItemStatus::Resolved,
+            ItemStatus::Closed,
+        ] {
+            let list = epic_list(&self.db.list_epics_by_status(&status)?, &stories)
+                .filtered(&self.quick_filter);
+
+            if !list.rows.is_empty() {
+                let folded = status == ItemStatus::Closed && !self.show_closed.get();
+
+                groups.push((status, list, folded));
+            }
+        }
 
-        Ok(())
+        Ok(groups)
     }
+}
 
-    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
-        let epics = self.db.read_db()?.epics;
-
-        match input {
-            "q" => Ok(Some(Action::Exit)),
-            "c" => Ok(Some(Action::CreateEpic)),
-            input => {
-                if let Ok(epic_id) = input.parse::<u32>() {
-                    if epics.contains_key(&epic_id) {
-                        return Ok(Some(Action::NavigateToEpicDetail { epic_id }));
+impl Page for HomePage {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let unread = self
+            .db
+            .list_notifications(self.db.user())?
+            .iter()
+            .filter(|notification| !notification.read)
+            .count();
+
+        writeln!(
+            out,
+            "----------------------------- EPICS -----------------------------"
+        )?;
+        self.quick_filter.draw(out)?;
+
+        match self.layout {
+            HomeLayout::Flat => self
+                .epic_list()?
+                .marking_stale(self.db.stale_after())
+                .draw(out, "id")?,
+            HomeLayout::Grouped => {
+                for (status, list, folded) in self.visible_groups()? {
+                    let title = status.to_string().to_uppercase();
+
+                    writeln!(out)?;
+                    if folded {
+                        writeln!(out, "{title} ({}), C to show", list.rows.len())?;
+                        continue;
                     }
+
+                    writeln!(out, "{title} ({})", list.rows.len())?;
+                    list.marking_stale(self.db.stale_after()).draw(out, "id")?;
                 }
+            }
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
 
+        if unread > 0 {
+            writeln!(out, "{unread} unread notifications")?;
+        }
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        let mut bindings = vec![
+            Binding::new("q", "quit", || Action::Exit),
+            Binding::new("c", "create epic", || Action::CreateEpic),
+            Binding::new("f", "saved filters", || Action::PickFilter),
+            Binding::new("n", "notifications", || Action::NavigateToNotifications),
+            Binding::new("s", "standup", || Action::NavigateToStandup),
+            Binding::new("S", "sprints", || Action::NavigateToSprints),
+            Binding::new("D", "dashboard", || Action::NavigateToDashboard),
+            Binding::new("T", "tree", || Action::NavigateToTree),
+            Binding::new("N", "notes", || Action::NavigateToNotes),
+            Binding::new("W", "workload", || Action::NavigateToWorkload),
+            Binding::new("L", "labels", || Action::NavigateToLabels),
+            Binding::new("K", "snapshots", || Action::NavigateToSnapshots),
+            Binding::new("w", "this week", || Action::NavigateToThisWeek),
+            Binding::new("O", "stale items", || Action::NavigateToStale),
+            Binding::new("B", "board", || Action::NavigateToBoard),
+            Binding::new("/", "filter", || Action::QuickFilter),
+            Binding::new("M", "my items", || Action::Search {
+                filter: Filter::assigned_to(self.db.user()),
+            }),
+        ];
+
+        if self.layout == HomeLayout::Grouped {
+            // Only changes how the page draws, so there is no action to hand
+            // to the navigator.
+            bindings.push(Binding::fallible("C", "show / hide closed", || {
+                self.show_closed.set(!self.show_closed.get());
                 Ok(None)
+            }));
+        }
+
+        bindings
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        if let Some(capture) = input.strip_prefix('+') {
+            return Ok(quick_add_story(None, capture));
+        }
+
+        if let Some(query) = input.strip_prefix('/').map(str::trim) {
+            if !query.is_empty() {
+                return Ok(Some(Action::Search {
+                    filter: parse_filter(query)?,
+                }));
             }
         }
+
+        Ok(self.epic_list()?.handle_input(input))
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(match self.layout {
+            HomeLayout::Flat => self.epic_list()?.labels(),
+            HomeLayout::Grouped => self
+                .visible_groups()?
+                .into_iter()
+                .filter(|(_, _, folded)| !folded)
+                .flat_map(|(_, list, _)| list.labels())
+                .collect(),
+        })
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new(
+                "+ story !prio @who #label ^epic:id due:day",
+                "capture story",
+            ),
+            KeyHint::new("/ status:s #label @who text", "search"),
+            KeyHint::new(":id:", "navigate to epic"),
+            KeyHint::new("u:id: / s:id:", "update epic"),
+            KeyHint::new("d:id:", "delete epic"),
+            KeyHint::new("U", "update all epics"),
+        ]
+    }
+
+    fn quick_filter(&self) -> Option<&QuickFilter> {
+        Some(&self.quick_filter)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -70,90 +291,265 @@ impl Page for HomePage {
     }
 }
 
+/// A row per epic of `epics`, badged with the counts and rollup of their
+/// stories in `stories`.
+fn epic_list(epics: &[Epic], stories: &HashMap<u32, Story>) -> ItemList {
+    let rows = epics
+        .iter()
+        .map(|epic| ItemRow {
+            handle: epic.detail.id.0.to_string(),
+            item: ItemType::Epic { id: epic.detail.id },
+            epic_id: None,
+            name: epic.detail.name.clone(),
+            status: epic.detail.status.clone(),
+            updated_at: last_change(&epic.detail),
+            due: epic.detail.due,
+            badge: Some({
+                let stories = || {
+                    epic.stories
+                        .iter()
+                        .filter_map(|story_id| stories.get(&story_id.0))
+                };
+
+                format!("{} {}", status_counts(stories()), rollup(stories()))
+            }),
+        })
+        .collect();
+
+    ItemList::new(rows)
+}
+
+fn story_list(epic_id: u32, stories: &[Story]) -> ItemList {
+    let rows = stories
+        .iter()
+        .map(|story| ItemRow {
+            handle: story.detail.id.0.to_string(),
+            item: ItemType::Story {
+                id: story.detail.id,
+            },
+            epic_id: Some(epic_id),
+            name: story.detail.name.clone(),
+            status: story.detail.status.clone(),
+            updated_at: last_change(&story.detail),
+            due: story.detail.due,
+            badge: None,
+        })
+        .collect();
+
+    ItemList::new(rows)
+}
+
+fn quick_add_story(epic_id: Option<u32>, capture: &str) -> Option<Action> {
+    let capture = capture.trim();
+
+    if capture.is_empty() {
+        return None;
+    }
+
+    Some(Action::QuickAddStory {
+        epic_id,
+        input: capture.to_owned(),
+    })
+}
+/// When the item was created and last updated, both as an age and as the
+/// time on `calendar`.
+fn print_timestamps(
+    out: &mut dyn Write,
+    detail: &ItemDetail,
+    calendar: &Calendar,
+) -> io::Result<()> {
+    let now = Utc::now();
+
+    for line in [
+        created(detail, calendar, now),
+        last_updated(detail, calendar, now),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        writeln!(out, "{line}")?;
+    }
+
+    Ok(())
+}
+
+fn print_history(out: &mut dyn Write, detail: &ItemDetail, calendar: &Calendar) -> io::Result<()> {
+    let timeline = status_timeline(detail, calendar)
+        .into_iter()
+        .chain(description_edits(detail, calendar))
+        .collect::<Vec<_>>();
+
+    if timeline.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "---------------------------- HISTORY ----------------------------"
+    )?;
+
+    for line in timeline {
+        writeln!(out, "{line}")?;
+    }
+
+    Ok(())
+}
+
 pub struct EpicDetail {
     pub epic_id: u32,
     pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
+    pub quick_filter: QuickFilter,
 }
 
-impl Page for EpicDetail {
-    fn draw_page(&self) -> Result<()> {
-        let db_state = self.db.read_db()?;
-        let epic = db_state
-            .epics
-            .get(&self.epic_id)
-            .ok_or_else(|| anyhow!("could not find epic!"))?;
+impl EpicDetail {
+    /// The stories of the epic, in the order it keeps them.
+    fn stories(&self) -> Result<Vec<Story>> {
+        let stories = self.db.list_stories_for_epic(ItemId(self.epic_id))?;
 
-        println!("------------------------------ EPIC ------------------------------");
-        println!("  id  |     name     |         description         |    status    ");
+        Ok(match self.db.read_db()?.story_order.get(&self.epic_id) {
+            Some(sort) => {
+                sorted_page(stories.into_iter(), 0, usize::MAX, *sort, |story| {
+                    &story.detail
+                })
+                .items
+            }
+            None => stories,
+        })
+    }
+}
 
-        println!(
+impl Page for EpicDetail {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let epic = self.db.get_epic(ItemId(self.epic_id))?;
+        let stories = self.stories()?;
+
+        writeln!(
+            out,
+            "------------------------------ EPIC ------------------------------"
+        )?;
+        writeln!(
+            out,
+            "  id  |     name     |         description         |    status    "
+        )?;
+
+        writeln!(
+            out,
             "{} | {} | {} | {}",
             get_column_string(&epic.detail.id.0.to_string(), 5),
             get_column_string(&epic.detail.name, 12),
             get_column_string(&epic.detail.description, 27),
             get_column_string(&epic.detail.status.to_string(), 14)
-        );
+        )?;
 
-        println!();
-        println!("---------------------------- STORIES ----------------------------");
-        println!("     id     |               name               |      status      ");
+        print_timestamps(out, &epic.detail, &self.calendar)?;
 
-        let stories = &db_state.stories;
-        epic.stories
-            .iter()
-            .sorted_by(|a, b| a.0.cmp(&b.0))
-            .for_each(|id| {
-                let story = stories.get(&id.0).unwrap();
-
-                println!(
-                    "{} | {} | {}",
-                    get_column_string(&story.detail.id.0.to_string(), 11),
-                    get_column_string(&story.detail.name, 32),
-                    get_column_string(&story.detail.status.to_string(), 17),
-                )
-            });
-        println!();
-        println!();
-
-        println!("[p] previous | [u] update epic | [d] delete epic | [c] create story | [:id:] navigate to story");
+        if !epic.default_labels.is_empty() {
+            writeln!(out, "new stories get: {}", epic.default_labels.join(", "))?;
+        }
+
+        let (lead, cycle) = flow_times(stories.iter());
+
+        if lead.is_some() || cycle.is_some() {
+            writeln!(out)?;
+            print_flow_times(out, lead, cycle)?;
+        }
+
+        print_history(out, &epic.detail, &self.calendar)?;
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "---------------------------- STORIES ----------------------------"
+        )?;
+        self.quick_filter.draw(out)?;
+
+        story_list(self.epic_id, &stories)
+            .filtered(&self.quick_filter)
+            .marking_stale(self.db.stale_after())
+            .draw(out, "id")?;
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
 
         Ok(())
     }
 
-    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
-        let db = self.db.read_db()?;
-        let stories = db.stories;
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        let epic_id = self.epic_id;
+
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("u", "update epic", move || Action::UpdateEpicStatus {
+                epic_id,
+            }),
+            Binding::fallible("d", "delete epic", move || {
+                let exists = self.db.get_epic(ItemId(epic_id)).is_ok();
+
+                Ok(exists.then_some(Action::DeleteEpic { epic_id }))
+            }),
+            Binding::new("g", "merge epic", move || Action::MergeEpic { epic_id }),
+            Binding::new("t", "turn into story", move || Action::DemoteEpic {
+                epic_id,
+            }),
+            Binding::new("l", "default labels", move || Action::EditDefaultLabels {
+                epic_id,
+            }),
+            Binding::new("y", "copy epic", move || Action::CopyEpic { epic_id }),
+            Binding::new("m", "markdown", move || Action::NavigateToEpicMarkdown {
+                epic_id,
+            }),
+            Binding::new("w", "watch epic", move || Action::ToggleWatch {
+                item: ItemType::Epic {
+                    id: ItemId(epic_id),
+                },
+            }),
+            Binding::new("c", "create story", move || Action::CreateStory { epic_id }),
+            Binding::new("T", "story from template", move || {
+                Action::CreateStoryFromTemplate { epic_id }
+            }),
+            Binding::new("/", "filter stories", || Action::QuickFilter),
+        ]
+    }
 
-        match input {
-            "p" => Ok(Some(Action::NavigateToPreviousPage)),
-            "u" => Ok(Some(Action::UpdateEpicStatus {
-                epic_id: self.epic_id,
-            })),
-            "d" => {
-                if db.epics.contains_key(&self.epic_id) {
-                    return Ok(Some(Action::DeleteEpic {
-                        epic_id: self.epic_id,
-                    }));
-                }
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        if let Some(capture) = input.strip_prefix('+') {
+            return Ok(quick_add_story(Some(self.epic_id), capture));
+        }
 
-                Ok(None)
-            }
-            "c" => Ok(Some(Action::CreateStory {
+        if let Some(sort) = input.strip_prefix("o ") {
+            return Ok(Some(Action::SortStories {
                 epic_id: self.epic_id,
-            })),
-            input => {
-                if let Ok(story_id) = input.parse::<u32>() {
-                    if stories.contains_key(&story_id) {
-                        return Ok(Some(Action::NavigateToStoryDetail {
-                            epic_id: self.epic_id,
-                            story_id,
-                        }));
-                    }
-                }
-
-                Ok(None)
-            }
+                sort: sort.trim().parse()?,
+            }));
         }
+
+        Ok(story_list(self.epic_id, &self.stories()?)
+            .filtered(&self.quick_filter)
+            .handle_input(input))
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(story_list(self.epic_id, &self.stories()?)
+            .filtered(&self.quick_filter)
+            .labels())
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new("+ story !prio @who #label due:day", "quick add story"),
+            KeyHint::new("o id|name|status|priority", "sort stories"),
+            KeyHint::new(":id:", "navigate to story"),
+            KeyHint::new("u:id: / s:id:", "update story"),
+            KeyHint::new("d:id:", "delete story"),
+            KeyHint::new("U", "update all stories"),
+        ]
+    }
+
+    fn quick_filter(&self) -> Option<&QuickFilter> {
+        Some(&self.quick_filter)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -165,46 +561,586 @@ pub struct StoryDetail {
     pub epic_id: u32,
     pub story_id: u32,
     pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
 }
 
 impl Page for StoryDetail {
-    fn draw_page(&self) -> Result<()> {
-        let db_state = self.db.read_db()?;
-        let story = db_state
-            .stories
-            .get(&self.story_id)
-            .ok_or_else(|| anyhow!("could not find story!"))?;
-
-        println!("------------------------------ STORY ------------------------------");
-        println!("  id  |     name     |         description         |    status    ");
-        println!(
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let story = self.db.get_story(ItemId(self.story_id))?;
+
+        writeln!(
+            out,
+            "------------------------------ STORY ------------------------------"
+        )?;
+        writeln!(
+            out,
+            "  id  |     name     |         description         |    status    "
+        )?;
+        writeln!(
+            out,
             "{} | {} | {} | {}",
             get_column_string(&story.detail.id.0.to_string(), 5),
             get_column_string(&story.detail.name, 12),
             get_column_string(&story.detail.description, 27),
             get_column_string(&story.detail.status.to_string(), 13),
-        );
+        )?;
+
+        print_timestamps(out, &story.detail, &self.calendar)?;
+
+        let fields = detail_fields(&story.detail, &self.calendar, self.calendar.today());
+
+        if !fields.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "{}", fields.join(" | "))?;
+        }
+
+        if !story.checklist.is_empty() {
+            writeln!(out)?;
+            writeln!(
+                out,
+                "--------------------------- CHECKLIST ---------------------------"
+            )?;
+
+            for (index, item) in story.checklist.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{}. [{}] {}",
+                    index + 1,
+                    if item.done { "x" } else { " " },
+                    item.text
+                )?;
+            }
+        }
+
+        if !story.links.is_empty() {
+            writeln!(out)?;
+            writeln!(
+                out,
+                "----------------------------- LINKS -----------------------------"
+            )?;
+
+            for link in &story.links {
+                writeln!(out, "{} {}", link.kind, story_key(link.story_id.0))?;
+            }
+        }
+
+        print_history(out, &story.detail, &self.calendar)?;
 
-        println!();
-        println!();
+        writeln!(out)?;
+        writeln!(out)?;
 
-        println!("[p] previous | [u] update story | [d] delete story");
+        print_key_hints(out, &self.key_hints())?;
 
         Ok(())
     }
 
-    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
-        match input {
-            "p" => Ok(Some(Action::NavigateToPreviousPage)),
-            "u" => Ok(Some(Action::UpdateStoryStatus {
-                story_id: self.story_id,
-            })),
-            "d" => Ok(Some(Action::DeleteStory {
-                epic_id: self.epic_id,
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        let (epic_id, story_id) = (self.epic_id, self.story_id);
+
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("u", "update story", move || Action::UpdateStoryStatus {
+                story_id,
+            }),
+            Binding::new("d", "delete story", move || Action::DeleteStory {
+                epic_id,
+                story_id,
+            }),
+            Binding::new("c", "clone story", move || Action::CloneStory {
+                epic_id,
+                story_id,
+            }),
+            Binding::new("s", "split story", move || Action::SplitStory {
+                epic_id,
+                story_id,
+            }),
+            Binding::new("e", "promote to epic", move || Action::PromoteStory {
+                story_id,
+            }),
+            Binding::new("y", "copy story", move || Action::CopyStory { story_id }),
+            Binding::new("m", "markdown", move || Action::NavigateToStoryMarkdown {
+                story_id,
+            }),
+            Binding::new("w", "watch story", move || Action::ToggleWatch {
+                item: ItemType::Story {
+                    id: ItemId(story_id),
+                },
+            }),
+            Binding::new("a", "add checklist item", move || {
+                Action::AddChecklistItem { story_id }
+            }),
+            Binding::new("=", "clear points", move || Action::SetStoryPoints {
+                story_id,
+                points: None,
+            }),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        if let Some(Ok(number)) = input.strip_prefix('x').map(|n| n.parse::<usize>()) {
+            if number > 0 {
+                return Ok(Some(Action::ToggleChecklistItem {
+                    story_id: self.story_id,
+                    index: number - 1,
+                }));
+            }
+        }
+
+        if let Some(points) = input.strip_prefix('=') {
+            return Ok(points
+                .parse::<u32>()
+                .ok()
+                .map(|points| Action::SetStoryPoints {
+                    story_id: self.story_id,
+                    points: Some(points),
+                }));
+        }
+
+        if let Some(blocker_id) = input.strip_prefix('b').and_then(parse_id) {
+            return Ok(Some(Action::AddBlocker {
                 story_id: self.story_id,
-            })),
-            _ => Ok(None),
+                blocker_id,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new("x:n:", "toggle checklist item"),
+            KeyHint::new("b:id:", "blocked by story"),
+            KeyHint::new("=:n:", "set points"),
+        ]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct MarkdownSnippet {
+    pub item: ItemType,
+    pub db: Rc<JiraDataBase>,
+}
+
+impl MarkdownSnippet {
+    fn render(&self) -> Result<String> {
+        match self.item {
+            ItemType::Epic { id } => {
+                let epic = self.db.get_epic(id)?;
+
+                Ok(epic_markdown(&epic, &stories_by_id(&self.db)?))
+            }
+            ItemType::Story { id } => Ok(story_markdown(&self.db.get_story(id)?)),
+            ItemType::None => Err(anyhow!("nothing to render!")),
+        }
+    }
+}
+
+impl Page for MarkdownSnippet {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let markdown = self.render()?;
+
+        writeln!(
+            out,
+            "---------------------------- MARKDOWN ----------------------------"
+        )?;
+        writeln!(out)?;
+        writeln!(out, "{markdown}")?;
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::fallible("y", "copy markdown", || {
+                Ok(Some(Action::CopyText {
+                    text: self.render()?,
+                }))
+            }),
+        ]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct SearchResults {
+    pub filter: Filter,
+    pub db: Rc<JiraDataBase>,
+    pub quick_filter: QuickFilter,
+}
+
+impl SearchResults {
+    fn results(&self) -> Result<ItemList> {
+        let db_state = self.db.read_db()?;
+        let rows = self
+            .db
+            .search(&self.filter)?
+            .into_iter()
+            .filter_map(|hit| {
+                let (detail, epic_id) = match hit.item {
+                    ItemType::Epic { id } => (&db_state.epics.get(&id.0)?.detail, None),
+                    ItemType::Story { id } => (
+                        &db_state.stories.get(&id.0)?.detail,
+                        db_state
+                            .epics
+                            .values()
+                            .find(|epic| epic.stories.contains(&id))
+                            .map(|epic| epic.detail.id.0),
+                    ),
+                    ItemType::None => return None,
+                };
+
+                Some((hit, detail, epic_id))
+            })
+            .enumerate()
+            .map(|(row, (hit, detail, epic_id))| ItemRow {
+                handle: (row + 1).to_string(),
+                name: format!("{} {}", hit.key, detail.name),
+                status: detail.status.clone(),
+                item: hit.item,
+                epic_id,
+                updated_at: last_change(detail),
+                due: detail.due,
+                badge: None,
+            })
+            .collect();
+
+        Ok(ItemList::new(rows).filtered(&self.quick_filter))
+    }
+}
+
+impl Page for SearchResults {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let results = self.results()?.marking_stale(self.db.stale_after());
+
+        writeln!(
+            out,
+            "----------------------------- SEARCH -----------------------------"
+        )?;
+        writeln!(out, "filter: {}", self.filter)?;
+        self.quick_filter.draw(out)?;
+        writeln!(out)?;
+
+        results.draw(out, "#")?;
+
+        if results.rows.is_empty() {
+            writeln!(out, "no matches")?;
         }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("s", "save filter", || Action::SaveFilter {
+                filter: self.filter.clone(),
+            }),
+            Binding::new("/", "narrow results", || Action::QuickFilter),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        let results = self.results()?;
+
+        if let Some(edits) = input.strip_prefix("e ") {
+            if results.rows.is_empty() {
+                return Ok(None);
+            }
+
+            return Ok(Some(Action::BulkEdit {
+                items: results.rows.into_iter().map(|row| row.item).collect(),
+                edits: parse_field_edits(edits)?,
+            }));
+        }
+
+        Ok(results.handle_input(input))
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self.results()?.labels())
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new(":#:", "open result"),
+            KeyHint::new("u:#: / s:#:", "update result"),
+            KeyHint::new("d:#:", "delete result"),
+            KeyHint::new("U", "update all results"),
+            KeyHint::new("e #label -#label !prio @who sprint:id", "edit all results"),
+        ]
+    }
+
+    fn quick_filter(&self) -> Option<&QuickFilter> {
+        Some(&self.quick_filter)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Lists the open epics and stories that are due today or overdue. Shown once
+/// at startup on top of the home page.
+pub struct DueReminders {
+    pub today: NaiveDate,
+    pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
+}
+
+impl DueReminders {
+    pub fn has_items(&self) -> Result<bool> {
+        Ok(!self.reminders()?.rows.is_empty())
+    }
+
+    fn reminders(&self) -> Result<ItemList> {
+        let db_state = self.db.read_db()?;
+        let rows = open_items_due_by(&db_state, self.today)
+            .into_iter()
+            .enumerate()
+            .map(|(row, (item, epic_id, key, detail))| {
+                let due = match detail.due {
+                    Some(due) if due < self.today => {
+                        format!("overdue since {}", self.calendar.date(due))
+                    }
+                    _ => "due today".to_owned(),
+                };
+
+                ItemRow {
+                    handle: (row + 1).to_string(),
+                    item,
+                    epic_id,
+                    name: format!("{key} {} ({due})", detail.name),
+                    status: detail.status.clone(),
+                    updated_at: last_change(detail),
+                    due: None,
+                    badge: None,
+                }
+            })
+            .collect();
+
+        Ok(ItemList::new(rows))
+    }
+}
+
+/// Every epic and story with the epic each story is in and their keys.
+fn all_items(db: &DB) -> impl Iterator<Item = (ItemType, Option<u32>, String, &ItemDetail)> {
+    let epics = db.epics.values().map(|epic| {
+        (
+            ItemType::Epic { id: epic.detail.id },
+            None,
+            epic.key(),
+            &epic.detail,
+        )
+    });
+    let stories = db.stories.values().map(|story| {
+        let epic_id = db
+            .epics
+            .values()
+            .find(|epic| epic.stories.contains(&story.detail.id))
+            .map(|epic| epic.detail.id.0);
+
+        (
+            ItemType::Story {
+                id: story.detail.id,
+            },
+            epic_id,
+            story.key(),
+            &story.detail,
+        )
+    });
+
+    epics.chain(stories)
+}
+
+/// The epics and stories that aren't done and are due by `until`, soonest
+/// first, with the epic each story is in and their keys.
+fn open_items_due_by(
+    db: &DB,
+    until: NaiveDate,
+) -> Vec<(ItemType, Option<u32>, String, &ItemDetail)> {
+    let is_due = |detail: &ItemDetail| {
+        !matches!(detail.status, ItemStatus::Resolved | ItemStatus::Closed)
+            && detail.due.is_some_and(|due| due <= until)
+    };
+
+    all_items(db)
+        .filter(|(_, _, _, detail)| is_due(detail))
+        .sorted_by_key(|(_, _, key, detail)| (detail.due, key.clone()))
+        .collect()
+}
+
+impl Page for DueReminders {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        writeln!(
+            out,
+            "------------------------- DUE AND OVERDUE -------------------------"
+        )?;
+
+        self.reminders()?.draw(out, "#")?;
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("enter", "continue", || Action::NavigateToPreviousPage).on(&["", "p"]),
+            Binding::fallible("o", "open first", || {
+                Ok(self.reminders()?.handle_input("1"))
+            }),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        Ok(self.reminders()?.handle_input(input))
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self.reminders()?.labels())
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(":#:", "open item")]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct Standup {
+    pub user: String,
+    pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
+}
+
+impl Standup {
+    fn render(&self) -> Result<String> {
+        Ok(standup(
+            &self.db.read_db()?,
+            &self.user,
+            &self.calendar,
+            Utc::now(),
+        ))
+    }
+}
+
+impl Page for Standup {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let report = self.render()?;
+
+        writeln!(
+            out,
+            "----------------------------- STANDUP -----------------------------"
+        )?;
+        writeln!(out)?;
+        writeln!(out, "{report}")?;
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::fallible("y", "copy standup", || {
+                Ok(Some(Action::CopyText {
+                    text: self.render()?,
+                }))
+            }),
+        ]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct Notifications {
+    pub user: String,
+    pub db: Rc<JiraDataBase>,
+}
+
+impl Page for Notifications {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let notifications = self.db.list_notifications(&self.user)?;
+
+        writeln!(
+            out,
+            "-------------------------- NOTIFICATIONS --------------------------"
+        )?;
+        writeln!(
+            out,
+            "     id     |                          message                          "
+        )?;
+
+        for notification in &notifications {
+            let marker = if notification.read { " " } else { "*" };
+
+            writeln!(
+                out,
+                "{} | {} {}",
+                get_column_string(&notification.id.to_string(), 11),
+                marker,
+                get_column_string(&notification.message, 56)
+            )?;
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("r", "mark all as read", || Action::MarkNotificationsRead),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        let notifications = self.db.list_notifications(&self.user)?;
+
+        if let Ok(id) = input.parse::<u32>() {
+            let exists = notifications
+                .iter()
+                .any(|notification| notification.id == id);
+
+            if exists {
+                return Ok(Some(Action::OpenNotification { id }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(":id:", "open item")]
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -222,35 +1158,75 @@ mod tests {
 
         #[test]
         fn draw_page_should_not_throw_error() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+            let page = HomePage::new(db, HomeLayout::Flat);
+            assert!(page.draw_page(&mut io::sink()).is_ok());
+        }
+
+        #[test]
+        fn draw_page_should_write_to_the_given_output() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            db.create_epic("Payments".to_owned(), "".to_owned())
+                .unwrap();
+
+            let page = HomePage::new(db, HomeLayout::Flat);
+            let mut out = Vec::new();
+
+            page.draw_page(&mut out).unwrap();
+
+            let out = String::from_utf8(out).unwrap();
+            assert!(out.contains("EPICS"));
+            assert!(out.contains("Payments"));
+        }
+
+        #[test]
+        fn draw_page_should_group_epics_by_status() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            let done = db.create_epic("Legacy".to_owned(), "".to_owned()).unwrap();
+            db.create_epic("Payments".to_owned(), "".to_owned())
+                .unwrap();
+            db.update_epic_status(done, ItemStatus::Closed).unwrap();
+
+            let page = HomePage::new(db, HomeLayout::Grouped);
+            let draw = || {
+                let mut out = Vec::new();
+                page.draw_page(&mut out).unwrap();
+                String::from_utf8(out).unwrap()
+            };
+
+            let out = draw();
+            assert!(out.contains("OPEN (1)"));
+            assert!(out.contains("CLOSED (1), C to show"));
+            assert!(!out.contains("Legacy"));
+            assert_eq!(
+                page.rows().unwrap(),
+                vec![("1".to_owned(), "Payments".to_owned())]
+            );
 
-            let page = HomePage { db };
-            assert_eq!(page.draw_page().is_ok(), true);
+            assert_eq!(page.handle_input("C").unwrap(), None);
+            assert!(draw().contains("Legacy"));
+            assert_eq!(page.rows().unwrap().len(), 2);
         }
 
         #[test]
         fn handle_input_should_not_throw_error() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
 
-            let page = HomePage { db };
-            assert_eq!(page.handle_input("").is_ok(), true);
+            let page = HomePage::new(db, HomeLayout::Flat);
+            assert!(page.handle_input("").is_ok());
         }
 
         #[test]
         fn handle_input_should_return_the_correct_actions() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
             let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
 
-            let page = HomePage { db };
+            let page = HomePage::new(db, HomeLayout::Flat);
 
             let q = "q";
             let c = "c";
+            let capture = "+Fix login ^epic:0";
             let valid_epic_id = epic_id.0.to_string();
             let invalid_epic_id = "999";
             let junk_input = "j983f2j";
@@ -259,6 +1235,82 @@ mod tests {
 
             assert_eq!(page.handle_input(q).unwrap(), Some(Action::Exit));
             assert_eq!(page.handle_input(c).unwrap(), Some(Action::CreateEpic));
+            assert_eq!(
+                page.handle_input(capture).unwrap(),
+                Some(Action::QuickAddStory {
+                    epic_id: None,
+                    input: "Fix login ^epic:0".to_owned()
+                })
+            );
+            assert_eq!(page.handle_input("f").unwrap(), Some(Action::PickFilter));
+            assert_eq!(
+                page.handle_input("n").unwrap(),
+                Some(Action::NavigateToNotifications)
+            );
+            assert_eq!(
+                page.handle_input("s").unwrap(),
+                Some(Action::NavigateToStandup)
+            );
+            assert_eq!(
+                page.handle_input("S").unwrap(),
+                Some(Action::NavigateToSprints)
+            );
+            assert_eq!(
+                page.handle_input("D").unwrap(),
+                Some(Action::NavigateToDashboard)
+            );
+            assert_eq!(
+                page.handle_input("W").unwrap(),
+                Some(Action::NavigateToWorkload)
+            );
+            assert_eq!(
+                page.handle_input("L").unwrap(),
+                Some(Action::NavigateToLabels)
+            );
+            assert_eq!(
+                page.handle_input("w").unwrap(),
+                Some(Action::NavigateToThisWeek)
+            );
+            assert_eq!(
+                page.handle_input("O").unwrap(),
+                Some(Action::NavigateToStale)
+            );
+            assert_eq!(
+                page.handle_input("B").unwrap(),
+                Some(Action::NavigateToBoard)
+            );
+            assert_eq!(
+                page.handle_input("M").unwrap(),
+                Some(Action::Search {
+                    filter: Filter::assigned_to(page.db.user())
+                })
+            );
+            assert_eq!(
+                page.handle_input("K").unwrap(),
+                Some(Action::NavigateToSnapshots)
+            );
+            assert_eq!(
+                page.handle_input("u0").unwrap(),
+                Some(Action::UpdateEpicStatus { epic_id: 0 })
+            );
+            assert_eq!(
+                page.handle_input("U").unwrap(),
+                Some(Action::BulkUpdateStatus {
+                    items: vec![ItemType::Epic { id: epic_id }]
+                })
+            );
+            assert_eq!(
+                page.handle_input("/ lgn #bug").unwrap(),
+                Some(Action::Search {
+                    filter: Filter {
+                        label: Some("bug".to_owned()),
+                        query: Some("lgn".to_owned()),
+                        ..Default::default()
+                    }
+                })
+            );
+            assert_eq!(page.handle_input("/ ").unwrap(), None);
+            assert!(page.handle_input("/ status:done").is_err());
             assert_eq!(
                 page.handle_input(&valid_epic_id).unwrap(),
                 Some(Action::NavigateToEpicDetail { epic_id: 0 })
@@ -281,47 +1333,92 @@ mod tests {
 
         #[test]
         fn draw_page_should_not_throw_error() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
             let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
 
             let page = EpicDetail {
                 epic_id: epic_id.0,
                 db,
+                calendar: Calendar::default(),
+                quick_filter: QuickFilter::default(),
             };
-            assert_eq!(page.draw_page().is_ok(), true);
+            assert!(page.draw_page(&mut io::sink()).is_ok());
         }
 
         #[test]
         fn handle_input_should_not_throw_error() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
             let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
 
             let page = EpicDetail {
                 epic_id: epic_id.0,
                 db,
+                calendar: Calendar::default(),
+                quick_filter: QuickFilter::default(),
+            };
+            assert!(page.handle_input("").is_ok());
+        }
+
+        #[test]
+        fn rows_should_follow_the_story_order_of_the_epic() {
+            use crate::db::SortBy;
+            use crate::model::Priority;
+
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
+            for (name, priority) in [("Docs", None), ("Refunds", Some(Priority::Critical))] {
+                let story = Story::new(ItemDetail {
+                    name: name.to_owned(),
+                    priority,
+                    ..Default::default()
+                });
+                db.add_story(story, Some(epic_id)).unwrap();
+            }
+
+            let page = EpicDetail {
+                epic_id: epic_id.0,
+                db: Rc::clone(&db),
+                calendar: Calendar::default(),
+                quick_filter: QuickFilter::default(),
+            };
+            let names = || {
+                page.rows()
+                    .unwrap()
+                    .into_iter()
+                    .map(|(_, name)| name)
+                    .collect::<Vec<_>>()
             };
-            assert_eq!(page.handle_input("").is_ok(), true);
+
+            assert_eq!(names(), vec!["Docs", "Refunds"]);
+            assert_eq!(
+                page.handle_input("o priority").unwrap(),
+                Some(Action::SortStories {
+                    epic_id: epic_id.0,
+                    sort: SortBy::Priority
+                })
+            );
+            assert!(page.handle_input("o due").is_err());
+
+            db.set_story_order(epic_id, SortBy::Priority).unwrap();
+            assert_eq!(names(), vec!["Refunds", "Docs"]);
         }
 
         #[test]
         fn draw_page_should_throw_error_for_invalid_epic_id() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
 
-            let page = EpicDetail { epic_id: 999, db };
-            assert_eq!(page.draw_page().is_err(), true);
+            let page = EpicDetail {
+                epic_id: 999,
+                db,
+                calendar: Calendar::default(),
+                quick_filter: QuickFilter::default(),
+            };
+            assert!(page.draw_page(&mut io::sink()).is_err());
         }
 
         #[test]
         fn handle_input_should_return_the_correct_actions() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
 
             let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
             let story_id = db
@@ -331,12 +1428,20 @@ mod tests {
             let page = EpicDetail {
                 epic_id: epic_id.0,
                 db,
+                calendar: Calendar::default(),
+                quick_filter: QuickFilter::default(),
             };
 
             let p = "p";
             let u = "u";
             let d = "d";
+            let g = "g";
+            let t = "t";
+            let y = "y";
+            let m = "m";
             let c = "c";
+            let quick_add = "+ Fix login";
+            let quick_add_without_name = "+  ";
             let invalid_story_id = "999";
             let junk_input = "j983f2j";
             let junk_input_with_valid_prefix = "p983f2j";
@@ -354,10 +1459,44 @@ mod tests {
                 page.handle_input(d).unwrap(),
                 Some(Action::DeleteEpic { epic_id: 0 })
             );
+            assert_eq!(
+                page.handle_input(g).unwrap(),
+                Some(Action::MergeEpic { epic_id: 0 })
+            );
+            assert_eq!(
+                page.handle_input(t).unwrap(),
+                Some(Action::DemoteEpic { epic_id: 0 })
+            );
+            assert_eq!(
+                page.handle_input("l").unwrap(),
+                Some(Action::EditDefaultLabels { epic_id: 0 })
+            );
+            assert_eq!(
+                page.handle_input(y).unwrap(),
+                Some(Action::CopyEpic { epic_id: 0 })
+            );
+            assert_eq!(
+                page.handle_input(m).unwrap(),
+                Some(Action::NavigateToEpicMarkdown { epic_id: 0 })
+            );
             assert_eq!(
                 page.handle_input(c).unwrap(),
                 Some(Action::CreateStory { epic_id: 0 })
             );
+            assert_eq!(
+                page.handle_input("w").unwrap(),
+                Some(Action::ToggleWatch {
+                    item: ItemType::Epic { id: ItemId(0) }
+                })
+            );
+            assert_eq!(
+                page.handle_input(quick_add).unwrap(),
+                Some(Action::QuickAddStory {
+                    epic_id: Some(0),
+                    input: "Fix login".to_owned()
+                })
+            );
+            assert_eq!(page.handle_input(quick_add_without_name).unwrap(), None);
             assert_eq!(
                 page.handle_input(&story_id.0.to_string()).unwrap(),
                 Some(Action::NavigateToStoryDetail {
@@ -383,9 +1522,7 @@ mod tests {
 
         #[test]
         fn draw_page_should_not_throw_error() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
             let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
             let story_id = db
                 .create_story("".to_string(), "".to_string(), None)
@@ -394,15 +1531,14 @@ mod tests {
                 epic_id: epic_id.0,
                 story_id: story_id.0,
                 db,
+                calendar: Calendar::default(),
             };
-            assert_eq!(page.draw_page().is_ok(), true);
+            assert!(page.draw_page(&mut io::sink()).is_ok());
         }
 
         #[test]
         fn handle_input_should_not_throw_error() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
             let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
             let story_id = db
                 .create_story("".to_string(), "".to_string(), Some(epic_id))
@@ -411,15 +1547,14 @@ mod tests {
                 epic_id: epic_id.0,
                 story_id: story_id.0,
                 db,
+                calendar: Calendar::default(),
             };
-            assert_eq!(page.handle_input("").is_ok(), true);
+            assert!(page.handle_input("").is_ok());
         }
 
         #[test]
         fn draw_page_should_throw_error_for_invalid_story_id() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
             let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
             let _ = db
                 .create_story("".to_string(), "".to_string(), Some(epic_id))
@@ -428,15 +1563,14 @@ mod tests {
                 epic_id: epic_id.0,
                 story_id: 999,
                 db,
+                calendar: Calendar::default(),
             };
-            assert_eq!(page.draw_page().is_err(), true);
+            assert!(page.draw_page(&mut io::sink()).is_err());
         }
 
         #[test]
         fn handle_input_should_return_the_correct_actions() {
-            let db = Rc::new(JiraDataBase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
             let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
             let story_id = db
                 .create_story("".to_string(), "".to_string(), Some(epic_id))
@@ -445,11 +1579,20 @@ mod tests {
                 epic_id: epic_id.0,
                 story_id: story_id.0,
                 db,
+                calendar: Calendar::default(),
             };
 
             let p = "p";
             let u = "u";
             let d = "d";
+            let c = "c";
+            let s = "s";
+            let e = "e";
+            let a = "a";
+            let x2 = "x2";
+            let x0 = "x0";
+            let y = "y";
+            let m = "m";
             let some_number = "1";
             let junk_input = "j983f2j";
             let junk_input_with_valid_prefix = "p983f2j";
@@ -472,6 +1615,80 @@ mod tests {
                     story_id: story_id.0
                 })
             );
+            assert_eq!(
+                page.handle_input(c).unwrap(),
+                Some(Action::CloneStory {
+                    epic_id: epic_id.0,
+                    story_id: story_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input(s).unwrap(),
+                Some(Action::SplitStory {
+                    epic_id: epic_id.0,
+                    story_id: story_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input(e).unwrap(),
+                Some(Action::PromoteStory {
+                    story_id: story_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input("w").unwrap(),
+                Some(Action::ToggleWatch {
+                    item: ItemType::Story { id: story_id }
+                })
+            );
+            assert_eq!(
+                page.handle_input("=5").unwrap(),
+                Some(Action::SetStoryPoints {
+                    story_id: story_id.0,
+                    points: Some(5)
+                })
+            );
+            assert_eq!(
+                page.handle_input("=").unwrap(),
+                Some(Action::SetStoryPoints {
+                    story_id: story_id.0,
+                    points: None
+                })
+            );
+            assert_eq!(page.handle_input("=x").unwrap(), None);
+            assert_eq!(
+                page.handle_input("b3").unwrap(),
+                Some(Action::AddBlocker {
+                    story_id: story_id.0,
+                    blocker_id: 3
+                })
+            );
+            assert_eq!(
+                page.handle_input(a).unwrap(),
+                Some(Action::AddChecklistItem {
+                    story_id: story_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input(x2).unwrap(),
+                Some(Action::ToggleChecklistItem {
+                    story_id: story_id.0,
+                    index: 1
+                })
+            );
+            assert_eq!(page.handle_input(x0).unwrap(), None);
+            assert_eq!(
+                page.handle_input(y).unwrap(),
+                Some(Action::CopyStory {
+                    story_id: story_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input(m).unwrap(),
+                Some(Action::NavigateToStoryMarkdown {
+                    story_id: story_id.0
+                })
+            );
             assert_eq!(page.handle_input(some_number).unwrap(), None);
             assert_eq!(page.handle_input(junk_input).unwrap(), None);
             assert_eq!(
@@ -484,4 +1701,304 @@ mod tests {
             );
         }
     }
+
+    mod markdown_snippet_page {
+        use super::*;
+        use crate::model::ItemId;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
+
+            let page = MarkdownSnippet {
+                item: ItemType::Epic { id: epic_id },
+                db,
+            };
+            assert!(page.draw_page(&mut io::sink()).is_ok());
+        }
+
+        #[test]
+        fn draw_page_should_throw_error_for_invalid_story_id() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+            let page = MarkdownSnippet {
+                item: ItemType::Story { id: ItemId(999) },
+                db,
+            };
+            assert!(page.draw_page(&mut io::sink()).is_err());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            let story_id = db
+                .create_story("Fix login".to_string(), "".to_string(), None)
+                .unwrap();
+
+            let page = MarkdownSnippet {
+                item: ItemType::Story { id: story_id },
+                db,
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("y").unwrap(),
+                Some(Action::CopyText {
+                    text: "### [STORY-0] Fix login\n\n\
+                           ![OPEN](https://img.shields.io/badge/status-OPEN-blue)"
+                        .to_owned()
+                })
+            );
+            assert_eq!(page.handle_input("j983f2j").unwrap(), None);
+        }
+    }
+
+    mod search_results_page {
+        use super::*;
+        use crate::model::FieldEdit;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            let _ = db.create_epic("Auth".to_string(), "".to_string()).unwrap();
+
+            let page = SearchResults {
+                filter: parse_filter("auth").unwrap(),
+                db,
+                quick_filter: QuickFilter::default(),
+            };
+            assert!(page.draw_page(&mut io::sink()).is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic("Login flows".to_string(), "".to_string())
+                .unwrap();
+            let story_id = db
+                .create_story("Fix login".to_string(), "".to_string(), Some(epic_id))
+                .unwrap();
+            let _ = db
+                .create_story("Login audit".to_string(), "".to_string(), None)
+                .unwrap();
+
+            let page = SearchResults {
+                filter: parse_filter("fix lgn").unwrap(),
+                db: Rc::clone(&db),
+                quick_filter: QuickFilter::default(),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("s").unwrap(),
+                Some(Action::SaveFilter {
+                    filter: parse_filter("fix lgn").unwrap()
+                })
+            );
+            assert_eq!(
+                page.handle_input("1").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: epic_id.0,
+                    story_id: story_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input("u1").unwrap(),
+                Some(Action::UpdateStoryStatus {
+                    story_id: story_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input("U").unwrap(),
+                Some(Action::BulkUpdateStatus {
+                    items: vec![ItemType::Story { id: story_id }]
+                })
+            );
+            assert_eq!(
+                page.handle_input("e #billing @alice").unwrap(),
+                Some(Action::BulkEdit {
+                    items: vec![ItemType::Story { id: story_id }],
+                    edits: vec![
+                        FieldEdit::AddLabel("billing".to_owned()),
+                        FieldEdit::SetAssignee(Some("alice".to_owned())),
+                    ]
+                })
+            );
+            assert!(page.handle_input("e billing").is_err());
+            assert_eq!(page.handle_input("0").unwrap(), None);
+            assert_eq!(page.handle_input("2").unwrap(), None);
+
+            let page = SearchResults {
+                filter: parse_filter("audit").unwrap(),
+                db,
+                quick_filter: QuickFilter::default(),
+            };
+
+            assert_eq!(page.handle_input("1").unwrap(), None);
+        }
+    }
+
+    mod notifications_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+            let page = Notifications {
+                user: "alice".to_owned(),
+                db,
+            };
+            assert!(page.draw_page(&mut io::sink()).is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
+            let story_id = db
+                .create_story("".to_string(), "".to_string(), Some(epic_id))
+                .unwrap();
+            let _ = db
+                .toggle_watch(&ItemType::Story { id: story_id }, "alice")
+                .unwrap();
+            db.update_story_status(story_id, crate::model::ItemStatus::InProgress)
+                .unwrap();
+
+            let page = Notifications {
+                user: "alice".to_owned(),
+                db: Rc::clone(&db),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("r").unwrap(),
+                Some(Action::MarkNotificationsRead)
+            );
+            assert_eq!(
+                page.handle_input("0").unwrap(),
+                Some(Action::OpenNotification { id: 0 })
+            );
+            assert_eq!(page.handle_input("1").unwrap(), None);
+
+            let page = Notifications {
+                user: "bob".to_owned(),
+                db,
+            };
+
+            assert_eq!(page.handle_input("0").unwrap(), None);
+        }
+    }
+
+    mod due_reminders_page {
+        use super::*;
+        use crate::model::Story;
+
+        fn today() -> NaiveDate {
+            NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+            let page = DueReminders {
+                today: today(),
+                db,
+                calendar: Calendar::default(),
+            };
+            assert!(page.draw_page(&mut io::sink()).is_ok());
+            assert!(!page.has_items().unwrap());
+        }
+
+        #[test]
+        fn handle_input_should_open_the_most_overdue_item_first() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+            let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
+            let due = |date: &str| {
+                Story::new(ItemDetail {
+                    due: Some(date.parse().unwrap()),
+                    ..Default::default()
+                })
+            };
+            let today_id = db.add_story(due("2024-05-01"), Some(epic_id)).unwrap();
+            let overdue_id = db.add_story(due("2024-04-20"), Some(epic_id)).unwrap();
+            let _ = db.add_story(due("2024-05-02"), Some(epic_id)).unwrap();
+
+            let page = DueReminders {
+                today: today(),
+                db,
+                calendar: Calendar::default(),
+            };
+
+            assert!(page.has_items().unwrap());
+            assert_eq!(
+                page.handle_input("").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("o").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: epic_id.0,
+                    story_id: overdue_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input("2").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: epic_id.0,
+                    story_id: today_id.0
+                })
+            );
+            assert_eq!(page.handle_input("3").unwrap(), None);
+        }
+    }
+
+    mod standup_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+            let page = Standup {
+                user: "alice".to_owned(),
+                db,
+                calendar: Calendar::default(),
+            };
+            assert!(page.draw_page(&mut io::sink()).is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+
+            let page = Standup {
+                user: "alice".to_owned(),
+                db,
+                calendar: Calendar::default(),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert!(matches!(
+                page.handle_input("y").unwrap(),
+                Some(Action::CopyText { text }) if text.contains("Blocked:\n- nothing")
+            ));
+            assert_eq!(page.handle_input("j983f2j").unwrap(), None);
+        }
+    }
 }
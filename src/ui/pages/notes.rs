@@ -0,0 +1,143 @@
+use anyhow::Result;
+use std::any::Any;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::db::JiraDataBase;
+use crate::model::Action;
+
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::Page;
+
+/// The board's scratchpad: free-form lines for meeting notes and whatever
+/// isn't a ticket yet, numbered from 1.
+pub struct Notes {
+    pub db: Rc<JiraDataBase>,
+}
+
+impl Notes {
+    /// The index of note `number`, when there is one.
+    fn index(&self, number: &str) -> Result<Option<usize>> {
+        let count = self.db.read_db()?.notes.len();
+
+        Ok(match number.trim().parse::<usize>() {
+            Ok(number) if number > 0 && number <= count => Some(number - 1),
+            _ => None,
+        })
+    }
+}
+
+impl Page for Notes {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let notes = self.db.read_db()?.notes;
+
+        writeln!(
+            out,
+            "------------------------------ NOTES ------------------------------"
+        )?;
+
+        if notes.is_empty() {
+            writeln!(out, "Nothing written down yet.")?;
+        }
+
+        for (index, note) in notes.iter().enumerate() {
+            writeln!(out, "{:>3}  {note}", index + 1)?;
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![Binding::new("p", "previous", || {
+            Action::NavigateToPreviousPage
+        })]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        if let Some(text) = input.strip_prefix('+').map(str::trim) {
+            return Ok((!text.is_empty()).then(|| Action::AddNote {
+                text: text.to_owned(),
+            }));
+        }
+
+        if let Some(number) = input.strip_prefix('x') {
+            return Ok(self
+                .index(number)?
+                .map(|index| Action::DeleteNote { index }));
+        }
+
+        if let Some(rest) = input.strip_prefix('s') {
+            let (number, capture) = rest.split_once(' ').unwrap_or((rest, ""));
+
+            return Ok(self.index(number)?.map(|index| Action::NoteToStory {
+                index,
+                capture: capture.trim().to_owned(),
+            }));
+        }
+
+        Ok(None)
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new("+ text", "add note"),
+            KeyHint::new("s:#: ^epic:id !prio #label", "turn note into story"),
+            KeyHint::new("x:#:", "delete note"),
+        ]
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .db
+            .read_db()?
+            .notes
+            .into_iter()
+            .enumerate()
+            .map(|(index, note)| (format!("s{}", index + 1), note))
+            .collect())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+
+    #[test]
+    fn handle_input_should_return_the_correct_actions() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        db.add_note("Ask about refunds".to_owned()).unwrap();
+
+        let page = Notes { db };
+
+        assert_eq!(
+            page.handle_input("+ Billing is slow").unwrap(),
+            Some(Action::AddNote {
+                text: "Billing is slow".to_owned()
+            })
+        );
+        assert_eq!(page.handle_input("+").unwrap(), None);
+        assert_eq!(
+            page.handle_input("x1").unwrap(),
+            Some(Action::DeleteNote { index: 0 })
+        );
+        assert_eq!(
+            page.handle_input("s1 ^epic:2 !high").unwrap(),
+            Some(Action::NoteToStory {
+                index: 0,
+                capture: "^epic:2 !high".to_owned()
+            })
+        );
+        assert_eq!(page.handle_input("s2").unwrap(), None);
+        assert_eq!(page.handle_input("x0").unwrap(), None);
+    }
+}
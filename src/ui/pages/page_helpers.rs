@@ -1,29 +1,234 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use ellipse::Ellipse;
+use std::collections::HashMap;
+
+use crate::calendar::Calendar;
+use crate::db::{JiraDataBase, SortBy};
+use crate::model::{ItemDetail, Story};
 
 pub fn get_column_string(text: &str, width: usize) -> String {
     match text.len().cmp(&width) {
         std::cmp::Ordering::Equal => text.to_string(),
-        std::cmp::Ordering::Greater => {
-            return match width {
-                0 => "".to_string(),
-                1 => ".".to_string(),
-                2 => "..".to_string(),
-                3 => "...".to_string(),
-                _ => text.truncate_ellipse(width - 3).to_string(),
-            };
-        }
+        std::cmp::Ordering::Greater => match width {
+            0 => "".to_string(),
+            1 => ".".to_string(),
+            2 => "..".to_string(),
+            3 => "...".to_string(),
+            _ => text.truncate_ellipse(width - 3).to_string(),
+        },
         std::cmp::Ordering::Less => {
             let mut column_string = text.to_owned();
 
             (0..(width - text.len())).for_each(|_| column_string.push(' '));
-            return column_string;
+            column_string
         }
     }
 }
 
+/// Formats the optional fields of an item that are set, e.g. `priority: HIGH`.
+/// The due date also says how far it is from `today`.
+pub fn detail_fields(detail: &ItemDetail, calendar: &Calendar, today: NaiveDate) -> Vec<String> {
+    let mut fields = Vec::new();
+
+    if let Some(priority) = &detail.priority {
+        fields.push(format!("priority: {priority}"));
+    }
+
+    if let Some(assignee) = &detail.assignee {
+        fields.push(format!("assignee: @{assignee}"));
+    }
+
+    if !detail.labels.is_empty() {
+        let labels = detail
+            .labels
+            .iter()
+            .map(|label| format!("#{label}"))
+            .collect::<Vec<String>>();
+
+        fields.push(format!("labels: {}", labels.join(" ")));
+    }
+
+    if let Some(due) = &detail.due {
+        fields.push(format!(
+            "due: {} ({})",
+            calendar.date(*due),
+            relative_date(*due, today)
+        ));
+    }
+
+    if let Some(points) = &detail.points {
+        fields.push(format!("points: {points}"));
+    }
+
+    if !detail.watchers.is_empty() {
+        let watchers = detail
+            .watchers
+            .iter()
+            .map(|watcher| format!("@{watcher}"))
+            .collect::<Vec<_>>();
+
+        fields.push(format!("watchers: {}", watchers.join(" ")));
+    }
+
+    fields
+}
+
+/// Who saved the last change to the item and when, e.g.
+/// `last updated by alice 2 days ago (2024-05-02 09:30)`.
+pub fn last_updated(
+    detail: &ItemDetail,
+    calendar: &Calendar,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    let by = detail.updated_by.as_ref()?;
+    let at = match detail.updated_at {
+        Some(at) => format!(" {}", timestamp(at, calendar, now)),
+        None => String::new(),
+    };
+
+    Some(format!("last updated by {by}{at}"))
+}
+
+/// When the item was created, e.g. `created 5 days ago (2024-04-29 10:00)`.
+pub fn created(detail: &ItemDetail, calendar: &Calendar, now: DateTime<Utc>) -> Option<String> {
+    let change = detail.history.first()?;
+
+    Some(format!("created {}", timestamp(change.at, calendar, now)))
+}
+
+fn timestamp(at: DateTime<Utc>, calendar: &Calendar, now: DateTime<Utc>) -> String {
+    format!("{} ({})", relative_time(at, now), calendar.date_time(at))
+}
+
+/// Every story of the board by ID, for pages that look them up from epics.
+pub fn stories_by_id(db: &JiraDataBase) -> Result<HashMap<u32, Story>> {
+    Ok(db
+        .list_stories(0, usize::MAX, SortBy::Id)?
+        .items
+        .into_iter()
+        .map(|story| (story.detail.id.0, story))
+        .collect())
+}
+
+/// When the item last changed: the last save, or else the last status change
+/// for items saved before saves were stamped.
+pub fn last_change(detail: &ItemDetail) -> Option<DateTime<Utc>> {
+    detail
+        .updated_at
+        .or_else(|| detail.history.last().map(|change| change.at))
+}
+
+/// How long ago `at` was, or how far off it is, e.g. `2 days ago` or
+/// `in 3 hours`.
+pub fn relative_time(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let offset = at - now;
+    let span = offset.abs();
+
+    let amount = match (span.num_minutes(), span.num_hours(), span.num_days()) {
+        (0, _, _) => return "just now".to_owned(),
+        (minutes, 0, _) => plural(minutes, "minute"),
+        (_, hours, 0) => plural(hours, "hour"),
+        (_, _, days) if days < 45 => plural(days, "day"),
+        (_, _, days) if days < 365 => plural(days / 30, "month"),
+        (_, _, days) => plural(days / 365, "year"),
+    };
+
+    match offset < Duration::zero() {
+        true => format!("{amount} ago"),
+        false => format!("in {amount}"),
+    }
+}
+
+/// How far `day` is from `today`, e.g. `tomorrow` or `3 days ago`.
+pub fn relative_date(day: NaiveDate, today: NaiveDate) -> String {
+    match (day - today).num_days() {
+        0 => "today".to_owned(),
+        1 => "tomorrow".to_owned(),
+        -1 => "yesterday".to_owned(),
+        days if days > 0 => format!("in {}", plural(days, "day")),
+        days => format!("{} ago", plural(-days, "day")),
+    }
+}
+
+fn plural(count: i64, unit: &str) -> String {
+    match count {
+        1 => format!("1 {unit}"),
+        count => format!("{count} {unit}s"),
+    }
+}
+
+/// One line per status the item went through, oldest first, with how long it
+/// stayed in the previous one and who moved it, e.g.
+/// `2024-05-02 09:30  IN PROGRESS (after 1d 2h) by alice`.
+pub fn status_timeline(detail: &ItemDetail, calendar: &Calendar) -> Vec<String> {
+    let mut previous = None;
+
+    detail
+        .history
+        .iter()
+        .map(|change| {
+            let mut line = format!("{}  {}", calendar.date_time(change.at), change.status);
+
+            if let Some(previous) = previous.replace(change.at) {
+                line.push_str(&format!(
+                    " (after {})",
+                    format_duration(change.at - previous)
+                ));
+            }
+
+            if let Some(by) = &change.by {
+                line.push_str(&format!(" by {by}"));
+            }
+
+            line
+        })
+        .collect()
+}
+
+/// Every edit of the description, oldest first: when and by whom, followed by
+/// the removed (`-`) and added (`+`) words.
+pub fn description_edits(detail: &ItemDetail, calendar: &Calendar) -> Vec<String> {
+    detail
+        .description_edits
+        .iter()
+        .flat_map(|edit| {
+            let by = edit
+                .by
+                .as_ref()
+                .map(|by| format!(" by {by}"))
+                .unwrap_or_default();
+
+            std::iter::once(format!(
+                "{}  description edited{by}",
+                calendar.date_time(edit.at)
+            ))
+            .chain(edit.diff.iter().map(|change| format!("    {change}")))
+        })
+        .collect()
+}
+
+fn format_duration(duration: Duration) -> String {
+    match (duration.num_days(), duration.num_hours() % 24) {
+        (0, 0) => format!("{}m", duration.num_minutes()),
+        (0, hours) => format!("{hours}h"),
+        (days, 0) => format!("{days}d"),
+        (days, hours) => format!("{days}d {hours}h"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{DescriptionEdit, ItemStatus, Priority, StatusChange};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn utc() -> Calendar {
+        Calendar {
+            zone: "UTC".parse().unwrap(),
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn test_get_column_string() {
@@ -59,4 +264,135 @@ mod tests {
         assert_eq!(get_column_string(text3, width), "testme".to_owned());
         assert_eq!(get_column_string(text4, width), "tes...".to_owned());
     }
+
+    #[test]
+    fn test_detail_fields() {
+        let mut detail = ItemDetail::default();
+
+        let today = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+
+        assert!(detail_fields(&detail, &Calendar::default(), today).is_empty());
+
+        detail.priority = Some(Priority::High);
+        detail.assignee = Some("alice".to_owned());
+        detail.labels = vec!["auth".to_owned(), "web".to_owned()];
+        detail.due = NaiveDate::from_ymd_opt(2024, 5, 3);
+
+        assert_eq!(
+            detail_fields(&detail, &Calendar::default(), today),
+            vec![
+                "priority: HIGH".to_owned(),
+                "assignee: @alice".to_owned(),
+                "labels: #auth #web".to_owned(),
+                "due: 2024-05-03 (in 2 days)".to_owned()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status_timeline() {
+        let mut detail = ItemDetail::default();
+
+        assert!(status_timeline(&detail, &utc()).is_empty());
+
+        let created = Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+        detail.history = vec![
+            StatusChange {
+                status: ItemStatus::Open,
+                at: created,
+                by: None,
+            },
+            StatusChange {
+                status: ItemStatus::InProgress,
+                at: created + Duration::minutes(45),
+                by: Some("alice".to_owned()),
+            },
+            StatusChange {
+                status: ItemStatus::Resolved,
+                at: created + Duration::hours(27),
+                by: Some("bob".to_owned()),
+            },
+            StatusChange {
+                status: ItemStatus::Closed,
+                at: created + Duration::days(3),
+                by: None,
+            },
+        ];
+
+        assert_eq!(
+            status_timeline(&detail, &utc()),
+            vec![
+                "2024-05-01 10:00  OPEN".to_owned(),
+                "2024-05-01 10:45  IN PROGRESS (after 45m) by alice".to_owned(),
+                "2024-05-02 13:00  RESOLVED (after 1d 2h) by bob".to_owned(),
+                "2024-05-04 10:00  Closed (after 1d 21h)".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_description_edits() {
+        let mut detail = ItemDetail::default();
+
+        assert!(description_edits(&detail, &utc()).is_empty());
+
+        detail.description_edits = vec![DescriptionEdit {
+            at: Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap(),
+            by: Some("alice".to_owned()),
+            diff: vec!["- 30".to_owned(), "+ 14".to_owned()],
+        }];
+
+        assert_eq!(
+            description_edits(&detail, &utc()),
+            vec![
+                "2024-05-01 10:00  description edited by alice".to_owned(),
+                "    - 30".to_owned(),
+                "    + 14".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_updated() {
+        let mut detail = ItemDetail::default();
+        let now = Utc.with_ymd_and_hms(2024, 5, 4, 12, 0, 0).unwrap();
+
+        assert_eq!(last_updated(&detail, &utc(), now), None);
+
+        detail.updated_by = Some("alice".to_owned());
+        assert_eq!(
+            last_updated(&detail, &utc(), now),
+            Some("last updated by alice".to_owned())
+        );
+
+        detail.updated_at = Some(Utc.with_ymd_and_hms(2024, 5, 2, 9, 30, 0).unwrap());
+        assert_eq!(
+            last_updated(&detail, &utc(), now),
+            Some("last updated by alice 2 days ago (2024-05-02 09:30)".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 4, 12, 0, 0).unwrap();
+
+        assert_eq!(relative_time(now - Duration::seconds(20), now), "just now");
+        assert_eq!(
+            relative_time(now - Duration::minutes(1), now),
+            "1 minute ago"
+        );
+        assert_eq!(relative_time(now + Duration::hours(3), now), "in 3 hours");
+        assert_eq!(relative_time(now - Duration::days(2), now), "2 days ago");
+        assert_eq!(relative_time(now - Duration::days(95), now), "3 months ago");
+        assert_eq!(relative_time(now + Duration::days(800), now), "in 2 years");
+
+        let today = now.date_naive();
+
+        assert_eq!(relative_date(today, today), "today");
+        assert_eq!(relative_date(today.pred_opt().unwrap(), today), "yesterday");
+        assert_eq!(
+            relative_date(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), today),
+            "3 days ago"
+        );
+    }
 }
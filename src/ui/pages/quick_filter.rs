@@ -0,0 +1,153 @@
+use std::cell::{Cell, RefCell};
+use std::io::{self, Write};
+
+use super::item_list::{ItemList, ItemRow};
+
+/// A key pressed while typing a quick filter.
+#[derive(Debug, PartialEq)]
+pub enum FilterKey {
+    Char(char),
+    Backspace,
+    /// Keeps the filter and goes back to the keys of the page.
+    Enter,
+    /// Clears the filter.
+    Esc,
+    /// A whole line, where keys can't be read one by one: the filter to keep,
+    /// or none when it's empty.
+    Line(String),
+}
+
+/// What was typed after `/` on a list page. It narrows the rows to the ones
+/// whose name or key contains it, ignoring case, as it's typed.
+#[derive(Default)]
+pub struct QuickFilter {
+    text: RefCell<String>,
+    typing: Cell<bool>,
+}
+
+impl QuickFilter {
+    /// Starts reading keys into the filter.
+    pub fn start(&self) {
+        self.typing.set(true);
+    }
+
+    pub fn is_typing(&self) -> bool {
+        self.typing.get()
+    }
+
+    pub fn text(&self) -> String {
+        self.text.borrow().clone()
+    }
+
+    pub fn press(&self, key: FilterKey) {
+        let mut text = self.text.borrow_mut();
+
+        match key {
+            FilterKey::Char(key) => text.push(key),
+            FilterKey::Backspace => {
+                text.pop();
+            }
+            FilterKey::Enter => self.typing.set(false),
+            FilterKey::Esc => {
+                text.clear();
+                self.typing.set(false);
+            }
+            FilterKey::Line(line) => {
+                *text = line.trim().to_owned();
+                self.typing.set(false);
+            }
+        }
+    }
+
+    fn matches(&self, row: &ItemRow) -> bool {
+        let text = self.text.borrow().to_lowercase();
+
+        row.name.to_lowercase().contains(&text) || row.item.key().to_lowercase().contains(&text)
+    }
+
+    /// Shows what is being typed, or what the rows are filtered by.
+    pub fn draw(&self, out: &mut dyn Write) -> io::Result<()> {
+        let text = self.text.borrow();
+
+        if self.typing.get() {
+            writeln!(out, "/{text}_  (enter keeps the filter, esc clears it)")
+        } else if !text.is_empty() {
+            writeln!(out, "filtered by \"{text}\", / to change")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl ItemList {
+    /// Keeps the rows `filter` lets through.
+    pub fn filtered(mut self, filter: &QuickFilter) -> Self {
+        self.rows.retain(|row| filter.matches(row));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ItemId, ItemStatus, ItemType};
+
+    fn row(id: u32, name: &str) -> ItemRow {
+        ItemRow {
+            handle: id.to_string(),
+            item: ItemType::Story { id: ItemId(id) },
+            epic_id: None,
+            name: name.to_owned(),
+            status: ItemStatus::Open,
+            updated_at: None,
+            due: None,
+            badge: None,
+        }
+    }
+
+    #[test]
+    fn filtered_should_narrow_the_rows_as_keys_are_typed() {
+        let filter = QuickFilter::default();
+        let handles = |filter: &QuickFilter| {
+            ItemList::new(vec![
+                row(1, "Refunds"),
+                row(12, "Receipts"),
+                row(3, "Coupons"),
+            ])
+            .filtered(filter)
+            .rows
+            .into_iter()
+            .map(|row| row.handle)
+            .collect::<Vec<_>>()
+        };
+
+        filter.start();
+        for key in "rE".chars() {
+            filter.press(FilterKey::Char(key));
+        }
+
+        assert!(filter.is_typing());
+        assert_eq!(handles(&filter), vec!["1", "12"]);
+
+        filter.press(FilterKey::Char('c'));
+        assert_eq!(handles(&filter), vec!["12"]);
+
+        filter.press(FilterKey::Backspace);
+        filter.press(FilterKey::Backspace);
+        filter.press(FilterKey::Backspace);
+        filter.press(FilterKey::Char('1'));
+        filter.press(FilterKey::Enter);
+
+        assert!(!filter.is_typing());
+        assert_eq!(handles(&filter), vec!["1", "12"]);
+
+        filter.press(FilterKey::Line("story-12".to_owned()));
+        assert_eq!(handles(&filter), vec!["12"]);
+
+        filter.start();
+        filter.press(FilterKey::Esc);
+
+        assert!(!filter.is_typing());
+        assert_eq!(handles(&filter), vec!["1", "12", "3"]);
+    }
+}
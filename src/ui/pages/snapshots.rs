@@ -0,0 +1,131 @@
+use anyhow::Result;
+use std::any::Any;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::archive::Archive;
+use crate::calendar::Calendar;
+use crate::model::Action;
+use crate::snapshots::Snapshot;
+
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::Page;
+
+/// The saved copies of the board, oldest first, numbered from 1.
+pub struct Snapshots {
+    pub archive: Rc<dyn Archive>,
+    pub calendar: Calendar,
+}
+
+impl Snapshots {
+    fn snapshots(&self) -> Result<Vec<Snapshot>> {
+        self.archive.list_snapshots()
+    }
+}
+
+impl Page for Snapshots {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let snapshots = self.snapshots()?;
+
+        writeln!(
+            out,
+            "---------------------------- SNAPSHOTS ----------------------------"
+        )?;
+
+        if snapshots.is_empty() {
+            writeln!(out, "No snapshots yet.")?;
+        }
+
+        for (index, snapshot) in snapshots.iter().enumerate() {
+            writeln!(
+                out,
+                "{:>3}  {}  {}",
+                index + 1,
+                self.calendar.date_time(snapshot.created_at),
+                snapshot.name
+            )?;
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("c", "create snapshot", || Action::CreateSnapshot),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        let Some(Ok(number)) = input.strip_prefix('r').map(|n| n.trim().parse::<usize>()) else {
+            return Ok(None);
+        };
+        let snapshots = self.snapshots()?;
+
+        Ok(number
+            .checked_sub(1)
+            .and_then(|index| snapshots.into_iter().nth(index))
+            .map(|snapshot| Action::RestoreSnapshot {
+                name: snapshot.name,
+            }))
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new("r:#:", "restore snapshot")]
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .snapshots()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, snapshot)| (format!("r{}", index + 1), snapshot.name))
+            .collect())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::MemoryArchive;
+    use crate::db::{test_utils::MockDB, JiraDataBase};
+
+    #[test]
+    fn handle_input_should_return_the_correct_actions() {
+        let archive = MemoryArchive::default();
+        let board = JiraDataBase::from_database(Box::new(MockDB::new()))
+            .read_db()
+            .unwrap();
+        archive.create_snapshot("before-reorg", &board).unwrap();
+
+        let page = Snapshots {
+            archive: Rc::new(archive),
+            calendar: Calendar::default(),
+        };
+        let mut out = Vec::new();
+        page.draw_page(&mut out).unwrap();
+
+        assert!(String::from_utf8(out).unwrap().contains("before-reorg"));
+        assert_eq!(
+            page.handle_input("c").unwrap(),
+            Some(Action::CreateSnapshot)
+        );
+        assert_eq!(
+            page.handle_input("r1").unwrap(),
+            Some(Action::RestoreSnapshot {
+                name: "before-reorg".to_owned()
+            })
+        );
+        assert_eq!(page.handle_input("r2").unwrap(), None);
+        assert_eq!(page.handle_input("1").unwrap(), None);
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use std::any::Any;
+use std::cmp::Reverse;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::calendar::Calendar;
+use crate::db::JiraDataBase;
+use crate::keys::parse_id;
+use crate::model::{Action, ItemId};
+use crate::reports::sprint_report;
+use crate::stats::sprint_points;
+
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::page_helpers::get_column_string;
+use super::sprints::print_velocity;
+use super::Page;
+
+/// The closed sprints, the most recently closed first, with the points each
+/// one finished.
+pub struct SprintArchive {
+    pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
+}
+
+impl Page for SprintArchive {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+        let closed = db_state
+            .sprints
+            .values()
+            .filter_map(|sprint| Some((sprint.closed?, sprint)))
+            .sorted_by_key(|(closed, sprint)| Reverse((*closed, sprint.id.0)))
+            .collect::<Vec<_>>();
+
+        writeln!(
+            out,
+            "-------------------------- SPRINT ARCHIVE --------------------------"
+        )?;
+        writeln!(
+            out,
+            "  id  |         name         |          dates          |   closed   |  points  "
+        )?;
+
+        for (closed, sprint) in &closed {
+            let points = sprint_points(&db_state, sprint);
+
+            writeln!(
+                out,
+                "{} | {} | {} | {} | {}",
+                get_column_string(&sprint.id.0.to_string(), 5),
+                get_column_string(&sprint.name, 20),
+                get_column_string(
+                    &format!(
+                        "{} - {}",
+                        self.calendar.date(sprint.start),
+                        self.calendar.date(sprint.end)
+                    ),
+                    23
+                ),
+                get_column_string(&self.calendar.date(*closed).to_string(), 10),
+                get_column_string(&format!("{}/{}", points.completed, points.committed), 9)
+            )?;
+        }
+
+        if closed.is_empty() {
+            writeln!(out, "No sprint has been closed yet.")?;
+        }
+
+        writeln!(out)?;
+        print_velocity(out, &db_state, self.calendar.today())?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![Binding::new("p", "previous", || {
+            Action::NavigateToPreviousPage
+        })]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        Ok(parse_id(input)
+            .filter(|id| {
+                self.db
+                    .get_sprint(ItemId(*id))
+                    .is_ok_and(|sprint| sprint.closed.is_some())
+            })
+            .map(|sprint_id| Action::NavigateToArchivedSprint { sprint_id }))
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(":id:", "open sprint report")]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// The report of a closed sprint, drawn from its stories as they stood when
+/// it closed.
+pub struct ArchivedSprint {
+    pub sprint_id: u32,
+    pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
+}
+
+impl Page for ArchivedSprint {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+        let sprint = db_state
+            .sprints
+            .get(&self.sprint_id)
+            .ok_or_else(|| anyhow!("could not find sprint!"))?;
+
+        writeln!(
+            out,
+            "------------------------- ARCHIVED SPRINT -------------------------"
+        )?;
+        write!(out, "{}", sprint_report(&db_state, sprint, &self.calendar))?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("o", "open sprint", || Action::NavigateToSprintDetail {
+                sprint_id: self.sprint_id,
+            }),
+        ]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::model::{ItemDetail, ItemStatus, Story};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn should_keep_closed_sprints_as_they_were() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let date = |day: u32| NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        let open = db
+            .create_sprint("Upcoming".to_owned(), date(15), date(28))
+            .unwrap();
+        let closed = db
+            .create_sprint("Launch".to_owned(), date(1), date(14))
+            .unwrap();
+        let story_id = db
+            .add_story(
+                Story::new(ItemDetail {
+                    name: "Receipts".to_owned(),
+                    status: ItemStatus::Resolved,
+                    points: Some(5),
+                    ..Default::default()
+                }),
+                None,
+            )
+            .unwrap();
+        db.add_story_to_sprint(closed, story_id).unwrap();
+        db.close_sprint(closed, date(14)).unwrap();
+        db.delete_story(story_id, None).unwrap();
+
+        let archive = SprintArchive {
+            db: Rc::clone(&db),
+            calendar: Calendar::default(),
+        };
+        let mut out = Vec::new();
+        archive.draw_page(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("Launch"));
+        assert!(out.contains("5/5"));
+        assert!(!out.contains("Upcoming"));
+        assert_eq!(
+            archive.handle_input(&closed.0.to_string()).unwrap(),
+            Some(Action::NavigateToArchivedSprint {
+                sprint_id: closed.0
+            })
+        );
+        assert_eq!(archive.handle_input(&open.0.to_string()).unwrap(), None);
+
+        let report = ArchivedSprint {
+            sprint_id: closed.0,
+            db,
+            calendar: Calendar::default(),
+        };
+        let mut out = Vec::new();
+        report.draw_page(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("- STORY-0 Receipts (5 pts)"));
+        assert!(out.contains("5 of 5 points completed, 1 of 1 stories done."));
+    }
+}
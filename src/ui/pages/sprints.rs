@@ -0,0 +1,447 @@
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use itertools::Itertools;
+use std::any::Any;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use crate::calendar::Calendar;
+use crate::config::Capacity;
+use crate::db::JiraDataBase;
+use crate::keys::parse_id;
+use crate::model::{Action, ItemId, ItemType, DB};
+use crate::stats::{rolling_average, sprint_loads, sprint_points, velocity};
+
+use super::item_list::{ItemList, ItemRow};
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::page_helpers::{get_column_string, last_change};
+use super::{Page, QuickFilter};
+
+/// How many past sprints the rolling velocity is averaged over.
+const VELOCITY_WINDOW: usize = 3;
+
+pub(super) fn print_velocity(out: &mut dyn Write, db: &DB, today: NaiveDate) -> io::Result<()> {
+    let velocity = velocity(db, today);
+
+    match rolling_average(&velocity, VELOCITY_WINDOW) {
+        Some(average) => writeln!(
+            out,
+            "velocity: {average:.1} points per sprint (last {} sprints)",
+            velocity.len().min(VELOCITY_WINDOW)
+        ),
+        None => writeln!(out, "velocity: no finished sprints yet"),
+    }
+}
+
+pub struct Sprints {
+    pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
+}
+
+impl Page for Sprints {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+
+        writeln!(
+            out,
+            "----------------------------- SPRINTS -----------------------------"
+        )?;
+        writeln!(
+            out,
+            "  id  |         name         |          dates          |  points  "
+        )?;
+
+        for sprint in db_state
+            .sprints
+            .values()
+            .sorted_by_key(|sprint| sprint.id.0)
+        {
+            let points = sprint_points(&db_state, sprint);
+            let name = match sprint.closed {
+                Some(_) => format!("{} (closed)", sprint.name),
+                None => sprint.name.clone(),
+            };
+
+            writeln!(
+                out,
+                "{} | {} | {} | {}",
+                get_column_string(&sprint.id.0.to_string(), 5),
+                get_column_string(&name, 20),
+                get_column_string(
+                    &format!(
+                        "{} - {}",
+                        self.calendar.date(sprint.start),
+                        self.calendar.date(sprint.end)
+                    ),
+                    23
+                ),
+                get_column_string(&format!("{}/{}", points.completed, points.committed), 9)
+            )?;
+        }
+
+        writeln!(out)?;
+        print_velocity(out, &db_state, self.calendar.today())?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("c", "create sprint", || Action::CreateSprint),
+            Binding::new("A", "archive", || Action::NavigateToSprintArchive),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        if let Some(sprint_id) = parse_id(input) {
+            if self.db.get_sprint(ItemId(sprint_id)).is_ok() {
+                return Ok(Some(Action::NavigateToSprintDetail { sprint_id }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(":id:", "navigate to sprint")]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct SprintDetail {
+    pub sprint_id: u32,
+    pub db: Rc<JiraDataBase>,
+    pub calendar: Calendar,
+    pub capacity: Capacity,
+    pub quick_filter: QuickFilter,
+}
+
+impl SprintDetail {
+    fn stories(&self, db: &DB) -> Result<ItemList> {
+        let sprint = db
+            .sprints
+            .get(&self.sprint_id)
+            .ok_or_else(|| anyhow!("could not find sprint!"))?;
+        let rows = sprint
+            .stories
+            .iter()
+            .filter_map(|id| db.stories.get(&id.0))
+            .sorted_by_key(|story| story.detail.id.0)
+            .map(|story| {
+                let points = match story.detail.points {
+                    Some(points) => format!(" ({points} pts)"),
+                    None => String::new(),
+                };
+
+                ItemRow {
+                    handle: story.detail.id.0.to_string(),
+                    item: ItemType::Story {
+                        id: story.detail.id,
+                    },
+                    epic_id: db
+                        .epics
+                        .values()
+                        .find(|epic| epic.stories.contains(&story.detail.id))
+                        .map(|epic| epic.detail.id.0),
+                    name: format!("{}{points}", story.detail.name),
+                    status: story.detail.status.clone(),
+                    updated_at: last_change(&story.detail),
+                    due: story.detail.due,
+                    badge: None,
+                }
+            })
+            .collect();
+
+        Ok(ItemList::new(rows).filtered(&self.quick_filter))
+    }
+}
+
+impl Page for SprintDetail {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+        let stories = self
+            .stories(&db_state)?
+            .marking_stale(self.db.stale_after());
+        let sprint = &db_state.sprints[&self.sprint_id];
+        let points = sprint_points(&db_state, sprint);
+
+        writeln!(
+            out,
+            "----------------------------- SPRINT -----------------------------"
+        )?;
+        writeln!(
+            out,
+            "{} {} | {} - {} | {}/{} points done",
+            sprint.key(),
+            sprint.name,
+            self.calendar.date(sprint.start),
+            self.calendar.date(sprint.end),
+            points.completed,
+            points.committed
+        )?;
+        if let Some(closed) = sprint.closed {
+            writeln!(out, "closed {}", self.calendar.date(closed))?;
+        }
+        print_velocity(out, &db_state, self.calendar.today())?;
+
+        for load in sprint_loads(&db_state, sprint) {
+            let capacity = match self.capacity.of(&load.assignee) {
+                Some(capacity) => format!(" of {capacity}"),
+                None => String::new(),
+            };
+            let line = format!("{}: {}{capacity} points", load.assignee, load.points);
+
+            match self.capacity.overage(&load.assignee, load.points) {
+                Some(overage) => writeln!(out, "{line}, over by {overage}")?,
+                None => writeln!(out, "{line}")?,
+            }
+        }
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "---------------------------- STORIES ----------------------------"
+        )?;
+        self.quick_filter.draw(out)?;
+
+        stories.draw(out, "id")?;
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("C", "close sprint", || Action::CloseSprint {
+                sprint_id: self.sprint_id,
+            }),
+            Binding::new("/", "filter stories", || Action::QuickFilter),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        if let Some(story_id) = input.strip_prefix('a').and_then(parse_id) {
+            if self.db.get_story(ItemId(story_id)).is_ok() {
+                return Ok(Some(Action::AddStoryToSprint {
+                    sprint_id: self.sprint_id,
+                    story_id,
+                }));
+            }
+
+            return Ok(None);
+        }
+
+        Ok(self.stories(&self.db.read_db()?)?.handle_input(input))
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self.stories(&self.db.read_db()?)?.labels())
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new("a:id:", "add story"),
+            KeyHint::new(":id:", "navigate to story"),
+            KeyHint::new("u:id: / s:id:", "update story"),
+            KeyHint::new("d:id:", "delete story"),
+            KeyHint::new("U", "update all stories"),
+        ]
+    }
+
+    fn quick_filter(&self) -> Option<&QuickFilter> {
+        Some(&self.quick_filter)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::model::{ItemDetail, ItemId, Story};
+    use chrono::NaiveDate;
+
+    fn board() -> (Rc<JiraDataBase>, ItemId, ItemId, ItemId) {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_string(), "".to_string()).unwrap();
+        let story_id = db
+            .create_story("".to_string(), "".to_string(), Some(epic_id))
+            .unwrap();
+        let sprint_id = db
+            .create_sprint(
+                "Sprint 1".to_string(),
+                NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 5, 14).unwrap(),
+            )
+            .unwrap();
+
+        (db, epic_id, story_id, sprint_id)
+    }
+
+    mod sprints_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let (db, ..) = board();
+
+            let page = Sprints {
+                db,
+                calendar: Calendar::default(),
+            };
+            assert!(page.draw_page(&mut io::sink()).is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let (db, _, _, sprint_id) = board();
+
+            let page = Sprints {
+                db,
+                calendar: Calendar::default(),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(page.handle_input("c").unwrap(), Some(Action::CreateSprint));
+            assert_eq!(
+                page.handle_input("A").unwrap(),
+                Some(Action::NavigateToSprintArchive)
+            );
+            assert_eq!(
+                page.handle_input(&sprint_id.0.to_string()).unwrap(),
+                Some(Action::NavigateToSprintDetail {
+                    sprint_id: sprint_id.0
+                })
+            );
+            assert_eq!(page.handle_input("999").unwrap(), None);
+        }
+    }
+
+    mod sprint_detail_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let (db, _, _, sprint_id) = board();
+
+            let page = SprintDetail {
+                sprint_id: sprint_id.0,
+                db,
+                calendar: Calendar::default(),
+                capacity: Capacity::default(),
+                quick_filter: QuickFilter::default(),
+            };
+            assert!(page.draw_page(&mut io::sink()).is_ok());
+        }
+
+        #[test]
+        fn draw_page_should_show_the_points_of_each_assignee_against_capacity() {
+            let (db, _, _, sprint_id) = board();
+            for (assignee, points) in [("alice", 3), ("bob", 8)] {
+                let story_id = db
+                    .add_story(
+                        Story::new(ItemDetail {
+                            assignee: Some(assignee.to_owned()),
+                            points: Some(points),
+                            ..Default::default()
+                        }),
+                        None,
+                    )
+                    .unwrap();
+                db.add_story_to_sprint(sprint_id, story_id).unwrap();
+            }
+
+            let page = SprintDetail {
+                sprint_id: sprint_id.0,
+                db,
+                calendar: Calendar::default(),
+                capacity: Capacity {
+                    default: Some(5),
+                    per_assignee: [("alice".to_owned(), 2)].into(),
+                },
+                quick_filter: QuickFilter::default(),
+            };
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            let out = String::from_utf8(out).unwrap();
+
+            assert!(out.contains("alice: 3 of 2 points, over by 1\n"));
+            assert!(out.contains("bob: 8 of 5 points, over by 3\n"));
+        }
+
+        #[test]
+        fn draw_page_should_throw_error_for_invalid_sprint_id() {
+            let (db, ..) = board();
+
+            let page = SprintDetail {
+                sprint_id: 999,
+                db,
+                calendar: Calendar::default(),
+                capacity: Capacity::default(),
+                quick_filter: QuickFilter::default(),
+            };
+            assert!(page.draw_page(&mut io::sink()).is_err());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let (db, epic_id, story_id, sprint_id) = board();
+
+            let page = SprintDetail {
+                sprint_id: sprint_id.0,
+                db: Rc::clone(&db),
+                calendar: Calendar::default(),
+                capacity: Capacity::default(),
+                quick_filter: QuickFilter::default(),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("C").unwrap(),
+                Some(Action::CloseSprint {
+                    sprint_id: sprint_id.0
+                })
+            );
+            assert_eq!(
+                page.handle_input("a0").unwrap(),
+                Some(Action::AddStoryToSprint {
+                    sprint_id: sprint_id.0,
+                    story_id: story_id.0
+                })
+            );
+            assert_eq!(page.handle_input("a9").unwrap(), None);
+            assert_eq!(page.handle_input("0").unwrap(), None);
+
+            db.add_story_to_sprint(sprint_id, story_id).unwrap();
+
+            assert_eq!(
+                page.handle_input("0").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: epic_id.0,
+                    story_id: story_id.0
+                })
+            );
+        }
+    }
+}
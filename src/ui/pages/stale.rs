@@ -0,0 +1,180 @@
+use anyhow::Result;
+use chrono::Utc;
+use itertools::Itertools;
+use std::any::Any;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::db::JiraDataBase;
+use crate::model::Action;
+use crate::stats::is_stale;
+
+use super::all_items;
+use super::item_list::{ItemList, ItemRow};
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::page_helpers::last_change;
+use super::{Page, QuickFilter};
+
+/// The epics and stories that aren't done and haven't changed for longer than
+/// `stale_after_days`, the longest untouched first.
+pub struct Stale {
+    pub db: Rc<JiraDataBase>,
+    pub quick_filter: QuickFilter,
+}
+
+impl Stale {
+    fn items(&self) -> Result<ItemList> {
+        let Some(after) = self.db.stale_after() else {
+            return Ok(ItemList::new(Vec::new()));
+        };
+        let db_state = self.db.read_db()?;
+        let now = Utc::now();
+        let rows = all_items(&db_state)
+            .map(|(item, epic_id, key, detail)| (item, epic_id, key, detail, last_change(detail)))
+            .filter(|(_, _, _, detail, changed)| is_stale(&detail.status, *changed, now, after))
+            .sorted_by_key(|(_, _, key, _, changed)| (*changed, key.clone()))
+            .enumerate()
+            .map(|(row, (item, epic_id, key, detail, changed))| ItemRow {
+                handle: (row + 1).to_string(),
+                item,
+                epic_id,
+                name: format!("{key} {}", detail.name),
+                status: detail.status.clone(),
+                updated_at: changed,
+                due: detail.due,
+                badge: None,
+            })
+            .collect();
+
+        Ok(ItemList::new(rows).filtered(&self.quick_filter))
+    }
+}
+
+impl Page for Stale {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let items = self.items()?;
+
+        writeln!(
+            out,
+            "---------------------------- STALE ITEMS ----------------------------"
+        )?;
+        self.quick_filter.draw(out)?;
+
+        items.draw(out, "#")?;
+
+        match self.db.stale_after() {
+            None => writeln!(
+                out,
+                "Stale items are off, set stale_after_days to turn them on."
+            )?,
+            Some(_) if items.rows.is_empty() => writeln!(out, "Nothing has gone stale.")?,
+            Some(_) => {}
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("/", "filter", || Action::QuickFilter),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        Ok(self.items()?.handle_input(input))
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self.items()?.labels())
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new(":#:", "open item"),
+            KeyHint::new("u:#: / s:#:", "update item"),
+            KeyHint::new("U", "update all items"),
+        ]
+    }
+
+    fn quick_filter(&self) -> Option<&QuickFilter> {
+        Some(&self.quick_filter)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::model::{ItemDetail, ItemStatus, ItemType, Story};
+    use chrono::{Days, Duration};
+
+    #[test]
+    fn draw_page_should_list_the_stale_items_oldest_first() {
+        let db = Rc::new(
+            JiraDataBase::from_database(Box::new(MockDB::new()))
+                .with_stale_after(Some(Days::new(30))),
+        );
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let mut ids = Vec::new();
+        let mut ages = Vec::new();
+
+        for (name, days_ago, status) in [
+            ("Receipts", 40, ItemStatus::Open),
+            ("Refunds", 90, ItemStatus::InProgress),
+            ("Invoices", 2, ItemStatus::Open),
+            ("Coupons", 90, ItemStatus::Closed),
+        ] {
+            let story = Story::new(ItemDetail {
+                name: name.to_owned(),
+                status,
+                ..Default::default()
+            });
+            let story_id = db.add_story(story, Some(epic_id)).unwrap();
+            ids.push(story_id);
+            ages.push((story_id, days_ago));
+        }
+
+        // Saving stamps the current time, so age the items behind its back.
+        let mut state = db.read_db().unwrap();
+        for (story_id, days_ago) in ages {
+            state
+                .stories
+                .get_mut(&story_id.0)
+                .unwrap()
+                .detail
+                .updated_at = Some(Utc::now() - Duration::days(days_ago));
+        }
+        db.database.write_db(&state).unwrap();
+
+        let page = Stale {
+            db,
+            quick_filter: QuickFilter::default(),
+        };
+        let mut out = Vec::new();
+        page.draw_page(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.find("Refunds").unwrap() < out.find("Receipts").unwrap());
+        assert!(!out.contains("Invoices"));
+        assert!(!out.contains("Coupons"));
+        assert_eq!(
+            page.handle_input("U").unwrap(),
+            Some(Action::BulkUpdateStatus {
+                items: vec![
+                    ItemType::Story { id: ids[1] },
+                    ItemType::Story { id: ids[0] }
+                ]
+            })
+        );
+    }
+}
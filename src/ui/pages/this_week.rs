@@ -0,0 +1,159 @@
+use anyhow::Result;
+use chrono::{Days, NaiveDate};
+use std::any::Any;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::db::JiraDataBase;
+use crate::model::Action;
+
+use super::item_list::{ItemList, ItemRow};
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::open_items_due_by;
+use super::page_helpers::last_change;
+use super::{Page, QuickFilter};
+
+/// How far ahead the page looks, today included.
+const WEEK: Days = Days::new(7);
+
+/// Everything due within the next week, overdue items included, across all
+/// epics and soonest first.
+pub struct ThisWeek {
+    pub today: NaiveDate,
+    pub db: Rc<JiraDataBase>,
+    pub quick_filter: QuickFilter,
+}
+
+impl ThisWeek {
+    fn items(&self) -> Result<ItemList> {
+        let db_state = self.db.read_db()?;
+        let rows = open_items_due_by(&db_state, self.today + WEEK)
+            .into_iter()
+            .enumerate()
+            .map(|(row, (item, epic_id, key, detail))| ItemRow {
+                handle: (row + 1).to_string(),
+                item,
+                epic_id,
+                name: format!("{key} {}", detail.name),
+                status: detail.status.clone(),
+                updated_at: last_change(detail),
+                due: detail.due,
+                badge: None,
+            })
+            .collect();
+
+        Ok(ItemList::new(rows).filtered(&self.quick_filter))
+    }
+}
+
+impl Page for ThisWeek {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let items = self.items()?.marking_stale(self.db.stale_after());
+
+        writeln!(
+            out,
+            "---------------------------- THIS WEEK ----------------------------"
+        )?;
+        self.quick_filter.draw(out)?;
+
+        items.draw(out, "#")?;
+
+        if items.rows.is_empty() {
+            writeln!(out, "Nothing is due this week.")?;
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::new("/", "filter", || Action::QuickFilter),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        Ok(self.items()?.handle_input(input))
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self.items()?.labels())
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new(":#:", "open item"),
+            KeyHint::new("u:#: / s:#:", "update item"),
+            KeyHint::new("U", "update all items"),
+        ]
+    }
+
+    fn quick_filter(&self) -> Option<&QuickFilter> {
+        Some(&self.quick_filter)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::model::{ItemDetail, ItemStatus, ItemType, Story};
+
+    #[test]
+    fn draw_page_should_list_the_items_due_this_week_soonest_first() {
+        let today = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db.create_epic("".to_owned(), "".to_owned()).unwrap();
+        let mut ids = Vec::new();
+
+        for (name, day, status) in [
+            ("Receipts", 10, ItemStatus::Open),
+            ("Refunds", 7, ItemStatus::InProgress),
+            ("Invoices", 20, ItemStatus::Open),
+            ("Coupons", 8, ItemStatus::Resolved),
+        ] {
+            let story = Story::new(ItemDetail {
+                name: name.to_owned(),
+                status,
+                due: NaiveDate::from_ymd_opt(2024, 5, day),
+                ..Default::default()
+            });
+            ids.push(db.add_story(story, Some(epic_id)).unwrap());
+        }
+
+        let page = ThisWeek {
+            today,
+            db,
+            quick_filter: QuickFilter::default(),
+        };
+        let mut out = Vec::new();
+        page.draw_page(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.find("Refunds").unwrap() < out.find("Receipts").unwrap());
+        assert!(!out.contains("Invoices"));
+        assert!(!out.contains("Coupons"));
+        assert_eq!(
+            page.handle_input("u2").unwrap(),
+            Some(Action::UpdateStoryStatus { story_id: ids[0].0 })
+        );
+        assert_eq!(
+            page.handle_input("U").unwrap(),
+            Some(Action::BulkUpdateStatus {
+                items: vec![
+                    ItemType::Story { id: ids[1] },
+                    ItemType::Story { id: ids[0] }
+                ]
+            })
+        );
+    }
+}
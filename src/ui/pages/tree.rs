@@ -0,0 +1,220 @@
+use anyhow::Result;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::db::{JiraDataBase, SortBy};
+use crate::model::{Action, Epic, ItemId, ItemType};
+
+use super::item_list::{ItemList, ItemRow};
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::page_helpers::{last_change, stories_by_id};
+use super::Page;
+
+/// Every epic with its stories nested underneath. Epics are picked by their
+/// ID and stories by `epic.story`, e.g. `3.7`.
+pub struct Tree {
+    pub db: Rc<JiraDataBase>,
+    /// The epics whose stories are folded away.
+    pub collapsed: RefCell<HashSet<u32>>,
+}
+
+impl Tree {
+    pub fn new(db: Rc<JiraDataBase>) -> Self {
+        Self {
+            db,
+            collapsed: RefCell::new(HashSet::new()),
+        }
+    }
+
+    fn epics(&self) -> Result<Vec<Epic>> {
+        Ok(self.db.list_epics(0, usize::MAX, SortBy::Id)?.items)
+    }
+
+    fn list(&self) -> Result<ItemList> {
+        let stories = stories_by_id(&self.db)?;
+        let collapsed = self.collapsed.borrow();
+        let mut rows = Vec::new();
+
+        for epic in self.epics()? {
+            let epic_id = epic.detail.id.0;
+            let folded = collapsed.contains(&epic_id);
+            let marker = match (epic.stories.is_empty(), folded) {
+                (true, _) => " ",
+                (false, true) => "+",
+                (false, false) => "-",
+            };
+
+            rows.push(ItemRow {
+                handle: epic_id.to_string(),
+                item: ItemType::Epic { id: epic.detail.id },
+                epic_id: None,
+                name: format!("{marker} {}", epic.detail.name),
+                status: epic.detail.status.clone(),
+                updated_at: last_change(&epic.detail),
+                due: epic.detail.due,
+                badge: folded.then(|| format!("[{} folded]", epic.stories.len())),
+            });
+
+            if folded {
+                continue;
+            }
+
+            for story in epic
+                .stories
+                .iter()
+                .filter_map(|story_id| stories.get(&story_id.0))
+            {
+                rows.push(ItemRow {
+                    handle: format!("{epic_id}.{}", story.detail.id.0),
+                    item: ItemType::Story {
+                        id: story.detail.id,
+                    },
+                    epic_id: Some(epic_id),
+                    name: format!("  └ {}", story.detail.name),
+                    status: story.detail.status.clone(),
+                    updated_at: last_change(&story.detail),
+                    due: story.detail.due,
+                    badge: None,
+                });
+            }
+        }
+
+        Ok(ItemList::new(rows))
+    }
+
+    /// Folds or unfolds the stories of `epic_id`.
+    fn toggle(&self, epic_id: u32) {
+        let mut collapsed = self.collapsed.borrow_mut();
+
+        if !collapsed.remove(&epic_id) {
+            collapsed.insert(epic_id);
+        }
+    }
+
+    /// Folds every epic, or unfolds them all when they already are.
+    fn toggle_all(&self) -> Result<()> {
+        let epics = self
+            .epics()?
+            .iter()
+            .map(|epic| epic.detail.id.0)
+            .collect::<HashSet<_>>();
+        let mut collapsed = self.collapsed.borrow_mut();
+
+        *collapsed = match epics.is_subset(&collapsed) {
+            true => HashSet::new(),
+            false => epics,
+        };
+        Ok(())
+    }
+}
+
+impl Page for Tree {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        writeln!(
+            out,
+            "------------------------------ TREE ------------------------------"
+        )?;
+
+        self.list()?
+            .marking_stale(self.db.stale_after())
+            .draw(out, "id")?;
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    // Folding only changes how the page draws, so there is no action to hand
+    // to the navigator.
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![
+            Binding::new("p", "previous", || Action::NavigateToPreviousPage),
+            Binding::fallible("E", "fold / unfold all", || {
+                self.toggle_all()?;
+                Ok(None)
+            }),
+        ]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        if let Some(epic_id) = input.strip_prefix('e') {
+            if let Some(epic_id) = epic_id
+                .trim()
+                .parse()
+                .ok()
+                .filter(|id| self.db.get_epic(ItemId(*id)).is_ok())
+            {
+                self.toggle(epic_id);
+                return Ok(None);
+            }
+        }
+
+        Ok(self.list()?.handle_input(input))
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new(":id: / :id:.:id:", "open epic / story"),
+            KeyHint::new("e:id:", "fold / unfold epic"),
+            KeyHint::new("u:id: / u:id:.:id:", "update epic / story"),
+        ]
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self.list()?.labels())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+
+    #[test]
+    fn draw_page_should_nest_stories_under_their_epic() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic("Payments".to_owned(), "".to_owned())
+            .unwrap();
+        db.create_story("Refunds".to_owned(), "".to_owned(), Some(epic_id))
+            .unwrap();
+
+        let page = Tree::new(db);
+        let draw = || {
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert!(draw().contains("  └ Refunds"));
+        assert_eq!(
+            page.handle_input("0.0").unwrap(),
+            Some(Action::NavigateToStoryDetail {
+                epic_id: 0,
+                story_id: 0
+            })
+        );
+
+        assert_eq!(page.handle_input("e0").unwrap(), None);
+        assert!(!draw().contains("Refunds"));
+        assert!(draw().contains("[1 folded]"));
+        assert_eq!(page.handle_input("0.0").unwrap(), None);
+
+        assert_eq!(page.handle_input("E").unwrap(), None);
+        assert!(draw().contains("Refunds"));
+        assert_eq!(
+            page.handle_input("0").unwrap(),
+            Some(Action::NavigateToEpicDetail { epic_id: 0 })
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::Result;
+use std::any::Any;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::config::Capacity;
+use crate::db::JiraDataBase;
+use crate::model::{Action, Filter};
+use crate::stats::{assignee_loads, AssigneeLoad};
+
+use super::key_hints::{print_key_hints, Binding, KeyHint};
+use super::page_helpers::get_column_string;
+use super::Page;
+
+/// What everyone has on their plate, one row per assignee. Anyone carrying
+/// more points than their capacity is marked as over it.
+pub struct Workload {
+    pub db: Rc<JiraDataBase>,
+    pub capacity: Capacity,
+}
+
+impl Workload {
+    fn loads(&self) -> Result<Vec<AssigneeLoad>> {
+        Ok(assignee_loads(&self.db.read_db()?))
+    }
+}
+
+impl Page for Workload {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let loads = self.loads()?;
+
+        writeln!(
+            out,
+            "---------------------------- WORKLOAD ----------------------------"
+        )?;
+
+        if loads.is_empty() {
+            writeln!(out, "Nobody has anything assigned.")?;
+        } else {
+            writeln!(
+                out,
+                "  # |     assignee     | open | in progress | points | capacity"
+            )?;
+        }
+
+        for (index, load) in loads.iter().enumerate() {
+            let capacity = self.capacity.of(&load.assignee);
+            let line = format!(
+                "{:>3} | {} | {:>4} | {:>11} | {:>6} | {:>8}",
+                index + 1,
+                get_column_string(&load.assignee, 16),
+                load.open,
+                load.in_progress,
+                load.points,
+                capacity
+                    .map(|points| points.to_string())
+                    .unwrap_or_default()
+            );
+
+            match self.capacity.overage(&load.assignee, load.points) {
+                Some(overage) => writeln!(out, "{line}  over by {overage}")?,
+                None => writeln!(out, "{line}")?,
+            }
+        }
+
+        writeln!(out)?;
+        writeln!(out)?;
+
+        print_key_hints(out, &self.key_hints())?;
+
+        Ok(())
+    }
+
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        vec![Binding::new("p", "previous", || {
+            Action::NavigateToPreviousPage
+        })]
+    }
+
+    fn handle_other_input(&self, input: &str) -> Result<Option<Action>> {
+        let Ok(number) = input.trim().parse::<usize>() else {
+            return Ok(None);
+        };
+
+        let loads = self.loads()?;
+
+        Ok(number
+            .checked_sub(1)
+            .and_then(|index| loads.into_iter().nth(index))
+            .map(|load| Action::Search {
+                filter: Filter {
+                    assignee: Some(load.assignee),
+                    ..Filter::default()
+                },
+            }))
+    }
+
+    fn input_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(":#:", "list their items")]
+    }
+
+    fn rows(&self) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .loads()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, load)| ((index + 1).to_string(), load.assignee))
+            .collect())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::model::{ItemDetail, Story};
+    use std::collections::HashMap;
+
+    #[test]
+    fn draw_page_should_mark_assignees_over_capacity() {
+        let db = Rc::new(JiraDataBase::from_database(Box::new(MockDB::new())));
+        for (assignee, points) in [("ana", 5), ("bo", 5)] {
+            let story = Story::new(ItemDetail {
+                assignee: Some(assignee.to_owned()),
+                points: Some(points),
+                ..Default::default()
+            });
+            db.add_story(story, None).unwrap();
+        }
+
+        let page = Workload {
+            db,
+            capacity: Capacity {
+                default: Some(6),
+                per_assignee: HashMap::from([("ana".to_owned(), 3)]),
+            },
+        };
+        let mut out = Vec::new();
+        page.draw_page(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("over by 2"));
+        assert_eq!(out.matches("over by").count(), 1);
+        assert_eq!(
+            page.handle_input("2").unwrap(),
+            Some(Action::Search {
+                filter: Filter {
+                    assignee: Some("bo".to_owned()),
+                    ..Filter::default()
+                }
+            })
+        );
+        assert_eq!(page.handle_input("3").unwrap(), None);
+        assert_eq!(page.handle_input("0").unwrap(), None);
+    }
+}
@@ -1,28 +1,156 @@
 use crate::{
-    io_utils::get_user_input,
-    model::{Epic, ItemDetail, ItemId, ItemStatus, Story},
+    calendar::Calendar,
+    model::{Epic, FieldEdit, ItemStatus, SavedFilter, Sprint, Story, StorySplit},
 };
+#[cfg(feature = "terminal")]
+use crate::{
+    capture::parse_due_date,
+    io_utils::{confirm, get_user_input},
+    keys::parse_id,
+    model::{ItemDetail, ItemId},
+};
+#[cfg(feature = "terminal")]
+use itertools::Itertools;
 
-pub struct Prompts {
-    pub create_epic: Box<dyn Fn() -> Epic>,
-    pub create_story: Box<dyn Fn() -> Story>,
-    pub delete_epic: Box<dyn Fn() -> bool>,
-    pub delete_story: Box<dyn Fn() -> bool>,
-    pub update_status: Box<dyn Fn() -> Option<ItemStatus>>,
+/// Everything the navigator asks the user while handling an action. Each
+/// frontend answers these its own way: `TerminalPrompts` reads stdin and
+/// tests answer with `test_utils::MockPrompts`.
+pub trait PromptProvider {
+    fn create_epic(&self) -> Epic;
+    fn create_story(&self) -> Story;
+    fn delete_epic(&self, epic: &Epic) -> bool;
+    fn delete_story(&self, story: &Story) -> bool;
+    fn merge_epic(&self) -> Option<u32>;
+    fn confirm_merge(&self, source: &Epic, target: &Epic) -> bool;
+    fn demote_epic(&self, epic: &Epic) -> Option<u32>;
+    /// The labels new stories of `epic` start with, `None` to keep its current
+    /// ones.
+    fn default_labels(&self, epic: &Epic) -> Option<Vec<String>>;
+    /// The labels of a story created in an epic with `defaults`.
+    fn story_labels(&self, defaults: &[String]) -> Vec<String>;
+    fn split_story(&self, story: &Story) -> Option<StorySplit>;
+    fn add_checklist_item(&self) -> String;
+    fn promote_story(&self, story: &Story) -> Option<bool>;
+    fn update_status(&self) -> Option<ItemStatus>;
+    fn save_filter(&self) -> String;
+    fn pick_filter(&self, filters: &[SavedFilter]) -> Option<usize>;
+    fn pick_template(&self, names: &[String]) -> Option<usize>;
+    /// The value of `placeholder` in the story made from a template.
+    fn template_value(&self, placeholder: &str) -> String;
+    fn create_sprint(&self, calendar: &Calendar) -> Option<Sprint>;
+    fn wip_override(&self, violations: &[String]) -> bool;
+    fn confirm_bulk_update(&self, count: usize, status: &ItemStatus) -> bool;
+    fn confirm_bulk_edit(&self, count: usize, edits: &[FieldEdit]) -> bool;
+    fn snapshot_name(&self) -> String;
+    fn restore_snapshot(&self, name: &str) -> bool;
+    /// Whether to save the unsaved changes before quitting, `None` to stay.
+    fn save_on_exit(&self) -> Option<bool>;
 }
 
-impl Prompts {
-    pub fn new() -> Self {
-        Self {
-            create_epic: Box::new(create_epic_prompt),
-            create_story: Box::new(create_story_prompt),
-            delete_epic: Box::new(delete_epic_prompt),
-            delete_story: Box::new(delete_story_prompt),
-            update_status: Box::new(update_status_prompt),
-        }
+/// Asks on stdin and stdout.
+#[cfg(feature = "terminal")]
+pub struct TerminalPrompts;
+
+#[cfg(feature = "terminal")]
+impl PromptProvider for TerminalPrompts {
+    fn create_epic(&self) -> Epic {
+        create_epic_prompt()
+    }
+
+    fn create_story(&self) -> Story {
+        create_story_prompt()
+    }
+
+    fn delete_epic(&self, epic: &Epic) -> bool {
+        delete_epic_prompt(epic)
+    }
+
+    fn delete_story(&self, story: &Story) -> bool {
+        delete_story_prompt(story)
+    }
+
+    fn merge_epic(&self) -> Option<u32> {
+        merge_epic_prompt()
+    }
+
+    fn confirm_merge(&self, source: &Epic, target: &Epic) -> bool {
+        confirm_merge_prompt(source, target)
+    }
+
+    fn demote_epic(&self, epic: &Epic) -> Option<u32> {
+        demote_epic_prompt(epic)
+    }
+
+    fn default_labels(&self, epic: &Epic) -> Option<Vec<String>> {
+        default_labels_prompt(epic)
+    }
+
+    fn story_labels(&self, defaults: &[String]) -> Vec<String> {
+        story_labels_prompt(defaults)
+    }
+
+    fn split_story(&self, story: &Story) -> Option<StorySplit> {
+        split_story_prompt(story)
+    }
+
+    fn add_checklist_item(&self) -> String {
+        add_checklist_item_prompt()
+    }
+
+    fn promote_story(&self, story: &Story) -> Option<bool> {
+        promote_story_prompt(story)
+    }
+
+    fn update_status(&self) -> Option<ItemStatus> {
+        update_status_prompt()
+    }
+
+    fn save_filter(&self) -> String {
+        save_filter_prompt()
+    }
+
+    fn pick_filter(&self, filters: &[SavedFilter]) -> Option<usize> {
+        pick_filter_prompt(filters)
+    }
+
+    fn pick_template(&self, names: &[String]) -> Option<usize> {
+        pick_template_prompt(names)
+    }
+
+    fn template_value(&self, placeholder: &str) -> String {
+        template_value_prompt(placeholder)
+    }
+
+    fn create_sprint(&self, calendar: &Calendar) -> Option<Sprint> {
+        create_sprint_prompt(calendar)
+    }
+
+    fn wip_override(&self, violations: &[String]) -> bool {
+        wip_override_prompt(violations)
+    }
+
+    fn confirm_bulk_update(&self, count: usize, status: &ItemStatus) -> bool {
+        confirm_bulk_update_prompt(count, status)
+    }
+
+    fn confirm_bulk_edit(&self, count: usize, edits: &[FieldEdit]) -> bool {
+        confirm_bulk_edit_prompt(count, edits)
+    }
+
+    fn snapshot_name(&self) -> String {
+        snapshot_name_prompt()
+    }
+
+    fn restore_snapshot(&self, name: &str) -> bool {
+        restore_snapshot_prompt(name)
+    }
+
+    fn save_on_exit(&self) -> Option<bool> {
+        save_on_exit_prompt()
     }
 }
 
+#[cfg(feature = "terminal")]
 fn create_epic_prompt() -> Epic {
     println!("----------------------------");
     println!("Epic Name: ");
@@ -31,17 +159,18 @@ fn create_epic_prompt() -> Epic {
     println!("Description: ");
     let description = get_user_input();
 
-    return Epic::new(
+    Epic::new(
         ItemDetail {
             name,
             description,
             id: ItemId(0),
-            status: ItemStatus::Open,
+            ..Default::default()
         },
         Vec::new(),
-    );
+    )
 }
 
+#[cfg(feature = "terminal")]
 fn create_story_prompt() -> Story {
     println!("----------------------------");
     println!("Story Name: ");
@@ -50,32 +179,203 @@ fn create_story_prompt() -> Story {
     println!("Description: ");
     let description = get_user_input();
 
-    return Story::new(ItemDetail {
+    Story::new(ItemDetail {
         description,
         id: ItemId(0),
         name,
-        status: ItemStatus::Open,
-    });
+        ..Default::default()
+    })
+}
+
+/// Names the epic and how many stories go with it, e.g.
+/// `Delete EPIC-3 'Payments' and its 4 stories?`.
+pub fn delete_epic_question(epic: &Epic) -> String {
+    match epic.stories.len() {
+        0 => format!("Delete {} '{}'?", epic.key(), epic.detail.name),
+        1 => format!(
+            "Delete {} '{}' and its story?",
+            epic.key(),
+            epic.detail.name
+        ),
+        count => format!(
+            "Delete {} '{}' and its {count} stories?",
+            epic.key(),
+            epic.detail.name
+        ),
+    }
+}
+
+/// Names both epics and how many stories move, e.g.
+/// `Merge EPIC-3 'Payments' and its 4 stories into EPIC-1 'Billing'?`.
+pub fn merge_epic_question(source: &Epic, target: &Epic) -> String {
+    let stories = match source.stories.len() {
+        0 => String::new(),
+        1 => " and its story".to_owned(),
+        count => format!(" and its {count} stories"),
+    };
+
+    format!(
+        "Merge {} '{}'{stories} into {} '{}'?",
+        source.key(),
+        source.detail.name,
+        target.key(),
+        target.detail.name
+    )
 }
 
-fn delete_epic_prompt() -> bool {
+pub fn delete_story_question(story: &Story) -> String {
+    format!("Delete {} '{}'?", story.key(), story.detail.name)
+}
+
+pub fn restore_snapshot_question(name: &str) -> String {
+    format!("Replace the whole board with the snapshot \"{name}\"?")
+}
+
+#[cfg(feature = "terminal")]
+fn delete_epic_prompt(epic: &Epic) -> bool {
+    println!("----------------------------");
+
+    confirm(&delete_epic_question(epic), false)
+}
+
+#[cfg(feature = "terminal")]
+fn delete_story_prompt(story: &Story) -> bool {
+    println!("----------------------------");
+
+    confirm(&delete_story_question(story), false)
+}
+
+#[cfg(feature = "terminal")]
+fn merge_epic_prompt() -> Option<u32> {
+    println!("----------------------------");
+    println!("Merge this epic into epic ID (its stories move over and it gets deleted): ");
+
+    parse_id(get_user_input().trim())
+}
+
+#[cfg(feature = "terminal")]
+fn confirm_merge_prompt(source: &Epic, target: &Epic) -> bool {
+    confirm(&merge_epic_question(source, target), false)
+}
+
+#[cfg(feature = "terminal")]
+fn demote_epic_prompt(epic: &Epic) -> Option<u32> {
+    println!("----------------------------");
+    println!(
+        "Turn this epic into a story of epic ID (its {} stories become checklist items and get deleted): ",
+        epic.stories.len()
+    );
+
+    parse_id(get_user_input().trim())
+}
+
+#[cfg(feature = "terminal")]
+fn default_labels_prompt(epic: &Epic) -> Option<Vec<String>> {
+    println!("----------------------------");
+    if !epic.default_labels.is_empty() {
+        println!("Default labels: {}", epic.default_labels.join(", "));
+    }
+    println!("Labels new stories start with, comma-separated (- for none, empty keeps them): ");
+
+    parse_label_list(&get_user_input())
+}
+
+#[cfg(feature = "terminal")]
+fn story_labels_prompt(defaults: &[String]) -> Vec<String> {
+    println!("Labels: {}", defaults.join(", "));
+    println!("Labels to use instead, comma-separated (- for none, empty keeps them): ");
+
+    parse_label_list(&get_user_input()).unwrap_or_else(|| defaults.to_vec())
+}
+
+/// A comma-separated list of labels, `-` for none or `None` when left empty.
+#[cfg(feature = "terminal")]
+fn parse_label_list(input: &str) -> Option<Vec<String>> {
+    match input.trim() {
+        "" => None,
+        "-" => Some(Vec::new()),
+        labels => Some(
+            labels
+                .split(',')
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .map(str::to_owned)
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(feature = "terminal")]
+fn split_story_prompt(story: &Story) -> Option<StorySplit> {
     println!("----------------------------");
-    println!("Are you sure you want to delete this epic? All stories in this epic will also be deleted [Y/n]: ");
+    println!("New Story Name (leave empty to cancel): ");
+    let name = get_user_input();
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut checklist = Vec::new();
+
+    if !story.checklist.is_empty() {
+        story
+            .checklist
+            .iter()
+            .enumerate()
+            .for_each(|(index, item)| println!("{}. {}", index + 1, item.text));
+        println!("Checklist items to move, e.g. 1,3 (leave empty for none): ");
+
+        for number in get_user_input().split(',').filter(|n| !n.trim().is_empty()) {
+            match number.trim().parse::<usize>() {
+                Ok(number) if number > 0 => checklist.push(number - 1),
+                _ => return None,
+            }
+        }
+    }
 
-    let input = get_user_input();
+    println!("Description: ");
+    let description = get_user_input();
+
+    Some(StorySplit {
+        name,
+        description,
+        checklist,
+    })
+}
+
+#[cfg(feature = "terminal")]
+fn add_checklist_item_prompt() -> String {
+    println!("----------------------------");
+    println!("Checklist Item: ");
 
-    return input.eq("Y") || input.eq("y");
+    get_user_input()
 }
 
-fn delete_story_prompt() -> bool {
+#[cfg(feature = "terminal")]
+fn promote_story_prompt(story: &Story) -> Option<bool> {
     println!("----------------------------");
-    println!("Are you sure you want to delete this story? [Y/n]: ");
 
-    let input = get_user_input();
+    if !confirm(
+        &format!("Turn {} '{}' into an epic?", story.key(), story.detail.name),
+        false,
+    ) {
+        return None;
+    }
+
+    if story.checklist.is_empty() {
+        return Some(false);
+    }
 
-    return input.eq("Y") || input.eq("y");
+    Some(confirm(
+        &format!(
+            "Move its {} checklist items into stories of the new epic, instead of discarding them?",
+            story.checklist.len()
+        ),
+        false,
+    ))
 }
 
+#[cfg(feature = "terminal")]
 fn update_status_prompt() -> Option<ItemStatus> {
     println!("----------------------------");
     println!("New Status (1 - OPEN, 2 - IN-PROGRESS, 3 - RESOLVED, 4 - CLOSED): ");
@@ -95,3 +395,315 @@ fn update_status_prompt() -> Option<ItemStatus> {
 
     None
 }
+
+#[cfg(feature = "terminal")]
+fn save_filter_prompt() -> String {
+    println!("----------------------------");
+    println!("Filter Name (saving under an existing name replaces it): ");
+
+    get_user_input()
+}
+
+#[cfg(feature = "terminal")]
+fn snapshot_name_prompt() -> String {
+    println!("----------------------------");
+    println!("Snapshot Name (e.g. before-reorg): ");
+
+    get_user_input().trim().to_owned()
+}
+
+#[cfg(feature = "terminal")]
+fn restore_snapshot_prompt(name: &str) -> bool {
+    println!("----------------------------");
+
+    confirm(&restore_snapshot_question(name), false)
+}
+
+#[cfg(feature = "terminal")]
+fn save_on_exit_prompt() -> Option<bool> {
+    println!("----------------------------");
+
+    if confirm("Save the changes before quitting?", true) {
+        return Some(true);
+    }
+
+    confirm("Quit without saving them?", false).then_some(false)
+}
+
+#[cfg(feature = "terminal")]
+fn pick_filter_prompt(filters: &[SavedFilter]) -> Option<usize> {
+    println!("----------------------------");
+    filters
+        .iter()
+        .enumerate()
+        .for_each(|(index, saved)| println!("{}. {} ({})", index + 1, saved.name, saved.filter));
+    println!("Filter to apply: ");
+
+    match get_user_input().trim().parse::<usize>() {
+        Ok(number) if number > 0 && number <= filters.len() => Some(number - 1),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "terminal")]
+fn pick_template_prompt(names: &[String]) -> Option<usize> {
+    println!("----------------------------");
+    names
+        .iter()
+        .enumerate()
+        .for_each(|(index, name)| println!("{}. {name}", index + 1));
+    println!("Template to use: ");
+
+    match get_user_input().trim().parse::<usize>() {
+        Ok(number) if number > 0 && number <= names.len() => Some(number - 1),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "terminal")]
+fn template_value_prompt(placeholder: &str) -> String {
+    println!("{placeholder}: ");
+
+    get_user_input()
+}
+
+#[cfg(feature = "terminal")]
+fn create_sprint_prompt(calendar: &Calendar) -> Option<Sprint> {
+    let format = calendar.format;
+
+    println!("----------------------------");
+    println!("Sprint Name: ");
+    let name = get_user_input();
+
+    println!("Start ({}, today, mon...): ", format.hint());
+    let start = parse_due_date(&get_user_input(), calendar.today(), format).ok()?;
+
+    println!("End ({}, fri...): ", format.hint());
+    let end = parse_due_date(&get_user_input(), start, format).ok()?;
+
+    Some(Sprint {
+        id: ItemId(0),
+        name,
+        start,
+        end,
+        stories: Vec::new(),
+        closed: None,
+        snapshot: Vec::new(),
+    })
+}
+
+#[cfg(feature = "terminal")]
+fn wip_override_prompt(violations: &[String]) -> bool {
+    println!("----------------------------");
+    println!("WIP limit exceeded:");
+    for violation in violations {
+        println!("- {violation}");
+    }
+
+    confirm("Start anyway?", false)
+}
+
+#[cfg(feature = "terminal")]
+fn confirm_bulk_update_prompt(count: usize, status: &ItemStatus) -> bool {
+    println!("----------------------------");
+
+    confirm(&format!("Move {count} items to {status}?"), false)
+}
+
+#[cfg(feature = "terminal")]
+fn confirm_bulk_edit_prompt(count: usize, edits: &[FieldEdit]) -> bool {
+    println!("----------------------------");
+
+    confirm(
+        &format!("{} on {count} items?", edits.iter().join(", ")),
+        false,
+    )
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use super::*;
+
+    type SplitStoryPrompt = Box<dyn Fn(&Story) -> Option<StorySplit>>;
+    type PromoteStoryPrompt = Box<dyn Fn(&Story) -> Option<bool>>;
+    type DeleteEpicPrompt = Box<dyn Fn(&Epic) -> bool>;
+    type DeleteStoryPrompt = Box<dyn Fn(&Story) -> bool>;
+    type ConfirmMergePrompt = Box<dyn Fn(&Epic, &Epic) -> bool>;
+    type DemoteEpicPrompt = Box<dyn Fn(&Epic) -> Option<u32>>;
+    type DefaultLabelsPrompt = Box<dyn Fn(&Epic) -> Option<Vec<String>>>;
+    type StoryLabelsPrompt = Box<dyn Fn(&[String]) -> Vec<String>>;
+    type PickFilterPrompt = Box<dyn Fn(&[SavedFilter]) -> Option<usize>>;
+    type PickTemplatePrompt = Box<dyn Fn(&[String]) -> Option<usize>>;
+    type WipOverridePrompt = Box<dyn Fn(&[String]) -> bool>;
+    type ConfirmBulkUpdatePrompt = Box<dyn Fn(usize, &ItemStatus) -> bool>;
+    type ConfirmBulkEditPrompt = Box<dyn Fn(usize, &[FieldEdit]) -> bool>;
+
+    /// Fails the test that asked `question` without giving it an answer.
+    fn unasked(question: &str) -> ! {
+        panic!("{question} was asked but the test doesn't answer it")
+    }
+
+    /// One closure per question, each failing the test until it's given one,
+    /// so a test answers only the questions it expects to be asked.
+    pub struct MockPrompts {
+        pub create_epic: Box<dyn Fn() -> Epic>,
+        pub create_story: Box<dyn Fn() -> Story>,
+        pub delete_epic: DeleteEpicPrompt,
+        pub delete_story: DeleteStoryPrompt,
+        pub merge_epic: Box<dyn Fn() -> Option<u32>>,
+        pub confirm_merge: ConfirmMergePrompt,
+        pub demote_epic: DemoteEpicPrompt,
+        pub default_labels: DefaultLabelsPrompt,
+        pub story_labels: StoryLabelsPrompt,
+        pub split_story: SplitStoryPrompt,
+        pub add_checklist_item: Box<dyn Fn() -> String>,
+        pub promote_story: PromoteStoryPrompt,
+        pub update_status: Box<dyn Fn() -> Option<ItemStatus>>,
+        pub save_filter: Box<dyn Fn() -> String>,
+        pub pick_filter: PickFilterPrompt,
+        pub pick_template: PickTemplatePrompt,
+        pub template_value: Box<dyn Fn(&str) -> String>,
+        pub create_sprint: Box<dyn Fn() -> Option<Sprint>>,
+        pub wip_override: WipOverridePrompt,
+        pub confirm_bulk_update: ConfirmBulkUpdatePrompt,
+        pub confirm_bulk_edit: ConfirmBulkEditPrompt,
+        pub snapshot_name: Box<dyn Fn() -> String>,
+        pub restore_snapshot: Box<dyn Fn(&str) -> bool>,
+        pub save_on_exit: Box<dyn Fn() -> Option<bool>>,
+    }
+
+    impl MockPrompts {
+        pub fn new() -> Self {
+            Self {
+                create_epic: Box::new(|| unasked("create_epic")),
+                create_story: Box::new(|| unasked("create_story")),
+                delete_epic: Box::new(|_| unasked("delete_epic")),
+                delete_story: Box::new(|_| unasked("delete_story")),
+                merge_epic: Box::new(|| unasked("merge_epic")),
+                confirm_merge: Box::new(|_, _| unasked("confirm_merge")),
+                demote_epic: Box::new(|_| unasked("demote_epic")),
+                default_labels: Box::new(|_| unasked("default_labels")),
+                story_labels: Box::new(|_| unasked("story_labels")),
+                split_story: Box::new(|_| unasked("split_story")),
+                add_checklist_item: Box::new(|| unasked("add_checklist_item")),
+                promote_story: Box::new(|_| unasked("promote_story")),
+                update_status: Box::new(|| unasked("update_status")),
+                save_filter: Box::new(|| unasked("save_filter")),
+                pick_filter: Box::new(|_| unasked("pick_filter")),
+                pick_template: Box::new(|_| unasked("pick_template")),
+                template_value: Box::new(|_| unasked("template_value")),
+                create_sprint: Box::new(|| unasked("create_sprint")),
+                wip_override: Box::new(|_| unasked("wip_override")),
+                confirm_bulk_update: Box::new(|_, _| unasked("confirm_bulk_update")),
+                confirm_bulk_edit: Box::new(|_, _| unasked("confirm_bulk_edit")),
+                snapshot_name: Box::new(|| unasked("snapshot_name")),
+                restore_snapshot: Box::new(|_| unasked("restore_snapshot")),
+                save_on_exit: Box::new(|| unasked("save_on_exit")),
+            }
+        }
+    }
+
+    impl Default for MockPrompts {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl PromptProvider for MockPrompts {
+        fn create_epic(&self) -> Epic {
+            (self.create_epic)()
+        }
+
+        fn create_story(&self) -> Story {
+            (self.create_story)()
+        }
+
+        fn delete_epic(&self, epic: &Epic) -> bool {
+            (self.delete_epic)(epic)
+        }
+
+        fn delete_story(&self, story: &Story) -> bool {
+            (self.delete_story)(story)
+        }
+
+        fn merge_epic(&self) -> Option<u32> {
+            (self.merge_epic)()
+        }
+
+        fn confirm_merge(&self, source: &Epic, target: &Epic) -> bool {
+            (self.confirm_merge)(source, target)
+        }
+
+        fn demote_epic(&self, epic: &Epic) -> Option<u32> {
+            (self.demote_epic)(epic)
+        }
+
+        fn default_labels(&self, epic: &Epic) -> Option<Vec<String>> {
+            (self.default_labels)(epic)
+        }
+
+        fn story_labels(&self, defaults: &[String]) -> Vec<String> {
+            (self.story_labels)(defaults)
+        }
+
+        fn split_story(&self, story: &Story) -> Option<StorySplit> {
+            (self.split_story)(story)
+        }
+
+        fn add_checklist_item(&self) -> String {
+            (self.add_checklist_item)()
+        }
+
+        fn promote_story(&self, story: &Story) -> Option<bool> {
+            (self.promote_story)(story)
+        }
+
+        fn update_status(&self) -> Option<ItemStatus> {
+            (self.update_status)()
+        }
+
+        fn save_filter(&self) -> String {
+            (self.save_filter)()
+        }
+
+        fn pick_filter(&self, filters: &[SavedFilter]) -> Option<usize> {
+            (self.pick_filter)(filters)
+        }
+
+        fn pick_template(&self, names: &[String]) -> Option<usize> {
+            (self.pick_template)(names)
+        }
+
+        fn template_value(&self, placeholder: &str) -> String {
+            (self.template_value)(placeholder)
+        }
+
+        fn create_sprint(&self, _: &Calendar) -> Option<Sprint> {
+            (self.create_sprint)()
+        }
+
+        fn wip_override(&self, violations: &[String]) -> bool {
+            (self.wip_override)(violations)
+        }
+
+        fn confirm_bulk_update(&self, count: usize, status: &ItemStatus) -> bool {
+            (self.confirm_bulk_update)(count, status)
+        }
+
+        fn confirm_bulk_edit(&self, count: usize, edits: &[FieldEdit]) -> bool {
+            (self.confirm_bulk_edit)(count, edits)
+        }
+
+        fn snapshot_name(&self) -> String {
+            (self.snapshot_name)()
+        }
+
+        fn restore_snapshot(&self, name: &str) -> bool {
+            (self.restore_snapshot)(name)
+        }
+
+        fn save_on_exit(&self) -> Option<bool> {
+            (self.save_on_exit)()
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+#[cfg(feature = "terminal")]
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    terminal::{Clear, ClearType},
+};
+#[cfg(feature = "terminal")]
+use std::io;
+use std::time::Duration;
+
+#[cfg(feature = "terminal")]
+use crate::io_utils::{
+    forget_shown_input, get_filter_key, get_hidden_input, get_user_input, get_user_input_within,
+    wait_for_key_press,
+};
+
+use super::{FilterKey, Page};
+
+/// Shows pages and reads back what the user types, so the same `Navigator` can
+/// sit behind the terminal, a GUI or a test.
+pub trait Renderer {
+    /// Makes room for the next page.
+    fn clear(&mut self) -> Result<()>;
+    /// Shows a one off message above the next page.
+    fn show_notice(&mut self, notice: &str);
+    fn draw(&mut self, page: &dyn Page) -> Result<()>;
+    fn read_input(&mut self) -> String;
+    /// Reads input like `read_input`, but may return `None` once `timeout`
+    /// passed without any, so the page can be refreshed. Frontends that can't
+    /// wait with a timeout just read.
+    fn poll_input(&mut self, _timeout: Duration) -> Option<String> {
+        Some(self.read_input())
+    }
+    /// Reads a passphrase like `read_input`, without showing it where the
+    /// frontend can.
+    fn read_secret(&mut self) -> String {
+        self.read_input()
+    }
+    /// Reads the next key of a quick filter. Frontends that only read lines
+    /// take the whole filter at once.
+    fn read_filter_key(&mut self) -> FilterKey {
+        FilterKey::Line(self.read_input())
+    }
+    /// Shows an error and waits until the user has seen it.
+    fn show_error(&mut self, message: &str);
+}
+
+/// Draws on stdout and reads stdin.
+#[cfg(feature = "terminal")]
+pub struct Terminal;
+
+#[cfg(feature = "terminal")]
+impl Renderer for Terminal {
+    fn clear(&mut self) -> Result<()> {
+        forget_shown_input();
+        Ok(execute!(io::stdout(), Clear(ClearType::All), MoveTo(0, 0))?)
+    }
+
+    fn show_notice(&mut self, notice: &str) {
+        println!("{notice}");
+        println!();
+    }
+
+    fn draw(&mut self, page: &dyn Page) -> Result<()> {
+        page.draw_page(&mut io::stdout().lock())
+    }
+
+    fn read_input(&mut self) -> String {
+        get_user_input()
+    }
+
+    fn poll_input(&mut self, timeout: Duration) -> Option<String> {
+        get_user_input_within(timeout)
+    }
+
+    fn read_secret(&mut self) -> String {
+        get_hidden_input()
+    }
+
+    fn read_filter_key(&mut self) -> FilterKey {
+        get_filter_key()
+    }
+
+    fn show_error(&mut self, message: &str) {
+        println!("{message}");
+        println!("Press any key to continue...");
+        wait_for_key_press();
+    }
+}